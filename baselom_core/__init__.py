@@ -2,25 +2,40 @@
 
 This package provides a high-performance baseball game engine implemented
 in Rust with Python bindings via PyO3/maturin.
+
+The engine types, ``apply_pitch`` and the exception classes come from the
+compiled ``baselom_core._core`` extension. When it is not built, the
+pure-Python placeholders in this package are used instead.
 """
 
-from baselom_core.engine import apply_pitch, initial_game_state
-from baselom_core.exceptions import (
-    BaselomError,
-    RuleViolation,
-    RuleViolationError,
-    StateError,
-    ValidationError,
-)
-from baselom_core.models import (
-    GameRules,
-    GameState,
-    Score,
-    ValidationResult,
-    normalize_lineups,
-)
+from baselom_core.engine import initial_game_state
+from baselom_core.models import ValidationResult, normalize_lineups
 from baselom_core.validators import validate_state
 
+try:
+    from baselom_core._core import (
+        BaselomError,
+        GameRules,
+        GameState,
+        RuleViolation,
+        Score,
+        StateError,
+        ValidationError,
+        apply_pitch,
+    )
+
+    RuleViolationError = RuleViolation
+except ImportError:  # pragma: no cover - extension module not built
+    from baselom_core.engine import apply_pitch
+    from baselom_core.exceptions import (
+        BaselomError,
+        RuleViolation,
+        RuleViolationError,
+        StateError,
+        ValidationError,
+    )
+    from baselom_core.models import GameRules, GameState, Score
+
 __all__ = [
     "BaselomError",
     "GameRules",
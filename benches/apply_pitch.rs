@@ -0,0 +1,70 @@
+//! Throughput of a million simple pitches: called strikes that change only
+//! the count, with the count reset by hand before it reaches strike three.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use baselom_core::{
+    apply_pitch, apply_pitch_mut, apply_pitch_unchecked, GameRules, GameState, PitchResult,
+};
+
+const PITCHES: usize = 1_000_000;
+
+fn start() -> GameState {
+    GameState::builder()
+        .runner_on(baselom_core::Base::First, "r1")
+        .runner_on(baselom_core::Base::Third, "r3")
+        .batter("b")
+        .pitcher("p")
+        .build()
+        .unwrap()
+}
+
+fn million_strikes(c: &mut Criterion) {
+    let rules = GameRules::default();
+    let mut group = c.benchmark_group("million_strikes");
+    group.sample_size(10);
+
+    group.bench_function("apply_pitch", |b| {
+        b.iter(|| {
+            let mut state = start();
+            for _ in 0..PITCHES {
+                if state.count.strikes == 2 {
+                    state.count.reset();
+                }
+                state = apply_pitch(&state, PitchResult::StrikeCalled, &rules).unwrap();
+            }
+            state
+        })
+    });
+
+    group.bench_function("apply_pitch_unchecked", |b| {
+        b.iter(|| {
+            let mut state = start();
+            for _ in 0..PITCHES {
+                if state.count.strikes == 2 {
+                    state.count.reset();
+                }
+                state = apply_pitch_unchecked(&state, PitchResult::StrikeCalled, &rules).unwrap();
+            }
+            state
+        })
+    });
+
+    group.bench_function("apply_pitch_mut", |b| {
+        b.iter(|| {
+            let mut state = start();
+            for _ in 0..PITCHES {
+                if state.count.strikes == 2 {
+                    state.count.reset();
+                }
+                apply_pitch_mut(&mut state, PitchResult::StrikeCalled, &rules).unwrap();
+            }
+            state
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, million_strikes);
+criterion_main!(benches);
@@ -0,0 +1,120 @@
+//! String player IDs against interned ones: the same innings played on a
+//! `GameState` and on a `CompactGameState`, counting heap allocations.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use baselom_core::{
+    step, step_pitch, GameEvent, GameRules, GameState, Lineup, PitchResult, PlayerKey,
+    PlayerRegistry,
+};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const HALF_INNINGS: usize = 1_000;
+
+fn start() -> GameState {
+    GameState::builder()
+        .pitcher("home_p")
+        .lineups(
+            Lineup::new(
+                (1..=8)
+                    .map(|n| format!("home_{}", n))
+                    .chain(["home_p".to_string()]),
+            )
+            .with_pitcher("home_p"),
+            Lineup::new(
+                (1..=8)
+                    .map(|n| format!("away_{}", n))
+                    .chain(["away_p".to_string()]),
+            )
+            .with_pitcher("away_p"),
+        )
+        .build()
+        .unwrap()
+}
+
+/// A half inning with two singles, a walk, a home run and three outs, then
+/// back to the top of the first so the game never ends.
+fn play_half_innings<P: PlayerKey>(state: &mut GameState<P>, rules: &GameRules) {
+    let single = GameEvent::Single {
+        advancement: vec![],
+    };
+    let out = GameEvent::GroundOut {
+        advancement: vec![],
+    };
+    for _ in 0..HALF_INNINGS {
+        step_pitch(state, PitchResult::Ball, rules).unwrap();
+        step_pitch(state, PitchResult::StrikeCalled, rules).unwrap();
+        step(state, &single, rules).unwrap();
+        step(state, &single, rules).unwrap();
+        for _ in 0..4 {
+            step_pitch(state, PitchResult::Ball, rules).unwrap();
+        }
+        step(state, &GameEvent::HomeRun, rules).unwrap();
+        for _ in 0..3 {
+            step(state, &out, rules).unwrap();
+        }
+        state.inning = 1;
+    }
+}
+
+fn allocations(run: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    run();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn compact_state(c: &mut Criterion) {
+    let rules = GameRules::default();
+    let mut registry = PlayerRegistry::new();
+    let compact = registry.compact(&start()).unwrap();
+
+    let mut state = start();
+    let strings = allocations(|| play_half_innings(&mut state, &rules));
+    let mut state = compact.clone();
+    let interned = allocations(|| play_half_innings(&mut state, &rules));
+    println!(
+        "allocations per half inning: string IDs {}, player IDs {}",
+        strings / HALF_INNINGS,
+        interned / HALF_INNINGS
+    );
+
+    let mut group = c.benchmark_group("half_innings");
+    group.bench_function("string_ids", |b| {
+        b.iter(|| {
+            let mut state = start();
+            play_half_innings(&mut state, &rules);
+            state
+        })
+    });
+    group.bench_function("player_ids", |b| {
+        b.iter(|| {
+            let mut state = compact.clone();
+            play_half_innings(&mut state, &rules);
+            state
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, compact_state);
+criterion_main!(benches);
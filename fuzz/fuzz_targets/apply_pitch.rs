@@ -0,0 +1,62 @@
+//! Random valid states and rules with a run of pitch strings: every pitch
+//! is either rejected with an error or leads to a valid state.
+//!
+//! The first eight bytes seed the state and rules. The next two override
+//! the inning and the batting team's score so the search reaches the
+//! extremes, and the rest are comma-separated pitch names.
+
+#![no_main]
+
+use baselom_core::engine::{apply_pitch, apply_play};
+use baselom_core::testing::Arbitrary;
+use baselom_core::validators::validate_state_with_rules;
+use baselom_core::{GameRules, GameState, GameStatus, PitchResult, Play, Team};
+use libfuzzer_sys::fuzz_target;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((seed, rest)) = data.split_first_chunk::<8>() else {
+        return;
+    };
+    let mut rng = StdRng::seed_from_u64(u64::from_le_bytes(*seed));
+    let rules = GameRules::arbitrary(&mut rng);
+    let mut state = GameState::arbitrary(&mut rng);
+    let rest = match rest {
+        [inning, runs, rest @ ..] => {
+            state.inning = (*inning).max(1);
+            let score = u32::MAX - u32::from(*runs);
+            match state.half.batting_team() {
+                Team::Away => state.score.away = score,
+                Team::Home => state.score.home = score,
+            }
+            rest
+        }
+        _ => rest,
+    };
+    if validate_state_with_rules(&state, &rules).is_err() {
+        return;
+    }
+
+    for (number, name) in String::from_utf8_lossy(rest).split(',').enumerate() {
+        if state.status == GameStatus::Final {
+            return;
+        }
+        if state.current_batter_id.is_none() {
+            let batter = Play::Batter(format!("batter{}", number));
+            state = apply_play(&state, &batter, &rules).expect("a new batter is always accepted");
+        }
+        let Ok(pitch) = name.trim().parse::<PitchResult>() else {
+            continue;
+        };
+        if let Ok(next) = apply_pitch(&state, pitch, &rules) {
+            if let Err(err) = validate_state_with_rules(&next, &rules) {
+                panic!(
+                    "{:?} from {:?} gave an invalid state: {}",
+                    pitch, state, err
+                );
+            }
+            state = next;
+        }
+    }
+});
@@ -0,0 +1,14 @@
+//! Arbitrary bytes as a serialized state: deserializing and validating
+//! must reject bad input with an error, never a panic.
+
+#![no_main]
+
+use baselom_core::validators::validate_state;
+use baselom_core::GameState;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(state) = serde_json::from_slice::<GameState>(data) {
+        let _ = validate_state(&state);
+    }
+});
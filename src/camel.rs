@@ -0,0 +1,789 @@
+//! camelCase object keys at the serde layer, for the WASM boundary.
+//!
+//! [`CamelKeys`] wraps a serializer so struct fields, map keys and enum tags
+//! come out in camelCase, and [`SnakeKeys`] wraps a deserializer so they are
+//! read back as snake_case (snake_case input is accepted too). Values keep
+//! their spelling and the keys of ID-keyed fields are left alone, as with
+//! [`crate::models::to_json_camel`], but without an intermediate JSON value.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+use crate::models::{camel_to_snake, snake_to_camel, ID_KEYED_FIELDS};
+
+thread_local! {
+    static CAMEL_NAMES: RefCell<HashMap<&'static str, &'static str>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The camelCase form of a field or variant name.
+///
+/// Serializers take these names as `&'static str`, so each converted name
+/// is leaked once and reused; there are only as many as the types have
+/// fields and variants.
+fn camel(name: &'static str) -> &'static str {
+    if !name.contains('_') {
+        return name;
+    }
+    CAMEL_NAMES.with(|names| {
+        *names
+            .borrow_mut()
+            .entry(name)
+            .or_insert_with(|| Box::leak(snake_to_camel(name).into_boxed_str()))
+    })
+}
+
+/// Whether a snake_case field name holds a map keyed by player ID.
+fn is_id_keyed(name: &str) -> bool {
+    ID_KEYED_FIELDS.contains(&name)
+}
+
+// =============================================================================
+// Serialization
+// =============================================================================
+
+/// A serializer (or one of its compound serializers) writing camelCase keys.
+pub(crate) struct CamelKeys<S> {
+    inner: S,
+    /// Leave the keys of a map in this value alone
+    keep_keys: bool,
+    /// This value is a map key
+    is_key: bool,
+}
+
+impl<S> CamelKeys<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        CamelKeys::wrap(inner, false, false)
+    }
+
+    fn wrap(inner: S, keep_keys: bool, is_key: bool) -> Self {
+        CamelKeys {
+            inner,
+            keep_keys,
+            is_key,
+        }
+    }
+}
+
+/// A value to serialize through [`CamelKeys`].
+struct Camel<'a, T: ?Sized> {
+    value: &'a T,
+    keep_keys: bool,
+    is_key: bool,
+}
+
+impl<T: Serialize + ?Sized> Serialize for Camel<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value
+            .serialize(CamelKeys::wrap(serializer, self.keep_keys, self.is_key))
+    }
+}
+
+fn nested<T: ?Sized>(value: &T) -> Camel<'_, T> {
+    Camel {
+        value,
+        keep_keys: false,
+        is_key: false,
+    }
+}
+
+macro_rules! forward_serialize {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<S::Ok, S::Error> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<S: Serializer> Serializer for CamelKeys<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = CamelKeys<S::SerializeSeq>;
+    type SerializeTuple = CamelKeys<S::SerializeTuple>;
+    type SerializeTupleStruct = CamelKeys<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = CamelKeys<S::SerializeTupleVariant>;
+    type SerializeMap = CamelKeys<S::SerializeMap>;
+    type SerializeStruct = CamelKeys<S::SerializeStruct>;
+    type SerializeStructVariant = CamelKeys<S::SerializeStructVariant>;
+
+    forward_serialize! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_bytes(&[u8]),
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        if self.is_key && !self.keep_keys {
+            self.inner.serialize_str(&snake_to_camel(v))
+        } else {
+            self.inner.serialize_str(v)
+        }
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_none()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_some(&Camel {
+            value,
+            keep_keys: self.keep_keys,
+            is_key: self.is_key,
+        })
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_unit_variant(name, variant_index, variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_struct(
+            name,
+            &Camel {
+                value,
+                keep_keys: self.keep_keys,
+                is_key: self.is_key,
+            },
+        )
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_newtype_variant(name, variant_index, camel(variant), &nested(value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Ok(CamelKeys::new(self.inner.serialize_seq(len)?))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Ok(CamelKeys::new(self.inner.serialize_tuple(len)?))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Ok(CamelKeys::new(
+            self.inner.serialize_tuple_struct(name, len)?,
+        ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Ok(CamelKeys::new(self.inner.serialize_tuple_variant(
+            name,
+            variant_index,
+            camel(variant),
+            len,
+        )?))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        Ok(CamelKeys::wrap(
+            self.inner.serialize_map(len)?,
+            self.keep_keys,
+            false,
+        ))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Ok(CamelKeys::new(self.inner.serialize_struct(name, len)?))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Ok(CamelKeys::new(self.inner.serialize_struct_variant(
+            name,
+            variant_index,
+            camel(variant),
+            len,
+        )?))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_compound {
+    ($($trait:ident::$method:ident),* $(,)?) => {
+        $(
+            impl<S: ser::$trait> ser::$trait for CamelKeys<S> {
+                type Ok = S::Ok;
+                type Error = S::Error;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+                    self.inner.$method(&nested(value))
+                }
+
+                fn end(self) -> Result<S::Ok, S::Error> {
+                    self.inner.end()
+                }
+            }
+        )*
+    };
+}
+
+forward_compound! {
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+}
+
+impl<S: ser::SerializeMap> ser::SerializeMap for CamelKeys<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), S::Error> {
+        self.inner.serialize_key(&Camel {
+            value: key,
+            keep_keys: self.keep_keys,
+            is_key: true,
+        })
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        self.inner.serialize_value(&nested(value))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+macro_rules! struct_fields {
+    ($($trait:ident),* $(,)?) => {
+        $(
+            impl<S: ser::$trait> ser::$trait for CamelKeys<S> {
+                type Ok = S::Ok;
+                type Error = S::Error;
+
+                fn serialize_field<T: Serialize + ?Sized>(
+                    &mut self,
+                    key: &'static str,
+                    value: &T,
+                ) -> Result<(), S::Error> {
+                    let value = Camel {
+                        value,
+                        keep_keys: is_id_keyed(key),
+                        is_key: false,
+                    };
+                    self.inner.serialize_field(camel(key), &value)
+                }
+
+                fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+                    self.inner.skip_field(camel(key))
+                }
+
+                fn end(self) -> Result<S::Ok, S::Error> {
+                    self.inner.end()
+                }
+            }
+        )*
+    };
+}
+
+struct_fields!(SerializeStruct, SerializeStructVariant);
+
+// =============================================================================
+// Deserialization
+// =============================================================================
+
+/// How [`SnakeKeys`] treats the names it reads.
+#[derive(Clone, Copy)]
+enum Names<'a> {
+    /// A value; `keep_keys` leaves the keys of a map in it alone
+    Value { keep_keys: bool },
+    /// A map key; `id_keyed` is set when it names an ID-keyed field
+    Key {
+        keep: bool,
+        id_keyed: &'a Cell<bool>,
+    },
+    /// An enum tag, renamed only when that gives one of `variants`
+    Variant { variants: &'static [&'static str] },
+}
+
+const VALUE: Names<'static> = Names::Value { keep_keys: false };
+
+/// A deserializer (or one of its visitors, seeds and accessors) reading
+/// camelCase keys as snake_case.
+pub(crate) struct SnakeKeys<'a, D> {
+    inner: D,
+    names: Names<'a>,
+}
+
+impl<D> SnakeKeys<'static, D> {
+    pub(crate) fn new(inner: D) -> Self {
+        SnakeKeys {
+            inner,
+            names: VALUE,
+        }
+    }
+}
+
+impl<'a, D> SnakeKeys<'a, D> {
+    fn with<T>(&self, inner: T) -> SnakeKeys<'a, T> {
+        SnakeKeys {
+            inner,
+            names: self.names,
+        }
+    }
+
+    /// The snake_case name to read for `name`, or `None` to read it as is.
+    fn rename(&self, name: &str) -> Option<String> {
+        match self.names {
+            Names::Value { .. } => None,
+            Names::Key { keep, id_keyed } => {
+                if keep {
+                    id_keyed.set(false);
+                    return None;
+                }
+                let snake = camel_to_snake(name);
+                id_keyed.set(is_id_keyed(name) || is_id_keyed(&snake));
+                Some(snake)
+            }
+            Names::Variant { variants } => {
+                let snake = camel_to_snake(name);
+                (variants.contains(&snake.as_str()) && !variants.contains(&name)).then_some(snake)
+            }
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error> {
+                let visitor = self.with(visitor);
+                self.inner.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for SnakeKeys<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = SnakeKeys {
+            inner: visitor,
+            names: Names::Variant { variants },
+        };
+        self.inner.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<V::Value, E> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for SnakeKeys<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<V::Value, E> {
+        match self.rename(v) {
+            Some(snake) => self.inner.visit_string(snake),
+            None => self.inner.visit_str(v),
+        }
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<V::Value, E> {
+        match self.rename(v) {
+            Some(snake) => self.inner.visit_string(snake),
+            None => self.inner.visit_borrowed_str(v),
+        }
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<V::Value, E> {
+        match self.rename(&v) {
+            Some(snake) => self.inner.visit_string(snake),
+            None => self.inner.visit_string(v),
+        }
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        let deserializer = self.with(deserializer);
+        self.inner.visit_some(deserializer)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        let deserializer = self.with(deserializer);
+        self.inner.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.inner.visit_seq(SnakeKeys::new(seq))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        let keep_keys = matches!(self.names, Names::Value { keep_keys: true });
+        self.inner.visit_map(SnakeMap {
+            inner: map,
+            keep_keys,
+            id_keyed: Cell::new(false),
+        })
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        let variants = match self.names {
+            Names::Variant { variants } => variants,
+            _ => &[],
+        };
+        self.inner.visit_enum(SnakeKeys {
+            inner: data,
+            names: Names::Variant { variants },
+        })
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for SnakeKeys<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        let deserializer = self.with(deserializer);
+        self.inner.deserialize(deserializer)
+    }
+}
+
+impl<'de, A: de::SeqAccess<'de>> de::SeqAccess<'de> for SnakeKeys<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error> {
+        self.inner.next_element_seed(SnakeKeys::new(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+/// A map read through [`SnakeKeys`], remembering whether the last key named
+/// an ID-keyed field so its value keeps its keys.
+struct SnakeMap<A> {
+    inner: A,
+    keep_keys: bool,
+    id_keyed: Cell<bool>,
+}
+
+impl<'de, A: de::MapAccess<'de>> de::MapAccess<'de> for SnakeMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        let seed = SnakeKeys {
+            inner: seed,
+            names: Names::Key {
+                keep: self.keep_keys,
+                id_keyed: &self.id_keyed,
+            },
+        };
+        self.inner.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        let seed = SnakeKeys {
+            inner: seed,
+            names: Names::Value {
+                keep_keys: self.id_keyed.get(),
+            },
+        };
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'de, A: de::EnumAccess<'de>> de::EnumAccess<'de> for SnakeKeys<'a, A> {
+    type Error = A::Error;
+    type Variant = SnakeKeys<'a, A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), A::Error> {
+        let (value, variant) = self.inner.variant_seed(SnakeKeys {
+            inner: seed,
+            names: self.names,
+        })?;
+        Ok((
+            value,
+            SnakeKeys {
+                inner: variant,
+                names: VALUE,
+            },
+        ))
+    }
+}
+
+impl<'de, A: de::VariantAccess<'de>> de::VariantAccess<'de> for SnakeKeys<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+        self.inner.newtype_variant_seed(SnakeKeys::new(seed))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.inner.tuple_variant(len, SnakeKeys::new(visitor))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        self.inner.struct_variant(fields, SnakeKeys::new(visitor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::apply_event;
+    use crate::log::GameLog;
+    use crate::models::{
+        from_json_camel, to_json_camel, Base, DefensiveLineup, GameEvent, GameRules, GameState,
+        Lineup, Play, Position,
+    };
+    use crate::stats::BoxScore;
+    use serde::de::DeserializeOwned;
+
+    fn to_camel_value<T: Serialize>(value: &T) -> serde_json::Value {
+        value
+            .serialize(CamelKeys::new(serde_json::value::Serializer))
+            .unwrap()
+    }
+
+    fn from_camel_value<T: DeserializeOwned>(value: serde_json::Value) -> T {
+        T::deserialize(SnakeKeys::new(value)).unwrap()
+    }
+
+    /// `value` through the wrappers matches the JSON camelCase path both ways.
+    fn assert_matches_json_path<T: Serialize + DeserializeOwned + PartialEq + fmt::Debug>(
+        value: &T,
+    ) {
+        let expected: serde_json::Value =
+            serde_json::from_str(&to_json_camel(value).unwrap()).unwrap();
+        let camel = to_camel_value(value);
+        assert_eq!(camel, expected);
+        assert_eq!(&from_camel_value::<T>(camel), value);
+    }
+
+    fn tracked_state() -> GameState {
+        let home = DefensiveLineup::new(
+            Position::FIELDERS
+                .iter()
+                .zip(1..)
+                .map(|(&position, n)| (position, format!("h{}", n))),
+        );
+        let mut state = GameState::builder()
+            .runner_on(Base::Second, "a2")
+            .batter("a3")
+            .pitcher("h1")
+            .lineups(
+                Lineup::new((1..=9).map(|n| format!("h{}", n))),
+                Lineup::new((1..=9).map(|n| format!("a{}", n))),
+            )
+            .build()
+            .unwrap();
+        state.defensive_lineups.home = home;
+        state.pitch_counts.insert("h1".to_string(), 12);
+        state.batters_faced.insert("h1".to_string(), 2);
+        state
+    }
+
+    #[test]
+    fn test_matches_json_camel_path() {
+        let state = tracked_state();
+        assert_matches_json_path(&state);
+        assert_matches_json_path(&GameRules::slowpitch_softball());
+        assert_matches_json_path(&GameEvent::Pickoff { base: Base::First });
+        assert_matches_json_path(&Play::<String>::Event(GameEvent::GroundOut {
+            advancement: vec![],
+        }));
+
+        let rules = GameRules::default();
+        let mut log = GameLog::new(state);
+        log.apply(Play::Event(GameEvent::HomeRun), &rules).unwrap();
+        assert_matches_json_path(&BoxScore::from_log(&log, &rules));
+    }
+
+    #[test]
+    fn test_keys_and_values_keep_their_spelling_where_they_should() {
+        let camel = to_camel_value(&tracked_state());
+        assert_eq!(camel["currentBatterId"], "a3");
+        assert_eq!(camel["pitchCounts"]["h1"], 12);
+        assert_eq!(camel["status"], "in_progress");
+        assert_eq!(camel["defensiveLineups"]["home"]["positions"]["1B"], "h3");
+    }
+
+    #[test]
+    fn test_reads_snake_case_and_camel_case() {
+        let state = tracked_state();
+        let snake = serde_json::to_value(&state).unwrap();
+        assert_eq!(from_camel_value::<GameState>(snake), state);
+
+        let event: GameEvent = from_camel_value(serde_json::json!({"groundOut": {}}));
+        assert_eq!(
+            event,
+            GameEvent::GroundOut {
+                advancement: vec![]
+            }
+        );
+        let expected = apply_event(&state, &event, &GameRules::default()).unwrap();
+        let json = to_json_camel(&expected).unwrap();
+        assert_eq!(from_json_camel::<GameState>(&json).unwrap(), expected);
+    }
+}
@@ -0,0 +1,407 @@
+//! Differences between two game states.
+//!
+//! A [`StateDelta`] lists only the fields that changed between two states,
+//! so a client can animate what happened on a play, or receive deltas
+//! instead of full states and rebuild each state with [`StateDelta::apply`].
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    Base, Bases, Count, DefensiveLineup, GameState, GameStatus, InningHalf, InningScore,
+    LeadChange, Lineup, PerTeam, Score, Team, TeamTotals,
+};
+
+/// A field's value before and after.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Change<T> {
+    pub from: T,
+    pub to: T,
+}
+
+/// A base whose runner changed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BaseChange {
+    pub base: Base,
+    /// Runner on the base before (`None` if it was empty)
+    pub from: Option<String>,
+    /// Runner on the base after (`None` if it is empty)
+    pub to: Option<String>,
+}
+
+/// The fields that differ between two states, created with
+/// [`GameState::diff`].
+///
+/// Unchanged fields are `None` and left out of the serialized form.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct StateDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inning: Option<Change<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub half: Option<Change<InningHalf>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outs: Option<Change<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<Change<Count>>,
+    /// Bases whose runner changed, from first to third
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bases: Vec<BaseChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<Change<Score>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home_team_id: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub away_team_id: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_batter_id: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_pitcher_id: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Change<GameStatus>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_batters: Option<Change<PerTeam<Option<String>>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_score: Option<Change<Vec<InningScore>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_totals: Option<Change<PerTeam<TeamTotals>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lineups: Option<Change<PerTeam<Lineup>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defensive_lineups: Option<Change<PerTeam<DefensiveLineup>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_players: Option<Change<PerTeam<Vec<String>>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batters_faced: Option<Change<BTreeMap<String, u32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub half_inning_batters: Option<Change<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub half_inning_runs: Option<Change<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dh_forfeited: Option<Change<PerTeam<bool>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pitch_counts: Option<Change<BTreeMap<String, u32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plate_appearance_pitches: Option<Change<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mound_visits: Option<Change<PerTeam<u8>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pitcher_removal_required: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responsible_pitchers: Option<Change<Bases>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs_allowed: Option<Change<BTreeMap<String, u32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub earned_runs_allowed: Option<Change<BTreeMap<String, u32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unearned_runners: Option<Change<[bool; 3]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outs_lost_to_errors: Option<Change<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lead_changes: Option<Change<Vec<LeadChange>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<Change<u32>>,
+}
+
+fn change<T: Clone + PartialEq>(from: &T, to: &T) -> Option<Change<T>> {
+    (from != to).then(|| Change {
+        from: from.clone(),
+        to: to.clone(),
+    })
+}
+
+fn apply_change<T: Clone>(field: &mut T, change: &Option<Change<T>>) {
+    if let Some(change) = change {
+        *field = change.to.clone();
+    }
+}
+
+impl GameState {
+    /// The changes that turn this state into `other`.
+    pub fn diff(&self, other: &GameState) -> StateDelta {
+        // Destructured so a new field cannot be left out of the delta.
+        let GameState {
+            inning,
+            half,
+            outs,
+            count,
+            bases,
+            score,
+            home_team_id,
+            away_team_id,
+            current_batter_id,
+            current_pitcher_id,
+            status,
+            last_batters,
+            line_score,
+            team_totals,
+            lineups,
+            defensive_lineups,
+            removed_players,
+            batters_faced,
+            half_inning_batters,
+            half_inning_runs,
+            dh_forfeited,
+            pitch_counts,
+            plate_appearance_pitches,
+            mound_visits,
+            pitcher_removal_required,
+            responsible_pitchers,
+            runs_allowed,
+            earned_runs_allowed,
+            unearned_runners,
+            outs_lost_to_errors,
+            lead_changes,
+            schema_version,
+        } = other;
+        StateDelta {
+            inning: change(&self.inning, inning),
+            half: change(&self.half, half),
+            outs: change(&self.outs, outs),
+            count: change(&self.count, count),
+            bases: Base::ALL
+                .into_iter()
+                .filter(|&base| self.bases.runner_at(base) != bases.runner_at(base))
+                .map(|base| BaseChange {
+                    base,
+                    from: self.bases.runner_at(base).map(str::to_string),
+                    to: bases.runner_at(base).map(str::to_string),
+                })
+                .collect(),
+            score: change(&self.score, score),
+            home_team_id: change(&self.home_team_id, home_team_id),
+            away_team_id: change(&self.away_team_id, away_team_id),
+            current_batter_id: change(&self.current_batter_id, current_batter_id),
+            current_pitcher_id: change(&self.current_pitcher_id, current_pitcher_id),
+            status: change(&self.status, status),
+            last_batters: change(&self.last_batters, last_batters),
+            line_score: change(&self.line_score, line_score),
+            team_totals: change(&self.team_totals, team_totals),
+            lineups: change(&self.lineups, lineups),
+            defensive_lineups: change(&self.defensive_lineups, defensive_lineups),
+            removed_players: change(&self.removed_players, removed_players),
+            batters_faced: change(&self.batters_faced, batters_faced),
+            half_inning_batters: change(&self.half_inning_batters, half_inning_batters),
+            half_inning_runs: change(&self.half_inning_runs, half_inning_runs),
+            dh_forfeited: change(&self.dh_forfeited, dh_forfeited),
+            pitch_counts: change(&self.pitch_counts, pitch_counts),
+            plate_appearance_pitches: change(
+                &self.plate_appearance_pitches,
+                plate_appearance_pitches,
+            ),
+            mound_visits: change(&self.mound_visits, mound_visits),
+            pitcher_removal_required: change(
+                &self.pitcher_removal_required,
+                pitcher_removal_required,
+            ),
+            responsible_pitchers: change(&self.responsible_pitchers, responsible_pitchers),
+            runs_allowed: change(&self.runs_allowed, runs_allowed),
+            earned_runs_allowed: change(&self.earned_runs_allowed, earned_runs_allowed),
+            unearned_runners: change(&self.unearned_runners, unearned_runners),
+            outs_lost_to_errors: change(&self.outs_lost_to_errors, outs_lost_to_errors),
+            lead_changes: change(&self.lead_changes, lead_changes),
+            schema_version: change(&self.schema_version, schema_version),
+        }
+    }
+}
+
+impl StateDelta {
+    /// Whether the two states were equal.
+    pub fn is_empty(&self) -> bool {
+        *self == StateDelta::default()
+    }
+
+    /// Runs each team scored between the two states (negative if the score
+    /// went down, as when diffing back to an earlier state).
+    pub fn score_delta(&self) -> PerTeam<i64> {
+        let runs = |team: Team| {
+            self.score.as_ref().map_or(0, |score| {
+                let (from, to) = match team {
+                    Team::Home => (score.from.home, score.to.home),
+                    Team::Away => (score.from.away, score.to.away),
+                };
+                i64::from(to) - i64::from(from)
+            })
+        };
+        PerTeam {
+            home: runs(Team::Home),
+            away: runs(Team::Away),
+        }
+    }
+
+    /// `base` with every changed field set to its new value.
+    ///
+    /// Applying `a.diff(&b)` to `a` gives `b`.
+    pub fn apply(&self, base: &GameState) -> GameState {
+        let mut state = base.clone();
+        apply_change(&mut state.inning, &self.inning);
+        apply_change(&mut state.half, &self.half);
+        apply_change(&mut state.outs, &self.outs);
+        apply_change(&mut state.count, &self.count);
+        for change in &self.bases {
+            state.bases.set(change.base, change.to.clone());
+        }
+        apply_change(&mut state.score, &self.score);
+        apply_change(&mut state.home_team_id, &self.home_team_id);
+        apply_change(&mut state.away_team_id, &self.away_team_id);
+        apply_change(&mut state.current_batter_id, &self.current_batter_id);
+        apply_change(&mut state.current_pitcher_id, &self.current_pitcher_id);
+        apply_change(&mut state.status, &self.status);
+        apply_change(&mut state.last_batters, &self.last_batters);
+        apply_change(&mut state.line_score, &self.line_score);
+        apply_change(&mut state.team_totals, &self.team_totals);
+        apply_change(&mut state.lineups, &self.lineups);
+        apply_change(&mut state.defensive_lineups, &self.defensive_lineups);
+        apply_change(&mut state.removed_players, &self.removed_players);
+        apply_change(&mut state.batters_faced, &self.batters_faced);
+        apply_change(&mut state.half_inning_batters, &self.half_inning_batters);
+        apply_change(&mut state.half_inning_runs, &self.half_inning_runs);
+        apply_change(&mut state.dh_forfeited, &self.dh_forfeited);
+        apply_change(&mut state.pitch_counts, &self.pitch_counts);
+        apply_change(
+            &mut state.plate_appearance_pitches,
+            &self.plate_appearance_pitches,
+        );
+        apply_change(&mut state.mound_visits, &self.mound_visits);
+        apply_change(
+            &mut state.pitcher_removal_required,
+            &self.pitcher_removal_required,
+        );
+        apply_change(&mut state.responsible_pitchers, &self.responsible_pitchers);
+        apply_change(&mut state.runs_allowed, &self.runs_allowed);
+        apply_change(&mut state.earned_runs_allowed, &self.earned_runs_allowed);
+        apply_change(&mut state.unearned_runners, &self.unearned_runners);
+        apply_change(&mut state.outs_lost_to_errors, &self.outs_lost_to_errors);
+        apply_change(&mut state.lead_changes, &self.lead_changes);
+        apply_change(&mut state.schema_version, &self.schema_version);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{play_events, start};
+    use crate::models::{GameEvent, GameRules, PitchResult, Play};
+
+    fn lineup(prefix: &str) -> Lineup {
+        Lineup::new((1..=9).map(|slot| format!("{}{}", prefix, slot)))
+            .with_pitcher(format!("{}9", prefix))
+    }
+
+    /// Every state of a short game with hits, walks, outs and a mound
+    /// visit, starting with the initial state.
+    fn game_states() -> Vec<GameState> {
+        let mut state = GameState::new();
+        state.lineups = PerTeam {
+            home: lineup("h"),
+            away: lineup("a"),
+        };
+        state.current_pitcher_id = Some("h9".to_string());
+        let state = start(&state).unwrap();
+        let single = || {
+            Play::Event(GameEvent::Single {
+                advancement: vec![],
+            })
+        };
+        let ground_out = || {
+            Play::Event(GameEvent::GroundOut {
+                advancement: vec![],
+            })
+        };
+        let mut plays = vec![
+            Play::Pitch(PitchResult::Ball),
+            Play::Pitch(PitchResult::StrikeCalled),
+            single(),
+            Play::Event(GameEvent::Double {
+                advancement: vec![],
+            }),
+            Play::MoundVisit(Team::Home),
+        ];
+        plays.extend((0..4).map(|_| Play::Pitch(PitchResult::Ball)));
+        plays.extend([
+            Play::Event(GameEvent::HomeRun),
+            ground_out(),
+            ground_out(),
+            ground_out(),
+        ]);
+        let mut states = vec![state.clone()];
+        states.extend(play_events(&state, &plays, &GameRules::default(), false).unwrap());
+        states
+    }
+
+    #[test]
+    fn test_diff_then_apply_gives_target() {
+        let states = game_states();
+        for from in &states {
+            for to in &states {
+                let delta = from.diff(to);
+                assert_eq!(delta.apply(from), *to);
+                assert_eq!(delta.is_empty(), from == to);
+
+                let json = serde_json::to_string(&delta).unwrap();
+                let streamed: StateDelta = serde_json::from_str(&json).unwrap();
+                assert_eq!(streamed, delta);
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_lists_changed_fields() {
+        let before = GameState::builder()
+            .runner_on(Base::First, "r1")
+            .count(1, 2)
+            .batter("b")
+            .build()
+            .unwrap();
+        let mut after = before.clone();
+        after.bases.set(Base::First, None);
+        after.bases.set(Base::Second, Some("r1".to_string()));
+        after.count = Count::default();
+        after.score.away = 2;
+
+        let delta = before.diff(&after);
+        assert_eq!(
+            delta.bases,
+            vec![
+                BaseChange {
+                    base: Base::First,
+                    from: Some("r1".to_string()),
+                    to: None,
+                },
+                BaseChange {
+                    base: Base::Second,
+                    from: None,
+                    to: Some("r1".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            delta.count,
+            Some(Change {
+                from: Count::new(1, 2),
+                to: Count::default(),
+            })
+        );
+        assert_eq!(delta.score_delta(), PerTeam { home: 0, away: 2 });
+        assert_eq!(
+            after.diff(&before).score_delta(),
+            PerTeam { home: 0, away: -2 }
+        );
+        assert!(delta.outs.is_none() && delta.inning.is_none());
+
+        let value = serde_json::to_value(&delta).unwrap();
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["bases", "count", "score"]);
+    }
+}
@@ -0,0 +1,365 @@
+//! Text renderings of a game state for debugging and terminal tools.
+//!
+//! The output is independent of locale and stable enough to compare in
+//! snapshot tests. Everything is ASCII except the base and out markers of
+//! [`GameState::render_diamond`].
+
+use std::fmt::{self, Write};
+
+use crate::models::{Base, GameState, GameStatus, InningHalf};
+use crate::player::PlayerKey;
+
+/// Innings shown in the line score even before they are played.
+const REGULATION_COLUMNS: usize = 9;
+
+/// Characters per line of [`GameState::render_diamond`].
+const DIAMOND_WIDTH: usize = 17;
+
+/// One-line summary such as `T5 | 2 out | 3-1 | R1,R3 | AWAY 4 - 2 HOME`,
+/// or `FINAL | AWAY 4 - 2 HOME` once the game is over. Games not in
+/// progress get their status appended.
+impl<P: PlayerKey> fmt::Display for GameState<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let score = format!("AWAY {} - {} HOME", self.score.away, self.score.home);
+        if self.status == GameStatus::Final {
+            return write!(f, "FINAL | {}", score);
+        }
+        let half = match self.half {
+            InningHalf::Top => 'T',
+            InningHalf::Bottom => 'B',
+        };
+        write!(
+            f,
+            "{}{} | {} out | {} | {} | {}",
+            half,
+            self.inning,
+            self.outs,
+            self.count,
+            occupied_bases(self),
+            score
+        )?;
+        match self.status {
+            GameStatus::NotStarted => f.write_str(" | NOT STARTED"),
+            GameStatus::Suspended => f.write_str(" | SUSPENDED"),
+            GameStatus::InProgress | GameStatus::Final => Ok(()),
+        }
+    }
+}
+
+/// Occupied bases as `R1,R3`, or `empty`.
+fn occupied_bases<P: PlayerKey>(state: &GameState<P>) -> String {
+    let occupied: Vec<String> = Base::ALL
+        .into_iter()
+        .filter(|&base| state.bases.runner_at(base).is_some())
+        .map(|base| format!("R{}", base.number()))
+        .collect();
+    if occupied.is_empty() {
+        "empty".to_string()
+    } else {
+        occupied.join(",")
+    }
+}
+
+impl<P: PlayerKey> GameState<P> {
+    /// Multi-line scoreboard: the line score with runs, hits and errors
+    /// (when the state keeps one), the one-line summary, and the batter and
+    /// pitcher.
+    ///
+    /// Innings the home team did not bat in a final game show `X`.
+    pub fn scoreboard(&self) -> String {
+        let mut board = String::new();
+        if !self.line_score.is_empty() {
+            self.write_line_score(&mut board);
+        }
+        let name = |player: &Option<P>| {
+            player
+                .as_ref()
+                .map_or_else(|| "none".to_string(), ToString::to_string)
+        };
+        // Writing to a String cannot fail.
+        let _ = write!(
+            board,
+            "{}\nBatter: {} | Pitcher: {}",
+            self,
+            name(&self.current_batter_id),
+            name(&self.current_pitcher_id)
+        );
+        board
+    }
+
+    /// A small diamond with occupied bases filled (`◆`) and empty ones
+    /// hollow (`◇`), then the count with outs as dots and the score:
+    ///
+    /// ```text
+    ///         ◆
+    ///     ◇       ◆
+    ///         ⌂
+    ///   3-2  OUT ●●○
+    /// AWAY  3 - 2  HOME
+    /// ```
+    ///
+    /// Every line is padded to the same width, whatever the state.
+    pub fn render_diamond(&self) -> String {
+        let base = |base: Base| {
+            if self.bases.runner_at(base).is_some() {
+                '◆'
+            } else {
+                '◇'
+            }
+        };
+        let outs: String = (0..3)
+            .map(|out| if out < self.outs { '●' } else { '○' })
+            .collect();
+        let lines = [
+            base(Base::Second).to_string(),
+            format!("{}       {}", base(Base::Third), base(Base::First)),
+            "⌂".to_string(),
+            format!("{}  OUT {}", self.count, outs),
+            format!("AWAY {:>2} - {:<2} HOME", self.score.away, self.score.home),
+        ];
+        lines
+            .iter()
+            .map(|line| format!("{:^width$}", line, width = DIAMOND_WIDTH))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn write_line_score(&self, board: &mut String) {
+        let columns = self.line_score.len().max(REGULATION_COLUMNS);
+        board.push_str("    ");
+        for inning in 1..=columns {
+            let _ = write!(board, " {:>2}", inning);
+        }
+        board.push_str("    R  H  E\n");
+
+        let final_game = self.status == GameStatus::Final;
+        let rows = [
+            ("AWAY", &self.team_totals.away, self.score.away),
+            ("HOME", &self.team_totals.home, self.score.home),
+        ];
+        for (label, totals, runs) in rows {
+            board.push_str(label);
+            for inning in 0..columns {
+                let entry = self.line_score.get(inning);
+                let cell = match (label, entry) {
+                    (_, None) => String::new(),
+                    ("AWAY", Some(entry)) => entry.away.to_string(),
+                    (_, Some(entry)) => match entry.home {
+                        Some(home) => home.to_string(),
+                        None if final_game => "X".to_string(),
+                        None => String::new(),
+                    },
+                };
+                let _ = write!(board, " {:>2}", cell);
+            }
+            let _ = writeln!(
+                board,
+                "   {:>2} {:>2} {:>2}",
+                runs, totals.hits, totals.errors
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{apply_event, start};
+    use crate::models::{
+        Base, GameEvent, GameRules, GameState, GameStatus, InningHalf, InningScore, TeamTotals,
+    };
+
+    fn bases_loaded_jam() -> GameState {
+        GameState::builder()
+            .inning(7)
+            .bottom()
+            .outs(2)
+            .count(3, 2)
+            .score(2, 3)
+            .runner_on(Base::First, "h1")
+            .runner_on(Base::Second, "h2")
+            .runner_on(Base::Third, "h3")
+            .batter("h4")
+            .pitcher("a9")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fresh_game() {
+        let state = GameState::new();
+        assert_eq!(
+            state.to_string(),
+            "T1 | 0 out | 0-0 | empty | AWAY 0 - 0 HOME | NOT STARTED"
+        );
+        assert_eq!(
+            state.scoreboard(),
+            concat!(
+                "      1  2  3  4  5  6  7  8  9    R  H  E\n",
+                "AWAY  0                            0  0  0\n",
+                "HOME                               0  0  0\n",
+                "T1 | 0 out | 0-0 | empty | AWAY 0 - 0 HOME | NOT STARTED\n",
+                "Batter: none | Pitcher: none",
+            )
+        );
+    }
+
+    #[test]
+    fn test_bases_loaded_jam() {
+        let state = bases_loaded_jam();
+        assert_eq!(
+            state.to_string(),
+            "B7 | 2 out | 3-2 | R1,R2,R3 | AWAY 3 - 2 HOME"
+        );
+        assert_eq!(
+            state.scoreboard(),
+            "B7 | 2 out | 3-2 | R1,R2,R3 | AWAY 3 - 2 HOME\nBatter: h4 | Pitcher: a9"
+        );
+    }
+
+    #[test]
+    fn test_runners_on_corners() {
+        let mut state = bases_loaded_jam();
+        state.bases.set(Base::Second, None);
+        state.half = InningHalf::Top;
+        state.inning = 5;
+        state.count.balls = 1;
+        state.current_batter_id = None;
+        assert_eq!(
+            state.to_string(),
+            "T5 | 2 out | 1-2 | R1,R3 | AWAY 3 - 2 HOME"
+        );
+        assert!(state.scoreboard().ends_with("Batter: none | Pitcher: a9"));
+    }
+
+    #[test]
+    fn test_final_state() {
+        let mut state = start(&GameState::new()).unwrap();
+        state.current_batter_id = Some("a1".to_string());
+        state.current_pitcher_id = Some("h1".to_string());
+        state.inning = 9;
+        state.outs = 2;
+        state.score.away = 2;
+        state.score.home = 4;
+        state.line_score = (0..9)
+            .map(|inning| InningScore {
+                away: [0, 0, 2, 0, 0, 0, 0, 0, 0][inning],
+                home: (inning < 8).then(|| [0, 1, 0, 0, 3, 0, 0, 0][inning]),
+            })
+            .collect();
+        state.team_totals.away = TeamTotals {
+            hits: 8,
+            errors: 1,
+            left_on_base: 6,
+        };
+        state.team_totals.home = TeamTotals {
+            hits: 5,
+            ..TeamTotals::default()
+        };
+        let state = apply_event(
+            &state,
+            &GameEvent::FlyOut {
+                advancement: Vec::new(),
+            },
+            &GameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.to_string(), "FINAL | AWAY 2 - 4 HOME");
+        assert_eq!(
+            state.scoreboard(),
+            concat!(
+                "      1  2  3  4  5  6  7  8  9    R  H  E\n",
+                "AWAY  0  0  2  0  0  0  0  0  0    2  8  1\n",
+                "HOME  0  1  0  0  3  0  0  0  X    4  5  0\n",
+                "FINAL | AWAY 2 - 4 HOME\n",
+                "Batter: none | Pitcher: h1",
+            )
+        );
+    }
+
+    #[test]
+    fn test_extra_innings_widen_the_line_score() {
+        let mut state = bases_loaded_jam();
+        state.line_score = vec![InningScore::default(); 11];
+        state.line_score[10].home = None;
+        let board = state.scoreboard();
+        assert!(board.starts_with("      1  2  3  4  5  6  7  8  9 10 11    R  H  E\n"));
+    }
+
+    #[test]
+    fn test_suspended() {
+        let mut state = bases_loaded_jam();
+        state.status = GameStatus::Suspended;
+        assert!(state.to_string().ends_with(" | SUSPENDED"));
+    }
+
+    #[test]
+    fn test_diamond_empty_bases() {
+        assert_eq!(
+            GameState::new().render_diamond(),
+            concat!(
+                "        ◇        \n",
+                "    ◇       ◇    \n",
+                "        ⌂        \n",
+                "  0-0  OUT ○○○   \n",
+                "AWAY  0 - 0  HOME",
+            )
+        );
+    }
+
+    #[test]
+    fn test_diamond_bases_loaded() {
+        assert_eq!(
+            bases_loaded_jam().render_diamond(),
+            concat!(
+                "        ◆        \n",
+                "    ◆       ◆    \n",
+                "        ⌂        \n",
+                "  3-2  OUT ●●○   \n",
+                "AWAY  3 - 2  HOME",
+            )
+        );
+    }
+
+    #[test]
+    fn test_diamond_partial_bases() {
+        let mut state = bases_loaded_jam();
+        state.bases.set(Base::Second, None);
+        state.outs = 1;
+        state.count.balls = 0;
+        state.score.away = 12;
+        assert_eq!(
+            state.render_diamond(),
+            concat!(
+                "        ◇        \n",
+                "    ◆       ◆    \n",
+                "        ⌂        \n",
+                "  0-2  OUT ●○○   \n",
+                "AWAY 12 - 2  HOME",
+            )
+        );
+
+        state.bases.set(Base::First, None);
+        state.bases.set(Base::Third, None);
+        state.bases.set(Base::Second, Some("h2".to_string()));
+        let diamond = state.render_diamond();
+        assert!(diamond.starts_with("        ◆        \n    ◇       ◇    \n"));
+    }
+
+    #[test]
+    fn test_diamond_width_is_stable() {
+        let mut state = bases_loaded_jam();
+        for outs in 0..3 {
+            for runs in [0, 9, 10, 99] {
+                state.outs = outs;
+                state.score.home = runs;
+                state
+                    .bases
+                    .set(Base::Third, (runs > 9).then(|| "h3".to_string()));
+                for line in state.render_diamond().lines() {
+                    assert_eq!(line.chars().count(), 17, "{:?}", line);
+                }
+            }
+        }
+    }
+}
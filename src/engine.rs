@@ -1,13 +1,6331 @@
 //! FSM engine logic for state transitions.
 
-use crate::models::{GameRules, GameState};
+use std::collections::BTreeMap;
+
+use crate::errors::BaselomError;
+use crate::models::{
+    Advance, Base, BaseOutEvent, BaseOutState, BaseOutTransition, Bases, CourtesyRunnerRule,
+    ExtraInningsTiebreaker, GameEvent, GameEventKind, GameRules, GameState, GameStatus, InningHalf,
+    InningScore, LeadChange, PerTeam, PitchResult, Play, Position, RunnerAdvance, Score,
+    StepOutcome, Team, TeamTotals, TransitionOutcome, MAX_INNING,
+};
+use crate::observer::{notify, TransitionObserver};
+use crate::player::PlayerKey;
+use crate::validators::validate_state_with_rules;
+
+/// Outs needed to end a half inning.
+const OUTS_PER_HALF_INNING: u8 = 3;
+
+/// Base number of home plate, one past third.
+const HOME_PLATE: u8 = 4;
+
+/// What happened during a transition besides the new state, collected as
+/// the engine applies it.
+#[derive(Debug)]
+struct Effects<P> {
+    /// Runs scored, counting scorers whose ID is unknown and runs past a
+    /// saturated total
+    runs: u32,
+    scorers: Vec<P>,
+    charged_pitchers: Vec<P>,
+    earned: Vec<bool>,
+    /// An error, passed ball or obstruction let runners reach or advance
+    misplay: bool,
+    /// The batter reached on an error instead of being put out
+    out_lost: bool,
+    /// Runs scoring on the play are batted in by the batter
+    bats_in_runs: bool,
+    /// Runs the scorer ruled not batted in (`RunnerAdvance::force_no_rbi`)
+    runs_not_batted_in: u32,
+    outs_recorded: u8,
+    half_inning_ended: bool,
+    /// The half inning ended on `GameRules::inning_run_limit`
+    half_inning_ended_by_run_limit: bool,
+    plate_appearance_ended: bool,
+}
+
+impl<P> Default for Effects<P> {
+    fn default() -> Self {
+        Effects {
+            runs: 0,
+            scorers: Vec::new(),
+            charged_pitchers: Vec::new(),
+            earned: Vec::new(),
+            misplay: false,
+            out_lost: false,
+            bats_in_runs: false,
+            runs_not_batted_in: 0,
+            outs_recorded: 0,
+            half_inning_ended: false,
+            half_inning_ended_by_run_limit: false,
+            plate_appearance_ended: false,
+        }
+    }
+}
+
+impl<P> Effects<P> {
+    /// Report the effects, given the state they led to.
+    fn into_step(self, state: &GameState<P>) -> StepOutcome<P> {
+        let rbis = if self.bats_in_runs {
+            self.runs.saturating_sub(self.runs_not_batted_in)
+        } else {
+            0
+        };
+        StepOutcome {
+            runs_scored: self.runs,
+            scorers: self.scorers,
+            charged_pitchers: self.charged_pitchers,
+            earned: self.earned,
+            rbis: u8::try_from(rbis).unwrap_or(u8::MAX),
+            outs_recorded: self.outs_recorded,
+            half_inning_ended: self.half_inning_ended,
+            half_inning_ended_by_run_limit: self.half_inning_ended_by_run_limit,
+            game_ended: state.status == GameStatus::Final,
+        }
+    }
+}
+
+/// What finishing a transition needs to know about the state it started
+/// from, kept so the state itself can be changed in place.
+///
+/// The pitcher is kept because a play that ends the half inning brings in
+/// the other team's pitcher, while its runs and batter still count against
+/// the one who threw it.
+struct Before<P> {
+    inning: u8,
+    half: InningHalf,
+    pitcher: Option<P>,
+    outs: u8,
+    score: Score,
+    bases: Bases<P>,
+    responsible_pitchers: Bases<P>,
+    unearned_runners: [bool; 3],
+    outs_lost_to_errors: u8,
+}
+
+impl<P: Clone> Before<P> {
+    fn of(state: &GameState<P>) -> Self {
+        Before {
+            inning: state.inning,
+            half: state.half,
+            pitcher: state.current_pitcher_id.clone(),
+            outs: state.outs,
+            score: state.score.clone(),
+            bases: state.bases.clone(),
+            responsible_pitchers: state.responsible_pitchers.clone(),
+            unearned_runners: state.unearned_runners,
+            outs_lost_to_errors: state.outs_lost_to_errors,
+        }
+    }
+}
 
 /// Apply a pitch result to the game state.
-pub fn apply_pitch(
-    _state: &GameState,
-    _pitch_result: &str,
-    _rules: &GameRules,
-) -> Result<GameState, crate::errors::BaselomError> {
-    // TODO: Implement pitch processing logic
-    todo!("Implement apply_pitch")
+///
+/// Returns `BaselomError::InvalidState` if the state is inconsistent under
+/// `rules`, `BaselomError::GameAlreadyFinal` (or another status error) if
+/// the game is not in progress, `BaselomError::NoCurrentBatter` if the
+/// pitch ends the plate appearance with nobody at bat, and
+/// `BaselomError::RuleViolation` if the pitcher has reached the pitch limit
+/// or must be replaced after a mound visit past the limit.
+pub fn apply_pitch<P: PlayerKey>(
+    state: &GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    apply_pitch_detailed(state, pitch_result, rules).map(|outcome| outcome.state)
+}
+
+/// Apply a pitch result without validating the state first.
+///
+/// For callers (such as simulation loops) that only feed the engine states
+/// it produced; an invalid state gives an unspecified result.
+pub fn apply_pitch_unchecked<P: PlayerKey>(
+    state: &GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    let mut next = state.clone();
+    pitch_step(&mut next, pitch_result, rules)?;
+    Ok(next)
+}
+
+/// Apply a pitch result, reporting runs, outs and inning changes along with
+/// the new state.
+///
+/// Errors as [`apply_pitch`].
+pub fn apply_pitch_detailed<P: PlayerKey>(
+    state: &GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Result<TransitionOutcome<P>, BaselomError> {
+    let mut next = state.clone();
+    let step = step_pitch(&mut next, pitch_result, rules)?;
+    Ok(step.with_state(next))
+}
+
+/// Apply a pitch result, reporting what happens on it to `observer`.
+///
+/// Errors as [`apply_pitch`]; the observer hears nothing of a rejected
+/// pitch.
+pub fn apply_pitch_observed<P: PlayerKey>(
+    state: &GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+    observer: &mut (impl TransitionObserver<P> + ?Sized),
+) -> Result<GameState<P>, BaselomError> {
+    let mut next = state.clone();
+    let outcome = step_pitch(&mut next, pitch_result, rules)?;
+    notify(
+        observer,
+        state.inning,
+        state.half,
+        Some(pitch_result),
+        &outcome,
+        &next,
+    );
+    Ok(next)
+}
+
+/// Apply a pitch result in place, for callers that do not need the
+/// previous state.
+///
+/// Errors as [`apply_pitch`], leaving `state` unchanged.
+pub fn apply_pitch_mut<P: PlayerKey>(
+    state: &mut GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Result<(), BaselomError> {
+    step_pitch(state, pitch_result, rules).map(|_| ())
+}
+
+/// Apply a pitch result in place, reporting runs, outs and inning changes.
+///
+/// Errors as [`apply_pitch`], leaving `state` unchanged.
+pub fn step_pitch<P: PlayerKey>(
+    state: &mut GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Result<StepOutcome<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    validate_state_with_rules(state, rules)?;
+    pitch_step(state, pitch_result, rules)
+}
+
+/// Apply a pitch result in place to a state assumed to be valid and in
+/// progress.
+fn pitch_step<P: PlayerKey>(
+    state: &mut GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Result<StepOutcome<P>, BaselomError> {
+    check_pitch(state, pitch_result, rules)?;
+    let before = Before::of(state);
+    let mut effects = pitch_in_place(state, pitch_result, rules);
+    finish_in_place(&before, state, &mut effects, rules);
+    Ok(effects.into_step(state))
+}
+
+/// Reject a pitch the state does not allow. Once this passes, applying the
+/// pitch cannot fail.
+fn check_pitch<P: PlayerKey>(
+    state: &GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Result<(), BaselomError> {
+    if pitch_ends_plate_appearance(state, pitch_result, rules) {
+        require_batter(state)?;
+    }
+    check_pitch_limit(state, rules)?;
+    check_pitcher_removal(state)
+}
+
+/// Apply a pitch that passed [`check_pitch`] to `state`, counting it for
+/// the pitcher.
+fn pitch_in_place<P: PlayerKey>(
+    state: &mut GameState<P>,
+    pitch_result: PitchResult,
+    rules: &GameRules,
+) -> Effects<P> {
+    let mut effects = Effects::default();
+    count_pitch(state);
+    match pitch_result {
+        PitchResult::Ball => add_ball(state, rules, &mut effects),
+        PitchResult::StrikeCalled
+        | PitchResult::StrikeSwinging
+        | PitchResult::FoulTip
+        | PitchResult::FoulBunt => add_strike(state, rules, &mut effects),
+        PitchResult::Foul => {
+            if state.count.strikes + 1 < rules.strikes_for_out {
+                add_strike(state, rules, &mut effects);
+            }
+        }
+        PitchResult::HitByPitch => {
+            end_plate_appearance(state, rules, &mut effects);
+            process_walk(state, &mut effects);
+        }
+        PitchResult::StrikeSwingingDropped => {
+            if state.count.strikes + 1 >= rules.strikes_for_out
+                && batter_may_run_on_dropped_strike(state, rules)
+            {
+                end_plate_appearance(state, rules, &mut effects);
+                process_walk(state, &mut effects);
+            } else {
+                add_strike(state, rules, &mut effects);
+            }
+        }
+    }
+    // Walks and hit batsmen force runs in; strikeouts bat none in.
+    effects.bats_in_runs = effects.plate_appearance_ended
+        && matches!(pitch_result, PitchResult::Ball | PitchResult::HitByPitch);
+    effects
+}
+
+/// Reject a pitch from a pitcher at `GameRules::pitch_limit`, unless the
+/// limit was reached during the current plate appearance and
+/// `GameRules::pitch_limit_finish_batter` lets the pitcher finish it.
+fn check_pitch_limit<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+) -> Result<(), BaselomError> {
+    let (Some(limit), Some(pitcher)) = (rules.pitch_limit, &state.current_pitcher_id) else {
+        return Ok(());
+    };
+    let thrown = state.pitches_thrown(pitcher.borrow());
+    let before_batter = thrown.saturating_sub(state.plate_appearance_pitches);
+    let finishing_batter = rules.pitch_limit_finish_batter && before_batter < u32::from(limit);
+    if thrown >= u32::from(limit) && !finishing_batter {
+        return Err(BaselomError::RuleViolation(format!(
+            "Pitcher {} has reached the pitch limit of {}",
+            pitcher, limit
+        )));
+    }
+    Ok(())
+}
+
+/// Reject a pitch from a pitcher who must leave after a mound visit past the
+/// limit.
+fn check_pitcher_removal<P: PlayerKey>(state: &GameState<P>) -> Result<(), BaselomError> {
+    match (&state.pitcher_removal_required, &state.current_pitcher_id) {
+        (Some(required), Some(pitcher)) if required == pitcher => {
+            Err(BaselomError::RuleViolation(format!(
+                "Pitcher {} must be replaced after a mound visit past the limit",
+                pitcher
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Count a pitch for the current pitcher.
+fn count_pitch<P: PlayerKey>(state: &mut GameState<P>) {
+    if let Some(pitcher) = &state.current_pitcher_id {
+        increment(&mut state.pitch_counts, pitcher);
+    }
+    state.plate_appearance_pitches = state.plate_appearance_pitches.saturating_add(1);
+}
+
+/// Add one to the count for `player_id`, only allocating the key for a
+/// player's first entry.
+fn increment<P: PlayerKey>(counts: &mut BTreeMap<P, u32>, player_id: &P) {
+    match counts.get_mut(player_id.borrow()) {
+        Some(count) => *count = count.saturating_add(1),
+        None => {
+            counts.insert(player_id.clone(), 1);
+        }
+    }
+}
+
+/// Apply a pitch result given as a string (for the Python/WASM boundary).
+///
+/// Returns `BaselomError::InvalidPitchResult` listing the allowed values if
+/// the string is not a known pitch result.
+pub fn apply_pitch_str<P: PlayerKey>(
+    state: &GameState<P>,
+    pitch_result: &str,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    apply_pitch(state, pitch_result.parse()?, rules)
+}
+
+/// Apply a pitch sequence in scorekeeping shorthand (e.g. `"BBCFS"`; see
+/// [`PitchResult::from_code`]).
+///
+/// Pitches are applied in order until the plate appearance or the game
+/// ends; returns the final state and the number of pitches applied, so any
+/// pitches left over show up as a count shorter than the sequence.
+///
+/// Returns `BaselomError::ValidationError` naming the character and its
+/// (zero-based) position if the sequence contains an unknown code, before
+/// applying any pitch, and otherwise errors as [`apply_pitch`].
+pub fn apply_pitch_sequence<P: PlayerKey>(
+    state: &GameState<P>,
+    sequence: &str,
+    rules: &GameRules,
+) -> Result<(GameState<P>, usize), BaselomError> {
+    let pitches = sequence
+        .chars()
+        .enumerate()
+        .map(|(position, code)| {
+            PitchResult::from_code(code).ok_or_else(|| {
+                BaselomError::ValidationError(format!(
+                    "Unknown pitch code '{}' at position {}",
+                    code, position
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let total = pitches.len();
+    let mut state = state.clone();
+    for (applied, pitch) in pitches.into_iter().enumerate() {
+        let ends_plate_appearance = pitch_ends_plate_appearance(&state, pitch, rules);
+        state = apply_pitch(&state, pitch, rules)?;
+        if ends_plate_appearance || state.status == GameStatus::Final {
+            return Ok((state, applied + 1));
+        }
+    }
+    Ok((state, total))
+}
+
+/// Apply a plate-appearance event to the game state.
+///
+/// Runners advance with default rules: one base on a single, two on a
+/// double, and all runners score on a triple or home run. Runs are credited
+/// to the batting team and the count resets for the next plate appearance.
+///
+/// Returns `BaselomError::InvalidState` if the state is inconsistent under
+/// `rules`, `BaselomError::GameAlreadyFinal` (or another status error) if
+/// the game is not in progress, `BaselomError::NoCurrentBatter` if the event
+/// ends the plate appearance with nobody at bat, or
+/// `BaselomError::RuleViolation` for an intentional walk when
+/// `GameRules::allow_auto_intentional_walk` is off.
+pub fn apply_event<P: PlayerKey>(
+    state: &GameState<P>,
+    event: &GameEvent,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    apply_event_detailed(state, event, rules).map(|outcome| outcome.state)
+}
+
+/// Apply a plate-appearance event without validating the state first.
+///
+/// See [`apply_pitch_unchecked`].
+pub fn apply_event_unchecked<P: PlayerKey>(
+    state: &GameState<P>,
+    event: &GameEvent,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    let mut next = state.clone();
+    event_step(&mut next, event, rules)?;
+    Ok(next)
+}
+
+/// Apply a plate-appearance event, reporting runs, outs and inning changes
+/// along with the new state.
+///
+/// Errors as [`apply_event`].
+pub fn apply_event_detailed<P: PlayerKey>(
+    state: &GameState<P>,
+    event: &GameEvent,
+    rules: &GameRules,
+) -> Result<TransitionOutcome<P>, BaselomError> {
+    let mut next = state.clone();
+    let step = step(&mut next, event, rules)?;
+    Ok(step.with_state(next))
+}
+
+/// Apply a plate-appearance event, reporting what happens on it to
+/// `observer`.
+///
+/// Errors as [`apply_event`]; the observer hears nothing of a rejected
+/// event.
+pub fn apply_event_observed<P: PlayerKey>(
+    state: &GameState<P>,
+    event: &GameEvent,
+    rules: &GameRules,
+    observer: &mut (impl TransitionObserver<P> + ?Sized),
+) -> Result<GameState<P>, BaselomError> {
+    let mut next = state.clone();
+    let outcome = step(&mut next, event, rules)?;
+    notify(observer, state.inning, state.half, None, &outcome, &next);
+    Ok(next)
+}
+
+/// Apply a plate-appearance event in place, reporting runs, outs and inning
+/// changes.
+///
+/// Errors as [`apply_event`]. The transition is all or nothing: on error
+/// `state` is left exactly as it was.
+pub fn step<P: PlayerKey>(
+    state: &mut GameState<P>,
+    event: &GameEvent,
+    rules: &GameRules,
+) -> Result<StepOutcome<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    validate_state_with_rules(state, rules)?;
+    event_step(state, event, rules)
+}
+
+/// Apply a plate-appearance event in place to a state assumed to be valid
+/// and in progress.
+///
+/// The event is worked out against the unchanged state, which is only
+/// replaced once nothing can fail.
+fn event_step<P: PlayerKey>(
+    state: &mut GameState<P>,
+    event: &GameEvent,
+    rules: &GameRules,
+) -> Result<StepOutcome<P>, BaselomError> {
+    let before = Before::of(state);
+    let (next, mut effects) = event_transition(state, event, rules)?;
+    *state = next;
+    finish_in_place(&before, state, &mut effects, rules);
+    Ok(effects.into_step(state))
+}
+
+/// Work out the state a plate-appearance event leads to, before the
+/// lineup, line score and per-PA counters are brought up to date.
+fn event_transition<P: PlayerKey>(
+    state: &GameState<P>,
+    event: &GameEvent,
+    rules: &GameRules,
+) -> Result<(GameState<P>, Effects<P>), BaselomError> {
+    if event.ends_plate_appearance() {
+        require_batter(state)?;
+    }
+    let mut effects = Effects::default();
+    let finished;
+    let state = if event.ends_plate_appearance() {
+        let mut next = state.clone();
+        end_plate_appearance(&mut next, rules, &mut effects);
+        finished = next;
+        &finished
+    } else {
+        state
+    };
+
+    let next = match event {
+        GameEvent::Single { advancement } => {
+            process_batted_ball(state, Some(Base::First), advancement, rules, &mut effects)?
+        }
+        GameEvent::Double { advancement } => {
+            process_batted_ball(state, Some(Base::Second), advancement, rules, &mut effects)?
+        }
+        GameEvent::Triple { advancement } => {
+            process_batted_ball(state, Some(Base::Third), advancement, rules, &mut effects)?
+        }
+        GameEvent::HomeRun => process_home_run(state, &mut effects),
+        GameEvent::GroundRuleDouble => process_hit(state, Base::Second, &mut effects),
+        GameEvent::GroundOut { advancement }
+        | GameEvent::FlyOut { advancement }
+        | GameEvent::LineOut { advancement } => {
+            process_batted_ball(state, None, advancement, rules, &mut effects)?
+        }
+        GameEvent::InfieldFly { advancement } => {
+            process_infield_fly(state, advancement, rules, &mut effects)?
+        }
+        GameEvent::IntentionalWalk => {
+            if !rules.allow_auto_intentional_walk {
+                return Err(BaselomError::RuleViolation(
+                    "Intentional walks without pitches are not allowed".to_string(),
+                ));
+            }
+            walk(state, &mut effects)
+        }
+        GameEvent::WildPitch | GameEvent::PassedBall => advance_all_runners(state, &mut effects),
+        GameEvent::Balk => process_balk(state, rules, &mut effects)?,
+        GameEvent::Pickoff { base } => process_pickoff(state, *base, rules, &mut effects)?,
+        GameEvent::PickoffError { base } => process_pickoff_error(state, *base, &mut effects)?,
+        GameEvent::DoublePlay {
+            runners_out,
+            batter_out,
+        } => process_double_play(state, runners_out, *batter_out, rules, &mut effects)?,
+        GameEvent::SacFly { tagging_up } => {
+            process_sac_fly(state, tagging_up, rules, &mut effects)?
+        }
+        GameEvent::SacBunt { advancing } => {
+            process_sac_bunt(state, advancing, rules, &mut effects)?
+        }
+        GameEvent::FieldersChoice { runner_out_at } => {
+            process_fielders_choice(state, *runner_out_at, rules, &mut effects)?
+        }
+        GameEvent::AwardBases {
+            batter_to,
+            runners_advance,
+        } => process_award_bases(state, *batter_to, *runners_advance, &mut effects)?,
+        GameEvent::CatchersInterference => GameState {
+            team_totals: charge_error(state),
+            ..walk(state, &mut effects)
+        },
+        GameEvent::Obstruction { runner, awarded } => {
+            process_obstruction(state, *runner, *awarded, &mut effects)?
+        }
+        GameEvent::ReachedOnError {
+            batter_to,
+            runners_advance,
+        } => GameState {
+            team_totals: charge_error(state),
+            ..process_award_bases(state, *batter_to, *runners_advance, &mut effects)?
+        },
+    };
+
+    effects.misplay = matches!(
+        event,
+        GameEvent::ReachedOnError { .. }
+            | GameEvent::CatchersInterference
+            | GameEvent::PickoffError { .. }
+            | GameEvent::PassedBall
+            | GameEvent::Obstruction { .. }
+    );
+    effects.out_lost = matches!(event, GameEvent::ReachedOnError { .. });
+    effects.bats_in_runs = event.bats_in_runs();
+    effects.runs_not_batted_in = runs_not_batted_in(event);
+    Ok((next, effects))
+}
+
+/// Runs on `event` by runners the scorer ruled not batted in.
+fn runs_not_batted_in(event: &GameEvent) -> u32 {
+    let advancement = match event {
+        GameEvent::Single { advancement }
+        | GameEvent::Double { advancement }
+        | GameEvent::Triple { advancement }
+        | GameEvent::GroundOut { advancement }
+        | GameEvent::FlyOut { advancement }
+        | GameEvent::LineOut { advancement }
+        | GameEvent::InfieldFly { advancement } => advancement.as_slice(),
+        _ => &[],
+    };
+    let judged = advancement
+        .iter()
+        .filter(|advance| advance.force_no_rbi && advance.to == Advance::Home)
+        .count();
+    u32::try_from(judged).unwrap_or(u32::MAX)
+}
+
+/// Apply a play: a new batter stepping in, a pitch, an event, a mound
+/// visit or a pitching change.
+///
+/// Errors as [`apply_pitch`], [`apply_event`], [`mound_visit`] or
+/// [`substitute_pitcher`]; a new batter is accepted whenever the game is in
+/// progress.
+pub fn apply_play<P: PlayerKey>(
+    state: &GameState<P>,
+    play: &Play<P>,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    apply_play_detailed(state, play, rules).map(|outcome| outcome.state)
+}
+
+/// Apply a play, reporting runs, outs and inning changes along with the new
+/// state.
+///
+/// Errors as [`apply_play`].
+pub fn apply_play_detailed<P: PlayerKey>(
+    state: &GameState<P>,
+    play: &Play<P>,
+    rules: &GameRules,
+) -> Result<TransitionOutcome<P>, BaselomError> {
+    let mut next = state.clone();
+    let step = step_play(&mut next, play, rules)?;
+    Ok(step.with_state(next))
+}
+
+/// Apply a play in place, reporting runs, outs and inning changes.
+///
+/// Errors as [`apply_play`], leaving `state` unchanged.
+pub fn step_play<P: PlayerKey>(
+    state: &mut GameState<P>,
+    play: &Play<P>,
+    rules: &GameRules,
+) -> Result<StepOutcome<P>, BaselomError> {
+    match play {
+        Play::Batter(batter) => {
+            ensure_in_progress(state)?;
+            state.current_batter_id = Some(batter.clone());
+            Ok(StepOutcome::default())
+        }
+        Play::Pitch(pitch_result) => step_pitch(state, *pitch_result, rules),
+        Play::Event(event) => step(state, event, rules),
+        Play::MoundVisit(team) => {
+            *state = mound_visit(state, *team, rules)?;
+            Ok(StepOutcome::default())
+        }
+        Play::PitchingChange { pitcher, injured } => {
+            *state = substitute_pitcher(state, pitcher.borrow(), *injured, rules)?;
+            Ok(StepOutcome::default())
+        }
+        Play::DoubleSwitch {
+            pitcher,
+            lineup_slot,
+            fielder,
+        } => {
+            *state = double_switch(
+                state,
+                pitcher.borrow(),
+                *lineup_slot,
+                fielder.borrow(),
+                rules,
+            )?;
+            Ok(StepOutcome::default())
+        }
+    }
+}
+
+/// Apply a scripted sequence of plays, returning the state after each one.
+///
+/// Once the game is final, the remaining plays are skipped when
+/// `ignore_after_final` is set, and rejected with
+/// `BaselomError::GameAlreadyFinal` otherwise.
+///
+/// On failure returns the index of the rejected play along with its error.
+pub fn play_events<P: PlayerKey>(
+    initial: &GameState<P>,
+    plays: &[Play<P>],
+    rules: &GameRules,
+    ignore_after_final: bool,
+) -> Result<Vec<GameState<P>>, (usize, BaselomError)> {
+    let mut states: Vec<GameState<P>> = Vec::with_capacity(plays.len());
+    for (index, play) in plays.iter().enumerate() {
+        let current = states.last().unwrap_or(initial);
+        if ignore_after_final && current.status == GameStatus::Final {
+            break;
+        }
+        let next = apply_play(current, play, rules).map_err(|err| (index, err))?;
+        states.push(next);
+    }
+    Ok(states)
+}
+
+/// The base-out state after `event` and the runs it scores, without
+/// building a `GameState`.
+///
+/// Runners move as [`apply_event`] moves them by default: forced runners
+/// on a walk, one base on a single, two on a double, and all runners score
+/// on a triple or home run. An out that makes the third out ends the half
+/// inning (`next` is `None`) without scoring.
+pub fn base_out_transition(state: BaseOutState, event: BaseOutEvent) -> BaseOutTransition {
+    let occupied = state.bases_occupied();
+    let (advanced, outs) = match event {
+        BaseOutEvent::Walk => {
+            // The batter pushes along the unbroken run of runners from first.
+            let forced = occupied & !(occupied + 1);
+            ((forced << 1 | 1) | (occupied & !forced), state.outs())
+        }
+        BaseOutEvent::Single => (occupied << 1 | 1, state.outs()),
+        BaseOutEvent::Double => (occupied << 2 | 0b10, state.outs()),
+        BaseOutEvent::Triple => (occupied << 3 | 0b100, state.outs()),
+        BaseOutEvent::HomeRun => (occupied << 4 | 0b1000, state.outs()),
+        BaseOutEvent::Out => (occupied, state.outs() + 1),
+    };
+    if outs == OUTS_PER_HALF_INNING {
+        return BaseOutTransition {
+            next: None,
+            runs_scored: 0,
+        };
+    }
+    // Bits past third base are runners who crossed the plate.
+    BaseOutTransition {
+        next: BaseOutState::new(advanced & 0b111, outs),
+        runs_scored: (advanced >> 3).count_ones() as u8,
+    }
+}
+
+/// The event kinds `state` accepts under `rules`, in
+/// [`GameEventKind::ALL`] order.
+///
+/// [`apply_event`] accepts [`GameEventKind::event`] for every kind listed
+/// and rejects it for every kind left out. Nothing is legal unless the game
+/// is in progress and the state is valid.
+pub fn legal_events<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> Vec<GameEventKind> {
+    if ensure_in_progress(state).is_err() || validate_state_with_rules(state, rules).is_err() {
+        return Vec::new();
+    }
+    GameEventKind::ALL
+        .into_iter()
+        .filter(|&kind| event_is_legal(state, kind, rules))
+        .collect()
+}
+
+/// The pitch results `state` accepts under `rules`, in
+/// [`PitchResult::ALL`] order, matching what [`apply_pitch`] accepts.
+pub fn legal_pitches<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> Vec<PitchResult> {
+    if ensure_in_progress(state).is_err() || validate_state_with_rules(state, rules).is_err() {
+        return Vec::new();
+    }
+    PitchResult::ALL
+        .into_iter()
+        .filter(|&pitch| check_pitch(state, pitch, rules).is_ok())
+        .collect()
+}
+
+/// Whether a valid, in-progress state accepts the event of `kind`.
+fn event_is_legal<P: PlayerKey>(
+    state: &GameState<P>,
+    kind: GameEventKind,
+    rules: &GameRules,
+) -> bool {
+    let batter = state.current_batter_id.is_some();
+    let two_outs = state.outs >= OUTS_PER_HALF_INNING - 1;
+    let runner_on = |base: Base| state.bases.runner_at(base).is_some();
+    let next_open = |base: Base| base.next().is_none_or(|next| !runner_on(next));
+    match kind {
+        GameEventKind::Single
+        | GameEventKind::Double
+        | GameEventKind::Triple
+        | GameEventKind::HomeRun
+        | GameEventKind::GroundRuleDouble
+        | GameEventKind::GroundOut
+        | GameEventKind::FlyOut
+        | GameEventKind::LineOut
+        | GameEventKind::AwardBases
+        | GameEventKind::ReachedOnError
+        | GameEventKind::CatchersInterference => batter,
+        GameEventKind::InfieldFly => {
+            batter && !two_outs && runner_on(Base::First) && runner_on(Base::Second)
+        }
+        GameEventKind::IntentionalWalk => batter && rules.allow_auto_intentional_walk,
+        GameEventKind::WildPitch | GameEventKind::PassedBall => true,
+        GameEventKind::Balk => {
+            !state.bases.is_empty()
+                || (rules.balk_with_empty_bases_is_ball
+                    && (batter || !pitch_ends_plate_appearance(state, PitchResult::Ball, rules)))
+        }
+        GameEventKind::Pickoff(base) => runner_on(base),
+        GameEventKind::PickoffError(base) | GameEventKind::Obstruction(base) => {
+            runner_on(base) && next_open(base)
+        }
+        GameEventKind::DoublePlay(base) => batter && !two_outs && runner_on(base),
+        GameEventKind::SacFly => batter && !two_outs && runner_on(Base::Third),
+        GameEventKind::SacBunt(base) => batter && !two_outs && runner_on(base) && next_open(base),
+        GameEventKind::FieldersChoice(base) => batter && runner_on(base),
+    }
+}
+
+/// Whether the game has reached a terminal condition.
+///
+/// A game is over once it is marked final, or when the batting home team
+/// wins without finishing its half inning: by taking the lead in the final
+/// regulation inning or any extra inning (a walk-off), or by reaching the
+/// mercy rule margin.
+pub fn is_game_over<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> bool {
+    state.status == GameStatus::Final || home_wins_while_batting(state, rules)
+}
+
+/// Whether the batting home team has won without finishing the half inning.
+fn home_wins_while_batting<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> bool {
+    let home_batting_with_lead =
+        state.half == InningHalf::Bottom && state.score.home > state.score.away;
+    is_walk_off(state, rules) || (home_batting_with_lead && mercy_rule_reached(state, rules))
+}
+
+/// Whether either team leads by the mercy rule margin in an inning where
+/// the rule applies.
+fn mercy_rule_reached<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> bool {
+    rules.mercy_rule.as_ref().is_some_and(|mercy| {
+        state.inning >= mercy.after_inning
+            && state.score.home.abs_diff(state.score.away) >= mercy.run_differential
+    })
+}
+
+/// Whether the home team has taken the lead in its last turn at bat.
+fn is_walk_off<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> bool {
+    state.half == InningHalf::Bottom
+        && is_regulation_complete(state.inning, rules)
+        && state.score.home > state.score.away
+}
+
+/// Complete a transition: end the half inning on the run limit, charge the
+/// runs, update the line score, end the game if the home team won, and
+/// clear the batter once their plate appearance is over.
+fn finish_in_place<P: PlayerKey>(
+    before: &Before<P>,
+    next: &mut GameState<P>,
+    effects: &mut Effects<P>,
+    rules: &GameRules,
+) {
+    let batting = before.half.batting_team();
+    let runs = next.score.get(batting) - before.score.get(batting);
+    if !effects.half_inning_ended && inning_run_limit_reached(before, next, runs, rules) {
+        effects.half_inning_ended = true;
+        effects.half_inning_ended_by_run_limit = true;
+        end_half_inning(next, rules);
+    }
+    charge_runs(before, next, effects);
+    update_line_score(before, next);
+    record_lead_change(before, next);
+    finish_if_decided(next, rules);
+    if effects.plate_appearance_ended {
+        next.lineups.get_mut(before.half.batting_team()).advance();
+        if let Some(pitcher) = &before.pitcher {
+            increment(&mut next.batters_faced, pitcher);
+        }
+        next.half_inning_batters = next.half_inning_batters.saturating_add(1);
+    }
+    if effects.plate_appearance_ended || effects.half_inning_ended {
+        next.plate_appearance_pitches = 0;
+    }
+    if effects.half_inning_ended {
+        next.half_inning_batters = 0;
+        next.half_inning_runs = 0;
+    } else {
+        next.half_inning_runs = next.half_inning_runs.saturating_add(runs);
+    }
+    if effects.plate_appearance_ended || effects.half_inning_ended {
+        next.current_batter_id = next_batter(before, next, effects.plate_appearance_ended);
+    }
+}
+
+/// Whether `runs` scoring on a play bring the batting team to
+/// `GameRules::inning_run_limit` for the half inning, in an inning the limit
+/// applies to and without the home team winning on them.
+///
+/// Every run on the play that reaches the limit counts, even past it.
+fn inning_run_limit_reached<P: PlayerKey>(
+    before: &Before<P>,
+    next: &GameState<P>,
+    runs: u32,
+    rules: &GameRules,
+) -> bool {
+    let Some(limit) = rules.inning_run_limit else {
+        return false;
+    };
+    let uncapped = rules.last_inning_uncapped && is_regulation_complete(before.inning, rules);
+    runs > 0
+        && !uncapped
+        && next.half_inning_runs.saturating_add(runs) >= limit
+        && !home_wins_while_batting(next, rules)
+}
+
+/// Record a lead change if the play put a different team ahead or tied the
+/// game.
+fn record_lead_change<P: PlayerKey>(before: &Before<P>, next: &mut GameState<P>) {
+    let leader = next.score.leader();
+    if leader == before.score.leader() {
+        return;
+    }
+    let batting = before.half.batting_team();
+    let mut pitchers = PerTeam::<Option<P>>::default();
+    *pitchers.get_mut(batting) = next.lineups.get(batting).pitcher.clone();
+    *pitchers.get_mut(batting.opponent()) = before.pitcher.clone();
+    next.lead_changes.push(LeadChange {
+        inning: before.inning,
+        half: before.half,
+        leader,
+        score: next.score.clone(),
+        pitchers,
+    });
+}
+
+/// The batter after a plate appearance or half inning ends: the batting
+/// team's next batter when it has a lineup, and otherwise nobody (or the
+/// unchanged batter, if the half inning ended without changing sides).
+fn next_batter<P: PlayerKey>(
+    before: &Before<P>,
+    state: &GameState<P>,
+    plate_appearance_ended: bool,
+) -> Option<P> {
+    if state.status == GameStatus::Final {
+        return None;
+    }
+    let sides_changed = (state.inning, state.half) != (before.inning, before.half);
+    match state.due_up() {
+        Some(batter) => Some(batter.to_owned()),
+        None if plate_appearance_ended || sides_changed => None,
+        None => state.current_batter_id.clone(),
+    }
+}
+
+/// Mark the game final if the home team won during the transition,
+/// stranding the runners still on base.
+fn finish_if_decided<P: PlayerKey>(state: &mut GameState<P>, rules: &GameRules) {
+    if home_wins_while_batting(state, rules) {
+        state.status = GameStatus::Final;
+        strand_runners(state);
+    }
+}
+
+/// Charge each run of a transition to the pitcher responsible for the
+/// scorer and decide whether it was earned, then record who is responsible
+/// for the runners left on base.
+///
+/// A runner who was already on base keeps their pitcher. The batter, and
+/// any runner no pitcher was recorded for, are charged to the current
+/// pitcher. When a half inning ends the record starts over, so a runner
+/// placed on base for the next half is charged to whoever pitches its first
+/// play.
+///
+/// Runs are earned or not per the approximation described on
+/// `GameState::earned_runs_allowed`.
+fn charge_runs<P: PlayerKey>(
+    before: &Before<P>,
+    next: &mut GameState<P>,
+    effects: &mut Effects<P>,
+) {
+    let pitcher = before.pitcher.clone();
+    let base_before = |runner: &P::Key| {
+        Base::ALL
+            .into_iter()
+            .find(|&base| before.bases.runner_at(base) == Some(runner))
+    };
+    let responsible = |from: Option<Base>| -> Option<P> {
+        from.and_then(|base| before.responsible_pitchers.runner_at(base))
+            .map(ToOwned::to_owned)
+            .or_else(|| pitcher.clone())
+    };
+    let unearned_runner =
+        |from: Option<Base>| from.is_some_and(|base| before.unearned_runners[base.index()]);
+    let inning_over =
+        before.outs.saturating_add(before.outs_lost_to_errors) >= OUTS_PER_HALF_INNING;
+
+    // A batter whose ID is unknown scores without being listed.
+    let batting = before.half.batting_team();
+    let runs = next.score.get(batting) - before.score.get(batting);
+    let unnamed = (runs as usize).saturating_sub(effects.scorers.len());
+    let scored_from = effects
+        .scorers
+        .iter()
+        .map(|scorer| base_before(scorer.borrow()))
+        .chain(std::iter::repeat_n(None, unnamed));
+    let mut charged = Vec::new();
+    let mut earned = Vec::new();
+    for from in scored_from {
+        let Some(charged_pitcher) = responsible(from) else {
+            continue;
+        };
+        let is_earned = !(inning_over || effects.misplay || unearned_runner(from));
+        increment(&mut next.runs_allowed, &charged_pitcher);
+        if is_earned {
+            increment(&mut next.earned_runs_allowed, &charged_pitcher);
+        }
+        charged.push(charged_pitcher);
+        earned.push(is_earned);
+    }
+    effects.charged_pitchers = charged;
+    effects.earned = earned;
+
+    if (next.inning, next.half) != (before.inning, before.half) {
+        next.responsible_pitchers = Bases::default();
+        next.unearned_runners = [false; 3];
+        next.outs_lost_to_errors = 0;
+        return;
+    }
+    let mut responsible_pitchers = Bases::default();
+    let mut unearned_runners = [false; 3];
+    for base in Base::ALL {
+        let Some(runner) = next.bases.runner_at(base) else {
+            continue;
+        };
+        let from = base_before(runner);
+        responsible_pitchers.set(base, responsible(from));
+        unearned_runners[base.index()] =
+            unearned_runner(from) || (effects.misplay && from != Some(base));
+    }
+    next.responsible_pitchers = responsible_pitchers;
+    next.unearned_runners = unearned_runners;
+    if effects.out_lost {
+        next.outs_lost_to_errors = next.outs_lost_to_errors.saturating_add(1);
+    }
+}
+
+/// Carry the runs scored in a transition into the line score, opening the
+/// next entry when a new half inning starts.
+///
+/// States without a line score are left alone.
+fn update_line_score<P: PlayerKey>(before: &Before<P>, next: &mut GameState<P>) {
+    if next.line_score.is_empty() {
+        return;
+    }
+
+    let current = line_score_entry(&mut next.line_score, before.inning);
+    current.away += next.score.away - before.score.away;
+    if next.score.home > before.score.home {
+        *current.home.get_or_insert(0) += next.score.home - before.score.home;
+    }
+
+    if (next.inning, next.half) != (before.inning, before.half) {
+        let entry = line_score_entry(&mut next.line_score, next.inning);
+        if next.half == InningHalf::Bottom {
+            entry.home.get_or_insert(0);
+        }
+    }
+}
+
+/// The line score entry for `inning`, adding entries up to it if needed.
+fn line_score_entry(line_score: &mut Vec<InningScore>, inning: u8) -> &mut InningScore {
+    let index = usize::from(inning.max(1) - 1);
+    if line_score.len() <= index {
+        line_score.resize(index + 1, InningScore::default());
+    }
+    &mut line_score[index]
+}
+
+/// Start a game that has not started yet, sending up the away team's first
+/// batter if it has a lineup and no batter is set.
+///
+/// Returns `BaselomError::StateError` unless the status is `NotStarted`.
+pub fn start<P: PlayerKey>(state: &GameState<P>) -> Result<GameState<P>, BaselomError> {
+    let mut started = change_status(state, GameStatus::NotStarted, GameStatus::InProgress)?;
+    if started.current_batter_id.is_none() {
+        started.current_batter_id = started.due_up().map(ToOwned::to_owned);
+    }
+    Ok(started)
+}
+
+/// Suspend a game in progress.
+///
+/// Returns `BaselomError::StateError` unless the status is `InProgress`.
+pub fn suspend<P: PlayerKey>(state: &GameState<P>) -> Result<GameState<P>, BaselomError> {
+    change_status(state, GameStatus::InProgress, GameStatus::Suspended)
+}
+
+/// Resume a suspended game.
+///
+/// Returns `BaselomError::StateError` unless the status is `Suspended`.
+pub fn resume<P: PlayerKey>(state: &GameState<P>) -> Result<GameState<P>, BaselomError> {
+    change_status(state, GameStatus::Suspended, GameStatus::InProgress)
+}
+
+/// Send a pinch hitter up in the batting team's lineup slot `lineup_slot`
+/// (zero-based), replacing the player there; if that player is at bat, the
+/// new player takes over the plate appearance.
+///
+/// Sending up the team's own pitcher while it uses the designated hitter
+/// puts the pitcher in the batting order and forfeits the designated hitter
+/// for the rest of the game.
+///
+/// Returns `BaselomError::ValidationError` if the slot does not exist, and
+/// `BaselomError::RuleViolation` if the new player is already in the game
+/// or was removed earlier without `GameRules::allow_reentry`.
+pub fn substitute_batter<P: PlayerKey>(
+    state: &GameState<P>,
+    lineup_slot: usize,
+    new_player_id: &P::Key,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    let team = state.half.batting_team();
+    let replaced = state
+        .lineups
+        .get(team)
+        .players
+        .get(lineup_slot)
+        .cloned()
+        .ok_or_else(|| {
+            BaselomError::ValidationError(format!(
+                "The {} lineup has no slot {}",
+                team, lineup_slot
+            ))
+        })?;
+    let lineup = state.lineups.get(team);
+    let pitcher_bats = lineup
+        .pitcher
+        .as_ref()
+        .is_some_and(|pitcher| pitcher.borrow() == new_player_id)
+        && state.uses_designated_hitter(team, rules);
+    let mut next = if pitcher_bats {
+        forfeit_designated_hitter(state, team, lineup_slot, replaced.borrow())
+    } else {
+        substitute(state, team, replaced.borrow(), new_player_id, rules)?
+    };
+    if next.current_batter_id.as_ref() == Some(&replaced) {
+        next.current_batter_id = Some(new_player_id.to_owned());
+    }
+    Ok(next)
+}
+
+/// Put `team`'s pitcher in `lineup_slot`, removing `replaced` (from their
+/// position too) and ending the team's use of the designated hitter.
+fn forfeit_designated_hitter<P: PlayerKey>(
+    state: &GameState<P>,
+    team: Team,
+    lineup_slot: usize,
+    replaced: &P::Key,
+) -> GameState<P> {
+    let mut next = state.clone();
+    let lineup = next.lineups.get_mut(team);
+    if let Some(pitcher) = lineup.pitcher.clone() {
+        lineup.players[lineup_slot] = pitcher;
+    }
+    next.defensive_lineups
+        .get_mut(team)
+        .positions
+        .retain(|_, player| player.borrow() != replaced);
+    next.removed_players.get_mut(team).push(replaced.to_owned());
+    *next.dh_forfeited.get_mut(team) = true;
+    next
+}
+
+/// Put a pinch runner on `base` in place of the runner there, who also
+/// leaves the batting team's lineup.
+///
+/// Returns `BaselomError::RuleViolation` if the base is empty, the new
+/// player is already in the game, or the new player was removed earlier
+/// without `GameRules::allow_reentry`.
+pub fn substitute_runner<P: PlayerKey>(
+    state: &GameState<P>,
+    base: Base,
+    new_player_id: &P::Key,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    let replaced = state.bases.runner_at(base).ok_or_else(|| {
+        BaselomError::RuleViolation(format!("No runner on {} base to replace", base))
+    })?;
+    let mut next = substitute(
+        state,
+        state.half.batting_team(),
+        replaced,
+        new_player_id,
+        rules,
+    )?;
+    next.bases.set(base, Some(new_player_id.to_owned()));
+    Ok(next)
+}
+
+/// Put a courtesy runner on `base` for the runner there, who stays in the
+/// game: the lineup, positions and removed players are unchanged.
+///
+/// `GameRules::courtesy_runner` decides whose runner may be replaced, by
+/// the position they play in the batting team's defensive lineup.
+///
+/// Returns `BaselomError::RuleViolation` if the rules allow no courtesy
+/// runners, the base is empty, the runner does not play an eligible
+/// position, or the courtesy runner is already in the game.
+pub fn courtesy_run<P: PlayerKey>(
+    state: &GameState<P>,
+    base: Base,
+    substitute_id: &P::Key,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    if rules.courtesy_runner == CourtesyRunnerRule::None {
+        return Err(BaselomError::RuleViolation(
+            "Courtesy runners are not allowed".to_string(),
+        ));
+    }
+    let runner = state.bases.runner_at(base).ok_or_else(|| {
+        BaselomError::RuleViolation(format!("No runner on {} base to run for", base))
+    })?;
+    let position = state
+        .defensive_lineups
+        .get(state.half.batting_team())
+        .position_of(runner);
+    if !position.is_some_and(|position| rules.courtesy_runner.allows(position)) {
+        return Err(BaselomError::RuleViolation(format!(
+            "Runner {} does not play a position eligible for a courtesy runner",
+            runner
+        )));
+    }
+    if player_in_game(state, substitute_id) {
+        return Err(BaselomError::RuleViolation(format!(
+            "Player {} is already in the game",
+            substitute_id
+        )));
+    }
+    let mut next = state.clone();
+    next.bases.set(base, Some(substitute_id.to_owned()));
+    Ok(next)
+}
+
+/// Bring in a new pitcher for the fielding team, replacing the current one
+/// (in the lineup too, if the pitcher bats).
+///
+/// With `GameRules::three_batter_minimum`, the current pitcher must have
+/// faced three batters unless no batter has completed a plate appearance
+/// this half inning, the pitcher is `injured`, or a mound visit past the
+/// limit requires the change.
+///
+/// Returns `BaselomError::RuleViolation` if there is no current pitcher,
+/// the three-batter minimum is not met, the new pitcher is already in the
+/// game, or the new pitcher was removed earlier without
+/// `GameRules::allow_reentry`.
+pub fn substitute_pitcher<P: PlayerKey>(
+    state: &GameState<P>,
+    new_pitcher_id: &P::Key,
+    injured: bool,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    let replaced = state
+        .current_pitcher_id
+        .as_ref()
+        .ok_or_else(|| BaselomError::RuleViolation("No current pitcher to replace".to_string()))?
+        .borrow();
+    let faced = state.batters_faced.get(replaced).copied().unwrap_or(0);
+    let required = state
+        .pitcher_removal_required
+        .as_ref()
+        .is_some_and(|pitcher| pitcher.borrow() == replaced);
+    if rules.three_batter_minimum
+        && !injured
+        && !required
+        && state.half_inning_batters > 0
+        && faced < 3
+    {
+        return Err(BaselomError::RuleViolation(format!(
+            "Pitcher {} has faced {} of the minimum 3 batters",
+            replaced, faced
+        )));
+    }
+    let mut next = substitute(
+        state,
+        state.half.fielding_team(),
+        replaced,
+        new_pitcher_id,
+        rules,
+    )?;
+    next.current_pitcher_id = Some(new_pitcher_id.to_owned());
+    if required {
+        next.pitcher_removal_required = None;
+    }
+    let lineup = next.lineups.get_mut(state.half.fielding_team());
+    if lineup
+        .pitcher
+        .as_ref()
+        .is_some_and(|pitcher| pitcher.borrow() == replaced)
+    {
+        lineup.pitcher = Some(new_pitcher_id.to_owned());
+    }
+    Ok(next)
+}
+
+/// Replace the fielding team's pitcher and the player in `lineup_slot` at
+/// once: `new_pitcher_id` bats in `lineup_slot`, and `new_fielder_id` takes
+/// the old pitcher's place in the batting order and the replaced player's
+/// position.
+///
+/// Each substitution follows the rules of [`substitute_pitcher`] or of any
+/// other substitution; if either is rejected, neither is made.
+///
+/// Returns `BaselomError::ValidationError` if the slot does not exist, and
+/// `BaselomError::RuleViolation` if there is no current pitcher, the
+/// pitcher does not bat or already bats in `lineup_slot`, the three-batter
+/// minimum is not met, either new player is already in the game, or either
+/// was removed earlier without `GameRules::allow_reentry`.
+pub fn double_switch<P: PlayerKey>(
+    state: &GameState<P>,
+    new_pitcher_id: &P::Key,
+    lineup_slot: usize,
+    new_fielder_id: &P::Key,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    let team = state.half.fielding_team();
+    let lineup = state.lineups.get(team);
+    let replaced = lineup.players.get(lineup_slot).ok_or_else(|| {
+        BaselomError::ValidationError(format!("The {} lineup has no slot {}", team, lineup_slot))
+    })?;
+    let pitcher = state
+        .current_pitcher_id
+        .as_ref()
+        .ok_or_else(|| BaselomError::RuleViolation("No current pitcher to replace".to_string()))?
+        .borrow();
+    let pitcher_slot = lineup.slot_of(pitcher).ok_or_else(|| {
+        BaselomError::RuleViolation(format!(
+            "Pitcher {} does not bat, so there is no slot to switch",
+            pitcher
+        ))
+    })?;
+    if pitcher_slot == lineup_slot {
+        return Err(BaselomError::RuleViolation(format!(
+            "Pitcher {} already bats in slot {}",
+            pitcher, lineup_slot
+        )));
+    }
+    let next = substitute_pitcher(state, new_pitcher_id, false, rules)?;
+    let mut next = substitute(&next, team, replaced.borrow(), new_fielder_id, rules)?;
+    next.lineups
+        .get_mut(team)
+        .players
+        .swap(pitcher_slot, lineup_slot);
+    Ok(next)
+}
+
+/// Record a mound visit by `team`, which must be the fielding team.
+///
+/// Visits count for the whole game. With `GameRules::mound_visit_limit`, a
+/// visit past the limit is rejected, or, with
+/// `GameRules::mound_visit_over_limit_removes_pitcher`, recorded and the
+/// current pitcher must be replaced before the next pitch.
+///
+/// Returns `BaselomError::RuleViolation` if `team` is batting or the visit
+/// is past the limit and rejected.
+pub fn mound_visit<P: PlayerKey>(
+    state: &GameState<P>,
+    team: Team,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    ensure_in_progress(state)?;
+    if team != state.half.fielding_team() {
+        return Err(BaselomError::RuleViolation(format!(
+            "The {} team is batting and cannot visit the mound",
+            team
+        )));
+    }
+    let visits = state.mound_visits.get(team).saturating_add(1);
+    let mut next = state.clone();
+    if let Some(limit) = rules.mound_visit_limit.filter(|limit| visits > *limit) {
+        if !rules.mound_visit_over_limit_removes_pitcher {
+            return Err(BaselomError::RuleViolation(format!(
+                "The {} team has used all {} of its mound visits",
+                team, limit
+            )));
+        }
+        next.pitcher_removal_required = state.current_pitcher_id.clone();
+    }
+    *next.mound_visits.get_mut(team) = visits;
+    Ok(next)
+}
+
+/// Swap `new_player_id` in for `replaced` in `team`'s lineup and at their
+/// position, recording the replaced player as removed.
+fn substitute<P: PlayerKey>(
+    state: &GameState<P>,
+    team: Team,
+    replaced: &P::Key,
+    new_player_id: &P::Key,
+    rules: &GameRules,
+) -> Result<GameState<P>, BaselomError> {
+    if player_in_game(state, new_player_id) {
+        return Err(BaselomError::RuleViolation(format!(
+            "Player {} is already in the game",
+            new_player_id
+        )));
+    }
+    let returning = state
+        .removed_players
+        .get(team)
+        .iter()
+        .any(|id| id.borrow() == new_player_id);
+    if returning && !rules.allow_reentry {
+        return Err(BaselomError::RuleViolation(format!(
+            "Player {} has been removed and cannot re-enter",
+            new_player_id
+        )));
+    }
+
+    let mut next = state.clone();
+    let lineup = next.lineups.get_mut(team);
+    if let Some(slot) = lineup.slot_of(replaced) {
+        lineup.players[slot] = new_player_id.to_owned();
+    }
+    next.defensive_lineups
+        .get_mut(team)
+        .replace(replaced, new_player_id);
+    let removed = next.removed_players.get_mut(team);
+    removed.retain(|id| id.borrow() != new_player_id);
+    removed.push(replaced.to_owned());
+    Ok(next)
+}
+
+/// Whether a player is in either lineup (as a batter or the team's
+/// pitcher), on base, at bat or pitching.
+fn player_in_game<P: PlayerKey>(state: &GameState<P>, player_id: &P::Key) -> bool {
+    let is_player = |id: &Option<P>| id.as_ref().is_some_and(|id| id.borrow() == player_id);
+    let in_lineup = [Team::Home, Team::Away].into_iter().any(|team| {
+        let lineup = state.lineups.get(team);
+        lineup.slot_of(player_id).is_some() || is_player(&lineup.pitcher)
+    });
+    in_lineup
+        || state.bases.iter().any(|(_, runner)| runner == player_id)
+        || is_player(&state.current_batter_id)
+        || is_player(&state.current_pitcher_id)
+}
+
+/// Move from one status to another, rejecting any other starting status.
+fn change_status<P: PlayerKey>(
+    state: &GameState<P>,
+    from: GameStatus,
+    to: GameStatus,
+) -> Result<GameState<P>, BaselomError> {
+    if state.status != from {
+        return Err(BaselomError::StateError(format!(
+            "Cannot change status to {:?} from {:?}",
+            to, state.status
+        )));
+    }
+    Ok(GameState {
+        status: to,
+        ..state.clone()
+    })
+}
+
+/// Reject transitions unless the game is in progress.
+fn ensure_in_progress<P: PlayerKey>(state: &GameState<P>) -> Result<(), BaselomError> {
+    match state.status {
+        GameStatus::InProgress => Ok(()),
+        GameStatus::NotStarted => Err(BaselomError::GameNotStarted),
+        GameStatus::Final => Err(BaselomError::GameAlreadyFinal),
+        GameStatus::Suspended => Err(BaselomError::GameSuspended),
+    }
+}
+
+/// Whether a pitch completes the plate appearance (a walk, hit batter or
+/// third strike).
+fn pitch_ends_plate_appearance<P: PlayerKey>(
+    state: &GameState<P>,
+    pitch: PitchResult,
+    rules: &GameRules,
+) -> bool {
+    match pitch {
+        PitchResult::Ball => state.count.balls + 1 >= rules.balls_for_walk,
+        PitchResult::HitByPitch => true,
+        PitchResult::Foul => false,
+        PitchResult::StrikeCalled
+        | PitchResult::StrikeSwinging
+        | PitchResult::FoulTip
+        | PitchResult::FoulBunt
+        | PitchResult::StrikeSwingingDropped => state.count.strikes + 1 >= rules.strikes_for_out,
+    }
+}
+
+/// Reject completing a plate appearance when nobody is at bat.
+fn require_batter<P: PlayerKey>(state: &GameState<P>) -> Result<(), BaselomError> {
+    if state.current_batter_id.is_none() {
+        return Err(BaselomError::NoCurrentBatter);
+    }
+    Ok(())
+}
+
+/// Finish the current plate appearance: reset the count to the rules'
+/// starting count and remember the batter as the batting team's last
+/// batter.
+///
+/// The batter stays set for the rest of the transition (to be placed on a
+/// base) and is cleared by [`finish_in_place`].
+fn end_plate_appearance<P: PlayerKey>(
+    state: &mut GameState<P>,
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) {
+    effects.plate_appearance_ended = true;
+    state.count = rules.starting_count;
+    *state.last_batters.get_mut(state.half.batting_team()) = state.current_batter_id.clone();
+}
+
+/// Add a ball, awarding a walk on the fourth.
+fn add_ball<P: PlayerKey>(state: &mut GameState<P>, rules: &GameRules, effects: &mut Effects<P>) {
+    if state.count.balls + 1 >= rules.balls_for_walk {
+        end_plate_appearance(state, rules, effects);
+        process_walk(state, effects);
+    } else {
+        state.count.balls += 1;
+    }
+}
+
+/// Whether the dropped third strike rule lets the batter run: first base
+/// must be open, unless there are two outs.
+fn batter_may_run_on_dropped_strike<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> bool {
+    rules.dropped_third_strike
+        && (state.bases.runner_at(Base::First).is_none() || state.outs == OUTS_PER_HALF_INNING - 1)
+}
+
+/// Add a strike, recording a strikeout on the third.
+fn add_strike<P: PlayerKey>(state: &mut GameState<P>, rules: &GameRules, effects: &mut Effects<P>) {
+    if state.count.strikes + 1 >= rules.strikes_for_out {
+        end_plate_appearance(state, rules, effects);
+        record_out(state, rules, effects);
+    } else {
+        state.count.strikes += 1;
+    }
+}
+
+/// Whether regulation play is complete after the given inning.
+///
+/// `max_innings: None` means the game has no regulation length and never
+/// ends on innings alone.
+fn is_regulation_complete(inning: u8, rules: &GameRules) -> bool {
+    rules.max_innings.is_some_and(|max| inning >= max)
+}
+
+/// Whether the game is over once the current half inning ends.
+///
+/// From the final regulation inning on, the game ends after the top half if
+/// the home team leads (the bottom half is not needed) and after the bottom
+/// half unless the score is tied. The mercy rule ends the game after either
+/// half, except that a visiting team leading after the top half must still
+/// let the home team bat. With `allow_tie_after_innings`, a game still tied
+/// after the bottom half of that inning ends as a tie. Every game ends after
+/// the bottom of [`MAX_INNING`].
+fn game_ends_after_half<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> bool {
+    let home_leads = state.score.home > state.score.away;
+    let bottom = state.half == InningHalf::Bottom;
+    if bottom && state.inning == MAX_INNING {
+        return true;
+    }
+    if mercy_rule_reached(state, rules) && (home_leads || bottom) {
+        return true;
+    }
+    if bottom
+        && state.score.home == state.score.away
+        && rules
+            .allow_tie_after_innings
+            .is_some_and(|limit| state.inning >= limit)
+    {
+        return true;
+    }
+    if !is_regulation_complete(state.inning, rules) {
+        return false;
+    }
+    if bottom {
+        state.score.home != state.score.away
+    } else {
+        home_leads
+    }
+}
+
+/// Record an out, ending the half inning on the third.
+fn record_out<P: PlayerKey>(state: &mut GameState<P>, rules: &GameRules, effects: &mut Effects<P>) {
+    effects.outs_recorded += 1;
+    state.outs += 1;
+    if state.outs < OUTS_PER_HALF_INNING {
+        return;
+    }
+
+    effects.half_inning_ended = true;
+    end_half_inning(state, rules);
+}
+
+/// End the half inning, stranding the runners on base.
+///
+/// If the game is decided, it is marked final instead of starting the next
+/// half. Otherwise the pitcher becomes the new fielding team's lineup
+/// pitcher, when its lineup names one.
+fn end_half_inning<P: PlayerKey>(state: &mut GameState<P>, rules: &GameRules) {
+    strand_runners(state);
+    state.outs = 0;
+    state.count = rules.starting_count;
+    if game_ends_after_half(state, rules) {
+        state.bases = Bases::default();
+        state.status = GameStatus::Final;
+        return;
+    }
+
+    let (inning, half) = match state.half {
+        InningHalf::Top => (state.inning, InningHalf::Bottom),
+        InningHalf::Bottom => (state.inning + 1, InningHalf::Top),
+    };
+    state.bases = starting_bases(state, inning, half, rules);
+    state.inning = inning;
+    state.half = half;
+    let fielding = half.fielding_team();
+    let pitcher = state.lineups.get(fielding).pitcher.clone().or_else(|| {
+        state
+            .defensive_lineups
+            .get(fielding)
+            .player_at(Position::Pitcher)
+            .map(ToOwned::to_owned)
+    });
+    if let Some(pitcher) = pitcher {
+        state.current_pitcher_id = Some(pitcher);
+    }
+}
+
+/// Add the runners on base to the batting team's left on base, as its half
+/// inning or the game ends.
+fn strand_runners<P: PlayerKey>(state: &mut GameState<P>) {
+    let stranded = state.bases.count_runners() as u32;
+    let totals = state.team_totals.get_mut(state.half.batting_team());
+    totals.left_on_base = totals.left_on_base.saturating_add(stranded);
+}
+
+/// [`record_out`] on a copy of `state`.
+fn with_out<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> GameState<P> {
+    let mut next = state.clone();
+    record_out(&mut next, rules, effects);
+    next
+}
+
+/// Base occupancy at the start of a half inning.
+///
+/// With the `RunnerOnSecond` tiebreaker, every half inning after regulation
+/// starts with the batting team's last batter on second. If that batter is
+/// unknown the bases start empty.
+fn starting_bases<P: PlayerKey>(
+    state: &GameState<P>,
+    inning: u8,
+    half: InningHalf,
+    rules: &GameRules,
+) -> Bases<P> {
+    let extra_inning = rules.max_innings.is_some_and(|max| inning > max);
+    match rules.extra_innings_tiebreaker {
+        Some(ExtraInningsTiebreaker::RunnerOnSecond) if extra_inning => {
+            let runner = state.last_batters.get(half.batting_team()).clone();
+            Bases::from((None, runner, None))
+        }
+        _ => Bases::default(),
+    }
+}
+
+/// Put the batter on `reached`, advancing every runner the same number of
+/// bases.
+fn process_hit<P: PlayerKey>(
+    state: &GameState<P>,
+    reached: Base,
+    effects: &mut Effects<P>,
+) -> GameState<P> {
+    let (mut bases, scorers) = advance_runners(&state.bases, reached.number());
+    bases.set(reached, state.current_batter_id.clone());
+
+    GameState {
+        bases,
+        score: credit_runs(state, scorers, effects),
+        team_totals: credit_hit(state),
+        ..state.clone()
+    }
+}
+
+/// Apply a batted ball on which the batter reaches `reached` with a hit, or
+/// is out when it is `None`.
+///
+/// Without `advancement`, runners move up as many bases as the batter on a
+/// hit and hold on an out. Otherwise each listed runner ends up where it
+/// says and the others follow those defaults.
+///
+/// When the play makes the third out, runners who scored before it count
+/// as on a time play, unless the third out is the batter's, put out before
+/// reaching first, or a runner is forced out
+/// (`RunnerAdvance::out_was_force`). The batter's out is taken to come
+/// first on a play with several outs, so a runner doubled off after a
+/// catch makes the third out.
+///
+/// Returns `BaselomError::StateError` if a listed base is empty or listed
+/// twice, a runner moves back, passes another runner or shares a base with
+/// one, a force out is claimed for a runner who was not forced or not out,
+/// or the play records more outs than remain in the half inning.
+fn process_batted_ball<P: PlayerKey>(
+    state: &GameState<P>,
+    reached: Option<Base>,
+    advancement: &[RunnerAdvance],
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    if advancement.is_empty() {
+        return Ok(match reached {
+            Some(base) => process_hit(state, base, effects),
+            None => with_out(state, rules, effects),
+        });
+    }
+
+    for (index, advance) in advancement.iter().enumerate() {
+        if state.bases.runner_at(advance.from).is_none() {
+            return Err(BaselomError::StateError(format!(
+                "No runner on {} base to advance",
+                advance.from
+            )));
+        }
+        if advancement[..index]
+            .iter()
+            .any(|earlier| earlier.from == advance.from)
+        {
+            return Err(BaselomError::StateError(format!(
+                "The runner on {} base is advanced more than once",
+                advance.from
+            )));
+        }
+        if let Advance::Base(to) = advance.to {
+            if to < advance.from {
+                return Err(BaselomError::StateError(format!(
+                    "The runner on {} base cannot move back to {} base",
+                    advance.from, to
+                )));
+            }
+        }
+        if advance.out_was_force
+            && (advance.to != Advance::Out || !state.bases.force_at(advance.from))
+        {
+            return Err(BaselomError::StateError(format!(
+                "The runner on {} base was not forced out",
+                advance.from
+            )));
+        }
+    }
+
+    // Everyone on the play with where they end up, lead runner first and
+    // the batter (`None`) last.
+    let mut movements: Vec<(Option<Base>, Option<P>, Advance)> = Base::ALL
+        .iter()
+        .rev()
+        .filter_map(|&base| {
+            let runner = state.bases.runner_at(base)?;
+            let to = match advancement.iter().find(|advance| advance.from == base) {
+                Some(advance) => advance.to,
+                None => match reached {
+                    Some(reached) => base
+                        .advanced_by(reached.number())
+                        .map_or(Advance::Home, Advance::Base),
+                    None => Advance::Base(base),
+                },
+            };
+            Some((Some(base), Some(runner.to_owned()), to))
+        })
+        .collect();
+    movements.push((
+        None,
+        state.current_batter_id.clone(),
+        reached.map_or(Advance::Out, Advance::Base),
+    ));
+
+    let mut ahead: Option<(Option<Base>, u8)> = None;
+    for &(from, _, to) in &movements {
+        let position = match to {
+            Advance::Base(base) => base.number(),
+            Advance::Home => HOME_PLATE,
+            Advance::Out => continue,
+        };
+        if let Some((lead, lead_position)) = ahead {
+            if position == lead_position && position != HOME_PLATE {
+                return Err(BaselomError::StateError(format!(
+                    "The {} and the {} cannot finish on the same base",
+                    mover(lead),
+                    mover(from)
+                )));
+            }
+            if position > lead_position {
+                return Err(BaselomError::StateError(format!(
+                    "The {} cannot pass the {}",
+                    mover(from),
+                    mover(lead)
+                )));
+            }
+        }
+        ahead = Some((from, position));
+    }
+
+    let outs_on_play = movements
+        .iter()
+        .filter(|(_, _, to)| *to == Advance::Out)
+        .count() as u8;
+    if state.outs + outs_on_play > OUTS_PER_HALF_INNING {
+        return Err(BaselomError::StateError(format!(
+            "Cannot record {} outs with {} already out",
+            outs_on_play, state.outs
+        )));
+    }
+
+    let mut bases = Bases::default();
+    let mut scorers = Vec::new();
+    for (_, player, to) in movements {
+        match to {
+            Advance::Base(base) => bases.set(base, player),
+            Advance::Home => scorers.push(player),
+            Advance::Out => {}
+        }
+    }
+    let force_out = advancement.iter().any(|advance| advance.out_was_force);
+    let batter_makes_third_out = reached.is_none() && outs_on_play == 1;
+    if (batter_makes_third_out || force_out) && state.outs + outs_on_play >= OUTS_PER_HALF_INNING {
+        scorers.clear();
+    }
+
+    let next = GameState {
+        bases,
+        score: credit_runs(state, scorers, effects),
+        team_totals: match reached {
+            Some(_) => credit_hit(state),
+            None => state.team_totals.clone(),
+        },
+        ..state.clone()
+    };
+    if outs_on_play == 0 {
+        return Ok(next);
+    }
+
+    // All but the last out are recorded here; `record_out` adds the last.
+    effects.outs_recorded += outs_on_play - 1;
+    let next = GameState {
+        outs: state.outs + outs_on_play - 1,
+        ..next
+    };
+    Ok(with_out(&next, rules, effects))
+}
+
+/// Declare the batter out on an infield fly, with runners advancing only as
+/// `advancement` says.
+///
+/// Returns `BaselomError::RuleViolation` with two outs or without runners on
+/// first and second, and `BaselomError::StateError` for a claimed force out
+/// (the batter's out removes the force) or advancement
+/// [`process_batted_ball`] rejects.
+fn process_infield_fly<P: PlayerKey>(
+    state: &GameState<P>,
+    advancement: &[RunnerAdvance],
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    if state.outs >= OUTS_PER_HALF_INNING - 1 {
+        return Err(BaselomError::RuleViolation(
+            "An infield fly cannot be declared with two outs".to_string(),
+        ));
+    }
+    if state.bases.runner_at(Base::First).is_none() || state.bases.runner_at(Base::Second).is_none()
+    {
+        return Err(BaselomError::RuleViolation(
+            "An infield fly requires runners on first and second base".to_string(),
+        ));
+    }
+    if let Some(advance) = advancement.iter().find(|advance| advance.out_was_force) {
+        return Err(BaselomError::StateError(format!(
+            "The runner on {} base was not forced out",
+            advance.from
+        )));
+    }
+    process_batted_ball(state, None, advancement, rules, effects)
+}
+
+/// Put the batter on `batter_to` and advance every runner
+/// `runners_advance` bases, without crediting a hit.
+///
+/// Returns `BaselomError::StateError` if a runner would finish on or behind
+/// the batter's base.
+fn process_award_bases<P: PlayerKey>(
+    state: &GameState<P>,
+    batter_to: Base,
+    runners_advance: u8,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    let behind_batter = state.bases.iter().find(|(base, _)| {
+        base.advanced_by(runners_advance)
+            .is_some_and(|target| target.number() <= batter_to.number())
+    });
+    if let Some((base, _)) = behind_batter {
+        return Err(BaselomError::StateError(format!(
+            "The runner on {} base must finish ahead of the batter",
+            base
+        )));
+    }
+
+    let (mut bases, scorers) = advance_runners(&state.bases, runners_advance);
+    bases.set(batter_to, state.current_batter_id.clone());
+
+    Ok(GameState {
+        bases,
+        score: credit_runs(state, scorers, effects),
+        ..state.clone()
+    })
+}
+
+/// Score every runner plus the batter, leaving the bases empty and the
+/// plate appearance finished.
+fn process_home_run<P: PlayerKey>(state: &GameState<P>, effects: &mut Effects<P>) -> GameState<P> {
+    let mut scorers: Vec<Option<P>> = state
+        .bases
+        .iter()
+        .map(|(_, runner)| Some(runner.to_owned()))
+        .collect();
+    scorers.reverse();
+    scorers.push(state.current_batter_id.clone());
+
+    GameState {
+        bases: Bases::default(),
+        score: credit_runs(state, scorers, effects),
+        team_totals: credit_hit(state),
+        ..state.clone()
+    }
+}
+
+/// Move every runner up one base without ending the plate appearance.
+fn advance_all_runners<P: PlayerKey>(
+    state: &GameState<P>,
+    effects: &mut Effects<P>,
+) -> GameState<P> {
+    let (bases, scorers) = advance_runners(&state.bases, 1);
+
+    GameState {
+        bases,
+        score: credit_runs(state, scorers, effects),
+        ..state.clone()
+    }
+}
+
+/// Advance every runner one base on a balk.
+///
+/// With the bases empty a balk is either a ball or rejected with
+/// `BaselomError::StateError`, depending on
+/// `GameRules::balk_with_empty_bases_is_ball`.
+fn process_balk<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    if !state.bases.is_empty() {
+        return Ok(advance_all_runners(state, effects));
+    }
+    if rules.balk_with_empty_bases_is_ball {
+        if pitch_ends_plate_appearance(state, PitchResult::Ball, rules) {
+            require_batter(state)?;
+        }
+        let mut next = state.clone();
+        add_ball(&mut next, rules, effects);
+        Ok(next)
+    } else {
+        Err(BaselomError::StateError(
+            "Balk requires at least one runner on base".to_string(),
+        ))
+    }
+}
+
+/// Remove the runner at `base` and record an out.
+///
+/// Returns `BaselomError::StateError` if the base is empty.
+fn process_pickoff<P: PlayerKey>(
+    state: &GameState<P>,
+    base: Base,
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    let mut bases = state.bases.clone();
+    if bases.take(base).is_none() {
+        return Err(BaselomError::StateError(format!(
+            "No runner on {} base to pick off",
+            base
+        )));
+    }
+
+    let picked_off = GameState {
+        bases,
+        ..state.clone()
+    };
+    Ok(with_out(&picked_off, rules, effects))
+}
+
+/// Advance the runner at `base` one base after an errant pickoff throw.
+///
+/// Returns `BaselomError::StateError` if the base is empty or the next base
+/// is occupied.
+fn process_pickoff_error<P: PlayerKey>(
+    state: &GameState<P>,
+    base: Base,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    let mut bases = state.bases.clone();
+    let Some(runner) = bases.take(base) else {
+        return Err(BaselomError::StateError(format!(
+            "No runner on {} base to advance",
+            base
+        )));
+    };
+
+    let mut scorers = Vec::new();
+    match base.next() {
+        None => scorers.push(Some(runner)),
+        Some(next) if bases.runner_at(next).is_some() => {
+            return Err(BaselomError::StateError(format!(
+                "Runner on {} base cannot advance to an occupied base",
+                base
+            )));
+        }
+        Some(next) => bases.set(next, Some(runner)),
+    }
+
+    Ok(GameState {
+        bases,
+        score: credit_runs(state, scorers, effects),
+        team_totals: charge_error(state),
+        ..state.clone()
+    })
+}
+
+/// Award the obstructed runner at `base` the base (or home) the umpire
+/// judges the runner would have reached.
+///
+/// Returns `BaselomError::StateError` if the base is empty, the award is an
+/// out or not ahead of the runner, or the awarded base is occupied.
+fn process_obstruction<P: PlayerKey>(
+    state: &GameState<P>,
+    base: Base,
+    awarded: Advance,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    let mut bases = state.bases.clone();
+    let Some(runner) = bases.take(base) else {
+        return Err(BaselomError::StateError(format!(
+            "No runner on {} base to award",
+            base
+        )));
+    };
+
+    let mut scorers = Vec::new();
+    match awarded {
+        Advance::Home => scorers.push(Some(runner)),
+        Advance::Base(to) if to <= base => {
+            return Err(BaselomError::StateError(format!(
+                "Runner on {} base must be awarded a base ahead",
+                base
+            )));
+        }
+        Advance::Base(to) if bases.runner_at(to).is_some() => {
+            return Err(BaselomError::StateError(format!(
+                "Runner on {} base cannot advance to an occupied base",
+                base
+            )));
+        }
+        Advance::Base(to) => bases.set(to, Some(runner)),
+        Advance::Out => {
+            return Err(BaselomError::StateError(
+                "Obstruction cannot put a runner out".to_string(),
+            ));
+        }
+    }
+
+    Ok(GameState {
+        bases,
+        score: credit_runs(state, scorers, effects),
+        ..state.clone()
+    })
+}
+
+/// Retire two or three players on one play.
+///
+/// Returns `BaselomError::ValidationError` for fewer than two outs; see
+/// [`retire_on_play`] for the other checks.
+fn process_double_play<P: PlayerKey>(
+    state: &GameState<P>,
+    runners_out: &[Base],
+    batter_out: bool,
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    if runners_out.len() + usize::from(batter_out) < 2 {
+        return Err(BaselomError::ValidationError(
+            "A double play must record at least two outs".to_string(),
+        ));
+    }
+    retire_on_play(state, runners_out, batter_out, rules, effects)
+}
+
+/// Put out a lead runner while the batter reaches first.
+fn process_fielders_choice<P: PlayerKey>(
+    state: &GameState<P>,
+    runner_out_at: Base,
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    retire_on_play(state, &[runner_out_at], false, rules, effects)
+}
+
+/// Retire the runners on `runners_out` (and the batter when `batter_out`)
+/// on a ground ball.
+///
+/// Runners not put out advance one base only if the batter forced them, and
+/// a batter who is not out takes first. If the play ends the half inning no
+/// runs score, since the final out is a force.
+///
+/// Returns `BaselomError::StateError` for an empty (or repeated) base or
+/// more outs than remain in the half inning.
+fn retire_on_play<P: PlayerKey>(
+    state: &GameState<P>,
+    runners_out: &[Base],
+    batter_out: bool,
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    let outs_on_play = runners_out.len() as u8 + u8::from(batter_out);
+    if state.outs + outs_on_play > OUTS_PER_HALF_INNING {
+        return Err(BaselomError::StateError(format!(
+            "Cannot record {} outs with {} already out",
+            outs_on_play, state.outs
+        )));
+    }
+
+    let forced = forced_runners(&state.bases);
+    let mut bases = state.bases.clone();
+    for &base in runners_out {
+        if bases.take(base).is_none() {
+            return Err(BaselomError::StateError(format!(
+                "No runner on {} base to put out",
+                base
+            )));
+        }
+    }
+
+    let mut scorers = advance_forced_runners(&mut bases, &forced);
+    if !batter_out {
+        bases.set(Base::First, state.current_batter_id.clone());
+    }
+    if state.outs + outs_on_play >= OUTS_PER_HALF_INNING {
+        scorers.clear();
+    }
+
+    // All but the last out are recorded here; `record_out` adds the last.
+    effects.outs_recorded += outs_on_play - 1;
+    let next = GameState {
+        outs: state.outs + outs_on_play - 1,
+        bases,
+        score: credit_runs(state, scorers, effects),
+        ..state.clone()
+    };
+    Ok(with_out(&next, rules, effects))
+}
+
+/// Score the runner from third on a fly out, advancing any other runners
+/// who tag up.
+///
+/// Returns `BaselomError::RuleViolation` with two outs (the catch is the
+/// third out, so no run can score), and `BaselomError::StateError` if third
+/// base is empty or a tagging runner has an empty or occupied base.
+fn process_sac_fly<P: PlayerKey>(
+    state: &GameState<P>,
+    tagging_up: &[Base],
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    if state.outs >= OUTS_PER_HALF_INNING - 1 {
+        return Err(BaselomError::RuleViolation(
+            "A sacrifice fly cannot score a run with two outs".to_string(),
+        ));
+    }
+
+    if state.bases.runner_at(Base::Third).is_none() {
+        return Err(BaselomError::StateError(
+            "A sacrifice fly requires a runner on third base".to_string(),
+        ));
+    }
+    let mut bases = state.bases.clone();
+    let scorer = bases.take(Base::Third);
+
+    for (base, next) in [(Base::Second, Base::Third), (Base::First, Base::Second)] {
+        if !tagging_up.contains(&base) {
+            continue;
+        }
+        let Some(runner) = bases.take(base) else {
+            return Err(BaselomError::StateError(format!(
+                "No runner on {} base to tag up",
+                base
+            )));
+        };
+        if bases.runner_at(next).is_some() {
+            return Err(BaselomError::StateError(format!(
+                "Runner on {} base cannot advance to an occupied base",
+                base
+            )));
+        }
+        bases.set(next, Some(runner));
+    }
+
+    let scored = GameState {
+        bases,
+        score: credit_runs(state, vec![scorer], effects),
+        ..state.clone()
+    };
+    Ok(with_out(&scored, rules, effects))
+}
+
+/// Put the batter out at first on a bunt, moving each runner on `advancing`
+/// up one base (scoring from third on a squeeze).
+///
+/// Returns `BaselomError::RuleViolation` with two outs (the out at first
+/// ends the half inning, so no runner can advance or score), and
+/// `BaselomError::StateError` if an advancing runner's base is empty or the
+/// next base stays occupied.
+fn process_sac_bunt<P: PlayerKey>(
+    state: &GameState<P>,
+    advancing: &[Base],
+    rules: &GameRules,
+    effects: &mut Effects<P>,
+) -> Result<GameState<P>, BaselomError> {
+    if state.outs >= OUTS_PER_HALF_INNING - 1 {
+        return Err(BaselomError::RuleViolation(
+            "A sacrifice bunt cannot be made with two outs".to_string(),
+        ));
+    }
+
+    let mut bases = state.bases.clone();
+    let mut scorers = Vec::new();
+    for base in [Base::Third, Base::Second, Base::First] {
+        if !advancing.contains(&base) {
+            continue;
+        }
+        let Some(runner) = bases.take(base) else {
+            return Err(BaselomError::StateError(format!(
+                "No runner on {} base to advance",
+                base
+            )));
+        };
+        match base.next() {
+            None => scorers.push(Some(runner)),
+            Some(next) if bases.runner_at(next).is_some() => {
+                return Err(BaselomError::StateError(format!(
+                    "Runner on {} base cannot advance to an occupied base",
+                    base
+                )));
+            }
+            Some(next) => bases.set(next, Some(runner)),
+        }
+    }
+
+    let advanced = GameState {
+        bases,
+        score: credit_runs(state, scorers, effects),
+        ..state.clone()
+    };
+    Ok(with_out(&advanced, rules, effects))
+}
+
+/// Bases whose runners are forced to advance when the batter becomes a
+/// runner.
+fn forced_runners<P: PlayerKey>(bases: &Bases<P>) -> Vec<Base> {
+    Base::ALL
+        .into_iter()
+        .filter(|&base| bases.runner_at(base).is_some() && bases.force_at(base))
+        .collect()
+}
+
+/// Move each runner on a `forced` base up one, lead runner first.
+///
+/// Returns the runners forced home.
+fn advance_forced_runners<P: PlayerKey>(bases: &mut Bases<P>, forced: &[Base]) -> Vec<Option<P>> {
+    let mut scorers = Vec::new();
+    for &base in forced.iter().rev() {
+        let Some(runner) = bases.take(base) else {
+            continue;
+        };
+        match base.next() {
+            Some(next) => bases.set(next, Some(runner)),
+            None => scorers.push(Some(runner)),
+        }
+    }
+    scorers
+}
+
+/// How an error names someone on a batted ball: the runner from `from`, or
+/// the batter for `None`.
+fn mover(from: Option<Base>) -> String {
+    match from {
+        Some(base) => format!("runner from {} base", base),
+        None => "batter".to_string(),
+    }
+}
+
+/// Move every runner forward `count` bases.
+///
+/// Returns the new base occupancy and the runners who scored, lead runner
+/// first.
+fn advance_runners<P: PlayerKey>(bases: &Bases<P>, count: u8) -> (Bases<P>, Vec<Option<P>>) {
+    let mut advanced = Bases::default();
+    let mut scorers = Vec::new();
+
+    for &base in Base::ALL.iter().rev() {
+        let Some(runner) = bases.runner_at(base) else {
+            continue;
+        };
+        match base.advanced_by(count) {
+            Some(target) => advanced.set(target, Some(runner.to_owned())),
+            None => scorers.push(Some(runner.to_owned())),
+        }
+    }
+
+    (advanced, scorers)
+}
+
+/// Credit a hit to the batting team.
+fn credit_hit<P: PlayerKey>(state: &GameState<P>) -> PerTeam<TeamTotals> {
+    let mut totals = state.team_totals.clone();
+    let batting = totals.get_mut(state.half.batting_team());
+    batting.hits = batting.hits.saturating_add(1);
+    totals
+}
+
+/// Charge an error to the fielding team.
+fn charge_error<P: PlayerKey>(state: &GameState<P>) -> PerTeam<TeamTotals> {
+    let mut totals = state.team_totals.clone();
+    let fielding = totals.get_mut(state.half.fielding_team());
+    fielding.errors = fielding.errors.saturating_add(1);
+    totals
+}
+
+/// Add a run for each of `scorers` to the batting team's total, noting who
+/// scored. A scorer is `None` when the batter's ID is unknown.
+///
+/// Totals stop at `u32::MAX`; runs past it are still reported as scored,
+/// in `runs_scored` and `rbis` as well as `scorers`.
+fn credit_runs<P: PlayerKey>(
+    state: &GameState<P>,
+    scorers: Vec<Option<P>>,
+    effects: &mut Effects<P>,
+) -> Score {
+    let runs = scorers.len() as u32;
+    effects.runs = effects.runs.saturating_add(runs);
+    effects.scorers.extend(scorers.into_iter().flatten());
+    let (batting, _) = state.batting_team();
+    let mut score = state.score.clone();
+    let total = score.get_mut(batting);
+    *total = total.saturating_add(runs);
+    score
+}
+
+/// Award first base to the batter, advancing forced runners.
+///
+/// Used for both walks and hit batters.
+fn process_walk<P: PlayerKey>(state: &mut GameState<P>, effects: &mut Effects<P>) {
+    let forced = forced_runners(&state.bases);
+    let scorers = advance_forced_runners(&mut state.bases, &forced);
+    state
+        .bases
+        .set(Base::First, state.current_batter_id.clone());
+    state.score = credit_runs(state, scorers, effects);
+}
+
+/// [`process_walk`] on a copy of `state`.
+fn walk<P: PlayerKey>(state: &GameState<P>, effects: &mut Effects<P>) -> GameState<P> {
+    let mut next = state.clone();
+    process_walk(&mut next, effects);
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Count, DefensiveLineup, Lineup, MercyRule, CURRENT_SCHEMA_VERSION};
+    use std::collections::BTreeMap;
+
+    fn create_test_state() -> GameState {
+        GameState {
+            inning: 1,
+            half: InningHalf::Top,
+            outs: 0,
+            count: Count::default(),
+            bases: Bases::new(),
+            score: Score::default(),
+            home_team_id: None,
+            away_team_id: None,
+            current_batter_id: Some("batter".to_string()),
+            current_pitcher_id: Some("pitcher".to_string()),
+            status: GameStatus::InProgress,
+            last_batters: PerTeam::default(),
+            line_score: Vec::new(),
+            team_totals: PerTeam::default(),
+            lineups: PerTeam::default(),
+            defensive_lineups: PerTeam::default(),
+            removed_players: PerTeam::default(),
+            batters_faced: BTreeMap::new(),
+            half_inning_batters: 0,
+            half_inning_runs: 0,
+            dh_forfeited: PerTeam::default(),
+            pitch_counts: BTreeMap::new(),
+            plate_appearance_pitches: 0,
+            mound_visits: PerTeam::default(),
+            pitcher_removal_required: None,
+            responsible_pitchers: Bases::new(),
+            runs_allowed: BTreeMap::new(),
+            earned_runs_allowed: BTreeMap::new(),
+            unearned_runners: [false; 3],
+            outs_lost_to_errors: 0,
+            lead_changes: Vec::new(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+        }
+    }
+
+    /// The state with a batter at the plate, keeping the current one if set
+    /// and otherwise picking an ID that is not on base.
+    fn with_batter(state: &GameState) -> GameState {
+        if state.current_batter_id.is_some() {
+            return state.clone();
+        }
+        let batter = ["batter", "batter2", "batter3", "batter4"]
+            .into_iter()
+            .find(|id| state.bases.iter().all(|(_, runner)| runner != *id))
+            .unwrap();
+        GameState {
+            current_batter_id: Some(batter.to_string()),
+            ..state.clone()
+        }
+    }
+
+    fn strikeout(state: &GameState, rules: &GameRules) -> GameState {
+        [PitchResult::StrikeSwinging; 3]
+            .iter()
+            .fold(with_batter(state), |state, pitch| {
+                apply_pitch(&state, *pitch, rules).unwrap()
+            })
+    }
+
+    fn apply_all(state: &GameState, pitches: &[PitchResult]) -> GameState {
+        let rules = GameRules::default();
+        pitches.iter().fold(state.clone(), |state, pitch| {
+            apply_pitch(&state, *pitch, &rules).unwrap()
+        })
+    }
+
+    /// Apply every sequence of up to `depth` pitches from `state`, checking
+    /// that each resulting count stays below the rules' thresholds.
+    fn assert_count_stays_valid(state: &GameState, rules: &GameRules, depth: usize) {
+        assert!(state.count.is_valid(rules), "invalid count {}", state.count);
+        if depth == 0 {
+            return;
+        }
+        for pitch in PitchResult::ALL {
+            let next = apply_pitch(&with_batter(state), pitch, rules).unwrap();
+            assert_count_stays_valid(&next, rules, depth - 1);
+        }
+    }
+
+    #[test]
+    fn test_count_never_exceeds_thresholds() {
+        let state = create_test_state();
+        assert_count_stays_valid(&state, &GameRules::default(), 6);
+
+        let short_count = GameRules {
+            balls_for_walk: 3,
+            strikes_for_out: 2,
+            ..GameRules::default()
+        };
+        assert_count_stays_valid(&state, &short_count, 6);
+    }
+
+    /// A fresh game in progress, with batter and pitcher set, keeping a line
+    /// score.
+    fn tracked_game() -> GameState {
+        GameState {
+            current_batter_id: Some("batter".to_string()),
+            current_pitcher_id: Some("pitcher".to_string()),
+            status: GameStatus::InProgress,
+            ..GameState::new()
+        }
+    }
+
+    fn three_outs(state: &GameState, rules: &GameRules) -> GameState {
+        (0..3).fold(state.clone(), |state, _| strikeout(&state, rules))
+    }
+
+    fn home_run(state: &GameState) -> GameState {
+        apply_event(
+            &with_batter(state),
+            &GameEvent::HomeRun,
+            &GameRules::default(),
+        )
+        .unwrap()
+    }
+
+    fn line(away: u32, home: Option<u32>) -> InningScore {
+        InningScore { away, home }
+    }
+
+    #[test]
+    fn test_line_score_tracks_runs_by_inning() {
+        let rules = GameRules::default();
+        let state = home_run(&home_run(&tracked_game()));
+        assert_eq!(state.line_score, vec![line(2, None)]);
+
+        let state = three_outs(&state, &rules);
+        assert_eq!(state.line_score, vec![line(2, Some(0))]);
+        let state = home_run(&state);
+        let state = three_outs(&state, &rules);
+        assert_eq!(state.line_score, vec![line(2, Some(1)), line(0, None)]);
+        assert!(crate::validators::validate_state(&state).is_ok());
+    }
+
+    #[test]
+    fn test_line_score_leaves_unplayed_bottom_half_empty() {
+        let rules = GameRules::default();
+        let mut state = tracked_game();
+        for _ in 0..7 {
+            state = three_outs(&three_outs(&state, &rules), &rules);
+        }
+        state = three_outs(&state, &rules);
+        state = home_run(&state);
+        state = three_outs(&state, &rules);
+        state = three_outs(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.inning, 9);
+        assert_eq!(state.line_score.len(), 9);
+        assert_eq!(state.line_score[7], line(0, Some(1)));
+        assert_eq!(state.line_score[8], line(0, None));
+    }
+
+    #[test]
+    fn test_line_score_records_walk_off_runs() {
+        let rules = GameRules::default();
+        let mut state = tracked_game();
+        for _ in 0..8 {
+            state = three_outs(&three_outs(&state, &rules), &rules);
+        }
+        state = three_outs(&state, &rules);
+        state = home_run(&state);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.line_score[8], line(0, Some(1)));
+        assert_eq!(state.score, Score { home: 1, away: 0 });
+    }
+
+    #[test]
+    fn test_untracked_line_score_stays_empty() {
+        let state = home_run(&create_test_state());
+        assert!(state.line_score.is_empty());
+    }
+
+    #[test]
+    fn test_single_credits_hit_to_batting_team() {
+        let state = apply_event(
+            &create_test_state(),
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &GameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(state.team_totals.away.hits, 1);
+        assert_eq!(state.team_totals.home.hits, 0);
+    }
+
+    #[test]
+    fn test_every_hit_type_credits_a_hit() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.half = InningHalf::Bottom;
+        for event in [
+            GameEvent::Single {
+                advancement: vec![],
+            },
+            GameEvent::Double {
+                advancement: vec![],
+            },
+            GameEvent::Triple {
+                advancement: vec![],
+            },
+            GameEvent::HomeRun,
+        ] {
+            state = apply_event(&with_batter(&state), &event, &rules).unwrap();
+        }
+        assert_eq!(state.team_totals.home.hits, 4);
+        assert_eq!(state.team_totals.away.hits, 0);
+    }
+
+    #[test]
+    fn test_pickoff_error_charges_fielding_team() {
+        let mut state = create_test_state();
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let event = GameEvent::PickoffError { base: Base::First };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.team_totals.home.errors, 1);
+        assert_eq!(state.team_totals.away.errors, 0);
+    }
+
+    #[test]
+    fn test_counters_saturate() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.pitch_counts.insert("pitcher".to_string(), u32::MAX);
+        state.batters_faced.insert("pitcher".to_string(), u32::MAX);
+        state.half_inning_batters = u32::MAX;
+        state.plate_appearance_pitches = u32::MAX;
+        state.team_totals.away.hits = u32::MAX;
+        state.team_totals.home.errors = u32::MAX;
+
+        let state = apply_pitch(&state, PitchResult::Foul, &rules).unwrap();
+        let state = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        let state = apply_event(
+            &state,
+            &GameEvent::PickoffError { base: Base::First },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(state.pitch_counts["pitcher"], u32::MAX);
+        assert_eq!(state.batters_faced["pitcher"], u32::MAX);
+        assert_eq!(state.half_inning_batters, u32::MAX);
+        assert_eq!(state.team_totals.away.hits, u32::MAX);
+        assert_eq!(state.team_totals.home.errors, u32::MAX);
+    }
+
+    #[test]
+    fn test_game_ends_after_last_inning() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = MAX_INNING;
+        state.outs = 2;
+        let state = strikeout(&state, &rules);
+        assert_eq!((state.inning, state.half), (MAX_INNING, InningHalf::Bottom));
+        assert_eq!(state.status, GameStatus::InProgress);
+
+        let mut state = strikeout(&state, &rules);
+        state.outs = 2;
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.inning, MAX_INNING);
+        assert_eq!(state.winner(), None);
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_walk_score_saturates() {
+        let mut state = create_test_state();
+        state.count.balls = 3;
+        state.score.away = u32::MAX - 1;
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        let state = apply_all(&state, &[PitchResult::Ball]);
+        assert_eq!(state.score.away, u32::MAX);
+
+        let mut state = with_batter(&state);
+        state.count.balls = 3;
+        let outcome =
+            apply_pitch_detailed(&state, PitchResult::Ball, &GameRules::default()).unwrap();
+        assert_eq!(outcome.state.score.away, u32::MAX);
+        assert_eq!(outcome.runs_scored, 1);
+        assert_eq!(outcome.rbis, 1);
+        assert_eq!(outcome.scorers, vec!["r2".to_string()]);
+    }
+
+    #[test]
+    fn test_detailed_outcome_reports_walk_scorer() {
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        state.count.balls = 3;
+        let outcome =
+            apply_pitch_detailed(&state, PitchResult::Ball, &GameRules::default()).unwrap();
+        assert_eq!(outcome.runs_scored, 1);
+        assert_eq!(outcome.scorers, vec!["r3".to_string()]);
+        assert_eq!(outcome.outs_recorded, 0);
+        assert!(!outcome.half_inning_ended);
+        assert!(!outcome.game_ended);
+        assert_eq!(outcome.state.score.away, 1);
+    }
+
+    #[test]
+    fn test_detailed_outcome_lists_scorers_lead_runner_first() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let outcome =
+            apply_event_detailed(&state, &GameEvent::HomeRun, &GameRules::default()).unwrap();
+        assert_eq!(outcome.runs_scored, 3);
+        assert_eq!(
+            outcome.scorers,
+            vec!["r3".to_string(), "r1".to_string(), "batter".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_detailed_outcome_reports_outs_and_half_inning_end() {
+        let mut state = create_test_state();
+        state.outs = 1;
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let event = GameEvent::DoublePlay {
+            runners_out: vec![Base::First],
+            batter_out: true,
+        };
+        let outcome = apply_event_detailed(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(outcome.outs_recorded, 2);
+        assert!(outcome.half_inning_ended);
+        assert!(!outcome.game_ended);
+        assert_eq!(outcome.state.half, InningHalf::Bottom);
+
+        let outcome =
+            apply_pitch_detailed(&state, PitchResult::StrikeCalled, &GameRules::default()).unwrap();
+        assert_eq!(outcome.outs_recorded, 0);
+        assert!(!outcome.half_inning_ended);
+    }
+
+    #[test]
+    fn test_detailed_outcome_reports_game_end() {
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.half = InningHalf::Bottom;
+        let outcome =
+            apply_event_detailed(&state, &GameEvent::HomeRun, &GameRules::default()).unwrap();
+        assert!(outcome.game_ended);
+        assert!(!outcome.half_inning_ended);
+        assert_eq!(outcome.outs_recorded, 0);
+
+        state.half = InningHalf::Top;
+        state.outs = 2;
+        state.score = Score { home: 1, away: 0 };
+        state.count.strikes = 2;
+        let outcome =
+            apply_pitch_detailed(&state, PitchResult::StrikeSwinging, &GameRules::default())
+                .unwrap();
+        assert!(outcome.game_ended);
+        assert!(outcome.half_inning_ended);
+        assert_eq!(outcome.outs_recorded, 1);
+    }
+
+    #[test]
+    fn test_no_runs_score_when_play_ends_half_inning() {
+        let mut state = create_test_state();
+        state.outs = 1;
+        bases_loaded(&mut state);
+        let event = GameEvent::DoublePlay {
+            runners_out: vec![Base::First],
+            batter_out: true,
+        };
+        let outcome = apply_event_detailed(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(outcome.runs_scored, 0);
+        assert!(outcome.scorers.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_state_rejected() {
+        let mut state = create_test_state();
+        state.outs = 7;
+        state.count.balls = 12;
+        let rules = GameRules::default();
+        assert!(matches!(
+            apply_pitch(&state, PitchResult::Ball, &rules),
+            Err(BaselomError::InvalidState(_))
+        ));
+        assert!(matches!(
+            apply_event(
+                &state,
+                &GameEvent::Single {
+                    advancement: vec![]
+                },
+                &rules
+            ),
+            Err(BaselomError::InvalidState(_))
+        ));
+        assert!(matches!(
+            apply_pitch_detailed(&state, PitchResult::Ball, &rules),
+            Err(BaselomError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn test_state_validated_against_rules() {
+        let rules = GameRules {
+            balls_for_walk: 3,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.count.balls = 3;
+        assert!(apply_pitch(&state, PitchResult::StrikeCalled, &GameRules::default()).is_ok());
+        assert!(matches!(
+            apply_pitch(&state, PitchResult::StrikeCalled, &rules),
+            Err(BaselomError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn test_unchecked_variants_skip_validation() {
+        let mut state = create_test_state();
+        state.inning = 0;
+        let rules = GameRules::default();
+        let pitched = apply_pitch_unchecked(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(pitched.count.balls, 1);
+        let hit = apply_event_unchecked(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(hit.bases.runner_at(Base::First), Some("batter"));
+        assert!(apply_pitch(&state, PitchResult::Ball, &rules).is_err());
+    }
+
+    #[test]
+    fn test_walk_without_batter_rejected() {
+        let mut state = create_test_state();
+        state.current_batter_id = None;
+        state.count.balls = 3;
+        let rules = GameRules::default();
+        let result = apply_pitch(&state, PitchResult::Ball, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: No current batter"
+        );
+        assert!(matches!(
+            apply_pitch(&state, PitchResult::HitByPitch, &rules),
+            Err(BaselomError::NoCurrentBatter)
+        ));
+
+        state.count.balls = 2;
+        let ball = apply_pitch(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(ball.count.balls, 3);
+    }
+
+    #[test]
+    fn test_strikeout_without_batter_rejected() {
+        let mut state = create_test_state();
+        state.current_batter_id = None;
+        state.count.strikes = 2;
+        let result = apply_pitch(&state, PitchResult::StrikeSwinging, &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::NoCurrentBatter)));
+
+        let foul = apply_pitch(&state, PitchResult::Foul, &GameRules::default()).unwrap();
+        assert_eq!(foul.count.strikes, 2);
+    }
+
+    #[test]
+    fn test_batted_ball_without_batter_rejected() {
+        let mut state = create_test_state();
+        state.current_batter_id = None;
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let rules = GameRules::default();
+        for event in [
+            GameEvent::Single {
+                advancement: vec![],
+            },
+            GameEvent::HomeRun,
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+        ] {
+            assert!(matches!(
+                apply_event(&state, &event, &rules),
+                Err(BaselomError::NoCurrentBatter)
+            ));
+        }
+        assert!(apply_event(&state, &GameEvent::WildPitch, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_walk_clears_batter() {
+        let mut state = create_test_state();
+        state.count.balls = 3;
+        let state = apply_pitch(&state, PitchResult::Ball, &GameRules::default()).unwrap();
+        assert_eq!(state.current_batter_id, None);
+        assert_eq!(state.bases.runner_at(Base::First), Some("batter"));
+        assert_eq!(state.last_batters.away, Some("batter".to_string()));
+    }
+
+    #[test]
+    fn test_plate_appearance_end_clears_batter() {
+        let rules = GameRules::default();
+        let state = create_test_state();
+        assert_eq!(strikeout(&state, &rules).current_batter_id, None);
+        let hbp = apply_pitch(&state, PitchResult::HitByPitch, &rules).unwrap();
+        assert_eq!(hbp.current_batter_id, None);
+        let single = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(single.current_batter_id, None);
+        assert_eq!(single.bases.runner_at(Base::First), Some("batter"));
+    }
+
+    #[test]
+    fn test_batter_kept_when_plate_appearance_continues() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        let ball = apply_pitch(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(ball.current_batter_id, Some("batter".to_string()));
+
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let wild_pitch = apply_event(&state, &GameEvent::WildPitch, &rules).unwrap();
+        assert_eq!(wild_pitch.current_batter_id, Some("batter".to_string()));
+    }
+
+    #[test]
+    fn test_ball_increments_count() {
+        let state = apply_all(&create_test_state(), &[PitchResult::Ball]);
+        assert_eq!(state.count.balls, 1);
+        assert_eq!(state.count.strikes, 0);
+    }
+
+    #[test]
+    fn test_fourth_ball_is_walk() {
+        let state = apply_all(&create_test_state(), &[PitchResult::Ball; 4]);
+        assert_eq!(state.count.balls, 0);
+        assert_eq!(state.bases.runner_at(Base::First), Some("batter"));
+    }
+
+    #[test]
+    fn test_walk_with_bases_loaded_scores_run() {
+        let mut state = create_test_state();
+        state.count.balls = 3;
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        let state = apply_all(&state, &[PitchResult::Ball]);
+        assert_eq!(state.score.away, 1);
+        assert_eq!(
+            state.bases,
+            Bases::from((
+                Some("batter".to_string()),
+                Some("r1".to_string()),
+                Some("r2".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_hit_by_pitch_with_empty_bases() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        let state = apply_all(&state, &[PitchResult::HitByPitch]);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("batter".to_string()), None, None))
+        );
+        assert_eq!((state.count.balls, state.count.strikes), (0, 0));
+        assert_eq!(state.outs, 0);
+    }
+
+    #[test]
+    fn test_hit_by_pitch_forces_runner_on_first() {
+        let mut state = create_test_state();
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let state = apply_all(&state, &[PitchResult::HitByPitch]);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("batter".to_string()), Some("r1".to_string()), None))
+        );
+        assert_eq!(state.score.away, 0);
+    }
+
+    #[test]
+    fn test_hit_by_pitch_with_bases_loaded_scores_run() {
+        let mut state = create_test_state();
+        state.half = InningHalf::Bottom;
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        let state = apply_all(&state, &[PitchResult::HitByPitch]);
+        assert_eq!(state.score, Score { home: 1, away: 0 });
+        assert_eq!(
+            state.bases,
+            Bases::from((
+                Some("batter".to_string()),
+                Some("r1".to_string()),
+                Some("r2".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_strikeout_records_out() {
+        let state = apply_all(
+            &create_test_state(),
+            &[
+                PitchResult::StrikeCalled,
+                PitchResult::StrikeSwinging,
+                PitchResult::FoulTip,
+            ],
+        );
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.count.strikes, 0);
+    }
+
+    #[test]
+    fn test_dropped_third_strike_batter_reaches() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        let state = apply_all(&state, &[PitchResult::StrikeSwingingDropped]);
+        assert_eq!(state.outs, 0);
+        assert_eq!(state.bases.runner_at(Base::First), Some("batter"));
+        assert_eq!(state.count.strikes, 0);
+    }
+
+    #[test]
+    fn test_dropped_third_strike_with_first_occupied_is_out() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        state.outs = 1;
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let state = apply_all(&state, &[PitchResult::StrikeSwingingDropped]);
+        assert_eq!(state.outs, 2);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("r1".to_string()), None, None))
+        );
+    }
+
+    #[test]
+    fn test_dropped_third_strike_with_two_outs_forces_runners() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        state.outs = 2;
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let state = apply_all(&state, &[PitchResult::StrikeSwingingDropped]);
+        assert_eq!(state.outs, 2);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("batter".to_string()), Some("r1".to_string()), None))
+        );
+    }
+
+    #[test]
+    fn test_dropped_third_strike_disabled() {
+        let rules = GameRules {
+            dropped_third_strike: false,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        let state = apply_pitch(&state, PitchResult::StrikeSwingingDropped, &rules).unwrap();
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.bases.runner_at(Base::First), None);
+    }
+
+    #[test]
+    fn test_dropped_strike_before_two_strikes_is_a_strike() {
+        let state = apply_all(&create_test_state(), &[PitchResult::StrikeSwingingDropped]);
+        assert_eq!(state.count.strikes, 1);
+        assert_eq!(state.bases.runner_at(Base::First), None);
+    }
+
+    #[test]
+    fn test_foul_bunt_adds_strike() {
+        let state = apply_all(&create_test_state(), &[PitchResult::FoulBunt]);
+        assert_eq!(state.count.strikes, 1);
+        assert_eq!(state.outs, 0);
+    }
+
+    #[test]
+    fn test_foul_bunt_with_two_strikes_is_strikeout() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        let state = apply_all(&state, &[PitchResult::FoulBunt]);
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.count.strikes, 0);
+    }
+
+    #[test]
+    fn test_foul_bunt_strikeout_for_third_out_ends_half_inning() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        state.outs = 2;
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let state = apply_pitch_str(&state, "foul_bunt", &GameRules::default()).unwrap();
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.outs, 0);
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+    }
+
+    #[test]
+    fn test_configurable_walk_and_strikeout_thresholds() {
+        let rules = GameRules {
+            balls_for_walk: 3,
+            strikes_for_out: 2,
+            ..GameRules::default()
+        };
+        let walked = [PitchResult::Ball; 3]
+            .iter()
+            .fold(create_test_state(), |state, pitch| {
+                apply_pitch(&state, *pitch, &rules).unwrap()
+            });
+        assert_eq!(walked.bases.runner_at(Base::First), Some("batter"));
+
+        let struck_out = [PitchResult::StrikeCalled; 2]
+            .iter()
+            .fold(create_test_state(), |state, pitch| {
+                apply_pitch(&state, *pitch, &rules).unwrap()
+            });
+        assert_eq!(struck_out.outs, 1);
+
+        let mut one_strike = create_test_state();
+        one_strike.count.strikes = 1;
+        let fouled = apply_pitch(&one_strike, PitchResult::Foul, &rules).unwrap();
+        assert_eq!(fouled.count.strikes, 1);
+        assert_eq!(fouled.outs, 0);
+    }
+
+    #[test]
+    fn test_foul_with_two_strikes_keeps_count() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        let state = apply_all(&state, &[PitchResult::Foul]);
+        assert_eq!(state.count.strikes, 2);
+        assert_eq!(state.outs, 0);
+    }
+
+    #[test]
+    fn test_third_out_ends_half_inning() {
+        let mut state = create_test_state();
+        state.outs = 2;
+        state.count.strikes = 2;
+        state.bases.set(Base::Second, Some("r2".to_string()));
+        let state = apply_all(&state, &[PitchResult::StrikeSwinging]);
+        assert_eq!(state.outs, 0);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.inning, 1);
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+    }
+
+    #[test]
+    fn test_nine_inning_game_with_lead_ends() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.score = Score { home: 2, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.inning, 9);
+    }
+
+    #[test]
+    fn test_tied_game_goes_to_extra_innings() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.score = Score { home: 3, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.inning, 10);
+        assert_eq!(state.half, InningHalf::Top);
+    }
+
+    #[test]
+    fn test_max_innings_respected() {
+        let rules = GameRules {
+            max_innings: Some(7),
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.inning = 7;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.score = Score { home: 0, away: 1 };
+        assert_eq!(strikeout(&state, &rules).status, GameStatus::Final);
+
+        let unlimited = GameRules {
+            max_innings: None,
+            ..GameRules::default()
+        };
+        let state = strikeout(&state, &unlimited);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.inning, 8);
+    }
+
+    #[test]
+    fn test_home_leading_after_top_of_ninth_skips_bottom() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.outs = 2;
+        state.score = Score { home: 5, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.inning, 9);
+        assert_eq!(state.half, InningHalf::Top);
+    }
+
+    #[test]
+    fn test_home_trailing_after_top_of_ninth_bats() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.outs = 2;
+        state.score = Score { home: 3, away: 5 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.half, InningHalf::Bottom);
+    }
+
+    #[test]
+    fn test_tied_after_top_of_ninth_bats() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.outs = 2;
+        state.score = Score { home: 3, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.half, InningHalf::Bottom);
+    }
+
+    #[test]
+    fn test_home_leading_after_top_of_extra_inning_skips_bottom() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 12;
+        state.outs = 2;
+        state.score = Score { home: 4, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.inning, 12);
+    }
+
+    #[test]
+    fn test_home_leading_after_top_of_eighth_bats() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 8;
+        state.outs = 2;
+        state.score = Score { home: 4, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.half, InningHalf::Bottom);
+    }
+
+    fn tiebreaker_rules() -> GameRules {
+        GameRules {
+            extra_innings_tiebreaker: Some(ExtraInningsTiebreaker::RunnerOnSecond),
+            ..GameRules::default()
+        }
+    }
+
+    #[test]
+    fn test_tiebreaker_runner_placed_in_tenth() {
+        let rules = tiebreaker_rules();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.score = Score { home: 2, away: 2 };
+        state.current_batter_id = Some("h9".to_string());
+        state.last_batters.away = Some("a4".to_string());
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.inning, 10);
+        assert_eq!(state.half, InningHalf::Top);
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("a4".to_string()), None))
+        );
+        assert_eq!(state.last_batters.home, Some("h9".to_string()));
+
+        // The bottom of the 10th starts with the home team's last batter.
+        let mut state = state;
+        state.outs = 2;
+        state.current_batter_id = Some("a5".to_string());
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("h9".to_string()), None))
+        );
+    }
+
+    #[test]
+    fn test_no_tiebreaker_runner_in_ninth() {
+        let rules = tiebreaker_rules();
+        let mut state = create_test_state();
+        state.inning = 8;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.last_batters.away = Some("a4".to_string());
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.inning, 9);
+        assert!(state.bases.runner_at(Base::Second).is_none());
+
+        let mut state = state;
+        state.outs = 2;
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert!(state.bases.runner_at(Base::Second).is_none());
+    }
+
+    #[test]
+    fn test_no_tiebreaker_runner_without_rule() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.last_batters.away = Some("a4".to_string());
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.inning, 10);
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+    }
+
+    #[test]
+    fn test_plate_appearance_records_last_batter() {
+        let rules = GameRules::default();
+        let state = apply_event(
+            &create_test_state(),
+            &GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(state.last_batters.away, Some("batter".to_string()));
+        assert_eq!(state.last_batters.home, None);
+    }
+
+    fn mercy_rules() -> GameRules {
+        GameRules {
+            mercy_rule: Some(MercyRule {
+                run_differential: 10,
+                after_inning: 7,
+            }),
+            ..GameRules::default()
+        }
+    }
+
+    #[test]
+    fn test_mercy_rule_visitors_must_let_home_bat() {
+        let rules = mercy_rules();
+        let mut state = create_test_state();
+        state.inning = 7;
+        state.outs = 2;
+        state.score = Score { home: 0, away: 12 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.half, InningHalf::Bottom);
+
+        let mut state = state;
+        state.outs = 2;
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.inning, 7);
+    }
+
+    #[test]
+    fn test_mercy_rule_home_team_ends_game_instantly() {
+        let rules = mercy_rules();
+        let mut state = create_test_state();
+        state.inning = 7;
+        state.half = InningHalf::Bottom;
+        state.score = Score { home: 7, away: 0 };
+        bases_loaded(&mut state);
+        let state = apply_event(&state, &GameEvent::HomeRun, &rules).unwrap();
+        assert_eq!(state.score.home, 11);
+        assert_eq!(state.status, GameStatus::Final);
+        assert!(is_game_over(&state, &rules));
+    }
+
+    #[test]
+    fn test_mercy_rule_home_lead_after_top_half_ends_game() {
+        let rules = mercy_rules();
+        let mut state = create_test_state();
+        state.inning = 8;
+        state.outs = 2;
+        state.score = Score { home: 15, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.half, InningHalf::Top);
+    }
+
+    #[test]
+    fn test_mercy_rule_not_applied_before_inning() {
+        let rules = mercy_rules();
+        let mut state = create_test_state();
+        state.inning = 6;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.score = Score { home: 0, away: 20 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.inning, 7);
+    }
+
+    fn run_limit_rules() -> GameRules {
+        GameRules {
+            inning_run_limit: Some(5),
+            last_inning_uncapped: true,
+            ..GameRules::default()
+        }
+    }
+
+    /// Bases loaded with three balls on the batter, `runs` already in.
+    fn loaded_full_count(inning: u8, half: InningHalf, runs: u32) -> GameState {
+        let mut state = create_test_state();
+        state.inning = inning;
+        state.half = half;
+        state.outs = 1;
+        state.count.balls = 3;
+        state.half_inning_runs = runs;
+        *state.score.get_mut(half.batting_team()) = runs;
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        state
+    }
+
+    #[test]
+    fn test_inning_run_limit_ends_half_on_bases_loaded_walk() {
+        let rules = run_limit_rules();
+        let state = loaded_full_count(3, InningHalf::Top, 3);
+        let outcome = apply_pitch_detailed(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(outcome.runs_scored, 1);
+        assert_eq!(outcome.scorers, vec!["r3".to_string()]);
+        assert_eq!(outcome.outs_recorded, 0);
+        assert!(!outcome.half_inning_ended);
+        let state = outcome.state;
+        assert_eq!(state.half_inning_runs, 4);
+
+        let mut state = state;
+        state.current_batter_id = Some("next".to_string());
+        state.count.balls = 3;
+        let outcome = apply_pitch_detailed(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(outcome.runs_scored, 1);
+        assert!(outcome.half_inning_ended);
+        assert!(outcome.half_inning_ended_by_run_limit);
+        assert!(!outcome.game_ended);
+        let state = outcome.state;
+        assert_eq!(state.score.away, 5);
+        assert_eq!((state.inning, state.half), (3, InningHalf::Bottom));
+        assert_eq!(state.outs, 0);
+        assert_eq!(state.count, Count::default());
+        assert!(state.bases.is_empty());
+        assert_eq!(state.half_inning_runs, 0);
+        assert_eq!(state.team_totals.away.left_on_base, 3);
+        assert_eq!(state.batters_faced.get("pitcher"), Some(&2));
+    }
+
+    #[test]
+    fn test_inning_run_limit_counts_every_run_on_the_play() {
+        let rules = run_limit_rules();
+        let state = loaded_full_count(2, InningHalf::Bottom, 3);
+        let outcome = apply_event_detailed(&state, &GameEvent::HomeRun, &rules).unwrap();
+        assert_eq!(outcome.runs_scored, 4);
+        assert!(outcome.half_inning_ended_by_run_limit);
+        assert_eq!(outcome.state.score.home, 7);
+        assert_eq!(
+            (outcome.state.inning, outcome.state.half),
+            (3, InningHalf::Top)
+        );
+    }
+
+    #[test]
+    fn test_last_inning_uncapped() {
+        let rules = run_limit_rules();
+        let state = loaded_full_count(9, InningHalf::Top, 4);
+        let outcome = apply_pitch_detailed(&state, PitchResult::Ball, &rules).unwrap();
+        assert!(!outcome.half_inning_ended);
+        assert_eq!(outcome.state.half_inning_runs, 5);
+
+        let capped = GameRules {
+            last_inning_uncapped: false,
+            ..run_limit_rules()
+        };
+        let outcome = apply_pitch_detailed(&state, PitchResult::Ball, &capped).unwrap();
+        assert!(outcome.half_inning_ended_by_run_limit);
+        assert_eq!(outcome.state.half, InningHalf::Bottom);
+    }
+
+    #[test]
+    fn test_inning_run_limit_yields_to_walk_off() {
+        let capped = GameRules {
+            last_inning_uncapped: false,
+            ..run_limit_rules()
+        };
+        let mut state = loaded_full_count(9, InningHalf::Bottom, 4);
+        state.score.away = 4;
+        let outcome = apply_pitch_detailed(&state, PitchResult::Ball, &capped).unwrap();
+        assert!(outcome.game_ended);
+        assert!(!outcome.half_inning_ended_by_run_limit);
+        assert_eq!(outcome.state.score, Score { home: 5, away: 4 });
+    }
+
+    fn tie_rules() -> GameRules {
+        GameRules {
+            allow_tie_after_innings: Some(12),
+            extra_innings_tiebreaker: Some(ExtraInningsTiebreaker::RunnerOnSecond),
+            ..GameRules::default()
+        }
+    }
+
+    #[test]
+    fn test_tied_game_ends_at_tie_limit() {
+        let rules = tie_rules();
+        let mut state = create_test_state();
+        state.inning = 12;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.score = Score { home: 3, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.inning, 12);
+        assert_eq!(state.winner(), None);
+    }
+
+    #[test]
+    fn test_tied_game_continues_before_tie_limit_with_tiebreaker() {
+        let rules = tie_rules();
+        let mut state = create_test_state();
+        state.inning = 10;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        state.score = Score { home: 3, away: 3 };
+        state.last_batters.away = Some("a2".to_string());
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.inning, 11);
+        assert_eq!(state.bases.runner_at(Base::Second), Some("a2"));
+    }
+
+    #[test]
+    fn test_tie_limit_does_not_end_top_half() {
+        let rules = tie_rules();
+        let mut state = create_test_state();
+        state.inning = 12;
+        state.outs = 2;
+        state.score = Score { home: 3, away: 3 };
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert_eq!(state.half, InningHalf::Bottom);
+    }
+
+    #[test]
+    fn test_tie_limit_before_max_innings() {
+        let rules = GameRules {
+            allow_tie_after_innings: Some(7),
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.inning = 7;
+        state.half = InningHalf::Bottom;
+        state.outs = 2;
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.winner(), None);
+    }
+
+    #[test]
+    fn test_final_state_rejects_pitches() {
+        let mut state = create_test_state();
+        state.status = GameStatus::Final;
+        let result = apply_pitch(&state, PitchResult::Ball, &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::GameAlreadyFinal)));
+    }
+
+    #[test]
+    fn test_walk_with_runner_on_second_only_forces_nobody() {
+        let mut state = create_test_state();
+        state.count.balls = 3;
+        state.bases.set(Base::Second, Some("r2".to_string()));
+        let state = apply_all(&state, &[PitchResult::Ball]);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("batter".to_string()), Some("r2".to_string()), None))
+        );
+    }
+
+    #[test]
+    fn test_single_advances_runners_one_base() {
+        let mut state = create_test_state();
+        state.count.balls = 2;
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let state = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &GameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("batter".to_string()), Some("r1".to_string()), None))
+        );
+        assert_eq!(state.score.away, 1);
+        assert_eq!(state.count.balls, 0);
+    }
+
+    #[test]
+    fn test_double_advances_runners_two_bases() {
+        let mut state = create_test_state();
+        state.half = InningHalf::Bottom;
+        state.bases = Bases::from((Some("r1".to_string()), Some("r2".to_string()), None));
+        let state = apply_event(
+            &state,
+            &GameEvent::Double {
+                advancement: vec![],
+            },
+            &GameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("batter".to_string()), Some("r1".to_string())))
+        );
+        assert_eq!(state.score.home, 1);
+        assert_eq!(state.score.away, 0);
+    }
+
+    #[test]
+    fn test_triple_clears_runners() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), Some("r2".to_string()), None));
+        let state = apply_event(
+            &state,
+            &GameEvent::Triple {
+                advancement: vec![],
+            },
+            &GameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            state.bases,
+            Bases::from((None, None, Some("batter".to_string())))
+        );
+        assert_eq!(state.score.away, 2);
+    }
+
+    #[test]
+    fn test_solo_home_run_scores_one() {
+        let mut state = create_test_state();
+        state.count.balls = 3;
+        state.count.strikes = 1;
+        let state = apply_event(&state, &GameEvent::HomeRun, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 0, away: 1 });
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+        assert_eq!((state.count.balls, state.count.strikes), (0, 0));
+        assert_eq!(state.current_batter_id, None);
+    }
+
+    #[test]
+    fn test_two_run_home_run() {
+        let mut state = create_test_state();
+        state.half = InningHalf::Bottom;
+        state.bases.set(Base::Second, Some("r2".to_string()));
+        let state = apply_event(&state, &GameEvent::HomeRun, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 2, away: 0 });
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+    }
+
+    #[test]
+    fn test_grand_slam_scores_four() {
+        let mut state = create_test_state();
+        state.score = Score { home: 1, away: 2 };
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        let state = apply_event(&state, &GameEvent::HomeRun, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 1, away: 6 });
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+        assert_eq!(state.current_batter_id, None);
+    }
+
+    #[test]
+    fn test_fly_out_holds_runners() {
+        let mut state = create_test_state();
+        state.count.strikes = 1;
+        state.bases = Bases::from((Some("r1".to_string()), None, None));
+        let state = apply_event(
+            &state,
+            &GameEvent::FlyOut {
+                advancement: vec![],
+            },
+            &GameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.count.strikes, 0);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("r1".to_string()), None, None))
+        );
+    }
+
+    #[test]
+    fn test_intentional_walk_ignores_count() {
+        let mut state = create_test_state();
+        state.count.strikes = 2;
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let state =
+            apply_event(&state, &GameEvent::IntentionalWalk, &GameRules::default()).unwrap();
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("batter".to_string()), Some("r1".to_string()), None))
+        );
+        assert_eq!((state.count.balls, state.count.strikes), (0, 0));
+    }
+
+    #[test]
+    fn test_intentional_walk_rejected_when_disabled() {
+        let rules = GameRules {
+            allow_auto_intentional_walk: false,
+            ..GameRules::default()
+        };
+        let result = apply_event(&create_test_state(), &GameEvent::IntentionalWalk, &rules);
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+    }
+
+    #[test]
+    fn test_wild_pitch_preserves_count_and_batter() {
+        let mut state = create_test_state();
+        state.count.balls = 2;
+        state.count.strikes = 1;
+        state.outs = 1;
+        state.bases = Bases::from((Some("r1".to_string()), None, None));
+        let state = apply_event(&state, &GameEvent::WildPitch, &GameRules::default()).unwrap();
+        assert_eq!((state.count.balls, state.count.strikes), (2, 1));
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.current_batter_id, Some("batter".to_string()));
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("r1".to_string()), None))
+        );
+        assert_eq!(state.last_batters.away, None);
+    }
+
+    #[test]
+    fn test_passed_ball_scores_runner_from_third() {
+        let mut state = create_test_state();
+        state.half = InningHalf::Bottom;
+        state.count.balls = 1;
+        state.bases = Bases::from((None, Some("r2".to_string()), Some("r3".to_string())));
+        let state = apply_event(&state, &GameEvent::PassedBall, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 1, away: 0 });
+        assert_eq!(
+            state.bases,
+            Bases::from((None, None, Some("r2".to_string())))
+        );
+        assert_eq!(state.count.balls, 1);
+    }
+
+    #[test]
+    fn test_wild_pitch_scores_for_away_team_in_top() {
+        let mut state = create_test_state();
+        state.bases.set(Base::Third, Some("r3".to_string()));
+        let state = apply_event(&state, &GameEvent::WildPitch, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 0, away: 1 });
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+    }
+
+    #[test]
+    fn test_balk_advances_runners_and_keeps_count() {
+        let mut state = create_test_state();
+        state.count.balls = 1;
+        state.count.strikes = 2;
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let state = apply_event(&state, &GameEvent::Balk, &GameRules::default()).unwrap();
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("r1".to_string()), None))
+        );
+        assert_eq!(state.score, Score { home: 0, away: 1 });
+        assert_eq!((state.count.balls, state.count.strikes), (1, 2));
+        assert_eq!(state.current_batter_id, Some("batter".to_string()));
+    }
+
+    #[test]
+    fn test_balk_scores_for_home_team_in_bottom() {
+        let mut state = create_test_state();
+        state.half = InningHalf::Bottom;
+        state.bases.set(Base::Third, Some("r3".to_string()));
+        let state = apply_event(&state, &GameEvent::Balk, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 1, away: 0 });
+    }
+
+    #[test]
+    fn test_balk_with_empty_bases_rejected_by_default() {
+        let result = apply_event(
+            &create_test_state(),
+            &GameEvent::Balk,
+            &GameRules::default(),
+        );
+        assert!(matches!(result, Err(BaselomError::StateError(_))));
+    }
+
+    #[test]
+    fn test_balk_with_empty_bases_is_ball_when_configured() {
+        let rules = GameRules {
+            balk_with_empty_bases_is_ball: true,
+            ..GameRules::default()
+        };
+        let state = apply_event(&create_test_state(), &GameEvent::Balk, &rules).unwrap();
+        assert_eq!(state.count.balls, 1);
+
+        let mut full_count = create_test_state();
+        full_count.count.balls = 3;
+        let state = apply_event(&full_count, &GameEvent::Balk, &rules).unwrap();
+        assert_eq!(state.bases.runner_at(Base::First), Some("batter"));
+    }
+
+    #[test]
+    fn test_pickoff_records_out_and_keeps_count() {
+        let mut state = create_test_state();
+        state.count.balls = 2;
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::Pickoff { base: Base::First };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.count.balls, 2);
+        assert_eq!(
+            state.bases,
+            Bases::from((None, None, Some("r3".to_string())))
+        );
+        assert_eq!(state.current_batter_id, Some("batter".to_string()));
+    }
+
+    #[test]
+    fn test_pickoff_for_third_out_ends_half_inning() {
+        let mut state = create_test_state();
+        state.outs = 2;
+        state.score = Score { home: 1, away: 2 };
+        state.bases = Bases::from((Some("r1".to_string()), Some("r2".to_string()), None));
+        let event = GameEvent::Pickoff { base: Base::Second };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.outs, 0);
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+        assert_eq!(state.score, Score { home: 1, away: 2 });
+        assert_eq!(state.current_batter_id, None);
+    }
+
+    #[test]
+    fn test_pickoff_at_empty_base_rejected() {
+        let event = GameEvent::Pickoff { base: Base::Third };
+        let result = apply_event(&create_test_state(), &event, &GameRules::default());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: No runner on third base to pick off"
+        );
+    }
+
+    #[test]
+    fn test_pickoff_error_advances_runner() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let state = apply_event(
+            &state,
+            &GameEvent::PickoffError { base: Base::First },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("r1".to_string()), Some("r3".to_string())))
+        );
+        assert_eq!(state.outs, 0);
+
+        let state = apply_event(
+            &state,
+            &GameEvent::PickoffError { base: Base::Third },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("r1".to_string()), None))
+        );
+        assert_eq!(state.score.away, 1);
+
+        let result = apply_event(
+            &state,
+            &GameEvent::PickoffError { base: Base::First },
+            &rules,
+        );
+        assert!(matches!(result, Err(BaselomError::StateError(_))));
+    }
+
+    fn bases_loaded(state: &mut GameState) {
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+    }
+
+    #[test]
+    fn test_inning_ending_double_play_scores_no_run() {
+        // Bases loaded, one out, 5-4-3: the runner from third does not score.
+        let mut state = create_test_state();
+        state.outs = 1;
+        state.score = Score { home: 2, away: 2 };
+        bases_loaded(&mut state);
+        let event = GameEvent::DoublePlay {
+            runners_out: vec![Base::First],
+            batter_out: true,
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 2, away: 2 });
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.outs, 0);
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+    }
+
+    #[test]
+    fn test_double_play_with_no_outs_advances_forced_runners() {
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        let event = GameEvent::DoublePlay {
+            runners_out: vec![Base::First],
+            batter_out: true,
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.outs, 2);
+        assert_eq!(state.score.away, 1);
+        assert_eq!(
+            state.bases,
+            Bases::from((None, None, Some("r2".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_double_play_unforced_runner_holds() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::DoublePlay {
+            runners_out: vec![Base::First],
+            batter_out: true,
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.outs, 2);
+        assert_eq!(state.score.away, 0);
+        assert_eq!(
+            state.bases,
+            Bases::from((None, None, Some("r3".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_triple_play_ends_half_inning() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), Some("r2".to_string()), None));
+        let event = GameEvent::DoublePlay {
+            runners_out: vec![Base::Second, Base::First],
+            batter_out: true,
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.outs, 0);
+    }
+
+    #[test]
+    fn test_double_play_validation() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases.set(Base::First, Some("r1".to_string()));
+
+        let empty_base = GameEvent::DoublePlay {
+            runners_out: vec![Base::Second],
+            batter_out: true,
+        };
+        let result = apply_event(&state, &empty_base, &rules);
+        assert!(matches!(result, Err(BaselomError::StateError(_))));
+
+        state.outs = 2;
+        let too_many = GameEvent::DoublePlay {
+            runners_out: vec![Base::First],
+            batter_out: true,
+        };
+        let result = apply_event(&state, &too_many, &rules);
+        assert!(matches!(result, Err(BaselomError::StateError(_))));
+
+        let one_out = GameEvent::DoublePlay {
+            runners_out: vec![],
+            batter_out: true,
+        };
+        let result = apply_event(&state, &one_out, &rules);
+        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_sac_fly_scores_runner_from_third() {
+        let mut state = create_test_state();
+        state.half = InningHalf::Bottom;
+        state.outs = 1;
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::SacFly { tagging_up: vec![] };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.score, Score { home: 1, away: 0 });
+        assert_eq!(state.outs, 2);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("r1".to_string()), None, None))
+        );
+    }
+
+    #[test]
+    fn test_sac_fly_with_runners_tagging_up() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        let event = GameEvent::SacFly {
+            tagging_up: vec![Base::Second],
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.score.away, 1);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("r1".to_string()), None, Some("r2".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_sac_fly_rejected_with_two_outs() {
+        let mut state = create_test_state();
+        state.outs = 2;
+        state.bases.set(Base::Third, Some("r3".to_string()));
+        let event = GameEvent::SacFly { tagging_up: vec![] };
+        let result = apply_event(&state, &event, &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+    }
+
+    #[test]
+    fn test_sac_fly_requires_runner_on_third() {
+        let event = GameEvent::SacFly { tagging_up: vec![] };
+        let result = apply_event(&create_test_state(), &event, &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::StateError(_))));
+    }
+
+    #[test]
+    fn test_fielders_choice_retires_lead_runner() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::FieldersChoice {
+            runner_out_at: Base::First,
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.outs, 1);
+        assert_eq!(
+            state.bases,
+            Bases::from((Some("batter".to_string()), None, Some("r3".to_string())))
+        );
+        assert_eq!(state.score.away, 0);
+    }
+
+    #[test]
+    fn test_fielders_choice_advances_trailing_forced_runners() {
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        let event = GameEvent::FieldersChoice {
+            runner_out_at: Base::Third,
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.outs, 1);
+        assert_eq!(
+            state.bases,
+            Bases::from((
+                Some("batter".to_string()),
+                Some("r1".to_string()),
+                Some("r2".to_string())
+            ))
+        );
+        assert_eq!(state.score.away, 0);
+    }
+
+    #[test]
+    fn test_fielders_choice_third_out_scores_no_run() {
+        let mut state = create_test_state();
+        state.outs = 2;
+        bases_loaded(&mut state);
+        let event = GameEvent::FieldersChoice {
+            runner_out_at: Base::First,
+        };
+        let state = apply_event(&state, &event, &GameRules::default()).unwrap();
+        assert_eq!(state.score.away, 0);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+    }
+
+    #[test]
+    fn test_fielders_choice_requires_runner() {
+        let event = GameEvent::FieldersChoice {
+            runner_out_at: Base::Second,
+        };
+        let result = apply_event(&create_test_state(), &event, &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::StateError(_))));
+    }
+
+    #[test]
+    fn test_apply_event_rejects_final_state() {
+        let mut state = create_test_state();
+        state.status = GameStatus::Final;
+        let result = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &GameRules::default(),
+        );
+        assert!(matches!(result, Err(BaselomError::GameAlreadyFinal)));
+    }
+
+    #[test]
+    fn test_bases_loaded_walk_off_walk_ends_game() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.half = InningHalf::Bottom;
+        state.count.balls = 3;
+        state.score = Score { home: 4, away: 4 };
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        let state = apply_pitch(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(state.score, Score { home: 5, away: 4 });
+        assert_eq!(state.status, GameStatus::Final);
+        assert!(is_game_over(&state, &rules));
+
+        let result = apply_pitch(&state, PitchResult::Ball, &rules);
+        assert!(matches!(result, Err(BaselomError::GameAlreadyFinal)));
+        let result = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        );
+        assert!(matches!(result, Err(BaselomError::GameAlreadyFinal)));
+    }
+
+    #[test]
+    fn test_walk_off_in_extra_innings() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 11;
+        state.half = InningHalf::Bottom;
+        state.score = Score { home: 2, away: 2 };
+        let state = apply_event(&state, &GameEvent::HomeRun, &rules).unwrap();
+        assert_eq!(state.status, GameStatus::Final);
+    }
+
+    #[test]
+    fn test_third_out_strands_runners() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.outs = 2;
+        state.bases = Bases::from((Some("r1".to_string()), Some("r2".to_string()), None));
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.team_totals.away.left_on_base, 2);
+        assert_eq!(state.team_totals.home.left_on_base, 0);
+
+        // The bottom half goes down in order, stranding nobody.
+        let state = (0..3).fold(state, |state, _| strikeout(&state, &rules));
+        assert_eq!(state.team_totals.home.left_on_base, 0);
+        assert_eq!(state.team_totals.away.left_on_base, 2);
+    }
+
+    #[test]
+    fn test_walk_off_strands_runners_once() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.half = InningHalf::Bottom;
+        state.count.balls = 3;
+        state.score = Score { home: 4, away: 4 };
+        bases_loaded(&mut state);
+        let state = apply_pitch(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.team_totals.home.left_on_base, 3);
+    }
+
+    #[test]
+    fn test_skipped_bottom_half_strands_nobody() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 9;
+        state.outs = 2;
+        state.score = Score { home: 5, away: 3 };
+        state.bases.set(Base::Third, Some("r3".to_string()));
+        let state = strikeout(&state, &rules);
+        assert_eq!(state.status, GameStatus::Final);
+        assert_eq!(state.team_totals.away.left_on_base, 1);
+        assert_eq!(state.team_totals.home.left_on_base, 0);
+    }
+
+    #[test]
+    fn test_home_lead_before_final_inning_is_not_walk_off() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.inning = 8;
+        state.half = InningHalf::Bottom;
+        let state = apply_event(&state, &GameEvent::HomeRun, &rules).unwrap();
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert!(!is_game_over(&state, &rules));
+    }
+
+    #[test]
+    fn test_not_started_game_requires_start() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.status = GameStatus::NotStarted;
+        let result = apply_pitch(&state, PitchResult::Ball, &rules);
+        assert!(matches!(result, Err(BaselomError::GameNotStarted)));
+
+        let state = start(&state).unwrap();
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert!(apply_pitch(&state, PitchResult::Ball, &rules).is_ok());
+        assert!(matches!(start(&state), Err(BaselomError::StateError(_))));
+    }
+
+    #[test]
+    fn test_suspend_and_resume() {
+        let rules = GameRules::default();
+        let state = suspend(&create_test_state()).unwrap();
+        assert_eq!(state.status, GameStatus::Suspended);
+        let result = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        );
+        assert!(matches!(result, Err(BaselomError::GameSuspended)));
+
+        let state = resume(&state).unwrap();
+        assert_eq!(state.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_final_game_cannot_be_resumed() {
+        let mut state = create_test_state();
+        state.status = GameStatus::Final;
+        assert!(matches!(suspend(&state), Err(BaselomError::StateError(_))));
+        assert!(matches!(resume(&state), Err(BaselomError::StateError(_))));
+    }
+
+    #[test]
+    fn test_apply_pitch_str_accepts_every_variant() {
+        let rules = GameRules::default();
+        for pitch in PitchResult::ALL {
+            let from_str = apply_pitch_str(&create_test_state(), pitch.as_str(), &rules).unwrap();
+            let typed = apply_pitch(&create_test_state(), pitch, &rules).unwrap();
+            assert_eq!(from_str, typed);
+        }
+    }
+
+    #[test]
+    fn test_apply_pitch_sequence() {
+        let rules = GameRules::default();
+        let (state, applied) = apply_pitch_sequence(&create_test_state(), "BBCF", &rules).unwrap();
+        assert_eq!(applied, 4);
+        assert_eq!(state.count, Count::new(2, 2));
+        assert_eq!(
+            state,
+            apply_all(
+                &create_test_state(),
+                &[
+                    PitchResult::Ball,
+                    PitchResult::Ball,
+                    PitchResult::StrikeCalled,
+                    PitchResult::Foul,
+                ]
+            )
+        );
+
+        let (state, applied) = apply_pitch_sequence(&create_test_state(), "BBCFS", &rules).unwrap();
+        assert_eq!(applied, 5);
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.count, Count::default());
+    }
+
+    #[test]
+    fn test_apply_pitch_sequence_stops_when_plate_appearance_ends() {
+        let rules = GameRules::default();
+        let (state, applied) = apply_pitch_sequence(&create_test_state(), "BHBB", &rules).unwrap();
+        assert_eq!(applied, 2);
+        assert_eq!(state.bases.runner_at(Base::First), Some("batter"));
+        assert_eq!(state.count, Count::default());
+    }
+
+    #[test]
+    fn test_apply_pitch_sequence_rejects_unknown_code() {
+        let rules = GameRules::default();
+        let result = apply_pitch_sequence(&create_test_state(), "BBXS", &rules);
+        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Unknown pitch code 'X' at position 2"
+        );
+    }
+
+    #[test]
+    fn test_apply_pitch_str_rejects_unknown() {
+        let result = apply_pitch_str(&create_test_state(), "strike_calld", &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::InvalidPitchResult(_))));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("ball, strike_called"));
+    }
+
+    /// Plays for one half inning: each listed event by a new batter from
+    /// `team`, numbered from `*next`.
+    fn scripted_half(team: &str, next: &mut usize, events: &[GameEvent]) -> Vec<Play> {
+        let mut plays = Vec::new();
+        for event in events {
+            *next = *next % 9 + 1;
+            plays.push(Play::Batter(format!("{}{}", team, next)));
+            plays.push(Play::Pitch(PitchResult::Ball));
+            plays.push(Play::Event(event.clone()));
+        }
+        plays
+    }
+
+    /// A nine-inning game the away team wins 3-1.
+    fn scripted_game() -> Vec<Play> {
+        let outs = [
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            GameEvent::FlyOut {
+                advancement: vec![],
+            },
+            GameEvent::LineOut {
+                advancement: vec![],
+            },
+        ];
+        let (mut away, mut home) = (0, 0);
+        let mut plays = Vec::new();
+        for inning in 1..=9 {
+            let mut away_events = Vec::new();
+            if inning == 3 {
+                away_events.extend([
+                    GameEvent::Single {
+                        advancement: vec![],
+                    },
+                    GameEvent::HomeRun,
+                ]);
+            }
+            if inning == 7 {
+                away_events.push(GameEvent::HomeRun);
+            }
+            away_events.extend(outs.clone());
+            plays.extend(scripted_half("a", &mut away, &away_events));
+
+            let mut home_events = Vec::new();
+            if inning == 5 {
+                home_events.extend([
+                    GameEvent::Double {
+                        advancement: vec![],
+                    },
+                    GameEvent::Double {
+                        advancement: vec![],
+                    },
+                ]);
+            }
+            home_events.extend(outs.clone());
+            plays.extend(scripted_half("h", &mut home, &home_events));
+        }
+        plays
+    }
+
+    #[test]
+    fn test_play_events_full_game() {
+        let rules = GameRules::default();
+        let plays = scripted_game();
+        let states = play_events(&tracked_game(), &plays, &rules, false).unwrap();
+        assert_eq!(states.len(), plays.len());
+
+        let last = states.last().unwrap();
+        assert_eq!(last.status, GameStatus::Final);
+        assert_eq!(last.inning, 9);
+        assert_eq!(last.score, Score { home: 1, away: 3 });
+        assert_eq!(last.winner(), Some(Team::Away));
+        assert_eq!(last.team_totals.away.hits, 3);
+        assert_eq!(last.team_totals.home.hits, 2);
+        assert_eq!(last.line_score[2].away, 2);
+        assert_eq!(last.line_score[6].away, 1);
+        assert_eq!(last.line_score[4].home, Some(1));
+        let replayed = play_events(&tracked_game(), &plays, &rules, false).unwrap();
+        assert_eq!(replayed, states);
+    }
+
+    #[test]
+    fn test_play_events_trailing_plays() {
+        let rules = GameRules::default();
+        let mut plays = scripted_game();
+        let game_length = plays.len();
+        plays.push(Play::Batter("a1".to_string()));
+        plays.push(Play::Event(GameEvent::Single {
+            advancement: vec![],
+        }));
+
+        let states = play_events(&tracked_game(), &plays, &rules, true).unwrap();
+        assert_eq!(states.len(), game_length);
+
+        let (index, err) = play_events(&tracked_game(), &plays, &rules, false).unwrap_err();
+        assert_eq!(index, game_length);
+        assert!(matches!(err, BaselomError::GameAlreadyFinal));
+    }
+
+    #[test]
+    fn test_play_events_reports_failing_index() {
+        let rules = GameRules::default();
+        let plays = [
+            Play::Event(GameEvent::Single {
+                advancement: vec![],
+            }),
+            Play::Pitch(PitchResult::Ball),
+            Play::Event(GameEvent::Single {
+                advancement: vec![],
+            }),
+        ];
+        let (index, err) = play_events(&tracked_game(), &plays, &rules, false).unwrap_err();
+        assert_eq!(index, 2);
+        assert!(matches!(err, BaselomError::NoCurrentBatter));
+    }
+
+    fn lineup(team: &str) -> Lineup {
+        Lineup::new((1..=9).map(|slot| format!("{}{}", team, slot)))
+    }
+
+    /// A game in progress with both lineups and the away leadoff batter up.
+    fn lineup_game() -> GameState {
+        let state = GameState {
+            current_pitcher_id: Some("pitcher".to_string()),
+            lineups: PerTeam {
+                home: lineup("h"),
+                away: lineup("a"),
+            },
+            ..GameState::new()
+        };
+        start(&state).unwrap()
+    }
+
+    #[test]
+    fn test_half_inning_brings_in_the_fielding_pitcher() {
+        let rules = GameRules::default();
+        let mut state = lineup_game();
+        state.lineups.home = lineup("h").with_pitcher("h9");
+        state.lineups.away = lineup("a").with_pitcher("a9");
+        state.current_pitcher_id = Some("h9".to_string());
+        let state = three_outs(&state, &rules);
+        assert_eq!(state.current_pitcher_id.as_deref(), Some("a9"));
+        assert_eq!(state.batters_faced.get("h9"), Some(&3));
+        assert_eq!(state.batters_faced.get("a9"), None);
+
+        let play = Play::PitchingChange {
+            pitcher: "a_rp".to_string(),
+            injured: false,
+        };
+        let state = apply_play(&state, &play, &rules).unwrap();
+        assert_eq!(state.lineups.away.pitcher.as_deref(), Some("a_rp"));
+        assert_eq!(state.lineups.away.slot_of("a_rp"), Some(8));
+        let state = three_outs(&state, &rules);
+        assert_eq!(state.current_pitcher_id.as_deref(), Some("h9"));
+        let state = three_outs(&state, &rules);
+        assert_eq!(state.current_pitcher_id.as_deref(), Some("a_rp"));
+    }
+
+    #[test]
+    fn test_lead_changes_on_walks_and_home_runs() {
+        let rules = GameRules::default();
+        let walk = |state: &GameState| {
+            (0..4).fold(state.clone(), |state, _| {
+                apply_pitch(&state, PitchResult::Ball, &rules).unwrap()
+            })
+        };
+        let homer = |state: &GameState| apply_event(state, &GameEvent::HomeRun, &rules).unwrap();
+        let mut state = lineup_game();
+        state.lineups.home = lineup("h").with_pitcher("h9");
+        state.lineups.away = lineup("a").with_pitcher("a9");
+        state.current_pitcher_id = Some("h9".to_string());
+
+        let state = homer(&state);
+        assert_eq!(state.leading_team(), Some(Team::Away));
+        let state = homer(&three_outs(&state, &rules));
+        assert_eq!(state.leading_team(), None);
+        let state = homer(&walk(&state));
+        assert_eq!(state.leading_team(), Some(Team::Home));
+        assert_eq!(state.score.differential(), 2);
+        let play = Play::PitchingChange {
+            pitcher: "h_rp".to_string(),
+            injured: false,
+        };
+        let state = apply_play(&three_outs(&state, &rules), &play, &rules).unwrap();
+        let state = (0..5).fold(state, |state, _| walk(&state));
+        assert_eq!(state.leading_team(), None);
+        let state = homer(&state);
+        assert_eq!(state.score.differential(), -4);
+
+        let pitchers = |home: &str, away: &str| PerTeam {
+            home: Some(home.to_string()),
+            away: Some(away.to_string()),
+        };
+        let change = |inning, half, leader, away, home, pitchers| LeadChange {
+            inning,
+            half,
+            leader,
+            score: Score { home, away },
+            pitchers,
+        };
+        assert_eq!(
+            state.lead_changes,
+            vec![
+                change(
+                    1,
+                    InningHalf::Top,
+                    Some(Team::Away),
+                    1,
+                    0,
+                    pitchers("h9", "a9")
+                ),
+                change(1, InningHalf::Bottom, None, 1, 1, pitchers("h9", "a9")),
+                change(
+                    1,
+                    InningHalf::Bottom,
+                    Some(Team::Home),
+                    1,
+                    3,
+                    pitchers("h9", "a9")
+                ),
+                change(2, InningHalf::Top, None, 3, 3, pitchers("h_rp", "a9")),
+                change(
+                    2,
+                    InningHalf::Top,
+                    Some(Team::Away),
+                    7,
+                    3,
+                    pitchers("h_rp", "a9")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lineup_advances_batter() {
+        let rules = GameRules::default();
+        let state = lineup_game();
+        assert_eq!(state.current_batter_id.as_deref(), Some("a1"));
+
+        let state = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(state.current_batter_id.as_deref(), Some("a2"));
+        assert_eq!(state.bases.runner_at(Base::First), Some("a1"));
+
+        let state = apply_pitch(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(state.current_batter_id.as_deref(), Some("a2"));
+    }
+
+    #[test]
+    fn test_lineup_resumes_in_next_inning() {
+        let rules = GameRules::default();
+        let mut state = lineup_game();
+        for event in [
+            GameEvent::Single {
+                advancement: vec![],
+            },
+            GameEvent::Single {
+                advancement: vec![],
+            },
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            GameEvent::FlyOut {
+                advancement: vec![],
+            },
+            GameEvent::LineOut {
+                advancement: vec![],
+            },
+        ] {
+            state = apply_event(&state, &event, &rules).unwrap();
+        }
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.current_batter_id.as_deref(), Some("h1"));
+
+        state = three_outs(&state, &rules);
+        assert_eq!(state.inning, 2);
+        assert_eq!(state.last_batters.away.as_deref(), Some("a5"));
+        assert_eq!(state.current_batter_id.as_deref(), Some("a6"));
+
+        state = three_outs(&state, &rules);
+        assert_eq!(state.current_batter_id.as_deref(), Some("h4"));
+    }
+
+    #[test]
+    fn test_lineup_wraps_to_leadoff() {
+        let rules = GameRules::default();
+        let mut state = lineup_game();
+        state.lineups.away.index = 8;
+        state.current_batter_id = Some("a9".to_string());
+        let state = apply_event(
+            &state,
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(state.current_batter_id.as_deref(), Some("a1"));
+        assert_eq!(state.lineups.away.index, 0);
+    }
+
+    #[test]
+    fn test_inning_ending_pickoff_keeps_batter_due_up() {
+        let rules = GameRules::default();
+        let mut state = lineup_game();
+        state.outs = 2;
+        state.bases.set(Base::First, Some("a9".to_string()));
+        let state = apply_event(&state, &GameEvent::Pickoff { base: Base::First }, &rules).unwrap();
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.current_batter_id.as_deref(), Some("h1"));
+        assert_eq!(state.lineups.away.current(), Some("a1"));
+    }
+
+    #[test]
+    fn test_pinch_runner_at_second() {
+        let rules = GameRules::default();
+        let mut state = lineup_game();
+        state = apply_event(
+            &state,
+            &GameEvent::Double {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        let state = substitute_runner(&state, Base::Second, "pr", &rules).unwrap();
+        assert_eq!(state.bases.runner_at(Base::Second), Some("pr"));
+        assert_eq!(state.lineups.away.players[0], "pr");
+        assert_eq!(state.removed_players.away, vec!["a1".to_string()]);
+
+        let result = substitute_runner(&state, Base::First, "pr2", &rules);
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+    }
+
+    #[test]
+    fn test_pinch_hitter_takes_over_plate_appearance() {
+        let rules = GameRules::default();
+        let state = apply_pitch(&lineup_game(), PitchResult::Ball, &rules).unwrap();
+        let state = substitute_batter(&state, 0, "ph", &rules).unwrap();
+        assert_eq!(state.current_batter_id.as_deref(), Some("ph"));
+        assert_eq!(state.count.balls, 1);
+
+        let result = substitute_batter(&state, 9, "ph2", &rules);
+        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_pitching_change() {
+        let rules = GameRules::default();
+        let state = substitute_pitcher(&lineup_game(), "reliever", false, &rules).unwrap();
+        assert_eq!(state.current_pitcher_id.as_deref(), Some("reliever"));
+        assert_eq!(state.removed_players.home, vec!["pitcher".to_string()]);
+    }
+
+    #[test]
+    fn test_illegal_reentry() {
+        let rules = GameRules::default();
+        let state = substitute_batter(&lineup_game(), 0, "ph", &rules).unwrap();
+        let result = substitute_batter(&state, 0, "a1", &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: Player a1 has been removed and cannot re-enter"
+        );
+
+        let reentry = GameRules {
+            allow_reentry: true,
+            ..GameRules::default()
+        };
+        let state = substitute_batter(&state, 0, "a1", &reentry).unwrap();
+        assert_eq!(state.current_batter_id.as_deref(), Some("a1"));
+        assert_eq!(state.removed_players.away, vec!["ph".to_string()]);
+    }
+
+    #[test]
+    fn test_substitute_already_in_game() {
+        let rules = GameRules::default();
+        let state = lineup_game();
+        for result in [
+            substitute_batter(&state, 0, "a5", &rules),
+            substitute_batter(&state, 0, "h3", &rules),
+            substitute_pitcher(&state, "pitcher", false, &rules),
+        ] {
+            assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+        }
+    }
+
+    fn three_batter_rules() -> GameRules {
+        GameRules {
+            three_batter_minimum: true,
+            ..GameRules::default()
+        }
+    }
+
+    /// The top of the 1st with a reliever in for the home team after facing
+    /// `batters` batters.
+    fn reliever_in(batters: usize) -> GameState {
+        let rules = three_batter_rules();
+        let start = substitute_pitcher(&lineup_game(), "reliever", false, &rules).unwrap();
+        let events = [
+            GameEvent::Single {
+                advancement: vec![],
+            },
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            GameEvent::FlyOut {
+                advancement: vec![],
+            },
+        ];
+        events[..batters].iter().fold(start, |state, event| {
+            apply_event(&state, event, &rules).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_batters_faced_tracked() {
+        let state = reliever_in(2);
+        assert_eq!(state.batters_faced.get("reliever"), Some(&2));
+        assert_eq!(state.half_inning_batters, 2);
+        assert_eq!(state.batters_faced.get("pitcher"), None);
+    }
+
+    #[test]
+    fn test_three_batter_minimum_rejects_early_change() {
+        let rules = three_batter_rules();
+        let result = substitute_pitcher(&reliever_in(2), "closer", false, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: Pitcher reliever has faced 2 of the minimum 3 batters"
+        );
+        assert!(substitute_pitcher(&reliever_in(2), "closer", true, &rules).is_ok());
+        assert!(
+            substitute_pitcher(&reliever_in(2), "closer", false, &GameRules::default()).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_three_batter_minimum_allows_change_after_half_inning() {
+        let rules = three_batter_rules();
+        let mut state = reliever_in(1);
+        state.outs = 2;
+        let mut state = apply_event(
+            &state,
+            &GameEvent::LineOut {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.half_inning_batters, 0);
+
+        state.current_pitcher_id = Some("away_pitcher".to_string());
+        let mut state = three_outs(&state, &rules);
+        state.current_pitcher_id = Some("reliever".to_string());
+        assert_eq!(state.inning, 2);
+        assert_eq!(state.batters_faced.get("reliever"), Some(&2));
+        assert!(substitute_pitcher(&state, "closer", false, &rules).is_ok());
+
+        let state = reliever_in(3);
+        assert!(substitute_pitcher(&state, "closer", false, &rules).is_ok());
+    }
+
+    fn dh_rules() -> GameRules {
+        GameRules {
+            designated_hitter: true,
+            ..GameRules::default()
+        }
+    }
+
+    /// A game in progress where both teams use a designated hitter, with the
+    /// home pitcher on the mound.
+    fn dh_game() -> GameState {
+        let mut state = lineup_game();
+        state.lineups.home = lineup("h").with_pitcher("pitcher");
+        state.lineups.away = lineup("a").with_pitcher("ap");
+        state
+    }
+
+    #[test]
+    fn test_pitching_change_with_designated_hitter() {
+        let rules = dh_rules();
+        let state = substitute_pitcher(&dh_game(), "reliever", false, &rules).unwrap();
+        assert_eq!(state.lineups.home.pitcher.as_deref(), Some("reliever"));
+        assert_eq!(state.lineups.home.players, lineup("h").players);
+        assert!(apply_pitch(&state, PitchResult::Ball, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_pitching_change_without_designated_hitter() {
+        let rules = GameRules::default();
+        let mut state = lineup_game();
+        state.lineups.home.players[8] = "pitcher".to_string();
+        state.lineups.home.pitcher = Some("pitcher".to_string());
+        let state = substitute_pitcher(&state, "reliever", false, &rules).unwrap();
+        assert_eq!(state.lineups.home.players[8], "reliever");
+        assert_eq!(state.lineups.home.pitcher.as_deref(), Some("reliever"));
+    }
+
+    #[test]
+    fn test_pitcher_batting_forfeits_designated_hitter() {
+        let rules = dh_rules();
+        let state = substitute_batter(&dh_game(), 0, "ap", &rules).unwrap();
+        assert!(state.dh_forfeited.away);
+        assert!(!state.uses_designated_hitter(Team::Away, &rules));
+        assert_eq!(state.current_batter_id.as_deref(), Some("ap"));
+        assert_eq!(state.removed_players.away, vec!["a1".to_string()]);
+        assert!(apply_pitch(&state, PitchResult::Ball, &rules).is_ok());
+
+        let result = substitute_batter(&dh_game(), 0, "ap", &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+    }
+
+    #[test]
+    fn test_start_rejects_pitcher_batting_with_designated_hitter() {
+        let rules = dh_rules();
+        let mut state = dh_game();
+        state.lineups.away.players[8] = "ap".to_string();
+        let result = apply_pitch(&state, PitchResult::Ball, &rules);
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+    }
+
+    /// `team`'s defense with `{team}9` pitching, `{team}2` through `{team}8`
+    /// at catcher through center field and `{team}1` in right field.
+    fn defense(team: &str) -> DefensiveLineup {
+        let players = [9, 2, 3, 4, 5, 6, 7, 8, 1].map(|slot| format!("{}{}", team, slot));
+        DefensiveLineup::new(Position::FIELDERS.into_iter().zip(players))
+    }
+
+    #[test]
+    fn test_substitutes_take_over_positions() {
+        let rules = GameRules::default();
+        let mut state = lineup_game();
+        state.lineups.home = lineup("h").with_pitcher("h9");
+        state.current_pitcher_id = Some("h9".to_string());
+        state.defensive_lineups = PerTeam {
+            home: defense("h"),
+            away: defense("a"),
+        };
+        assert_eq!(
+            state.position_of("h2"),
+            Some((Team::Home, Position::Catcher))
+        );
+
+        let state = substitute_pitcher(&state, "h_rp", false, &rules).unwrap();
+        assert_eq!(
+            state.position_of("h_rp"),
+            Some((Team::Home, Position::Pitcher))
+        );
+        assert_eq!(state.position_of("h9"), None);
+        let state = substitute_batter(&state, 0, "a_ph", &rules).unwrap();
+        assert_eq!(
+            state.position_of("a_ph"),
+            Some((Team::Away, Position::RightField))
+        );
+
+        let state = three_outs(&state, &rules);
+        assert_eq!(state.current_pitcher_id.as_deref(), Some("a9"));
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+    }
+
+    /// The top of the 7th with the home pitcher `h9` batting ninth and
+    /// fielding the home defense.
+    fn seventh_inning() -> GameState {
+        let mut state = lineup_game();
+        state.inning = 7;
+        state.lineups.home = lineup("h").with_pitcher("h9");
+        state.current_pitcher_id = Some("h9".to_string());
+        state.defensive_lineups.home = defense("h");
+        state
+    }
+
+    #[test]
+    fn test_double_switch() {
+        let rules = GameRules {
+            three_batter_minimum: true,
+            ..GameRules::default()
+        };
+        let state = double_switch(&seventh_inning(), "h_rp", 6, "h_lf", &rules).unwrap();
+        assert_eq!(state.lineups.home.players[6], "h_rp");
+        assert_eq!(state.lineups.home.players[8], "h_lf");
+        assert_eq!(state.lineups.home.pitcher.as_deref(), Some("h_rp"));
+        assert_eq!(state.current_pitcher_id.as_deref(), Some("h_rp"));
+        assert_eq!(
+            state.position_of("h_rp"),
+            Some((Team::Home, Position::Pitcher))
+        );
+        assert_eq!(
+            state.position_of("h_lf"),
+            Some((Team::Home, Position::LeftField))
+        );
+        assert_eq!(
+            state.removed_players.home,
+            vec!["h9".to_string(), "h7".to_string()]
+        );
+        assert!(apply_pitch(&state, PitchResult::Ball, &rules).is_ok());
+
+        let mut tired = seventh_inning();
+        tired.half_inning_batters = 1;
+        let result = double_switch(&tired, "h_rp", 6, "h_lf", &rules);
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+        let result = double_switch(&seventh_inning(), "h_rp", 8, "h_lf", &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: Pitcher h9 already bats in slot 8"
+        );
+    }
+
+    #[test]
+    fn test_double_switch_rejects_reentry_without_partial_changes() {
+        let mut session = crate::GameSession::new(seventh_inning(), GameRules::default());
+        session.pitching_change("h_rp", false).unwrap();
+        let before = session.state().clone();
+
+        let result = session.double_switch("h_rp2", 6, "h9");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: Player h9 has been removed and cannot re-enter"
+        );
+        assert_eq!(session.state(), &before);
+        assert_eq!(session.state().current_pitcher_id.as_deref(), Some("h_rp"));
+        assert_eq!(session.state().lineups.home.slot_of("h_rp2"), None);
+    }
+
+    /// The away team batting with its catcher `a2` on second and its
+    /// pitcher `a9` on first, under `courtesy_runner`.
+    fn courtesy_game(courtesy_runner: CourtesyRunnerRule) -> (GameState, GameRules) {
+        let mut state = lineup_game();
+        state.defensive_lineups.away = defense("a");
+        state.bases.set(Base::First, Some("a9".to_string()));
+        state.bases.set(Base::Second, Some("a2".to_string()));
+        state.bases.set(Base::Third, Some("a5".to_string()));
+        let rules = GameRules {
+            courtesy_runner,
+            ..GameRules::default()
+        };
+        (state, rules)
+    }
+
+    #[test]
+    fn test_courtesy_runner_for_eligible_positions() {
+        let (state, rules) = courtesy_game(CourtesyRunnerRule::CatcherOnly);
+        let next = courtesy_run(&state, Base::Second, "a_cr", &rules).unwrap();
+        assert_eq!(next.bases.runner_at(Base::Second), Some("a_cr"));
+        assert_eq!(next.lineups, state.lineups);
+        assert_eq!(next.defensive_lineups, state.defensive_lineups);
+        assert!(next.removed_players.away.is_empty());
+        assert_eq!(
+            courtesy_run(&state, Base::First, "a_cr", &rules)
+                .unwrap_err()
+                .to_string(),
+            "Rule violation: Runner a9 does not play a position eligible for a courtesy runner"
+        );
+
+        let (state, rules) = courtesy_game(CourtesyRunnerRule::CatcherAndPitcher);
+        let next = courtesy_run(&state, Base::First, "a_cr", &rules).unwrap();
+        assert_eq!(next.bases.runner_at(Base::First), Some("a_cr"));
+        let outcome = apply_event_detailed(&next, &GameEvent::HomeRun, &rules).unwrap();
+        assert!(outcome.scorers.contains(&"a_cr".to_string()));
+        assert!(!outcome.scorers.contains(&"a9".to_string()));
+        assert!(outcome.state.lineups.away.slot_of("a9").is_some());
+    }
+
+    #[test]
+    fn test_courtesy_runner_rejected() {
+        let (state, rules) = courtesy_game(CourtesyRunnerRule::None);
+        assert_eq!(
+            courtesy_run(&state, Base::Second, "a_cr", &rules)
+                .unwrap_err()
+                .to_string(),
+            "Rule violation: Courtesy runners are not allowed"
+        );
+
+        let (state, rules) = courtesy_game(CourtesyRunnerRule::CatcherAndPitcher);
+        for (base, runner) in [
+            (Base::Third, "a_cr"),
+            (Base::Second, "a4"),
+            (Base::Second, "h1"),
+        ] {
+            let result = courtesy_run(&state, base, runner, &rules);
+            assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+        }
+    }
+
+    #[test]
+    fn test_pitcher_batting_clears_designated_hitter_position() {
+        let rules = dh_rules();
+        let mut state = dh_game();
+        let mut away = defense("a");
+        away.positions.insert(Position::Pitcher, "ap".to_string());
+        away.positions
+            .insert(Position::DesignatedHitter, "a9".to_string());
+        state.defensive_lineups.away = away;
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+
+        let slot = state.lineups.away.slot_of("a9").unwrap();
+        let state = substitute_batter(&state, slot, "ap", &rules).unwrap();
+        assert_eq!(
+            state
+                .defensive_lineups
+                .away
+                .player_at(Position::DesignatedHitter),
+            None
+        );
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+    }
+
+    fn pitch_limit_rules(limit: u16) -> GameRules {
+        GameRules {
+            pitch_limit: Some(limit),
+            ..GameRules::default()
+        }
+    }
+
+    #[test]
+    fn test_pitch_counts_tracked() {
+        let rules = GameRules::default();
+        let state = apply_all(
+            &create_test_state(),
+            &[
+                PitchResult::Ball,
+                PitchResult::Foul,
+                PitchResult::HitByPitch,
+            ],
+        );
+        assert_eq!(state.pitches_thrown("pitcher"), 3);
+        assert_eq!(state.pitches_thrown("reliever"), 0);
+        assert_eq!(state.plate_appearance_pitches, 0);
+
+        let state = apply_pitch(&with_batter(&state), PitchResult::Ball, &rules).unwrap();
+        assert_eq!(state.pitches_thrown("pitcher"), 4);
+        assert_eq!(state.plate_appearance_pitches, 1);
+    }
+
+    #[test]
+    fn test_inherited_runner_charged_to_starter() {
+        let rules = GameRules::default();
+        let double = GameEvent::Double {
+            advancement: vec![],
+        };
+        let state = apply_event(&create_test_state(), &double, &rules).unwrap();
+        assert_eq!(
+            state.responsible_pitchers.runner_at(Base::Second),
+            Some("pitcher")
+        );
+
+        let state = substitute_pitcher(&state, "reliever", false, &rules).unwrap();
+        let triple = GameEvent::Triple {
+            advancement: vec![],
+        };
+        let outcome = apply_event_detailed(&with_batter(&state), &triple, &rules).unwrap();
+        assert_eq!(outcome.scorers, vec!["batter".to_string()]);
+        assert_eq!(outcome.charged_pitchers, vec!["pitcher".to_string()]);
+        assert_eq!(
+            outcome.state.responsible_pitchers,
+            Bases::from((None, None, Some("reliever".to_string())))
+        );
+
+        let outcome =
+            apply_event_detailed(&with_batter(&outcome.state), &GameEvent::HomeRun, &rules)
+                .unwrap();
+        assert_eq!(outcome.charged_pitchers, vec!["reliever".to_string(); 2]);
+        assert_eq!(outcome.state.runs_allowed_by("pitcher"), 1);
+        assert_eq!(outcome.state.runs_allowed_by("reliever"), 2);
+        assert_eq!(outcome.state.runs_allowed_by("batter"), 0);
+    }
+
+    fn reached_on_error() -> GameEvent {
+        GameEvent::ReachedOnError {
+            batter_to: Base::First,
+            runners_advance: 1,
+        }
+    }
+
+    #[test]
+    fn test_clean_inning_runs_are_earned() {
+        let rules = GameRules::default();
+        let double = GameEvent::Double {
+            advancement: vec![],
+        };
+        let state = apply_event(&create_test_state(), &double, &rules).unwrap();
+        let single = GameEvent::Single {
+            advancement: vec![RunnerAdvance {
+                from: Base::Second,
+                to: Advance::Home,
+                out_was_force: false,
+                force_no_rbi: false,
+            }],
+        };
+        let outcome = apply_event_detailed(&with_batter(&state), &single, &rules).unwrap();
+        assert_eq!(outcome.charged_pitchers, vec!["pitcher".to_string()]);
+        assert_eq!(outcome.earned, vec![true]);
+        assert_eq!(outcome.state.earned_runs_allowed_by("pitcher"), 1);
+        assert_eq!(outcome.state.unearned_runners, [false; 3]);
+    }
+
+    #[test]
+    fn test_runner_who_reached_on_error_scores_unearned() {
+        let rules = GameRules::default();
+        let state = apply_event(&create_test_state(), &reached_on_error(), &rules).unwrap();
+        assert_eq!(state.unearned_runners, [true, false, false]);
+        assert_eq!(state.outs_lost_to_errors, 1);
+
+        let triple = GameEvent::Triple {
+            advancement: vec![],
+        };
+        let outcome = apply_event_detailed(&with_batter(&state), &triple, &rules).unwrap();
+        assert_eq!(outcome.scorers, vec!["batter".to_string()]);
+        assert_eq!(outcome.earned, vec![false]);
+        assert_eq!(outcome.state.unearned_runners, [false, false, false]);
+        assert_eq!(outcome.state.runs_allowed_by("pitcher"), 1);
+        assert_eq!(outcome.state.earned_runs_allowed_by("pitcher"), 0);
+
+        // The batter who tripled scores on a passed ball: also unearned.
+        let outcome = apply_event_detailed(&outcome.state, &GameEvent::PassedBall, &rules).unwrap();
+        assert_eq!(outcome.earned, vec![false]);
+        assert_eq!(outcome.state.earned_runs_allowed_by("pitcher"), 0);
+    }
+
+    #[test]
+    fn test_two_out_error_makes_later_runs_unearned() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.outs = 2;
+        state.bases.set(Base::Second, Some("r2".to_string()));
+        let outcome = apply_event_detailed(&state, &reached_on_error(), &rules).unwrap();
+        assert_eq!(outcome.state.outs_lost_to_errors, 1);
+        // r2 moved up on the error, and the batter reached on it.
+        assert_eq!(outcome.state.unearned_runners, [true, false, true]);
+
+        let state = substitute_pitcher(&outcome.state, "reliever", false, &rules).unwrap();
+        let outcome =
+            apply_event_detailed(&with_batter(&state), &GameEvent::HomeRun, &rules).unwrap();
+        assert_eq!(
+            outcome.charged_pitchers,
+            vec![
+                "pitcher".to_string(),
+                "pitcher".to_string(),
+                "reliever".to_string()
+            ]
+        );
+        assert_eq!(outcome.earned, vec![false; 3]);
+        assert_eq!(outcome.state.runs_allowed_by("pitcher"), 2);
+        assert_eq!(outcome.state.runs_allowed_by("reliever"), 1);
+        assert!(outcome.state.earned_runs_allowed.is_empty());
+
+        let state = strikeout(&outcome.state, &rules);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.outs_lost_to_errors, 0);
+    }
+
+    /// Runs batted in on `play` from `state`.
+    fn rbis(state: &GameState, play: Play) -> u8 {
+        let outcome = apply_play_detailed(state, &play, &GameRules::default()).unwrap();
+        assert!(outcome.runs_scored > 0, "no run scored on {:?}", play);
+        outcome.rbis
+    }
+
+    #[test]
+    fn test_bases_loaded_walk_bats_in_a_run() {
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        state.count.balls = 3;
+        assert_eq!(rbis(&state, Play::Pitch(PitchResult::Ball)), 1);
+        assert_eq!(rbis(&state, Play::Pitch(PitchResult::HitByPitch)), 1);
+    }
+
+    #[test]
+    fn test_double_play_bats_in_no_run() {
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        let event = GameEvent::DoublePlay {
+            runners_out: vec![Base::First],
+            batter_out: true,
+        };
+        assert_eq!(rbis(&state, Play::Event(event)), 0);
+    }
+
+    #[test]
+    fn test_ground_out_force_double_play_bats_in_no_run() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::GroundOut {
+            advancement: vec![
+                RunnerAdvance {
+                    from: Base::First,
+                    to: Advance::Out,
+                    out_was_force: true,
+                    force_no_rbi: false,
+                },
+                RunnerAdvance {
+                    from: Base::Third,
+                    to: Advance::Home,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                },
+            ],
+        };
+        assert_eq!(rbis(&state, Play::Event(event)), 0);
+    }
+
+    #[test]
+    fn test_sac_fly_bats_in_a_run() {
+        let mut state = create_test_state();
+        state.bases.set(Base::Third, Some("r3".to_string()));
+        let event = GameEvent::SacFly {
+            tagging_up: vec![Base::Third],
+        };
+        assert_eq!(rbis(&state, Play::Event(event)), 1);
+    }
+
+    #[test]
+    fn test_runs_without_the_batter_are_not_batted_in() {
+        let mut state = create_test_state();
+        state.bases.set(Base::Third, Some("r3".to_string()));
+        assert_eq!(rbis(&state, Play::Event(GameEvent::WildPitch)), 0);
+        assert_eq!(rbis(&state, Play::Event(GameEvent::Balk)), 0);
+        assert_eq!(rbis(&state, Play::Event(reached_on_error())), 0);
+    }
+
+    #[test]
+    fn test_scorer_can_rule_a_run_not_batted_in() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((None, Some("r2".to_string()), Some("r3".to_string())));
+        let scores = |from, force_no_rbi| RunnerAdvance {
+            from,
+            to: Advance::Home,
+            out_was_force: false,
+            force_no_rbi,
+        };
+        let single = |advancement| Play::Event(GameEvent::Single { advancement });
+        assert_eq!(
+            rbis(
+                &state,
+                single(vec![
+                    scores(Base::Third, false),
+                    scores(Base::Second, false)
+                ])
+            ),
+            2
+        );
+        assert_eq!(
+            rbis(
+                &state,
+                single(vec![scores(Base::Third, false), scores(Base::Second, true)])
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn test_responsible_pitcher_follows_the_base() {
+        let rules = GameRules::default();
+        let hit = apply_all(&create_test_state(), &[PitchResult::HitByPitch]);
+        let state = substitute_runner(&hit, Base::First, "pinch", &rules).unwrap();
+        let state = substitute_pitcher(&state, "reliever", false, &rules).unwrap();
+        let state = apply_all(&with_batter(&state), &[PitchResult::HitByPitch]);
+        assert_eq!(
+            state.responsible_pitchers,
+            Bases::from((
+                Some("reliever".to_string()),
+                Some("pitcher".to_string()),
+                None
+            ))
+        );
+
+        let state = three_outs(&state, &rules);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.responsible_pitchers, Bases::new());
+        assert!(state.runs_allowed.is_empty());
+    }
+
+    #[test]
+    fn test_apply_pitch_mut_matches_apply_pitch() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        let mut expected = state.clone();
+        let pitches = [
+            PitchResult::Ball,
+            PitchResult::Foul,
+            PitchResult::StrikeCalled,
+            PitchResult::Ball,
+            PitchResult::Ball,
+            PitchResult::Ball,
+            PitchResult::HitByPitch,
+            PitchResult::StrikeSwinging,
+            PitchResult::StrikeCalled,
+            PitchResult::FoulTip,
+            PitchResult::StrikeSwingingDropped,
+        ];
+        for _ in 0..4 {
+            for pitch in pitches {
+                state = with_batter(&state);
+                expected = with_batter(&expected);
+                expected = apply_pitch(&expected, pitch, &rules).unwrap();
+                apply_pitch_mut(&mut state, pitch, &rules).unwrap();
+                assert_eq!(state, expected);
+            }
+        }
+        assert!(state.line_score.len() > 1 || state.inning > 1);
+    }
+
+    #[test]
+    fn test_apply_pitch_mut_leaves_state_on_error() {
+        let rules = pitch_limit_rules(1);
+        let mut state = apply_pitch(&create_test_state(), PitchResult::HitByPitch, &rules).unwrap();
+        let before = with_batter(&state);
+        state = before.clone();
+        let result = apply_pitch_mut(&mut state, PitchResult::Ball, &rules);
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+        assert_eq!(state, before);
+
+        state.status = GameStatus::Final;
+        let before = state.clone();
+        assert!(apply_pitch_mut(&mut state, PitchResult::Ball, &rules).is_err());
+        assert_eq!(state, before);
+    }
+
+    #[test]
+    fn test_step_matches_apply_event_detailed() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        let events = [
+            single_with(&[]),
+            GameEvent::WildPitch,
+            GameEvent::IntentionalWalk,
+            GameEvent::HomeRun,
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+        ];
+        for _ in 0..3 {
+            for event in &events {
+                let expected = apply_event_detailed(&with_batter(&state), event, &rules).unwrap();
+                state = with_batter(&state);
+                let step = step(&mut state, event, &rules).unwrap();
+                assert_eq!(step.with_state(state.clone()), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_leaves_state_on_error() {
+        let rules = GameRules {
+            allow_auto_intentional_walk: false,
+            ..GameRules::default()
+        };
+        let mut state = with_batter(&create_test_state());
+        bases_loaded(&mut state);
+        state.outs = 2;
+        let before = state.clone();
+        let events = [
+            GameEvent::IntentionalWalk,
+            single_with(&[(Base::First, Advance::Base(Base::First))]),
+            GameEvent::FlyOut {
+                advancement: vec![RunnerAdvance {
+                    from: Base::First,
+                    to: Advance::Out,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                }],
+            },
+            GameEvent::Pickoff { base: Base::First },
+        ];
+        for event in &events[..3] {
+            assert!(step(&mut state, event, &rules).is_err());
+            assert_eq!(state, before);
+        }
+
+        state.current_batter_id = None;
+        let before = state.clone();
+        assert!(matches!(
+            step(&mut state, &GameEvent::HomeRun, &rules),
+            Err(BaselomError::NoCurrentBatter)
+        ));
+        assert_eq!(state, before);
+
+        state.status = GameStatus::Final;
+        let before = state.clone();
+        assert!(step(&mut state, &events[3], &rules).is_err());
+        assert_eq!(state, before);
+    }
+
+    #[test]
+    fn test_step_play() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.current_batter_id = None;
+
+        let step = step_play(&mut state, &Play::Batter("batter".to_string()), &rules).unwrap();
+        assert_eq!(step, StepOutcome::default());
+        assert_eq!(state.current_batter_id.as_deref(), Some("batter"));
+
+        let step = step_play(&mut state, &Play::Event(GameEvent::HomeRun), &rules).unwrap();
+        assert_eq!(step.runs_scored, 1);
+        assert_eq!(step.scorers, vec!["batter".to_string()]);
+        assert_eq!(state.score.away, 1);
+
+        let before = state.clone();
+        let result = step_play(&mut state, &Play::Pitch(PitchResult::HitByPitch), &rules);
+        assert!(matches!(result, Err(BaselomError::NoCurrentBatter)));
+        assert_eq!(state, before);
+    }
+
+    /// Every base-out state and count, with and without a batter, in each
+    /// game status.
+    fn legality_states() -> Vec<GameState> {
+        let mut states = Vec::new();
+        for situation in BaseOutState::ALL {
+            for (balls, strikes) in (0..4).flat_map(|balls| (0..3).map(move |s| (balls, s))) {
+                for batter in [Some("batter".to_string()), None] {
+                    for status in [GameStatus::InProgress, GameStatus::Final] {
+                        let mut state = create_test_state();
+                        state.outs = situation.outs();
+                        for (index, base) in Base::ALL.into_iter().enumerate() {
+                            if situation.is_occupied(base) {
+                                state.bases.set(base, Some(format!("r{}", index + 1)));
+                            }
+                        }
+                        state.count = Count::new(balls, strikes);
+                        state.current_batter_id = batter.clone();
+                        state.status = status;
+                        states.push(state);
+                    }
+                }
+            }
+        }
+        states
+    }
+
+    fn legality_rules() -> Vec<GameRules> {
+        vec![
+            GameRules::default(),
+            GameRules {
+                allow_auto_intentional_walk: false,
+                balk_with_empty_bases_is_ball: true,
+                ..GameRules::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_legal_events_match_apply_event() {
+        for rules in legality_rules() {
+            for state in legality_states() {
+                let legal = legal_events(&state, &rules);
+                for kind in GameEventKind::ALL {
+                    let result = apply_event(&state, &kind.event(), &rules);
+                    assert_eq!(
+                        legal.contains(&kind),
+                        result.is_ok(),
+                        "{:?} in {:?}: {:?}",
+                        kind,
+                        state,
+                        result
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_legal_pitches_match_apply_pitch() {
+        let mut limited = pitch_limit_rules(10);
+        limited.pitch_limit_finish_batter = false;
+        let mut rules = legality_rules();
+        rules.push(limited);
+        for rules in rules {
+            for mut state in legality_states() {
+                for removal in [None, Some("pitcher".to_string())] {
+                    state.pitch_counts.insert("pitcher".to_string(), 10);
+                    state.pitcher_removal_required = removal;
+                    let legal = legal_pitches(&state, &rules);
+                    for pitch in PitchResult::ALL {
+                        let result = apply_pitch(&state, pitch, &rules);
+                        assert_eq!(legal.contains(&pitch), result.is_ok(), "{:?}", pitch);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_legal_events() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases.set(Base::First, Some("r1".to_string()));
+        state.outs = 2;
+        let legal = legal_events(&state, &rules);
+        assert!(legal.contains(&GameEventKind::Pickoff(Base::First)));
+        assert!(!legal.contains(&GameEventKind::Pickoff(Base::Second)));
+        assert!(!legal.contains(&GameEventKind::SacFly));
+        assert!(legal.contains(&GameEventKind::IntentionalWalk));
+
+        state.status = GameStatus::Final;
+        assert!(legal_events(&state, &rules).is_empty());
+        assert!(legal_pitches(&state, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_pitch_limit_reached_mid_plate_appearance() {
+        let rules = pitch_limit_rules(3);
+        let mut state = create_test_state();
+        for _ in 0..3 {
+            state = apply_pitch(&state, PitchResult::Ball, &rules).unwrap();
+        }
+        assert_eq!(state.pitches_thrown("pitcher"), 3);
+
+        // The batter in progress may be finished
+        let state = apply_pitch(&state, PitchResult::Ball, &rules).unwrap();
+        assert_eq!(state.bases.runner_at(Base::First), Some("batter"));
+
+        let result = apply_pitch(&with_batter(&state), PitchResult::Ball, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: Pitcher pitcher has reached the pitch limit of 3"
+        );
+
+        let relieved = GameState {
+            current_pitcher_id: Some("reliever".to_string()),
+            ..with_batter(&state)
+        };
+        assert!(apply_pitch(&relieved, PitchResult::Ball, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_pitch_limit_without_finishing_batter() {
+        let rules = GameRules {
+            pitch_limit_finish_batter: false,
+            ..pitch_limit_rules(2)
+        };
+        let state = apply_all(
+            &create_test_state(),
+            &[PitchResult::Ball, PitchResult::Ball],
+        );
+        let result = apply_pitch(&state, PitchResult::Ball, &rules);
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+    }
+
+    fn mound_visit_rules(over_limit_removes_pitcher: bool) -> GameRules {
+        GameRules {
+            mound_visit_limit: Some(1),
+            mound_visit_over_limit_removes_pitcher: over_limit_removes_pitcher,
+            three_batter_minimum: true,
+            ..GameRules::default()
+        }
+    }
+
+    #[test]
+    fn test_mound_visits_counted_for_the_game() {
+        let rules = GameRules::default();
+        let state = mound_visit(&lineup_game(), Team::Home, &rules).unwrap();
+        assert_eq!(state.mound_visits.home, 1);
+        let result = mound_visit(&state, Team::Away, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: The away team is batting and cannot visit the mound"
+        );
+
+        let state = [
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            GameEvent::FlyOut {
+                advancement: vec![],
+            },
+        ]
+        .iter()
+        .fold(state, |state, event| {
+            apply_event(&state, event, &rules).unwrap()
+        });
+        assert_eq!(state.half, InningHalf::Bottom);
+        let state = mound_visit(&state, Team::Away, &rules).unwrap();
+        assert_eq!(state.mound_visits, PerTeam { home: 1, away: 1 });
+        assert_eq!(state.mound_visits_remaining(Team::Home, &rules), None);
+
+        let json = serde_json::to_string(&state).unwrap();
+        let deserialized: GameState = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, state);
+    }
+
+    #[test]
+    fn test_mound_visit_past_limit_rejected() {
+        let rules = mound_visit_rules(false);
+        let state = mound_visit(&lineup_game(), Team::Home, &rules).unwrap();
+        assert_eq!(state.mound_visits_remaining(Team::Home, &rules), Some(0));
+        let result = mound_visit(&state, Team::Home, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: The home team has used all 1 of its mound visits"
+        );
+
+        let mut over = state.clone();
+        over.mound_visits.home = 2;
+        let result = apply_pitch(&over, PitchResult::Ball, &rules);
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_mound_visit_past_limit_forces_pitching_change() {
+        let rules = mound_visit_rules(true);
+        let state = apply_event(
+            &lineup_game(),
+            &GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        )
+        .unwrap();
+        let state = mound_visit(&state, Team::Home, &rules).unwrap();
+        assert!(state.pitcher_removal_required.is_none());
+        let state = mound_visit(&state, Team::Home, &rules).unwrap();
+        assert_eq!(state.mound_visits.home, 2);
+        assert_eq!(state.pitcher_removal_required.as_deref(), Some("pitcher"));
+
+        let result = apply_pitch(&state, PitchResult::Ball, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: Pitcher pitcher must be replaced after a mound visit past the limit"
+        );
+
+        // The required change overrides the three-batter minimum.
+        let state = substitute_pitcher(&state, "reliever", false, &rules).unwrap();
+        assert!(state.pitcher_removal_required.is_none());
+        assert!(apply_pitch(&state, PitchResult::Ball, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_ground_rule_double_moves_runner_from_first_to_third() {
+        let mut state = create_test_state();
+        state.count = Count {
+            balls: 2,
+            strikes: 1,
+        };
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let outcome =
+            apply_event_detailed(&state, &GameEvent::GroundRuleDouble, &GameRules::default())
+                .unwrap();
+        assert_eq!(
+            outcome.state.bases,
+            Bases::from((None, Some("batter".to_string()), Some("r1".to_string())))
+        );
+        assert_eq!(outcome.runs_scored, 0);
+        assert_eq!(outcome.outs_recorded, 0);
+        assert_eq!(outcome.state.count, Count::default());
+        assert_eq!(outcome.state.team_totals.away.hits, 1);
+    }
+
+    #[test]
+    fn test_ground_rule_double_scores_runner_from_second() {
+        let mut state = create_test_state();
+        state.bases = Bases::from((None, Some("r2".to_string()), Some("r3".to_string())));
+        let outcome =
+            apply_event_detailed(&state, &GameEvent::GroundRuleDouble, &GameRules::default())
+                .unwrap();
+        assert_eq!(outcome.runs_scored, 2);
+        assert_eq!(outcome.scorers, vec!["r3".to_string(), "r2".to_string()]);
+        assert_eq!(
+            outcome.state.bases,
+            Bases::from((None, Some("batter".to_string()), None))
+        );
+        assert_eq!(outcome.state.score.away, 2);
+    }
+
+    #[test]
+    fn test_award_bases() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::AwardBases {
+            batter_to: Base::First,
+            runners_advance: 1,
+        };
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(
+            next.bases,
+            Bases::from((Some("batter".to_string()), Some("r1".to_string()), None))
+        );
+        assert_eq!(next.score.away, 1);
+        assert_eq!(next.outs, 0);
+        assert_eq!(next.team_totals.away.hits, 0);
+
+        let event = GameEvent::AwardBases {
+            batter_to: Base::Second,
+            runners_advance: 1,
+        };
+        let result = apply_event(&state, &event, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: The runner on first base must finish ahead of the batter"
+        );
+    }
+
+    #[test]
+    fn test_reached_on_error_charges_fielding_team() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.count = Count {
+            balls: 1,
+            strikes: 2,
+        };
+        state.bases.set(Base::Second, Some("r2".to_string()));
+        let event = GameEvent::ReachedOnError {
+            batter_to: Base::First,
+            runners_advance: 1,
+        };
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(
+            next.bases,
+            Bases::from((Some("batter".to_string()), None, Some("r2".to_string())))
+        );
+        assert_eq!(next.count, Count::default());
+        assert_eq!(next.team_totals.home.errors, 1);
+        assert_eq!(next.team_totals.away.hits, 0);
+        assert_eq!(next.team_totals.away.errors, 0);
+
+        state.half = InningHalf::Bottom;
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(next.team_totals.away.errors, 1);
+        assert_eq!(next.team_totals.home.errors, 0);
+        assert_eq!(next.team_totals.home.hits, 0);
+    }
+
+    fn single_with(advancement: &[(Base, Advance)]) -> GameEvent {
+        GameEvent::Single {
+            advancement: advancement
+                .iter()
+                .map(|&(from, to)| RunnerAdvance {
+                    from,
+                    to,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_single_with_runner_advancement() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), Some("r2".to_string()), None));
+
+        let event = single_with(&[
+            (Base::Second, Advance::Home),
+            (Base::First, Advance::Base(Base::Third)),
+        ]);
+        let outcome = apply_event_detailed(&state, &event, &rules).unwrap();
+        assert_eq!(outcome.scorers, vec!["r2".to_string()]);
+        assert_eq!(
+            outcome.state.bases,
+            Bases::from((Some("batter".to_string()), None, Some("r1".to_string())))
+        );
+        assert_eq!(outcome.state.team_totals.away.hits, 1);
+
+        // Unlisted runners keep the default advancement.
+        let event = single_with(&[(Base::Second, Advance::Base(Base::Third))]);
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(
+            next.bases,
+            Bases::from((
+                Some("batter".to_string()),
+                Some("r1".to_string()),
+                Some("r2".to_string())
+            ))
+        );
+        assert_eq!(next.score.away, 0);
+    }
+
+    #[test]
+    fn test_runner_thrown_out_at_home_for_third_out() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.outs = 2;
+        state.bases = Bases::from((None, Some("r2".to_string()), Some("r3".to_string())));
+        let event = single_with(&[(Base::Second, Advance::Out)]);
+        let outcome = apply_event_detailed(&state, &event, &rules).unwrap();
+        assert_eq!(outcome.scorers, vec!["r3".to_string()]);
+        assert_eq!(outcome.state.score.away, 1);
+        assert_eq!(outcome.outs_recorded, 1);
+        assert!(outcome.half_inning_ended);
+        assert_eq!(outcome.state.half, InningHalf::Bottom);
+    }
+
+    #[test]
+    fn test_ground_out_with_runner_advancement() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::GroundOut {
+            advancement: vec![
+                RunnerAdvance {
+                    from: Base::Third,
+                    to: Advance::Home,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                },
+                RunnerAdvance {
+                    from: Base::First,
+                    to: Advance::Base(Base::Second),
+                    out_was_force: false,
+                    force_no_rbi: false,
+                },
+            ],
+        };
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(next.outs, 1);
+        assert_eq!(next.score.away, 1);
+        assert_eq!(
+            next.bases,
+            Bases::from((None, Some("r1".to_string()), None))
+        );
+        assert_eq!(next.team_totals.away.hits, 0);
+
+        // A force for the third out wipes out the run.
+        state.outs = 1;
+        let event = GameEvent::GroundOut {
+            advancement: vec![
+                RunnerAdvance {
+                    from: Base::Third,
+                    to: Advance::Home,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                },
+                RunnerAdvance {
+                    from: Base::First,
+                    to: Advance::Out,
+                    out_was_force: true,
+                    force_no_rbi: false,
+                },
+            ],
+        };
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(next.half, InningHalf::Bottom);
+        assert_eq!(next.score.away, 0);
+    }
+
+    #[test]
+    fn test_runner_doubled_off_after_run_scores() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.outs = 1;
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let event = GameEvent::FlyOut {
+            advancement: vec![
+                RunnerAdvance {
+                    from: Base::Third,
+                    to: Advance::Home,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                },
+                RunnerAdvance {
+                    from: Base::First,
+                    to: Advance::Out,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                },
+            ],
+        };
+        let outcome = apply_event_detailed(&state, &event, &rules).unwrap();
+        assert_eq!(outcome.runs_scored, 1);
+        assert_eq!(outcome.scorers, vec!["r3".to_string()]);
+        assert!(outcome.half_inning_ended);
+        assert_eq!(outcome.state.score.away, 1);
+
+        // The batter's own third out still wipes out the run.
+        state.outs = 2;
+        let event = GameEvent::GroundOut {
+            advancement: vec![RunnerAdvance {
+                from: Base::Third,
+                to: Advance::Home,
+                out_was_force: false,
+                force_no_rbi: false,
+            }],
+        };
+        let outcome = apply_event_detailed(&state, &event, &rules).unwrap();
+        assert_eq!(outcome.runs_scored, 0);
+        assert!(outcome.half_inning_ended);
+    }
+
+    #[test]
+    fn test_invalid_runner_advancement_rejected() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases = Bases::from((Some("r1".to_string()), Some("r2".to_string()), None));
+        let cases = [
+            (
+                single_with(&[(Base::Third, Advance::Home)]),
+                "No runner on third base to advance",
+            ),
+            (
+                single_with(&[(Base::First, Advance::Home), (Base::First, Advance::Out)]),
+                "The runner on first base is advanced more than once",
+            ),
+            (
+                single_with(&[(Base::Second, Advance::Base(Base::First))]),
+                "The runner on second base cannot move back to first base",
+            ),
+            (
+                single_with(&[(Base::Second, Advance::Base(Base::Second))]),
+                "The runner from second base and the runner from first base cannot finish on the same base",
+            ),
+            (
+                single_with(&[
+                    (Base::Second, Advance::Base(Base::Third)),
+                    (Base::First, Advance::Home),
+                ]),
+                "The runner from first base cannot pass the runner from second base",
+            ),
+            (
+                single_with(&[(Base::First, Advance::Base(Base::First))]),
+                "The runner from first base and the batter cannot finish on the same base",
+            ),
+        ];
+        for (event, message) in cases {
+            let result = apply_event(&state, &event, &rules);
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                format!("State error: {}", message)
+            );
+        }
+
+        state.outs = 2;
+        let event = GameEvent::FlyOut {
+            advancement: vec![RunnerAdvance {
+                from: Base::First,
+                to: Advance::Out,
+                out_was_force: false,
+                force_no_rbi: false,
+            }],
+        };
+        let result = apply_event(&state, &event, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: Cannot record 2 outs with 2 already out"
+        );
+    }
+
+    #[test]
+    fn test_runner_advancement_serialization() {
+        let event = single_with(&[
+            (Base::Second, Advance::Home),
+            (Base::First, Advance::Base(Base::Third)),
+        ]);
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"single":{"advancement":[{"from":"second","to":"home","out_was_force":false,"force_no_rbi":false},{"from":"first","to":{"base":"third"},"out_was_force":false,"force_no_rbi":false}]}}"#
+        );
+        let deserialized: GameEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, event);
+
+        let default: GameEvent = serde_json::from_str(r#"{"single":{}}"#).unwrap();
+        assert_eq!(
+            default,
+            GameEvent::Single {
+                advancement: vec![]
+            }
+        );
+    }
+
+    fn two_out_corners() -> GameState {
+        let mut state = create_test_state();
+        state.outs = 2;
+        state.bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        state
+    }
+
+    #[test]
+    fn test_run_counts_on_tag_play_for_third_out() {
+        // The runner from third crosses the plate before the runner from
+        // first is tagged out trying for third.
+        let event = single_with(&[(Base::First, Advance::Out)]);
+        let outcome =
+            apply_event_detailed(&two_out_corners(), &event, &GameRules::default()).unwrap();
+        assert!(outcome.half_inning_ended);
+        assert_eq!(outcome.scorers, vec!["r3".to_string()]);
+        assert_eq!(outcome.state.score.away, 1);
+    }
+
+    #[test]
+    fn test_run_does_not_count_on_force_for_third_out() {
+        let event = GameEvent::Single {
+            advancement: vec![RunnerAdvance {
+                from: Base::First,
+                to: Advance::Out,
+                out_was_force: true,
+                force_no_rbi: false,
+            }],
+        };
+        let outcome =
+            apply_event_detailed(&two_out_corners(), &event, &GameRules::default()).unwrap();
+        assert!(outcome.half_inning_ended);
+        assert_eq!(outcome.runs_scored, 0);
+        assert!(outcome.scorers.is_empty());
+
+        // The batter put out at first is the same as a force.
+        let event = GameEvent::GroundOut {
+            advancement: vec![RunnerAdvance {
+                from: Base::Third,
+                to: Advance::Home,
+                out_was_force: false,
+                force_no_rbi: false,
+            }],
+        };
+        let next = apply_event(&two_out_corners(), &event, &GameRules::default()).unwrap();
+        assert_eq!(next.score.away, 0);
+    }
+
+    #[test]
+    fn test_force_out_requires_forced_runner() {
+        let event = GameEvent::Single {
+            advancement: vec![RunnerAdvance {
+                from: Base::Third,
+                to: Advance::Out,
+                out_was_force: true,
+                force_no_rbi: false,
+            }],
+        };
+        let result = apply_event(&two_out_corners(), &event, &GameRules::default());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: The runner on third base was not forced out"
+        );
+    }
+
+    #[test]
+    fn test_infield_fly_with_bases_loaded() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        bases_loaded(&mut state);
+        let event = GameEvent::InfieldFly {
+            advancement: vec![],
+        };
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(next.outs, 1);
+        assert_eq!(next.bases, state.bases);
+        assert_eq!(next.score.away, 0);
+        assert_eq!(next.count, Count::default());
+
+        // A runner tagging up advances at the runner's own risk.
+        let event = GameEvent::InfieldFly {
+            advancement: vec![RunnerAdvance {
+                from: Base::Third,
+                to: Advance::Home,
+                out_was_force: false,
+                force_no_rbi: false,
+            }],
+        };
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(next.score.away, 1);
+        assert_eq!(next.bases.runner_at(Base::Third), None);
+    }
+
+    #[test]
+    fn test_infield_fly_requires_runners_on_first_and_second() {
+        let rules = GameRules::default();
+        let event = GameEvent::InfieldFly {
+            advancement: vec![],
+        };
+        let mut state = create_test_state();
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let result = apply_event(&state, &event, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: An infield fly requires runners on first and second base"
+        );
+
+        bases_loaded(&mut state);
+        state.outs = 2;
+        let result = apply_event(&state, &event, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: An infield fly cannot be declared with two outs"
+        );
+    }
+
+    #[test]
+    fn test_catchers_interference_advances_only_forced_runners() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.count = Count {
+            balls: 1,
+            strikes: 1,
+        };
+        state.bases.set(Base::Second, Some("r2".to_string()));
+        let next = apply_event(&state, &GameEvent::CatchersInterference, &rules).unwrap();
+        assert_eq!(
+            next.bases,
+            Bases::from((Some("batter".to_string()), Some("r2".to_string()), None))
+        );
+        assert_eq!(next.count, Count::default());
+        assert_eq!(next.team_totals.home.errors, 1);
+        assert_eq!(next.team_totals.away.hits, 0);
+        assert_eq!(next.pitches_thrown("pitcher"), 0);
+    }
+
+    #[test]
+    fn test_obstruction_awarding_home_scores() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.count = Count {
+            balls: 2,
+            strikes: 0,
+        };
+        state.bases.set(Base::Second, Some("r2".to_string()));
+        let event = GameEvent::Obstruction {
+            runner: Base::Second,
+            awarded: Advance::Home,
+        };
+        let outcome = apply_event_detailed(&state, &event, &rules).unwrap();
+        assert_eq!(outcome.scorers, vec!["r2".to_string()]);
+        assert!(outcome.state.bases.is_empty());
+        assert_eq!(outcome.state.count, state.count);
+        assert_eq!(outcome.state.current_batter_id, state.current_batter_id);
+
+        let event = GameEvent::Obstruction {
+            runner: Base::Second,
+            awarded: Advance::Base(Base::First),
+        };
+        let result = apply_event(&state, &event, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: Runner on second base must be awarded a base ahead"
+        );
+    }
+
+    #[test]
+    fn test_sac_bunt_advances_runner_from_first() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.outs = 1;
+        state.bases.set(Base::First, Some("r1".to_string()));
+        let event = GameEvent::SacBunt {
+            advancing: vec![Base::First],
+        };
+        let next = apply_event(&state, &event, &rules).unwrap();
+        assert_eq!(next.outs, 2);
+        assert_eq!(
+            next.bases,
+            Bases::from((None, Some("r1".to_string()), None))
+        );
+        assert_eq!(next.current_batter_id, None);
+    }
+
+    #[test]
+    fn test_squeeze_scores_runner_from_third() {
+        let rules = GameRules::default();
+        let mut state = create_test_state();
+        state.bases = Bases::from((None, Some("r2".to_string()), Some("r3".to_string())));
+        let event = GameEvent::SacBunt {
+            advancing: vec![Base::Second, Base::Third],
+        };
+        let outcome = apply_event_detailed(&state, &event, &rules).unwrap();
+        assert_eq!(outcome.scorers, vec!["r3".to_string()]);
+        assert_eq!(outcome.state.outs, 1);
+        assert_eq!(
+            outcome.state.bases,
+            Bases::from((None, None, Some("r2".to_string())))
+        );
+
+        state.outs = 2;
+        let result = apply_event(&state, &event, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Rule violation: A sacrifice bunt cannot be made with two outs"
+        );
+    }
+
+    #[test]
+    fn test_base_out_transition_matches_engine() {
+        let rules = GameRules::default();
+        for state in BaseOutState::ALL {
+            let game = GameState {
+                current_batter_id: Some("batter".to_string()),
+                current_pitcher_id: Some("pitcher".to_string()),
+                ..state.to_game_state()
+            };
+            for event in BaseOutEvent::ALL {
+                let game_event = match event {
+                    BaseOutEvent::Walk => GameEvent::IntentionalWalk,
+                    BaseOutEvent::Single => GameEvent::Single {
+                        advancement: vec![],
+                    },
+                    BaseOutEvent::Double => GameEvent::Double {
+                        advancement: vec![],
+                    },
+                    BaseOutEvent::Triple => GameEvent::Triple {
+                        advancement: vec![],
+                    },
+                    BaseOutEvent::HomeRun => GameEvent::HomeRun,
+                    BaseOutEvent::Out => GameEvent::GroundOut {
+                        advancement: vec![],
+                    },
+                };
+                let outcome = apply_event_detailed(&game, &game_event, &rules).unwrap();
+                let transition = base_out_transition(state, event);
+                let expected_next = (!outcome.half_inning_ended)
+                    .then(|| BaseOutState::try_from(&outcome.state).unwrap());
+                assert_eq!(transition.next, expected_next, "{:?} in {:?}", event, state);
+                assert_eq!(u32::from(transition.runs_scored), outcome.runs_scored);
+            }
+        }
+    }
+
+    #[test]
+    fn test_base_out_event_from_event_matches_engine() {
+        let rules = GameRules::default();
+        for state in BaseOutState::ALL {
+            let game = GameState {
+                current_batter_id: Some("batter".to_string()),
+                current_pitcher_id: Some("pitcher".to_string()),
+                ..state.to_game_state()
+            };
+            for kind in legal_events(&game, &rules) {
+                let event = kind.event();
+                let Some(base_out_event) = BaseOutEvent::from_event(&event) else {
+                    continue;
+                };
+                let outcome = apply_event_detailed(&game, &event, &rules).unwrap();
+                let transition = base_out_transition(state, base_out_event);
+                let expected_next = (!outcome.half_inning_ended)
+                    .then(|| BaseOutState::try_from(&outcome.state).unwrap());
+                assert_eq!(transition.next, expected_next, "{:?} in {:?}", kind, state);
+                assert_eq!(u32::from(transition.runs_scored), outcome.runs_scored);
+            }
+        }
+        assert_eq!(
+            BaseOutEvent::from_event(&single_with(&[(Base::First, Advance::Base(Base::Third))])),
+            None
+        );
+        assert_eq!(
+            BaseOutEvent::from_event(&GameEventKind::SacFly.event()),
+            None
+        );
+        assert_eq!(
+            BaseOutEvent::from_event(&GameEventKind::DoublePlay(Base::First).event()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_base_out_transition_walks_and_outs() {
+        let state = |bases, outs| BaseOutState::new(bases, outs).unwrap();
+        for from in BaseOutState::ALL {
+            let walk = base_out_transition(from, BaseOutEvent::Walk);
+            let next = walk.next.unwrap();
+            assert_eq!(next.outs(), from.outs());
+            assert!(next.is_occupied(Base::First));
+            assert_eq!(next.runners(), (from.runners() + 1).min(3));
+            assert_eq!(walk.runs_scored, u8::from(from.bases_occupied() == 0b111));
+
+            let out = base_out_transition(from, BaseOutEvent::Out);
+            assert_eq!(out.runs_scored, 0);
+            if from.outs() == 2 {
+                assert_eq!(out.next, None);
+            } else {
+                assert_eq!(
+                    out.next,
+                    Some(state(from.bases_occupied(), from.outs() + 1))
+                );
+            }
+        }
+        assert_eq!(
+            base_out_transition(state(0b010, 1), BaseOutEvent::Walk).next,
+            Some(state(0b011, 1))
+        );
+        assert_eq!(
+            base_out_transition(state(0b101, 0), BaseOutEvent::Walk).next,
+            Some(state(0b111, 0))
+        );
+    }
 }
@@ -1,8 +1,16 @@
 //! Error types for the Baselom Core engine.
 
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde::Deserialize;
 use thiserror::Error;
 
+use crate::validators::{ValidationCode, ValidationIssue};
+
 /// Main error type for Baselom operations.
+///
+/// The message-carrying variants cover general failures; the others name
+/// specific conditions so callers can branch on [`BaselomError::error_code`]
+/// instead of the message text.
 #[derive(Error, Debug)]
 pub enum BaselomError {
     /// Invalid input data
@@ -16,11 +24,194 @@ pub enum BaselomError {
     /// Rule constraint violated
     #[error("Rule violation: {0}")]
     RuleViolation(String),
+
+    /// Game state failed validation
+    #[error("Validation error: {}", .0.message)]
+    InvalidState(ValidationIssue),
+
+    /// Unknown pitch result string
+    #[error("Validation error: {0}")]
+    InvalidPitchResult(String),
+
+    /// Transition attempted before the game started
+    #[error("State error: Game has not started")]
+    GameNotStarted,
+
+    /// Transition attempted after the game ended
+    #[error("State error: Game is already final")]
+    GameAlreadyFinal,
+
+    /// Transition attempted while the game is suspended
+    #[error("State error: Game is suspended")]
+    GameSuspended,
+
+    /// Plate appearance completed with nobody at bat
+    #[error("State error: No current batter")]
+    NoCurrentBatter,
+}
+
+/// Stable machine-readable error code.
+///
+/// Serializes as `SCREAMING_SNAKE_CASE`, the same text as
+/// [`ErrorCode::as_str`].
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ValidationError,
+    StateError,
+    RuleViolation,
+    InvalidPitchResult,
+    GameNotStarted,
+    GameAlreadyFinal,
+    GameSuspended,
+    NoCurrentBatter,
+    TooManyOuts,
+    TooManyBalls,
+    TooManyStrikes,
+    InvalidInning,
+    DuplicateRunner,
+    BatterOnBase,
+    LineScoreMismatch,
+    FinalPastTieLimit,
+    FinalExtraInningTie,
+    MissingTiebreakerRunner,
+    LineupTooShort,
+    DuplicateLineupPlayer,
+    InvalidLineupIndex,
+    PitcherBatsWithDesignatedHitter,
+    PitcherNotInLineup,
+    TooManyMoundVisits,
+    MissingPosition,
+    PlayerAtTwoPositions,
+    DesignatedHitterNotUsed,
+    PitcherPositionMismatch,
+}
+
+impl ErrorCode {
+    /// The code as text (e.g. `"NO_CURRENT_BATTER"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::ValidationError => "VALIDATION_ERROR",
+            ErrorCode::StateError => "STATE_ERROR",
+            ErrorCode::RuleViolation => "RULE_VIOLATION",
+            ErrorCode::InvalidPitchResult => "INVALID_PITCH_RESULT",
+            ErrorCode::GameNotStarted => "GAME_NOT_STARTED",
+            ErrorCode::GameAlreadyFinal => "GAME_ALREADY_FINAL",
+            ErrorCode::GameSuspended => "GAME_SUSPENDED",
+            ErrorCode::NoCurrentBatter => "NO_CURRENT_BATTER",
+            ErrorCode::TooManyOuts => "TOO_MANY_OUTS",
+            ErrorCode::TooManyBalls => "TOO_MANY_BALLS",
+            ErrorCode::TooManyStrikes => "TOO_MANY_STRIKES",
+            ErrorCode::InvalidInning => "INVALID_INNING",
+            ErrorCode::DuplicateRunner => "DUPLICATE_RUNNER",
+            ErrorCode::BatterOnBase => "BATTER_ON_BASE",
+            ErrorCode::LineScoreMismatch => "LINE_SCORE_MISMATCH",
+            ErrorCode::FinalPastTieLimit => "FINAL_PAST_TIE_LIMIT",
+            ErrorCode::FinalExtraInningTie => "FINAL_EXTRA_INNING_TIE",
+            ErrorCode::MissingTiebreakerRunner => "MISSING_TIEBREAKER_RUNNER",
+            ErrorCode::LineupTooShort => "LINEUP_TOO_SHORT",
+            ErrorCode::DuplicateLineupPlayer => "DUPLICATE_LINEUP_PLAYER",
+            ErrorCode::InvalidLineupIndex => "INVALID_LINEUP_INDEX",
+            ErrorCode::PitcherBatsWithDesignatedHitter => "PITCHER_BATS_WITH_DESIGNATED_HITTER",
+            ErrorCode::PitcherNotInLineup => "PITCHER_NOT_IN_LINEUP",
+            ErrorCode::TooManyMoundVisits => "TOO_MANY_MOUND_VISITS",
+            ErrorCode::MissingPosition => "MISSING_POSITION",
+            ErrorCode::PlayerAtTwoPositions => "PLAYER_AT_TWO_POSITIONS",
+            ErrorCode::DesignatedHitterNotUsed => "DESIGNATED_HITTER_NOT_USED",
+            ErrorCode::PitcherPositionMismatch => "PITCHER_POSITION_MISMATCH",
+        }
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl From<ValidationCode> for ErrorCode {
+    fn from(code: ValidationCode) -> Self {
+        match code {
+            ValidationCode::TooManyOuts => ErrorCode::TooManyOuts,
+            ValidationCode::TooManyBalls => ErrorCode::TooManyBalls,
+            ValidationCode::TooManyStrikes => ErrorCode::TooManyStrikes,
+            ValidationCode::InvalidInning => ErrorCode::InvalidInning,
+            ValidationCode::DuplicateRunner => ErrorCode::DuplicateRunner,
+            ValidationCode::BatterOnBase => ErrorCode::BatterOnBase,
+            ValidationCode::LineScoreMismatch => ErrorCode::LineScoreMismatch,
+            ValidationCode::FinalPastTieLimit => ErrorCode::FinalPastTieLimit,
+            ValidationCode::FinalExtraInningTie => ErrorCode::FinalExtraInningTie,
+            ValidationCode::MissingTiebreakerRunner => ErrorCode::MissingTiebreakerRunner,
+            ValidationCode::LineupTooShort => ErrorCode::LineupTooShort,
+            ValidationCode::DuplicateLineupPlayer => ErrorCode::DuplicateLineupPlayer,
+            ValidationCode::InvalidLineupIndex => ErrorCode::InvalidLineupIndex,
+            ValidationCode::PitcherBatsWithDesignatedHitter => {
+                ErrorCode::PitcherBatsWithDesignatedHitter
+            }
+            ValidationCode::PitcherNotInLineup => ErrorCode::PitcherNotInLineup,
+            ValidationCode::TooManyMoundVisits => ErrorCode::TooManyMoundVisits,
+            ValidationCode::MissingPosition => ErrorCode::MissingPosition,
+            ValidationCode::PlayerAtTwoPositions => ErrorCode::PlayerAtTwoPositions,
+            ValidationCode::DesignatedHitterNotUsed => ErrorCode::DesignatedHitterNotUsed,
+            ValidationCode::PitcherPositionMismatch => ErrorCode::PitcherPositionMismatch,
+        }
+    }
+}
+
+impl BaselomError {
+    /// Stable code identifying the kind of error.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            BaselomError::ValidationError(_) => ErrorCode::ValidationError,
+            BaselomError::StateError(_) => ErrorCode::StateError,
+            BaselomError::RuleViolation(_) => ErrorCode::RuleViolation,
+            BaselomError::InvalidState(issue) => issue.code.into(),
+            BaselomError::InvalidPitchResult(_) => ErrorCode::InvalidPitchResult,
+            BaselomError::GameNotStarted => ErrorCode::GameNotStarted,
+            BaselomError::GameAlreadyFinal => ErrorCode::GameAlreadyFinal,
+            BaselomError::GameSuspended => ErrorCode::GameSuspended,
+            BaselomError::NoCurrentBatter => ErrorCode::NoCurrentBatter,
+        }
+    }
+}
+
+/// Serializes as `{"code": ..., "message": ...}`, with the `Display` text as
+/// the message.
+impl Serialize for BaselomError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("BaselomError", 2)?;
+        error.serialize_field("code", &self.error_code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
+
+/// Describes the `{"code": ..., "message": ...}` serialized form.
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for BaselomError {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "BaselomError".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        #[derive(schemars::JsonSchema)]
+        #[schemars(rename = "BaselomError")]
+        #[allow(dead_code)]
+        struct Serialized {
+            /// Stable machine-readable error code
+            code: ErrorCode,
+            /// Human-readable description
+            message: String,
+        }
+        Serialized::json_schema(generator)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_validation_error_display() {
@@ -39,4 +230,68 @@ mod tests {
         let err = BaselomError::RuleViolation("rule violation".to_string());
         assert_eq!(format!("{}", err), "Rule violation: rule violation");
     }
+
+    #[test]
+    fn test_every_variant_has_a_unique_code() {
+        let issue = ValidationIssue {
+            code: ValidationCode::TooManyOuts,
+            field: "outs".to_string(),
+            message: "Outs must be between 0 and 2".to_string(),
+        };
+        let errors = [
+            BaselomError::ValidationError(String::new()),
+            BaselomError::StateError(String::new()),
+            BaselomError::RuleViolation(String::new()),
+            BaselomError::InvalidState(issue),
+            BaselomError::InvalidPitchResult(String::new()),
+            BaselomError::GameNotStarted,
+            BaselomError::GameAlreadyFinal,
+            BaselomError::GameSuspended,
+            BaselomError::NoCurrentBatter,
+        ];
+        let codes: HashSet<_> = errors.iter().map(|err| err.error_code()).collect();
+        assert_eq!(codes.len(), errors.len());
+    }
+
+    #[test]
+    fn test_specific_variant_display() {
+        assert_eq!(
+            BaselomError::GameAlreadyFinal.to_string(),
+            "State error: Game is already final"
+        );
+        assert_eq!(
+            BaselomError::NoCurrentBatter.to_string(),
+            "State error: No current batter"
+        );
+    }
+
+    #[test]
+    fn test_error_serialization() {
+        let json = serde_json::to_string(&BaselomError::NoCurrentBatter).unwrap();
+        assert_eq!(
+            json,
+            r#"{"code":"NO_CURRENT_BATTER","message":"State error: No current batter"}"#
+        );
+
+        let issue = ValidationIssue {
+            code: ValidationCode::TooManyOuts,
+            field: "outs".to_string(),
+            message: "Outs must be between 0 and 2".to_string(),
+        };
+        let json = serde_json::to_string(&BaselomError::InvalidState(issue)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"code":"TOO_MANY_OUTS","message":"Validation error: Outs must be between 0 and 2"}"#
+        );
+    }
+
+    #[test]
+    fn test_error_code_round_trip() {
+        let code: ErrorCode = serde_json::from_str(r#""GAME_ALREADY_FINAL""#).unwrap();
+        assert_eq!(code, ErrorCode::GameAlreadyFinal);
+        assert_eq!(
+            serde_json::to_string(&code).unwrap(),
+            r#""GAME_ALREADY_FINAL""#
+        );
+    }
 }
@@ -29,15 +29,56 @@ use pyo3::prelude::*;
 use wasm_bindgen::prelude::*;
 
 // Core modules (platform-agnostic)
+#[cfg(any(test, feature = "wasm"))]
+mod camel;
+pub mod delta;
+pub mod display;
 pub mod engine;
 pub mod errors;
+pub mod log;
 pub mod models;
+pub mod observer;
+pub mod parse;
+pub mod player;
+pub mod presets;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod session;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod stats;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod validators;
 
 // Re-export core types for convenience
-pub use errors::BaselomError;
-pub use models::{GameRules, GameState, Score};
-pub use validators::validate_state;
+pub use delta::{BaseChange, Change, StateDelta};
+pub use engine::{
+    apply_event, apply_event_detailed, apply_event_observed, apply_event_unchecked, apply_pitch,
+    apply_pitch_detailed, apply_pitch_mut, apply_pitch_observed, apply_pitch_sequence,
+    apply_pitch_str, apply_pitch_unchecked, apply_play, apply_play_detailed, base_out_transition,
+    courtesy_run, double_switch, is_game_over, legal_events, legal_pitches, mound_visit,
+    play_events, step, step_pitch, step_play, substitute_batter, substitute_pitcher,
+    substitute_runner,
+};
+pub use errors::{BaselomError, ErrorCode};
+pub use log::{GameLog, LogEntry, LogRow, Situation};
+pub use models::{
+    Advance, Base, BaseOutEvent, BaseOutState, BaseOutTransition, Bases, Count, CourtesyRunnerRule,
+    DefensiveLineup, ExtraInningsTiebreaker, GameEvent, GameEventKind, GameRules, GameState,
+    GameStateBuilder, GameStatus, InningHalf, InningScore, LeadChange, Lineup, MercyRule, PerTeam,
+    PitchResult, Play, Position, RunnerAdvance, Score, SituationKey, StepOutcome, Team, TeamTotals,
+    TransitionOutcome, CURRENT_SCHEMA_VERSION, MAX_INNING,
+};
+pub use observer::{GameAnnouncement, TransitionObserver};
+pub use parse::{parse_play, parse_record, PlayRecord};
+pub use player::{CompactGameState, PlayerId, PlayerKey, PlayerRegistry};
+pub use session::GameSession;
+pub use stats::{decisions, BattingLine, BoxScore, Decisions, PitchingLine};
+pub use validators::{
+    validate_state, validate_state_full, validate_state_full_with_rules, validate_state_with_rules,
+    ValidationCode, ValidationIssue,
+};
 
 // =============================================================================
 // Python Bindings (feature = "python")
@@ -47,20 +88,743 @@ pub use validators::validate_state;
 #[cfg(feature = "python")]
 #[pymodule]
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    let exceptions = [
+        (
+            "BaselomException",
+            "BaselomError",
+            py.get_type::<py_exceptions::BaselomException>(),
+        ),
+        (
+            "ValidationException",
+            "ValidationError",
+            py.get_type::<py_exceptions::ValidationException>(),
+        ),
+        (
+            "StateException",
+            "StateError",
+            py.get_type::<py_exceptions::StateException>(),
+        ),
+        (
+            "RuleViolationException",
+            "RuleViolation",
+            py.get_type::<py_exceptions::RuleViolationException>(),
+        ),
+    ];
+    for (name, alias, exception) in exceptions {
+        m.add(name, &exception)?;
+        m.add(alias, exception)?;
+    }
+    m.add_class::<PyGameState>()?;
+    m.add_class::<PyGameRules>()?;
+    m.add_class::<PyScore>()?;
+    m.add_class::<PyOutcome>()?;
+    m.add_class::<PyGameSession>()?;
+    m.add_function(wrap_pyfunction!(py_apply_pitch, m)?)?;
+    m.add_function(wrap_pyfunction!(py_apply_events_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(py_play_events, m)?)?;
+    m.add_function(wrap_pyfunction!(py_box_score, m)?)?;
+    m.add_function(wrap_pyfunction!(py_apply_pitch_sequence, m)?)?;
+    m.add_function(wrap_pyfunction!(py_win_probability, m)?)?;
     Ok(())
 }
 
+/// Python exception hierarchy for engine errors.
+///
+/// `BaselomException` subclasses `ValueError`, so callers that caught the
+/// `ValueError` raised by earlier versions keep working. Each class is also
+/// registered under its earlier `*Error` name.
+#[cfg(feature = "python")]
+mod py_exceptions {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyValueError;
+
+    create_exception!(
+        baselom_core,
+        BaselomException,
+        PyValueError,
+        "Base class for Baselom engine errors."
+    );
+    create_exception!(
+        baselom_core,
+        ValidationException,
+        BaselomException,
+        "Invalid input or an inconsistent game state."
+    );
+    create_exception!(
+        baselom_core,
+        StateException,
+        BaselomException,
+        "A transition the game's status or situation does not allow."
+    );
+    create_exception!(
+        baselom_core,
+        RuleViolationException,
+        BaselomException,
+        "A transition the game rules forbid."
+    );
+}
+
+/// Raise `err` as the matching Python exception.
+#[cfg(feature = "python")]
+fn py_error(err: BaselomError) -> PyErr {
+    let message = err.to_string();
+    py_error_with_message(&err, message)
+}
+
+/// Raise the Python exception matching `err` with a custom message.
+#[cfg(feature = "python")]
+fn py_error_with_message(err: &BaselomError, message: String) -> PyErr {
+    let exception = match err {
+        BaselomError::ValidationError(_)
+        | BaselomError::InvalidState(_)
+        | BaselomError::InvalidPitchResult(_) => {
+            py_exceptions::ValidationException::new_err(message)
+        }
+        BaselomError::RuleViolation(_) => py_exceptions::RuleViolationException::new_err(message),
+        BaselomError::StateError(_)
+        | BaselomError::GameNotStarted
+        | BaselomError::GameAlreadyFinal
+        | BaselomError::GameSuspended
+        | BaselomError::NoCurrentBatter => py_exceptions::StateException::new_err(message),
+    };
+    with_error_code(exception, err.error_code())
+}
+
+/// Attach `code` to the exception instance as its `code` attribute.
+#[cfg(feature = "python")]
+fn with_error_code(exception: PyErr, code: ErrorCode) -> PyErr {
+    Python::with_gil(|py| {
+        // Setting an attribute on a fresh exception instance cannot fail in
+        // practice; if it ever does, the exception is still worth raising.
+        let _ = exception.value(py).setattr("code", code.as_str());
+        exception
+    })
+}
+
+/// Raise malformed JSON input as a `ValidationException`.
+#[cfg(feature = "python")]
+fn py_json_error(err: serde_json::Error) -> PyErr {
+    with_error_code(
+        py_exceptions::ValidationException::new_err(format!("Invalid JSON: {}", err)),
+        ErrorCode::ValidationError,
+    )
+}
+
+/// Parse optional rules JSON, using the defaults when omitted.
+#[cfg(feature = "python")]
+fn py_rules_json(rules_json: Option<&str>) -> PyResult<GameRules> {
+    match rules_json {
+        Some(json) => serde_json::from_str(json).map_err(py_json_error),
+        None => Ok(GameRules::default()),
+    }
+}
+
+/// Convert a serializable value to plain Python objects via `json.loads`.
+#[cfg(feature = "python")]
+fn to_py_dict<'py, T: serde::Serialize>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>> {
+    let json = serde_json::to_string(value).map_err(py_json_error)?;
+    py.import("json")?.call_method1("loads", (json,))
+}
+
+/// Convert plain Python objects to a value via `json.dumps`.
+#[cfg(feature = "python")]
+fn from_py_dict<T: serde::de::DeserializeOwned>(data: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = data
+        .py()
+        .import("json")?
+        .call_method1("dumps", (data,))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(py_json_error)
+}
+
+/// Game state (Python), immutable like the Rust `GameState`.
+#[cfg(feature = "python")]
+#[pyclass(name = "GameState", module = "baselom_core._core", eq, frozen)]
+#[derive(Clone, PartialEq)]
+pub struct PyGameState {
+    inner: GameState,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyGameState {
+    /// A game that has not started yet, or the state in `data` (the dict
+    /// form produced by `to_dict`).
+    #[new]
+    #[pyo3(signature = (data=None))]
+    fn new(data: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let inner = match data {
+            Some(data) => from_py_dict(data)?,
+            None => GameState::new(),
+        };
+        Ok(PyGameState { inner })
+    }
+
+    /// Build a state from the dict form produced by `to_dict`.
+    #[staticmethod]
+    fn from_dict(data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyGameState {
+            inner: from_py_dict(data)?,
+        })
+    }
+
+    /// The state as plain dicts, lists and scalars.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py_dict(py, &self.inner)
+    }
+
+    /// The state as compact MessagePack `bytes`.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let bytes = self.inner.to_bytes().map_err(py_error)?;
+        Ok(pyo3::types::PyBytes::new(py, &bytes))
+    }
+
+    /// Load a state from the `bytes` produced by `to_bytes`.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(PyGameState {
+            inner: GameState::from_bytes(data).map_err(py_error)?,
+        })
+    }
+
+    #[getter]
+    fn inning(&self) -> u8 {
+        self.inner.inning
+    }
+
+    /// `"top"` or `"bottom"`.
+    #[getter]
+    fn half(&self) -> &'static str {
+        match self.inner.half {
+            InningHalf::Top => "top",
+            InningHalf::Bottom => "bottom",
+        }
+    }
+
+    #[getter]
+    fn outs(&self) -> u8 {
+        self.inner.outs
+    }
+
+    #[getter]
+    fn balls(&self) -> u8 {
+        self.inner.count.balls
+    }
+
+    #[getter]
+    fn strikes(&self) -> u8 {
+        self.inner.count.strikes
+    }
+
+    /// Runner IDs on first, second and third (`None` for an empty base).
+    #[getter]
+    fn bases(&self) -> (Option<String>, Option<String>, Option<String>) {
+        let [first, second, third] = self.inner.bases.clone().into_array();
+        (first, second, third)
+    }
+
+    #[getter]
+    fn score(&self) -> PyScore {
+        PyScore {
+            inner: self.inner.score.clone(),
+        }
+    }
+
+    #[getter]
+    fn home_team_id(&self) -> Option<String> {
+        self.inner.home_team_id.clone()
+    }
+
+    #[getter]
+    fn away_team_id(&self) -> Option<String> {
+        self.inner.away_team_id.clone()
+    }
+
+    #[getter]
+    fn current_batter_id(&self) -> Option<String> {
+        self.inner.current_batter_id.clone()
+    }
+
+    #[getter]
+    fn current_pitcher_id(&self) -> Option<String> {
+        self.inner.current_pitcher_id.clone()
+    }
+
+    /// `"not_started"`, `"in_progress"`, `"suspended"` or `"final"`.
+    #[getter]
+    fn status(&self) -> &'static str {
+        match self.inner.status {
+            GameStatus::NotStarted => "not_started",
+            GameStatus::InProgress => "in_progress",
+            GameStatus::Suspended => "suspended",
+            GameStatus::Final => "final",
+        }
+    }
+
+    /// Pickle support: `GameState(self.to_dict())` rebuilds the state.
+    fn __getnewargs__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyAny>,)> {
+        Ok((self.to_dict(py)?,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GameState(inning={}, half='{}', outs={}, count={}-{}, score={}-{}, status='{}')",
+            self.inner.inning,
+            self.half(),
+            self.inner.outs,
+            self.inner.count.balls,
+            self.inner.count.strikes,
+            self.inner.score.away,
+            self.inner.score.home,
+            self.status()
+        )
+    }
+}
+
+/// Game rules (Python); keyword arguments override the defaults, e.g.
+/// `GameRules(designated_hitter=True)`.
+#[cfg(feature = "python")]
+#[pyclass(name = "GameRules", module = "baselom_core._core", eq, frozen)]
+#[derive(Clone, PartialEq)]
+pub struct PyGameRules {
+    inner: GameRules,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyGameRules {
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn new(kwargs: Option<&Bound<'_, pyo3::types::PyDict>>) -> PyResult<Self> {
+        let inner = match kwargs {
+            Some(kwargs) => from_py_dict(kwargs.as_any())?,
+            None => GameRules::default(),
+        };
+        Ok(PyGameRules { inner })
+    }
+
+    /// Build rules from a dict; missing keys take their defaults.
+    #[staticmethod]
+    fn from_dict(data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyGameRules {
+            inner: from_py_dict(data)?,
+        })
+    }
+
+    /// The rules as plain dicts and scalars.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py_dict(py, &self.inner)
+    }
+
+    #[getter]
+    fn designated_hitter(&self) -> bool {
+        self.inner.designated_hitter
+    }
+
+    #[getter]
+    fn max_innings(&self) -> Option<u8> {
+        self.inner.max_innings
+    }
+
+    #[getter]
+    fn allow_auto_intentional_walk(&self) -> bool {
+        self.inner.allow_auto_intentional_walk
+    }
+
+    #[getter]
+    fn dropped_third_strike(&self) -> bool {
+        self.inner.dropped_third_strike
+    }
+
+    #[getter]
+    fn balls_for_walk(&self) -> u8 {
+        self.inner.balls_for_walk
+    }
+
+    #[getter]
+    fn strikes_for_out(&self) -> u8 {
+        self.inner.strikes_for_out
+    }
+
+    #[getter]
+    fn allow_tie_after_innings(&self) -> Option<u8> {
+        self.inner.allow_tie_after_innings
+    }
+
+    #[getter]
+    fn min_lineup_size(&self) -> u8 {
+        self.inner.min_lineup_size
+    }
+
+    #[getter]
+    fn pitch_limit(&self) -> Option<u16> {
+        self.inner.pitch_limit
+    }
+
+    #[getter]
+    fn mound_visit_limit(&self) -> Option<u8> {
+        self.inner.mound_visit_limit
+    }
+
+    /// Pickle support: `GameRules(**self.to_dict())` rebuilds the rules.
+    fn __getnewargs_ex__<'py>(&self, py: Python<'py>) -> PyResult<((), Bound<'py, PyAny>)> {
+        Ok(((), self.to_dict(py)?))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GameRules(designated_hitter={}, max_innings={}, balls_for_walk={}, strikes_for_out={})",
+            if self.inner.designated_hitter { "True" } else { "False" },
+            self.inner
+                .max_innings
+                .map_or("None".to_string(), |innings| innings.to_string()),
+            self.inner.balls_for_walk,
+            self.inner.strikes_for_out
+        )
+    }
+}
+
+/// Runs for each team (Python).
+#[cfg(feature = "python")]
+#[pyclass(name = "Score", module = "baselom_core._core", eq, frozen)]
+#[derive(Clone, PartialEq)]
+pub struct PyScore {
+    inner: Score,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyScore {
+    #[new]
+    #[pyo3(signature = (home=0, away=0))]
+    fn new(home: u32, away: u32) -> Self {
+        PyScore {
+            inner: Score { home, away },
+        }
+    }
+
+    #[staticmethod]
+    fn from_dict(data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyScore {
+            inner: from_py_dict(data)?,
+        })
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py_dict(py, &self.inner)
+    }
+
+    #[getter]
+    fn home(&self) -> u32 {
+        self.inner.home
+    }
+
+    #[getter]
+    fn away(&self) -> u32 {
+        self.inner.away
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Score(home={}, away={})", self.inner.home, self.inner.away)
+    }
+}
+
+/// Apply a pitch result such as `"ball"` and return the new state (Python).
+///
+/// Raises `ValidationError`, `StateError` or `RuleViolation`.
+#[cfg(feature = "python")]
+#[pyfunction(name = "apply_pitch")]
+#[pyo3(signature = (state, pitch_result, rules=None))]
+fn py_apply_pitch(
+    state: PyRef<'_, PyGameState>,
+    pitch_result: &str,
+    rules: Option<PyRef<'_, PyGameRules>>,
+) -> PyResult<PyGameState> {
+    let default_rules = GameRules::default();
+    let rules = rules
+        .as_deref()
+        .map_or(&default_rules, |rules| &rules.inner);
+    let next = apply_pitch_str(&state.inner, pitch_result, rules).map_err(py_error)?;
+    Ok(PyGameState { inner: next })
+}
+
+/// Summary of one play applied by `apply_events_batch` (Python).
+#[cfg(feature = "python")]
+#[pyclass(name = "Outcome", module = "baselom_core._core", eq, frozen)]
+#[derive(Clone, PartialEq)]
+pub struct PyOutcome {
+    #[pyo3(get)]
+    runs_scored: u32,
+    #[pyo3(get)]
+    scorers: Vec<String>,
+    #[pyo3(get)]
+    rbis: u8,
+    #[pyo3(get)]
+    outs_recorded: u8,
+    #[pyo3(get)]
+    half_inning_ended: bool,
+    #[pyo3(get)]
+    game_ended: bool,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyOutcome {
+    fn __repr__(&self) -> String {
+        format!(
+            "Outcome(runs_scored={}, outs_recorded={}, half_inning_ended={}, game_ended={})",
+            self.runs_scored,
+            self.outs_recorded,
+            py_bool(self.half_inning_ended),
+            py_bool(self.game_ended)
+        )
+    }
+}
+
+#[cfg(feature = "python")]
+fn py_bool(value: bool) -> &'static str {
+    if value {
+        "True"
+    } else {
+        "False"
+    }
+}
+
+/// Read one play for `apply_events_batch`: a play dict such as
+/// `{"batter": "a1"}`, or a pitch result or event name such as `"ball"` or
+/// `"single"` (an event's details take their defaults).
+#[cfg(feature = "python")]
+fn py_play(item: &Bound<'_, PyAny>) -> PyResult<Play> {
+    let Ok(name) = item.extract::<String>() else {
+        return from_py_dict(item);
+    };
+    if let Ok(pitch_result) = name.parse::<PitchResult>() {
+        return Ok(Play::Pitch(pitch_result));
+    }
+    let unit = serde_json::Value::String(name.clone());
+    let with_defaults = serde_json::json!({ name.as_str(): {} });
+    serde_json::from_value(unit)
+        .or_else(|_| serde_json::from_value(with_defaults))
+        .map(Play::Event)
+        .map_err(|_| {
+            with_error_code(
+                py_exceptions::ValidationException::new_err(format!(
+                    "Unknown pitch result or event '{}'",
+                    name
+                )),
+                ErrorCode::ValidationError,
+            )
+        })
+}
+
+/// Add the index of the rejected play to a batch error.
+#[cfg(feature = "python")]
+fn with_play_index(py: Python<'_>, err: PyErr, index: usize) -> PyErr {
+    let message = format!("Play {}: {}", index, err.value(py));
+    let indexed = PyErr::from_type(err.get_type(py), message);
+    if let Ok(code) = err.value(py).getattr("code") {
+        let _ = indexed.value(py).setattr("code", code);
+    }
+    let _ = indexed.value(py).setattr("index", index);
+    indexed
+}
+
+/// Apply a list of plays in one call and return the final state with an
+/// outcome per play (Python).
+///
+/// Raises the matching `BaselomException` subclass with the rejected
+/// play's position as its `index` attribute.
+#[cfg(feature = "python")]
+#[pyfunction(name = "apply_events_batch")]
+#[pyo3(signature = (state, events, rules=None))]
+fn py_apply_events_batch(
+    py: Python<'_>,
+    state: PyRef<'_, PyGameState>,
+    events: Vec<Bound<'_, PyAny>>,
+    rules: Option<PyRef<'_, PyGameRules>>,
+) -> PyResult<(PyGameState, Vec<PyOutcome>)> {
+    let default_rules = GameRules::default();
+    let rules = rules
+        .as_deref()
+        .map_or(&default_rules, |rules| &rules.inner);
+    let plays = events
+        .iter()
+        .enumerate()
+        .map(|(index, item)| py_play(item).map_err(|err| with_play_index(py, err, index)))
+        .collect::<PyResult<Vec<Play>>>()?;
+
+    let mut current = state.inner.clone();
+    let mut outcomes = Vec::with_capacity(plays.len());
+    for (index, play) in plays.iter().enumerate() {
+        let outcome = apply_play_detailed(&current, play, rules)
+            .map_err(|err| with_play_index(py, py_error(err), index))?;
+        outcomes.push(PyOutcome {
+            runs_scored: outcome.runs_scored,
+            scorers: outcome.scorers,
+            rbis: outcome.rbis,
+            outs_recorded: outcome.outs_recorded,
+            half_inning_ended: outcome.half_inning_ended,
+            game_ended: outcome.game_ended,
+        });
+        current = outcome.state;
+    }
+    Ok((PyGameState { inner: current }, outcomes))
+}
+
+/// Apply a pitch sequence such as `"BBCFS"` and return the resulting state
+/// as JSON with the number of pitches applied (Python).
+#[cfg(feature = "python")]
+#[pyfunction(name = "apply_pitch_sequence")]
+#[pyo3(signature = (state_json, sequence, rules_json=None))]
+fn py_apply_pitch_sequence(
+    state_json: &str,
+    sequence: &str,
+    rules_json: Option<&str>,
+) -> PyResult<(String, usize)> {
+    let state: GameState = serde_json::from_str(state_json).map_err(py_json_error)?;
+    let rules = py_rules_json(rules_json)?;
+    let (state, applied) = apply_pitch_sequence(&state, sequence, &rules).map_err(py_error)?;
+    Ok((
+        serde_json::to_string(&state).map_err(py_json_error)?,
+        applied,
+    ))
+}
+
+/// Apply a JSON array of plays and return the JSON array of states after
+/// each one (Python).
+///
+/// Raises the matching `BaselomError` subclass naming the index of a
+/// rejected play.
+#[cfg(feature = "python")]
+#[pyfunction(name = "play_events")]
+#[pyo3(signature = (state_json, plays_json, rules_json=None, ignore_after_final=false))]
+fn py_play_events(
+    state_json: &str,
+    plays_json: &str,
+    rules_json: Option<&str>,
+    ignore_after_final: bool,
+) -> PyResult<String> {
+    let state: GameState = serde_json::from_str(state_json).map_err(py_json_error)?;
+    let plays: Vec<Play> = serde_json::from_str(plays_json).map_err(py_json_error)?;
+    let rules = py_rules_json(rules_json)?;
+    let states = play_events(&state, &plays, &rules, ignore_after_final)
+        .map_err(|(index, err)| py_error_with_message(&err, format!("Play {}: {}", index, err)))?;
+    serde_json::to_string(&states).map_err(py_json_error)
+}
+
+/// Box score of a JSON game log (Python), returned as JSON with `batting`
+/// and `pitching` lines by player ID.
+#[cfg(feature = "python")]
+#[pyfunction(name = "box_score")]
+#[pyo3(signature = (log_json, rules_json=None))]
+fn py_box_score(log_json: &str, rules_json: Option<&str>) -> PyResult<String> {
+    let log: GameLog = serde_json::from_str(log_json).map_err(py_json_error)?;
+    let rules = py_rules_json(rules_json)?;
+    serde_json::to_string(&BoxScore::from_log(&log, &rules)).map_err(py_json_error)
+}
+
+/// Estimate each team's chance of winning from `state` with the
+/// league-average outcome model (Python), returning a dict with `home`,
+/// `away` and `tie`.
+///
+/// The simulations run without holding the GIL.
+#[cfg(feature = "python")]
+#[pyfunction(name = "win_probability")]
+#[pyo3(signature = (state, n=1000, seed=0, rules=None))]
+fn py_win_probability<'py>(
+    py: Python<'py>,
+    state: PyRef<'_, PyGameState>,
+    n: usize,
+    seed: u64,
+    rules: Option<PyRef<'_, PyGameRules>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let state = state.inner.clone();
+    let rules = rules.map_or_else(GameRules::default, |rules| rules.inner.clone());
+    let odds = py
+        .allow_threads(|| sim::win_probability(&state, &rules, &sim::LeagueAverage, n, seed))
+        .map_err(py_error)?;
+    to_py_dict(py, &odds)
+}
+
+/// Game session with undo/redo (Python), exchanging states and plays as
+/// JSON strings.
+#[cfg(feature = "python")]
+#[pyclass(name = "GameSession")]
+pub struct PyGameSession {
+    inner: GameSession,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyGameSession {
+    #[new]
+    #[pyo3(signature = (state_json, rules_json=None, history_limit=None))]
+    fn new(
+        state_json: &str,
+        rules_json: Option<&str>,
+        history_limit: Option<usize>,
+    ) -> PyResult<Self> {
+        let state: GameState = serde_json::from_str(state_json).map_err(py_json_error)?;
+        let rules = py_rules_json(rules_json)?;
+        let limit = history_limit.unwrap_or(session::DEFAULT_HISTORY_LIMIT);
+        Ok(PyGameSession {
+            inner: GameSession::with_history_limit(state, rules, limit),
+        })
+    }
+
+    /// Apply a play (e.g. `{"pitch": "ball"}`) and return the outcome as JSON.
+    fn apply(&mut self, play_json: &str) -> PyResult<String> {
+        let play: Play = serde_json::from_str(play_json).map_err(py_json_error)?;
+        let outcome = self.inner.apply(&play).map_err(py_error)?;
+        serde_json::to_string(&outcome).map_err(py_json_error)
+    }
+
+    fn undo(&mut self) -> PyResult<()> {
+        self.inner.undo().map_err(py_error)
+    }
+
+    fn redo(&mut self) -> PyResult<()> {
+        self.inner.redo().map_err(py_error)
+    }
+
+    fn history_len(&self) -> usize {
+        self.inner.history_len()
+    }
+
+    /// Current state as JSON.
+    fn state(&self) -> PyResult<String> {
+        serde_json::to_string(self.inner.state()).map_err(py_json_error)
+    }
+}
+
 // =============================================================================
-// WASM Bindings (feature = "wasm") - Planned for v0.2.0
+// WASM Bindings (feature = "wasm")
 // =============================================================================
 
-/// WASM module initialization.
-/// This will be expanded in v0.2.0 with full game state bindings.
+/// WASM module initialization: reports Rust panics on the browser console
+/// instead of an opaque `unreachable` trap.
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(start)]
 pub fn wasm_init() {
-    // Future: Initialize WASM module, set up panic hook, etc.
+    console_error_panic_hook::set_once();
 }
 
 /// Get the library version (WASM).
@@ -69,3 +833,406 @@ pub fn wasm_init() {
 pub fn version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
+
+/// Build a plain JS object from property names and values.
+#[cfg(feature = "wasm")]
+fn js_object(properties: &[(&str, JsValue)]) -> JsValue {
+    let object = js_sys::Object::new();
+    for (name, value) in properties {
+        let _ = js_sys::Reflect::set(&object, &JsValue::from_str(name), value);
+    }
+    object.into()
+}
+
+/// Convert an error to a JS object with `code` and `message` properties.
+#[cfg(feature = "wasm")]
+fn js_error_value(err: BaselomError) -> JsValue {
+    js_object(&[
+        ("code", err.error_code().as_str().into()),
+        ("message", err.to_string().into()),
+    ])
+}
+
+/// Parse a JSON argument with camelCase keys (snake_case keys are also
+/// accepted), reporting malformed input as a validation error.
+#[cfg(feature = "wasm")]
+fn from_js_json<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, JsValue> {
+    models::from_json_camel(json).map_err(js_error_value)
+}
+
+/// Serialize a result for JS, with camelCase keys.
+#[cfg(feature = "wasm")]
+fn to_js_json<T: serde::Serialize>(value: &T) -> Result<String, JsValue> {
+    models::to_json_camel(value).map_err(js_error_value)
+}
+
+/// Convert a JS value (a plain object from JS, not a JSON string) to a
+/// Rust value, reading the JS values directly rather than through
+/// `JSON.stringify`. camelCase keys are read as snake_case.
+#[cfg(feature = "wasm")]
+fn from_js<T: serde::de::DeserializeOwned>(value: &JsValue) -> Result<T, JsValue> {
+    let deserializer = serde_wasm_bindgen::Deserializer::from(value.clone());
+    T::deserialize(camel::SnakeKeys::new(deserializer))
+        .map_err(|e| js_error_value(BaselomError::ValidationError(e.to_string())))
+}
+
+/// Convert rules from JS, using the defaults for `undefined` or `null`.
+#[cfg(feature = "wasm")]
+fn rules_from_js(value: &JsValue) -> Result<GameRules, JsValue> {
+    if value.is_undefined() || value.is_null() {
+        Ok(GameRules::default())
+    } else {
+        from_js(value)
+    }
+}
+
+/// Convert a Rust value to a plain JS object with camelCase keys, built
+/// directly rather than through `JSON.parse`.
+#[cfg(feature = "wasm")]
+fn to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    value
+        .serialize(camel::CamelKeys::new(&serializer))
+        .map_err(|e| js_error_value(BaselomError::StateError(e.to_string())))
+}
+
+/// A game in progress at the top of the 1st under the given rules, as
+/// JSON (WASM).
+///
+/// Set `currentBatterId` and `currentPitcherId` (or lineups) before
+/// applying plate-appearance results.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = newGame)]
+pub fn new_game(rules_json: &str) -> Result<String, JsValue> {
+    let rules: GameRules = from_js_json(rules_json)?;
+    let state = engine::start(&GameState::with_rules(&rules)).map_err(js_error_value)?;
+    to_js_json(&state)
+}
+
+/// Apply a pitch result such as `"ball"` and return the new state as JSON
+/// (WASM).
+///
+/// Errors are thrown as `{code, message}` objects. Prefer
+/// [`apply_pitch_js`], which takes and returns plain JS objects.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = applyPitch)]
+pub fn apply_pitch_wasm(
+    state_json: &str,
+    pitch_result: &str,
+    rules_json: &str,
+) -> Result<String, JsValue> {
+    let state: GameState = from_js_json(state_json)?;
+    let rules: GameRules = from_js_json(rules_json)?;
+    let next = apply_pitch_str(&state, pitch_result, &rules).map_err(js_error_value)?;
+    to_js_json(&next)
+}
+
+/// Apply an event such as `{"single": {}}` and return the new state as JSON
+/// (WASM).
+///
+/// Errors are thrown as `{code, message}` objects. Prefer
+/// [`apply_event_js`], which takes and returns plain JS objects.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = applyEvent)]
+pub fn apply_event_wasm(
+    state_json: &str,
+    event_json: &str,
+    rules_json: &str,
+) -> Result<String, JsValue> {
+    let state: GameState = from_js_json(state_json)?;
+    let event: GameEvent = from_js_json(event_json)?;
+    let rules: GameRules = from_js_json(rules_json)?;
+    let next = apply_event(&state, &event, &rules).map_err(js_error_value)?;
+    to_js_json(&next)
+}
+
+/// Apply a pitch result such as `"ball"` to a state object and return the
+/// new state object (WASM); `rules` may be omitted for the defaults.
+///
+/// The preferred form of [`apply_pitch_wasm`]. Errors are thrown as
+/// `{code, message}` objects.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = applyPitchJs)]
+pub fn apply_pitch_js(state: JsValue, pitch: &str, rules: JsValue) -> Result<JsValue, JsValue> {
+    let state: GameState = from_js(&state)?;
+    let rules = rules_from_js(&rules)?;
+    let next = apply_pitch_str(&state, pitch, &rules).map_err(js_error_value)?;
+    to_js(&next)
+}
+
+/// Apply an event object such as `{single: {}}` to a state object and
+/// return the new state object (WASM); `rules` may be omitted for the
+/// defaults.
+///
+/// The preferred form of [`apply_event_wasm`]. Errors are thrown as
+/// `{code, message}` objects.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = applyEventJs)]
+pub fn apply_event_js(state: JsValue, event: JsValue, rules: JsValue) -> Result<JsValue, JsValue> {
+    let state: GameState = from_js(&state)?;
+    let event: GameEvent = from_js(&event)?;
+    let rules = rules_from_js(&rules)?;
+    let next = apply_event(&state, &event, &rules).map_err(js_error_value)?;
+    to_js(&next)
+}
+
+/// Validate a state object (WASM), throwing the first problem as a
+/// `{code, message}` object.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = validateStateJs)]
+pub fn validate_state_js(state: JsValue) -> Result<(), JsValue> {
+    let state: GameState = from_js(&state)?;
+    validate_state(&state).map_err(js_error_value)
+}
+
+/// Encode a state object as compact MessagePack bytes (WASM), returned as
+/// a `Uint8Array`.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = stateToBytes)]
+pub fn state_to_bytes(state: JsValue) -> Result<Vec<u8>, JsValue> {
+    let state: GameState = from_js(&state)?;
+    state.to_bytes().map_err(js_error_value)
+}
+
+/// Decode bytes from [`state_to_bytes`] into a state object (WASM).
+///
+/// Truncated or malformed input is thrown as a `{code, message}` object.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = stateFromBytes)]
+pub fn state_from_bytes(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    let state = GameState::from_bytes(bytes).map_err(js_error_value)?;
+    to_js(&state)
+}
+
+/// Check a state for consistency (WASM), throwing the first problem as a
+/// `{code, message}` object.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = validateState)]
+pub fn validate_state_wasm(state_json: &str) -> Result<(), JsValue> {
+    let state: GameState = from_js_json(state_json)?;
+    validate_state(&state).map_err(js_error_value)
+}
+
+/// Apply a JSON array of plays and return the JSON array of states after
+/// each one (WASM).
+///
+/// Errors are thrown as `{code, message}` objects, with the `index` of the
+/// failing play when a play is rejected.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = playEvents)]
+pub fn wasm_play_events(
+    state_json: &str,
+    plays_json: &str,
+    rules_json: Option<String>,
+    ignore_after_final: bool,
+) -> Result<String, JsValue> {
+    let state: GameState = from_js_json(state_json)?;
+    let plays: Vec<Play> = from_js_json(plays_json)?;
+    let rules: GameRules = match rules_json {
+        Some(json) => from_js_json(&json)?,
+        None => GameRules::default(),
+    };
+    let states =
+        play_events(&state, &plays, &rules, ignore_after_final).map_err(|(index, err)| {
+            js_object(&[
+                ("index", index.into()),
+                ("code", err.error_code().as_str().into()),
+                ("message", err.to_string().into()),
+            ])
+        })?;
+    to_js_json(&states)
+}
+
+/// Box score of a JSON game log (WASM), returned as JSON with `batting`
+/// and `pitching` lines by player ID.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = boxScore)]
+pub fn wasm_box_score(log_json: &str, rules_json: Option<String>) -> Result<String, JsValue> {
+    let log: GameLog = from_js_json(log_json)?;
+    let rules: GameRules = match rules_json {
+        Some(json) => from_js_json(&json)?,
+        None => GameRules::default(),
+    };
+    to_js_json(&BoxScore::from_log(&log, &rules))
+}
+
+/// Game session with undo/redo (WASM), exchanging states and plays as JSON
+/// strings.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = GameSession)]
+pub struct WasmGameSession {
+    inner: GameSession,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class = GameSession)]
+impl WasmGameSession {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        state_json: &str,
+        rules_json: Option<String>,
+        history_limit: Option<usize>,
+    ) -> Result<WasmGameSession, JsValue> {
+        let state: GameState = from_js_json(state_json)?;
+        let rules: GameRules = match rules_json {
+            Some(json) => from_js_json(&json)?,
+            None => GameRules::default(),
+        };
+        let limit = history_limit.unwrap_or(session::DEFAULT_HISTORY_LIMIT);
+        Ok(WasmGameSession {
+            inner: GameSession::with_history_limit(state, rules, limit),
+        })
+    }
+
+    /// Apply a play (e.g. `{"pitch": "ball"}`) and return the outcome as JSON.
+    pub fn apply(&mut self, play_json: &str) -> Result<String, JsValue> {
+        let play: Play = from_js_json(play_json)?;
+        let outcome = self.inner.apply(&play).map_err(js_error_value)?;
+        to_js_json(&outcome)
+    }
+
+    pub fn undo(&mut self) -> Result<(), JsValue> {
+        self.inner.undo().map_err(js_error_value)
+    }
+
+    pub fn redo(&mut self) -> Result<(), JsValue> {
+        self.inner.redo().map_err(js_error_value)
+    }
+
+    #[wasm_bindgen(js_name = historyLen)]
+    pub fn history_len(&self) -> usize {
+        self.inner.history_len()
+    }
+
+    /// Current state as JSON.
+    pub fn state(&self) -> Result<String, JsValue> {
+        to_js_json(self.inner.state())
+    }
+}
+
+/// A game and its rules kept on the Rust side of the boundary (WASM), so
+/// JS only exchanges small values per play.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame {
+    session: GameSession,
+}
+
+/// Persisted form of a [`WasmGame`].
+#[cfg(feature = "wasm")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedGame {
+    state: GameState,
+    #[serde(default)]
+    rules: GameRules,
+}
+
+#[cfg(feature = "wasm")]
+impl WasmGame {
+    /// Apply a play and summarize its outcome for animation:
+    /// `{runsScored, outsRecorded, halfInningEnded, gameOver}`.
+    fn play(&mut self, play: Play) -> Result<JsValue, JsValue> {
+        let outcome = self.session.apply(&play).map_err(js_error_value)?;
+        Ok(js_object(&[
+            ("runsScored", outcome.runs_scored.into()),
+            ("outsRecorded", outcome.outs_recorded.into()),
+            ("halfInningEnded", outcome.half_inning_ended.into()),
+            ("gameOver", outcome.game_ended.into()),
+        ]))
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame {
+    /// Start a game under the given rules (the defaults when omitted).
+    #[wasm_bindgen(constructor)]
+    pub fn new(rules_json: Option<String>) -> Result<WasmGame, JsValue> {
+        let rules: GameRules = match rules_json {
+            Some(json) => from_js_json(&json)?,
+            None => GameRules::default(),
+        };
+        let state = engine::start(&GameState::with_rules(&rules)).map_err(js_error_value)?;
+        Ok(WasmGame {
+            session: GameSession::new(state, rules),
+        })
+    }
+
+    /// Restore a game saved with `toJSON`.
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: &str) -> Result<WasmGame, JsValue> {
+        let saved: SavedGame = from_js_json(json)?;
+        Ok(WasmGame {
+            session: GameSession::new(saved.state, saved.rules),
+        })
+    }
+
+    /// The state and rules as JSON (undo history is not saved).
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        to_js_json(&SavedGame {
+            state: self.session.state().clone(),
+            rules: self.session.rules().clone(),
+        })
+    }
+
+    /// Send a batter to the plate.
+    pub fn batter(&mut self, batter_id: &str) -> Result<JsValue, JsValue> {
+        self.play(Play::Batter(batter_id.to_string()))
+    }
+
+    /// Apply a pitch result such as `"ball"`.
+    #[wasm_bindgen(js_name = applyPitch)]
+    pub fn apply_pitch(&mut self, result: &str) -> Result<JsValue, JsValue> {
+        let pitch_result: PitchResult = result.parse().map_err(js_error_value)?;
+        self.play(Play::Pitch(pitch_result))
+    }
+
+    /// Apply an event such as `{"single": {}}`.
+    #[wasm_bindgen(js_name = applyEvent)]
+    pub fn apply_event(&mut self, event_json: &str) -> Result<JsValue, JsValue> {
+        let event: GameEvent = from_js_json(event_json)?;
+        self.play(Play::Event(event))
+    }
+
+    /// Take back the last play.
+    pub fn undo(&mut self) -> Result<(), JsValue> {
+        self.session.undo().map_err(js_error_value)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn inning(&self) -> u8 {
+        self.session.state().inning
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn outs(&self) -> u8 {
+        self.session.state().outs
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn balls(&self) -> u8 {
+        self.session.state().count.balls
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn strikes(&self) -> u8 {
+        self.session.state().count.strikes
+    }
+
+    /// The score as `{home, away}`.
+    pub fn score(&self) -> JsValue {
+        let score = &self.session.state().score;
+        js_object(&[("home", score.home.into()), ("away", score.away.into())])
+    }
+
+    /// Whether first, second and third base are occupied, in that order.
+    #[wasm_bindgen(js_name = basesOccupied)]
+    pub fn bases_occupied(&self) -> js_sys::Array {
+        let bases = &self.session.state().bases;
+        Base::ALL
+            .into_iter()
+            .map(|base| JsValue::from_bool(bases.runner_at(base).is_some()))
+            .collect()
+    }
+}
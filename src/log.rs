@@ -0,0 +1,1035 @@
+//! Play-by-play recording and replay.
+//!
+//! The engine only produces the next state; a [`GameLog`] keeps the ordered
+//! list of plays that led to it, so a game can be stored and re-derived.
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::apply_play_detailed;
+use crate::errors::BaselomError;
+use crate::models::{
+    Advance, Base, Bases, Count, GameEvent, GameRules, GameState, InningHalf, PitchResult, Play,
+    Score, TransitionOutcome,
+};
+
+/// Where a play happened: the parts of the state it was applied to that
+/// describe the situation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Situation {
+    pub inning: u8,
+    pub half: InningHalf,
+    pub outs: u8,
+    pub count: Count,
+    pub bases: Bases,
+    pub batter: Option<String>,
+    pub pitcher: Option<String>,
+    pub score: Score,
+}
+
+impl Situation {
+    /// The situation of `state`.
+    pub fn of(state: &GameState) -> Self {
+        Situation {
+            inning: state.inning,
+            half: state.half,
+            outs: state.outs,
+            count: state.count,
+            bases: state.bases.clone(),
+            batter: state.current_batter_id.clone(),
+            pitcher: state.current_pitcher_id.clone(),
+            score: state.score.clone(),
+        }
+    }
+}
+
+/// A play and what happened on it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogEntry {
+    /// The play applied
+    pub play: Play,
+    /// The situation the play was applied in
+    pub before: Situation,
+    /// Runs scored on the play
+    pub runs_scored: u32,
+    /// IDs of the players who scored, in the order they crossed the plate
+    pub scorers: Vec<String>,
+    /// The pitcher charged with each run, in the order of `scorers`
+    #[serde(default)]
+    pub charged_pitchers: Vec<String>,
+    /// Whether each run was earned, in the order of `scorers`
+    #[serde(default)]
+    pub earned: Vec<bool>,
+    /// Runs batted in by the batter
+    #[serde(default)]
+    pub rbis: u8,
+    /// Outs recorded on the play
+    pub outs_recorded: u8,
+    /// Whether the half inning ended
+    pub half_inning_ended: bool,
+    /// Whether the half inning ended on the inning run limit
+    #[serde(default)]
+    pub half_inning_ended_by_run_limit: bool,
+    /// Whether the game ended
+    pub game_ended: bool,
+}
+
+impl LogEntry {
+    pub(crate) fn new(play: Play, before: &GameState, outcome: &TransitionOutcome) -> Self {
+        LogEntry {
+            play,
+            before: Situation::of(before),
+            runs_scored: outcome.runs_scored,
+            scorers: outcome.scorers.clone(),
+            charged_pitchers: outcome.charged_pitchers.clone(),
+            earned: outcome.earned.clone(),
+            rbis: outcome.rbis,
+            outs_recorded: outcome.outs_recorded,
+            half_inning_ended: outcome.half_inning_ended,
+            half_inning_ended_by_run_limit: outcome.half_inning_ended_by_run_limit,
+            game_ended: outcome.game_ended,
+        }
+    }
+}
+
+/// Ordered record of the plays applied to a game, with the state they led to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameLog {
+    entries: Vec<LogEntry>,
+    state: GameState,
+}
+
+impl GameLog {
+    /// Start an empty log at `state`.
+    pub fn new(state: GameState) -> Self {
+        GameLog {
+            entries: Vec::new(),
+            state,
+        }
+    }
+
+    /// Recorded plays, oldest first.
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    /// State after the last recorded play.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Record a play that has already been applied, moving to its outcome's
+    /// state.
+    pub fn append(&mut self, play: Play, outcome: TransitionOutcome) -> &LogEntry {
+        self.entries
+            .push(LogEntry::new(play, &self.state, &outcome));
+        self.state = outcome.state;
+        self.entries.last().unwrap()
+    }
+
+    /// Apply `play` to the current state and record it.
+    ///
+    /// Returns the engine's error, leaving the log unchanged, if the play is
+    /// rejected.
+    pub fn apply(&mut self, play: Play, rules: &GameRules) -> Result<&LogEntry, BaselomError> {
+        let outcome = apply_play_detailed(&self.state, &play, rules)?;
+        Ok(self.append(play, outcome))
+    }
+
+    /// Send `batter` to the plate and record it.
+    pub fn batter(&mut self, batter: &str, rules: &GameRules) -> Result<&LogEntry, BaselomError> {
+        self.apply(Play::Batter(batter.to_string()), rules)
+    }
+
+    /// Apply a pitch and record it.
+    pub fn pitch(
+        &mut self,
+        pitch_result: PitchResult,
+        rules: &GameRules,
+    ) -> Result<&LogEntry, BaselomError> {
+        self.apply(Play::Pitch(pitch_result), rules)
+    }
+
+    /// Apply an event and record it.
+    pub fn event(
+        &mut self,
+        event: GameEvent,
+        rules: &GameRules,
+    ) -> Result<&LogEntry, BaselomError> {
+        self.apply(Play::Event(event), rules)
+    }
+
+    /// Bring in `pitcher` for the fielding team and record it.
+    pub fn pitching_change(
+        &mut self,
+        pitcher: &str,
+        injured: bool,
+        rules: &GameRules,
+    ) -> Result<&LogEntry, BaselomError> {
+        let play = Play::PitchingChange {
+            pitcher: pitcher.to_string(),
+            injured,
+        };
+        self.apply(play, rules)
+    }
+
+    /// Make a double switch for the fielding team and record it.
+    pub fn double_switch(
+        &mut self,
+        pitcher: &str,
+        lineup_slot: usize,
+        fielder: &str,
+        rules: &GameRules,
+    ) -> Result<&LogEntry, BaselomError> {
+        let play = Play::DoubleSwitch {
+            pitcher: pitcher.to_string(),
+            lineup_slot,
+            fielder: fielder.to_string(),
+        };
+        self.apply(play, rules)
+    }
+
+    /// Re-apply every recorded play from `initial_state` and return the
+    /// final state.
+    ///
+    /// Returns `BaselomError::StateError` if a play's outcome or the final
+    /// state differs from the log, and the engine's error if a play is
+    /// rejected.
+    pub fn replay(
+        &self,
+        initial_state: &GameState,
+        rules: &GameRules,
+    ) -> Result<GameState, BaselomError> {
+        let mut state = initial_state.clone();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let outcome = apply_play_detailed(&state, &entry.play, rules)?;
+            let replayed = LogEntry {
+                before: entry.before.clone(),
+                ..LogEntry::new(entry.play.clone(), &state, &outcome)
+            };
+            if replayed != *entry {
+                return Err(BaselomError::StateError(format!(
+                    "Replay differs from the log at play {}",
+                    index + 1
+                )));
+            }
+            state = outcome.state;
+        }
+        if state != self.state {
+            return Err(BaselomError::StateError(
+                "Replay does not reach the logged state".to_string(),
+            ));
+        }
+        Ok(state)
+    }
+}
+
+/// Columns of [`GameLog::to_csv`], in order.
+pub const CSV_COLUMNS: [&str; 11] = [
+    "inning",
+    "half",
+    "outs",
+    "count",
+    "bases",
+    "event",
+    "batter",
+    "pitcher",
+    "runs_scored",
+    "away_score",
+    "home_score",
+];
+
+/// One row of the flat play-by-play table written by [`GameLog::to_csv`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LogRow {
+    pub inning: u8,
+    pub half: InningHalf,
+    /// Outs before the play
+    pub outs: u8,
+    /// Count before the play
+    pub count: Count,
+    /// Occupied bases before the play: 1 for first, 2 for second, 4 for
+    /// third
+    pub bases: u8,
+    /// `batter`, `mound_visit`, the pitch result (`ball`) or the event name
+    /// (`single`)
+    pub event: String,
+    pub batter: Option<String>,
+    pub pitcher: Option<String>,
+    pub runs_scored: u32,
+    /// Score after the play
+    pub score: Score,
+}
+
+impl GameLog {
+    /// The plays as rows of a flat table, one per play.
+    pub fn rows(&self) -> Vec<LogRow> {
+        let final_score = &self.state.score;
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let before = &entry.before;
+                LogRow {
+                    inning: before.inning,
+                    half: before.half,
+                    outs: before.outs,
+                    count: before.count,
+                    bases: before.bases.occupied_mask(),
+                    event: play_name(&entry.play).to_string(),
+                    batter: before.batter.clone(),
+                    pitcher: before.pitcher.clone(),
+                    runs_scored: entry.runs_scored,
+                    score: self
+                        .entries
+                        .get(index + 1)
+                        .map_or(final_score, |next| &next.before.score)
+                        .clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// The plays as CSV, one row per play after a header row.
+    ///
+    /// The columns are [`CSV_COLUMNS`], in that order, and will not change:
+    /// `inning`, `half` (`top` or `bottom`), `outs` and `count` (`3-2`)
+    /// before the play, `bases` (the occupied-base bitmask before the play),
+    /// `event`, `batter` and `pitcher` (empty when unknown), `runs_scored`,
+    /// then `away_score` and `home_score` after the play. Fields containing
+    /// commas, quotes or line breaks are quoted, with quotes doubled.
+    pub fn to_csv(&self) -> String {
+        let mut csv = CSV_COLUMNS.join(",");
+        csv.push('\n');
+        for row in self.rows() {
+            let fields = [
+                row.inning.to_string(),
+                half_name(row.half).to_string(),
+                row.outs.to_string(),
+                row.count.to_string(),
+                row.bases.to_string(),
+                row.event,
+                row.batter.unwrap_or_default(),
+                row.pitcher.unwrap_or_default(),
+                row.runs_scored.to_string(),
+                row.score.away.to_string(),
+                row.score.home.to_string(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Read back the rows written by [`GameLog::to_csv`].
+    ///
+    /// The table does not hold enough to rebuild the log itself (runner IDs
+    /// and event details are flattened), so this returns its rows.
+    ///
+    /// Returns `BaselomError::ValidationError` if the header differs from
+    /// [`CSV_COLUMNS`] or a row cannot be read.
+    pub fn from_csv(csv: &str) -> Result<Vec<LogRow>, BaselomError> {
+        let mut records = parse_csv(csv)?.into_iter();
+        if records.next() != Some(CSV_COLUMNS.map(String::from).to_vec()) {
+            return Err(BaselomError::ValidationError(format!(
+                "CSV header must be {}",
+                CSV_COLUMNS.join(",")
+            )));
+        }
+        records
+            .enumerate()
+            .map(|(index, record)| {
+                parse_row(&record).ok_or_else(|| {
+                    BaselomError::ValidationError(format!(
+                        "Invalid CSV row {}: {}",
+                        index + 1,
+                        record.join(",")
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+fn play_name(play: &Play) -> &'static str {
+    match play {
+        Play::Batter(_) => "batter",
+        Play::Pitch(pitch_result) => pitch_result.as_str(),
+        Play::Event(event) => event.as_str(),
+        Play::MoundVisit(_) => "mound_visit",
+        Play::PitchingChange { .. } => "pitching_change",
+        Play::DoubleSwitch { .. } => "double_switch",
+    }
+}
+
+fn half_name(half: InningHalf) -> &'static str {
+    match half {
+        InningHalf::Top => "top",
+        InningHalf::Bottom => "bottom",
+    }
+}
+
+/// `field`, quoted if it contains a comma, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split CSV text into records of fields, honouring quoted fields.
+fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>, BaselomError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(BaselomError::ValidationError(
+            "Unterminated quoted CSV field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn parse_row(record: &[String]) -> Option<LogRow> {
+    let [inning, half, outs, count, bases, event, batter, pitcher, runs_scored, away, home] =
+        record
+    else {
+        return None;
+    };
+    let (balls, strikes) = count.split_once('-')?;
+    let id = |id: &String| (!id.is_empty()).then(|| id.clone());
+    Some(LogRow {
+        inning: inning.parse().ok()?,
+        half: match half.as_str() {
+            "top" => InningHalf::Top,
+            "bottom" => InningHalf::Bottom,
+            _ => return None,
+        },
+        outs: outs.parse().ok()?,
+        count: Count::new(balls.parse().ok()?, strikes.parse().ok()?),
+        bases: bases.parse().ok().filter(|&bases: &u8| bases < 8)?,
+        event: event.clone(),
+        batter: id(batter),
+        pitcher: id(pitcher),
+        runs_scored: runs_scored.parse().ok()?,
+        score: Score {
+            away: away.parse().ok()?,
+            home: home.parse().ok()?,
+        },
+    })
+}
+
+/// Write the events of `log` as Retrosheet `play` records
+/// (`play,inning,team,batter,count,pitches,event`), one per event, in the
+/// form [`crate::parse::parse_record`] reads back.
+///
+/// Each record lists the batter's pitches so far, those of earlier records
+/// before a `.`. Strikeouts, walks and hit batsmen get a record on the
+/// deciding pitch. Runner movements come from the situations before and
+/// after each play.
+///
+/// The engine does not track fielders, so outs, errors and pickoffs name
+/// typical ones (`63/G` for a ground out, `E6` for an error). Plays with no
+/// Retrosheet form the parser reads back (obstruction, awarded bases,
+/// reaching on a dropped third strike) are written in the closest
+/// notation, and hits on which a runner holds re-parse with the engine's
+/// default advancement.
+pub fn to_retrosheet(log: &GameLog) -> Vec<String> {
+    let final_situation = Situation::of(&log.state);
+    let mut records = Vec::new();
+    let mut pitches = String::new();
+    for (index, entry) in log.entries.iter().enumerate() {
+        let after = log
+            .entries
+            .get(index + 1)
+            .map_or(&final_situation, |next| &next.before);
+        let (play, ends_plate_appearance) = match &entry.play {
+            Play::Batter(batter) => {
+                if entry.before.batter.as_ref() != Some(batter) {
+                    pitches.clear();
+                }
+                continue;
+            }
+            Play::MoundVisit(_) | Play::PitchingChange { .. } | Play::DoubleSwitch { .. } => {
+                continue
+            }
+            Play::Pitch(pitch_result) => {
+                pitches.push(pitch_code(*pitch_result));
+                let ended = entry.half_inning_ended
+                    || entry.game_ended
+                    || after.count == Count::default()
+                    || after.batter != entry.before.batter;
+                if !ended {
+                    continue;
+                }
+                (pitch_play(*pitch_result, entry, after), true)
+            }
+            Play::Event(event) => {
+                if is_ball_in_play(event) {
+                    pitches.push('X');
+                }
+                (
+                    event_play(event, entry, after),
+                    event.ends_plate_appearance(),
+                )
+            }
+        };
+        records.push(format!(
+            "play,{},{},{},{}{},{},{}",
+            entry.before.inning,
+            match entry.before.half {
+                InningHalf::Top => 0,
+                InningHalf::Bottom => 1,
+            },
+            entry.before.batter.as_deref().unwrap_or_default(),
+            entry.before.count.balls.min(3),
+            entry.before.count.strikes.min(2),
+            pitches,
+            play
+        ));
+        if ends_plate_appearance || entry.half_inning_ended {
+            pitches.clear();
+        } else {
+            pitches.push('.');
+        }
+    }
+    records
+}
+
+/// Retrosheet pitch code. Retrosheet has no code for a dropped third
+/// strike, which is a swinging strike.
+fn pitch_code(pitch_result: PitchResult) -> char {
+    match pitch_result {
+        PitchResult::Ball => 'B',
+        PitchResult::StrikeCalled => 'C',
+        PitchResult::StrikeSwinging | PitchResult::StrikeSwingingDropped => 'S',
+        PitchResult::Foul => 'F',
+        PitchResult::FoulTip => 'T',
+        PitchResult::FoulBunt => 'L',
+        PitchResult::HitByPitch => 'H',
+    }
+}
+
+/// Whether the batter put the ball in play (`X` in the pitches).
+fn is_ball_in_play(event: &GameEvent) -> bool {
+    event.ends_plate_appearance()
+        && !matches!(
+            event,
+            GameEvent::IntentionalWalk
+                | GameEvent::CatchersInterference
+                | GameEvent::AwardBases { .. }
+        )
+}
+
+/// The play for a pitch that ended the plate appearance.
+fn pitch_play(pitch_result: PitchResult, entry: &LogEntry, after: &Situation) -> String {
+    let description = match pitch_result {
+        PitchResult::Ball => "W",
+        PitchResult::HitByPitch => "HP",
+        _ if entry.outs_recorded > 0 => "K",
+        _ => "K+WP",
+    };
+    let mut movements = Vec::new();
+    if description == "K+WP" {
+        movements.push("B-1".to_string());
+    }
+    movements.extend(runner_movements(entry, after, &[]));
+    with_movements(description.to_string(), movements)
+}
+
+/// The play for an event.
+fn event_play(event: &GameEvent, entry: &LogEntry, after: &Situation) -> String {
+    let mut movements = Vec::new();
+    let mut described = Vec::new();
+    let description = match event {
+        GameEvent::Single { .. } => "S8".to_string(),
+        GameEvent::Double { .. } => "D7".to_string(),
+        GameEvent::Triple { .. } => "T9".to_string(),
+        GameEvent::HomeRun => "HR".to_string(),
+        GameEvent::GroundRuleDouble => "DGR".to_string(),
+        GameEvent::GroundOut { .. } => "63/G".to_string(),
+        GameEvent::FlyOut { .. } => "8/F".to_string(),
+        GameEvent::LineOut { .. } => "6/L".to_string(),
+        GameEvent::InfieldFly { .. } => "6/IF".to_string(),
+        GameEvent::IntentionalWalk => "IW".to_string(),
+        GameEvent::WildPitch => "WP".to_string(),
+        GameEvent::PassedBall => "PB".to_string(),
+        GameEvent::Balk => "BK".to_string(),
+        GameEvent::Pickoff { base } => {
+            described.push(*base);
+            format!(
+                "PO{}(1{})",
+                base.number(),
+                covering_fielder(Advance::Base(*base))
+            )
+        }
+        GameEvent::PickoffError { base } => {
+            format!(
+                "PO{}(E{})",
+                base.number(),
+                covering_fielder(Advance::Base(*base))
+            )
+        }
+        GameEvent::DoublePlay {
+            runners_out,
+            batter_out,
+        } => {
+            described.extend(runners_out);
+            let mut description = "6".to_string();
+            for base in runners_out {
+                description.push(covering_fielder(next_stop(*base)));
+                description.push_str(&format!("({})", base.number()));
+            }
+            if *batter_out {
+                description.push('3');
+            }
+            let outs = runners_out.len() + usize::from(*batter_out);
+            description.push_str(if outs >= 3 { "/TP" } else { "/DP" });
+            description
+        }
+        GameEvent::SacFly { .. } => "8/SF".to_string(),
+        GameEvent::SacBunt { .. } => "13/SH".to_string(),
+        GameEvent::FieldersChoice { runner_out_at } => {
+            described.push(*runner_out_at);
+            movements.push(format!(
+                "{}X{}",
+                runner_out_at.number(),
+                advance_label(next_stop(*runner_out_at))
+            ));
+            "FC6".to_string()
+        }
+        GameEvent::AwardBases { batter_to, .. } => {
+            movements.push(format!("B-{}", batter_to.number()));
+            "OA".to_string()
+        }
+        GameEvent::ReachedOnError { batter_to, .. } => {
+            if *batter_to != Base::First {
+                movements.push(format!("B-{}", batter_to.number()));
+            }
+            "E6".to_string()
+        }
+        GameEvent::CatchersInterference => "C/E2".to_string(),
+        GameEvent::Obstruction { .. } => "OA".to_string(),
+    };
+    movements.extend(runner_movements(entry, after, &described));
+    with_movements(description, movements)
+}
+
+/// Fielder covering a base: the second baseman at second, the third
+/// baseman at third and the catcher at home.
+fn covering_fielder(advance: Advance) -> char {
+    match advance {
+        Advance::Base(Base::First) => '3',
+        Advance::Base(Base::Second) => '4',
+        Advance::Base(Base::Third) => '5',
+        Advance::Home | Advance::Out => '2',
+    }
+}
+
+/// Movements of the runners on base before `entry`, lead runner first,
+/// leaving out those on `described` bases and runners who held.
+///
+/// A runner who neither scored nor is on base afterwards was put out,
+/// unless the play ended the half inning or the game and cleared the
+/// bases.
+fn runner_movements(entry: &LogEntry, after: &Situation, described: &[Base]) -> Vec<String> {
+    let bases_cleared = entry.half_inning_ended || entry.game_ended;
+    let mut movements = Vec::new();
+    for (base, runner) in entry
+        .before
+        .bases
+        .iter()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+    {
+        if described.contains(&base) {
+            continue;
+        }
+        let to = if entry.scorers.iter().any(|scorer| scorer == runner) {
+            Advance::Home
+        } else if let Some((to, _)) = after.bases.iter().find(|(_, other)| *other == runner) {
+            Advance::Base(to)
+        } else if bases_cleared {
+            continue;
+        } else {
+            Advance::Out
+        };
+        match to {
+            Advance::Base(to) if to.number() <= base.number() => {}
+            Advance::Out => movements.push(format!(
+                "{}X{}",
+                base.number(),
+                advance_label(next_stop(base))
+            )),
+            to => movements.push(format!("{}-{}", base.number(), advance_label(to))),
+        }
+    }
+    movements
+}
+
+/// Where a runner on `base` is headed when forced.
+fn next_stop(base: Base) -> Advance {
+    base.next().map_or(Advance::Home, Advance::Base)
+}
+
+fn advance_label(advance: Advance) -> String {
+    match advance {
+        Advance::Base(base) => base.number().to_string(),
+        Advance::Home | Advance::Out => "H".to_string(),
+    }
+}
+
+fn with_movements(description: String, movements: Vec<String>) -> String {
+    if movements.is_empty() {
+        description
+    } else {
+        format!("{}.{}", description, movements.join(";"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Base, GameStatus, InningHalf, RunnerAdvance};
+
+    fn initial_state() -> GameState {
+        GameState {
+            current_pitcher_id: Some("pitcher".to_string()),
+            status: GameStatus::InProgress,
+            ..GameState::new()
+        }
+    }
+
+    /// The top of the first: a single, a two-run homer, then three outs.
+    fn scripted_half_inning(rules: &GameRules) -> GameLog {
+        let mut log = GameLog::new(initial_state());
+        log.batter("a1", rules).unwrap();
+        log.pitch(PitchResult::Ball, rules).unwrap();
+        log.pitch(PitchResult::StrikeCalled, rules).unwrap();
+        log.event(
+            GameEvent::Single {
+                advancement: vec![],
+            },
+            rules,
+        )
+        .unwrap();
+        log.batter("a2", rules).unwrap();
+        log.event(GameEvent::HomeRun, rules).unwrap();
+        log.batter("a3", rules).unwrap();
+        for _ in 0..3 {
+            log.pitch(PitchResult::StrikeSwinging, rules).unwrap();
+        }
+        log.batter("a4", rules).unwrap();
+        log.event(
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            rules,
+        )
+        .unwrap();
+        log.batter("a5", rules).unwrap();
+        log.event(
+            GameEvent::FlyOut {
+                advancement: vec![],
+            },
+            rules,
+        )
+        .unwrap();
+        log
+    }
+
+    #[test]
+    fn test_log_records_outcomes() {
+        let rules = GameRules::default();
+        let log = scripted_half_inning(&rules);
+        assert_eq!(log.entries().len(), 14);
+
+        let home_run = &log.entries()[5];
+        assert_eq!(home_run.play, Play::Event(GameEvent::HomeRun));
+        assert_eq!(home_run.runs_scored, 2);
+        assert_eq!(home_run.scorers, vec!["a1".to_string(), "a2".to_string()]);
+
+        let last = log.entries().last().unwrap();
+        assert_eq!(last.outs_recorded, 1);
+        assert!(last.half_inning_ended);
+        assert_eq!(log.state().half, InningHalf::Bottom);
+        assert_eq!(log.state().score.away, 2);
+    }
+
+    #[test]
+    fn test_replay_is_deterministic() {
+        let rules = GameRules::default();
+        let log = scripted_half_inning(&rules);
+        let first = log.replay(&initial_state(), &rules).unwrap();
+        let second = log.replay(&initial_state(), &rules).unwrap();
+        assert_eq!(first, *log.state());
+        assert_eq!(first, second);
+        assert_eq!(scripted_half_inning(&rules), log);
+    }
+
+    #[test]
+    fn test_replay_detects_divergence() {
+        let rules = GameRules::default();
+        let log = scripted_half_inning(&rules);
+
+        let mut start = initial_state();
+        start.bases.set(Base::Third, Some("r3".to_string()));
+        let result = log.replay(&start, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: Replay differs from the log at play 4"
+        );
+
+        let mut tampered = log.clone();
+        tampered.entries[5].runs_scored = 3;
+        assert!(matches!(
+            tampered.replay(&initial_state(), &rules),
+            Err(BaselomError::StateError(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejected_play_leaves_log_unchanged() {
+        let rules = GameRules::default();
+        let mut log = GameLog::new(initial_state());
+        let result = log.event(
+            GameEvent::Single {
+                advancement: vec![],
+            },
+            &rules,
+        );
+        assert!(matches!(result, Err(BaselomError::NoCurrentBatter)));
+        assert!(log.entries().is_empty());
+        assert_eq!(*log.state(), initial_state());
+    }
+
+    #[test]
+    fn test_log_serialization() {
+        let rules = GameRules::default();
+        let log = scripted_half_inning(&rules);
+        let json = serde_json::to_string(&log).unwrap();
+        assert!(json.contains(r#""play":{"batter":"a1"}"#));
+        assert!(json.contains(r#""play":{"pitch":"ball"}"#));
+        assert!(json.contains(r#""play":{"event":"home_run"}"#));
+
+        let deserialized: GameLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, log);
+        assert!(deserialized.replay(&initial_state(), &rules).is_ok());
+    }
+
+    #[test]
+    fn test_retrosheet_export() {
+        let rules = GameRules::default();
+        let log = scripted_half_inning(&rules);
+        assert_eq!(
+            to_retrosheet(&log),
+            vec![
+                "play,1,0,a1,11,BCX,S8",
+                "play,1,0,a2,00,X,HR.1-H",
+                "play,1,0,a3,02,SSS,K",
+                "play,1,0,a4,00,X,63/G",
+                "play,1,0,a5,00,X,8/F",
+            ]
+        );
+    }
+
+    fn single() -> GameEvent {
+        GameEvent::Single {
+            advancement: vec![],
+        }
+    }
+
+    /// Two half innings using most of the plays both the exporter and the
+    /// parser support.
+    fn varied_log(rules: &GameRules) -> GameLog {
+        use PitchResult::*;
+
+        let mut log = GameLog::new(initial_state());
+        let mut plate_appearance =
+            |batter: &str, pitches: &[PitchResult], event: Option<GameEvent>| {
+                log.batter(batter, rules).unwrap();
+                for &pitch in pitches {
+                    log.pitch(pitch, rules).unwrap();
+                }
+                if let Some(event) = event {
+                    log.event(event, rules).unwrap();
+                }
+            };
+        plate_appearance("a1", &[Ball, StrikeCalled], Some(single()));
+        plate_appearance("a2", &[Ball], Some(GameEvent::WildPitch));
+        plate_appearance("a2", &[Ball, Ball, Ball], None);
+        plate_appearance("a3", &[StrikeSwinging, HitByPitch], None);
+        plate_appearance(
+            "a4",
+            &[Foul],
+            Some(GameEvent::ReachedOnError {
+                batter_to: Base::Second,
+                runners_advance: 2,
+            }),
+        );
+        plate_appearance(
+            "a5",
+            &[],
+            Some(GameEvent::SacFly {
+                tagging_up: vec![Base::Second],
+            }),
+        );
+        plate_appearance(
+            "a6",
+            &[StrikeCalled, Foul],
+            Some(GameEvent::GroundOut {
+                advancement: vec![RunnerAdvance {
+                    from: Base::Third,
+                    to: Advance::Home,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                }],
+            }),
+        );
+        plate_appearance(
+            "a7",
+            &[Ball],
+            Some(GameEvent::Double {
+                advancement: vec![],
+            }),
+        );
+        plate_appearance("a8", &[], Some(GameEvent::Pickoff { base: Base::Second }));
+
+        plate_appearance("h1", &[], Some(single()));
+        plate_appearance(
+            "h2",
+            &[],
+            Some(GameEvent::PickoffError { base: Base::First }),
+        );
+        plate_appearance("h2", &[Ball], Some(GameEvent::PassedBall));
+        plate_appearance("h2", &[], Some(single()));
+        plate_appearance("h3", &[StrikeCalled], Some(GameEvent::HomeRun));
+        plate_appearance("h4", &[], Some(GameEvent::IntentionalWalk));
+        plate_appearance("h5", &[], Some(single()));
+        plate_appearance(
+            "h6",
+            &[],
+            Some(GameEvent::FieldersChoice {
+                runner_out_at: Base::First,
+            }),
+        );
+        plate_appearance(
+            "h7",
+            &[],
+            Some(GameEvent::DoublePlay {
+                runners_out: vec![Base::First],
+                batter_out: true,
+            }),
+        );
+        log
+    }
+
+    #[test]
+    fn test_retrosheet_round_trip() {
+        let rules = GameRules::default();
+        let log = varied_log(&rules);
+        assert_eq!(log.state().inning, 2);
+        assert_eq!((log.state().score.away, log.state().score.home), (4, 3));
+
+        let records = to_retrosheet(&log);
+        assert_eq!(records[1], "play,1,0,a2,10,B,WP.1-2");
+        assert_eq!(records[2], "play,1,0,a2,30,B.BBB,W");
+        assert_eq!(records[4], "play,1,0,a4,01,FX,E6.B-2;3-H;2-H;1-3");
+        assert_eq!(records[5], "play,1,0,a5,00,X,8/SF.3-H;2-3");
+        assert_eq!(records[8], "play,1,0,a8,00,,PO2(14)");
+        assert_eq!(records[10], "play,1,1,h2,00,,PO1(E3).1-2");
+        assert_eq!(records[11], "play,1,1,h2,10,.B,PB.2-3");
+        assert_eq!(records[6], "play,1,0,a6,02,CFX,63/G.3-H");
+        assert_eq!(records[12], "play,1,1,h2,10,.B.X,S8.3-H");
+        assert_eq!(records[16], "play,1,1,h6,00,X,FC6.1X2;2-3");
+        assert_eq!(records[17], "play,1,1,h7,00,X,64(1)3/DP");
+        assert_eq!(records.len(), 18);
+
+        let mut state = initial_state();
+        for record in &records {
+            let record = crate::parse::parse_record(record).unwrap();
+            for play in record.plays() {
+                state = crate::engine::apply_play(&state, &play, &rules).unwrap();
+            }
+        }
+        assert_eq!(state, *log.state());
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let rules = GameRules::default();
+        let mut log = GameLog::new(GameState {
+            current_pitcher_id: Some("Smith, \"Lefty\"".to_string()),
+            ..initial_state()
+        });
+        log.batter("Doe, Jr.", &rules).unwrap();
+        log.pitch(PitchResult::Ball, &rules).unwrap();
+        log.event(single(), &rules).unwrap();
+        log.batter("a2", &rules).unwrap();
+        log.event(GameEvent::HomeRun, &rules).unwrap();
+
+        let csv = log.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "inning,half,outs,count,bases,event,batter,pitcher,runs_scored,away_score,home_score",
+                "1,top,0,0-0,0,batter,,\"Smith, \"\"Lefty\"\"\",0,0,0",
+                "1,top,0,0-0,0,ball,\"Doe, Jr.\",\"Smith, \"\"Lefty\"\"\",0,0,0",
+                "1,top,0,1-0,0,single,\"Doe, Jr.\",\"Smith, \"\"Lefty\"\"\",0,0,0",
+                "1,top,0,0-0,1,batter,,\"Smith, \"\"Lefty\"\"\",0,0,0",
+                "1,top,0,0-0,1,home_run,a2,\"Smith, \"\"Lefty\"\"\",2,2,0",
+            ]
+        );
+
+        let rows = GameLog::from_csv(&csv).unwrap();
+        assert_eq!(rows, log.rows());
+        assert_eq!(rows[2].batter.as_deref(), Some("Doe, Jr."));
+        assert_eq!(rows[4].runs_scored, 2);
+        assert_eq!(rows[4].score, Score { away: 2, home: 0 });
+    }
+
+    #[test]
+    fn test_csv_import_errors() {
+        assert!(matches!(
+            GameLog::from_csv("inning,half\n1,top\n"),
+            Err(BaselomError::ValidationError(_))
+        ));
+
+        let header = CSV_COLUMNS.join(",");
+        let bad_half = format!("{}\n1,middle,0,0-0,0,ball,a1,p1,0,0,0\n", header);
+        assert_eq!(
+            GameLog::from_csv(&bad_half).unwrap_err().to_string(),
+            "Validation error: Invalid CSV row 1: 1,middle,0,0-0,0,ball,a1,p1,0,0,0"
+        );
+        let unterminated = format!("{}\n1,top,0,0-0,0,ball,\"a1,p1,0,0,0\n", header);
+        assert!(GameLog::from_csv(&unterminated).is_err());
+        assert_eq!(GameLog::from_csv(&header).unwrap(), Vec::new());
+    }
+}
@@ -1,50 +1,2495 @@
 //! Core data structures for the baseball game state.
 
-use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::errors::BaselomError;
+use crate::player::PlayerKey;
+
+/// Version of the serialized [`GameState`] layout written by this library.
+///
+/// Version 1 is the unversioned layout of earlier releases; see
+/// [`GameState::from_json_migrating`].
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// The last inning a game can reach. A game still going when the bottom
+/// half of this inning ends is over, tied or not, since the next inning
+/// cannot be represented.
+pub const MAX_INNING: u8 = u8::MAX;
 
 /// Represents the current state of a baseball game.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct GameState {
+///
+/// Players are identified by `P`: the caller's string IDs by default, or
+/// interned [`crate::player::PlayerId`]s in a
+/// [`crate::player::CompactGameState`].
+///
+/// `Hash` agrees with `Eq` and can key in-memory caches, but hash values are
+/// not stable across library versions or builds; persist the serialized form
+/// instead.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "GameState"))]
+#[serde(bound(deserialize = "P: PlayerKey + Deserialize<'de>"))]
+pub struct GameState<P = String> {
     /// 1-based inning number
     pub inning: u8,
-    /// True if top of inning (away team batting)
-    pub top: bool,
+    /// Half of the inning being played
+    ///
+    /// Older states stored this as a `top` boolean, which still loads.
+    #[serde(alias = "top")]
+    pub half: InningHalf,
     /// Number of outs (0-2)
     pub outs: u8,
-    /// Base runners: (first, second, third)
-    pub bases: (Option<String>, Option<String>, Option<String>),
+    /// Balls and strikes on the current batter, serialized as top-level
+    /// `balls` and `strikes` keys
+    #[serde(flatten)]
+    pub count: Count,
+    /// Base runners
+    pub bases: Bases<P>,
     /// Current score
+    #[serde(default)]
     pub score: Score,
+    /// ID of the home team, if known
+    #[serde(default)]
+    pub home_team_id: Option<String>,
+    /// ID of the away team, if known
+    #[serde(default)]
+    pub away_team_id: Option<String>,
     /// ID of current batter
-    pub current_batter_id: Option<String>,
+    pub current_batter_id: Option<P>,
     /// ID of current pitcher
-    pub current_pitcher_id: Option<String>,
+    pub current_pitcher_id: Option<P>,
+    /// Whether the game is still being played
+    #[serde(default)]
+    pub status: GameStatus,
+    /// ID of the last batter to complete a plate appearance for each team
+    #[serde(default)]
+    pub last_batters: PerTeam<Option<P>>,
+    /// Runs per inning, one entry per inning started
+    ///
+    /// Left empty for states set up mid-game, in which case it is not kept.
+    #[serde(default)]
+    pub line_score: Vec<InningScore>,
+    /// Hits and errors for each team
+    #[serde(default)]
+    pub team_totals: PerTeam<TeamTotals>,
+    /// Batting order for each team
+    ///
+    /// Left empty when batters are managed by the caller, in which case the
+    /// engine clears the batter after each plate appearance instead.
+    #[serde(default)]
+    pub lineups: PerTeam<Lineup<P>>,
+    /// Who plays each position for each team
+    ///
+    /// Left empty when positions are not tracked.
+    #[serde(default)]
+    pub defensive_lineups: PerTeam<DefensiveLineup<P>>,
+    /// Players each team has taken out of the game by substitution
+    #[serde(default)]
+    pub removed_players: PerTeam<Vec<P>>,
+    /// Completed plate appearances against each pitcher, by pitcher ID
+    #[serde(default)]
+    pub batters_faced: BTreeMap<P, u32>,
+    /// Plate appearances completed in the current half inning
+    #[serde(default)]
+    pub half_inning_batters: u32,
+    /// Runs the batting team has scored in the current half inning
+    #[serde(default)]
+    pub half_inning_runs: u32,
+    /// Teams that have given up the designated hitter for the rest of the
+    /// game
+    #[serde(default)]
+    pub dh_forfeited: PerTeam<bool>,
+    /// Pitches thrown by each pitcher, by pitcher ID (counted by
+    /// `apply_pitch`; batted-ball events do not add a pitch)
+    #[serde(default)]
+    pub pitch_counts: BTreeMap<P, u32>,
+    /// Pitches thrown in the current plate appearance
+    #[serde(default)]
+    pub plate_appearance_pitches: u32,
+    /// Mound visits each team has made this game
+    #[serde(default)]
+    pub mound_visits: PerTeam<u8>,
+    /// Pitcher who went past `GameRules::mound_visit_limit` and must be
+    /// replaced before the next pitch
+    #[serde(default)]
+    pub pitcher_removal_required: Option<P>,
+    /// Pitcher responsible for each runner on base, by base: the one who
+    /// was pitching when the runner reached, even after being relieved
+    #[serde(default)]
+    pub responsible_pitchers: Bases<P>,
+    /// Runs charged to each pitcher, by pitcher ID
+    #[serde(default)]
+    pub runs_allowed: BTreeMap<P, u32>,
+    /// Earned runs charged to each pitcher, by pitcher ID
+    ///
+    /// This approximates the scoring rules instead of replaying the inning
+    /// without its errors. A run is unearned if it scores on a misplay (an
+    /// error, catcher's interference, a passed ball or obstruction), or if
+    /// the scorer reached or moved up on one. Every run is also unearned
+    /// once outs plus `outs_lost_to_errors` reach three. A runner who would
+    /// have scored anyway, such as on a later hit, still counts as unearned.
+    #[serde(default)]
+    pub earned_runs_allowed: BTreeMap<P, u32>,
+    /// Whether the runner on each base, first to third, reached or moved up
+    /// on a misplay, so that their run would be unearned
+    #[serde(default)]
+    pub unearned_runners: [bool; 3],
+    /// Outs lost to errors in the current half inning: batters who reached
+    /// on an error instead of being put out
+    #[serde(default)]
+    pub outs_lost_to_errors: u8,
+    /// Each time the lead changed hands or the game became tied, in order
+    #[serde(default)]
+    pub lead_changes: Vec<LeadChange<P>>,
+    /// Version of the serialized layout; states saved before versioning
+    /// load as version 1
+    #[serde(default = "first_schema_version")]
+    pub schema_version: u32,
+}
+
+fn first_schema_version() -> u32 {
+    1
+}
+
+impl GameState {
+    /// A game that has not started yet: top of the 1st, no outs, 0-0 count,
+    /// empty bases and no score.
+    ///
+    /// Call `engine::start` to put the game in progress.
+    pub fn new() -> Self {
+        GameState {
+            inning: 1,
+            half: InningHalf::Top,
+            outs: 0,
+            count: Count::default(),
+            bases: Bases::new(),
+            score: Score::default(),
+            home_team_id: None,
+            away_team_id: None,
+            current_batter_id: None,
+            current_pitcher_id: None,
+            status: GameStatus::NotStarted,
+            last_batters: PerTeam::default(),
+            line_score: vec![InningScore::default()],
+            team_totals: PerTeam::default(),
+            lineups: PerTeam::default(),
+            defensive_lineups: PerTeam::default(),
+            removed_players: PerTeam::default(),
+            batters_faced: BTreeMap::new(),
+            half_inning_batters: 0,
+            half_inning_runs: 0,
+            dh_forfeited: PerTeam::default(),
+            pitch_counts: BTreeMap::new(),
+            plate_appearance_pitches: 0,
+            mound_visits: PerTeam::default(),
+            pitcher_removal_required: None,
+            responsible_pitchers: Bases::new(),
+            runs_allowed: BTreeMap::new(),
+            earned_runs_allowed: BTreeMap::new(),
+            unearned_runners: [false; 3],
+            outs_lost_to_errors: 0,
+            lead_changes: Vec::new(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+        }
+    }
+
+    /// A game that has not started yet under the given rules, with the
+    /// first batter at the rules' starting count.
+    ///
+    /// The first inning is never an extra inning, so no tiebreaker runner is
+    /// placed.
+    pub fn with_rules(rules: &GameRules) -> Self {
+        GameState {
+            count: rules.starting_count,
+            ..GameState::new()
+        }
+    }
+
+    /// A builder for setting up a game situation, starting from a game in
+    /// progress at the top of the 1st.
+    pub fn builder() -> GameStateBuilder {
+        GameStateBuilder::default()
+    }
+}
+
+impl<P: PlayerKey> GameState<P> {
+    /// ID of `team`, if known.
+    pub fn team_id(&self, team: Team) -> Option<&str> {
+        match team {
+            Team::Home => self.home_team_id.as_deref(),
+            Team::Away => self.away_team_id.as_deref(),
+        }
+    }
+
+    /// The team at bat, with its ID if known.
+    pub fn batting_team(&self) -> (Team, Option<&str>) {
+        let team = self.half.batting_team();
+        (team, self.team_id(team))
+    }
+
+    /// The team in the field, with its ID if known.
+    pub fn fielding_team(&self) -> (Team, Option<&str>) {
+        let team = self.half.fielding_team();
+        (team, self.team_id(team))
+    }
+
+    /// The team and position `player_id` plays in the field, if tracked.
+    pub fn position_of(&self, player_id: &P::Key) -> Option<(Team, Position)> {
+        [Team::Away, Team::Home].into_iter().find_map(|team| {
+            self.defensive_lineups
+                .get(team)
+                .position_of(player_id)
+                .map(|position| (team, position))
+        })
+    }
+
+    /// The batting team's next batter per its lineup, or `None` if the team
+    /// has no lineup.
+    pub fn due_up(&self) -> Option<&P::Key> {
+        self.lineups.get(self.half.batting_team()).current()
+    }
+
+    /// Pitches thrown so far by `pitcher_id`.
+    pub fn pitches_thrown(&self, pitcher_id: &P::Key) -> u32 {
+        self.pitch_counts.get(pitcher_id).copied().unwrap_or(0)
+    }
+
+    /// Runs charged so far to `pitcher_id`.
+    pub fn runs_allowed_by(&self, pitcher_id: &P::Key) -> u32 {
+        self.runs_allowed.get(pitcher_id).copied().unwrap_or(0)
+    }
+
+    /// Earned runs charged so far to `pitcher_id`.
+    pub fn earned_runs_allowed_by(&self, pitcher_id: &P::Key) -> u32 {
+        self.earned_runs_allowed
+            .get(pitcher_id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Mound visits `team` has left before reaching the limit, or `None` if
+    /// the rules set no limit.
+    pub fn mound_visits_remaining(&self, team: Team, rules: &GameRules) -> Option<u8> {
+        rules
+            .mound_visit_limit
+            .map(|limit| limit.saturating_sub(*self.mound_visits.get(team)))
+    }
+
+    /// Whether `team` bats a designated hitter in place of its pitcher.
+    pub fn uses_designated_hitter(&self, team: Team, rules: &GameRules) -> bool {
+        rules.designated_hitter && !*self.dh_forfeited.get(team)
+    }
+
+    /// The base-out-count situation, without player IDs, for keying
+    /// run-expectancy tables and similar caches.
+    pub fn situation_key(&self) -> SituationKey {
+        SituationKey {
+            inning: self.inning,
+            half: self.half,
+            outs: self.outs,
+            bases_occupied: self.bases.occupied_mask(),
+            balls: self.count.balls,
+            strikes: self.count.strikes,
+        }
+    }
+
+    /// The team that won, once the game is final.
+    ///
+    /// Returns `None` while the game is not final and for tied games.
+    pub fn winner(&self) -> Option<Team> {
+        if self.status != GameStatus::Final {
+            return None;
+        }
+        self.leading_team()
+    }
+
+    /// The team ahead right now, or `None` while the score is tied.
+    pub fn leading_team(&self) -> Option<Team> {
+        self.score.leader()
+    }
+
+    /// The same state with every player ID mapped through `f`.
+    ///
+    /// Returns the first error from `f`.
+    pub fn map_players<Q: Ord, E>(
+        &self,
+        mut f: impl FnMut(&P) -> Result<Q, E>,
+    ) -> Result<GameState<Q>, E> {
+        let GameState {
+            inning,
+            half,
+            outs,
+            count,
+            bases,
+            score,
+            home_team_id,
+            away_team_id,
+            current_batter_id,
+            current_pitcher_id,
+            status,
+            last_batters,
+            line_score,
+            team_totals,
+            lineups,
+            defensive_lineups,
+            removed_players,
+            batters_faced,
+            half_inning_batters,
+            half_inning_runs,
+            dh_forfeited,
+            pitch_counts,
+            plate_appearance_pitches,
+            mound_visits,
+            pitcher_removal_required,
+            responsible_pitchers,
+            runs_allowed,
+            earned_runs_allowed,
+            unearned_runners,
+            outs_lost_to_errors,
+            lead_changes,
+            schema_version,
+        } = self;
+        let mut option = |player: &Option<P>| player.as_ref().map(&mut f).transpose();
+        let current_batter_id = option(current_batter_id)?;
+        let current_pitcher_id = option(current_pitcher_id)?;
+        let pitcher_removal_required = option(pitcher_removal_required)?;
+        let last_batters = PerTeam {
+            home: option(&last_batters.home)?,
+            away: option(&last_batters.away)?,
+        };
+        let [first, second, third] = &bases.runners;
+        let bases = Bases {
+            runners: [option(first)?, option(second)?, option(third)?],
+        };
+        let [first, second, third] = &responsible_pitchers.runners;
+        let responsible_pitchers = Bases {
+            runners: [option(first)?, option(second)?, option(third)?],
+        };
+        let mut lineup = |lineup: &Lineup<P>| -> Result<Lineup<Q>, E> {
+            Ok(Lineup {
+                players: lineup
+                    .players
+                    .iter()
+                    .map(&mut f)
+                    .collect::<Result<_, _>>()?,
+                index: lineup.index,
+                pitcher: lineup.pitcher.as_ref().map(&mut f).transpose()?,
+            })
+        };
+        let lineups = PerTeam {
+            home: lineup(&lineups.home)?,
+            away: lineup(&lineups.away)?,
+        };
+        let mut defense = |defense: &DefensiveLineup<P>| -> Result<DefensiveLineup<Q>, E> {
+            Ok(DefensiveLineup {
+                positions: defense
+                    .positions
+                    .iter()
+                    .map(|(&position, player)| Ok((position, f(player)?)))
+                    .collect::<Result<_, E>>()?,
+            })
+        };
+        let defensive_lineups = PerTeam {
+            home: defense(&defensive_lineups.home)?,
+            away: defense(&defensive_lineups.away)?,
+        };
+        let removed_players = PerTeam {
+            home: removed_players
+                .home
+                .iter()
+                .map(&mut f)
+                .collect::<Result<_, _>>()?,
+            away: removed_players
+                .away
+                .iter()
+                .map(&mut f)
+                .collect::<Result<_, _>>()?,
+        };
+        let mut counts = |counts: &BTreeMap<P, u32>| {
+            counts
+                .iter()
+                .map(|(player, &count)| Ok((f(player)?, count)))
+                .collect::<Result<BTreeMap<Q, u32>, E>>()
+        };
+        let batters_faced = counts(batters_faced)?;
+        let pitch_counts = counts(pitch_counts)?;
+        let runs_allowed = counts(runs_allowed)?;
+        let earned_runs_allowed = counts(earned_runs_allowed)?;
+        let lead_changes = lead_changes
+            .iter()
+            .map(|change| {
+                Ok(LeadChange {
+                    inning: change.inning,
+                    half: change.half,
+                    leader: change.leader,
+                    score: change.score.clone(),
+                    pitchers: PerTeam {
+                        home: change.pitchers.home.as_ref().map(&mut f).transpose()?,
+                        away: change.pitchers.away.as_ref().map(&mut f).transpose()?,
+                    },
+                })
+            })
+            .collect::<Result<_, E>>()?;
+
+        Ok(GameState {
+            inning: *inning,
+            half: *half,
+            outs: *outs,
+            count: *count,
+            bases,
+            score: score.clone(),
+            home_team_id: home_team_id.clone(),
+            away_team_id: away_team_id.clone(),
+            current_batter_id,
+            current_pitcher_id,
+            status: *status,
+            last_batters,
+            line_score: line_score.clone(),
+            team_totals: team_totals.clone(),
+            lineups,
+            defensive_lineups,
+            removed_players,
+            batters_faced,
+            half_inning_batters: *half_inning_batters,
+            half_inning_runs: *half_inning_runs,
+            dh_forfeited: dh_forfeited.clone(),
+            pitch_counts,
+            plate_appearance_pitches: *plate_appearance_pitches,
+            mound_visits: mound_visits.clone(),
+            pitcher_removal_required,
+            responsible_pitchers,
+            runs_allowed,
+            earned_runs_allowed,
+            unearned_runners: *unearned_runners,
+            outs_lost_to_errors: *outs_lost_to_errors,
+            lead_changes,
+            schema_version: *schema_version,
+        })
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        GameState::new()
+    }
+}
+
+/// Upgrades a serialized state from version `n + 1` to `n + 2`, indexed by
+/// `n`.
+type Migration = fn(&mut serde_json::Map<String, serde_json::Value>);
+
+const MIGRATIONS: [Migration; (CURRENT_SCHEMA_VERSION - 1) as usize] = [migrate_v1_to_v2];
+
+/// Version 2 replaced the `top` flag with `half` and always stores the
+/// count.
+fn migrate_v1_to_v2(state: &mut serde_json::Map<String, serde_json::Value>) {
+    if let Some(top) = state.remove("top") {
+        if !state.contains_key("half") {
+            let half = match top.as_bool() {
+                Some(false) => "bottom",
+                _ => "top",
+            };
+            state.insert("half".to_string(), half.into());
+        }
+    }
+    for key in ["balls", "strikes"] {
+        state.entry(key).or_insert(0.into());
+    }
+}
+
+impl GameState {
+    /// Load a serialized state of any supported schema version, upgrading
+    /// it to [`CURRENT_SCHEMA_VERSION`].
+    ///
+    /// A state without a `schema_version` is treated as version 1. Returns
+    /// `BaselomError::ValidationError` for malformed JSON and for versions
+    /// newer than this library supports.
+    pub fn from_json_migrating(json: &str) -> Result<GameState, BaselomError> {
+        let invalid = |err: serde_json::Error| {
+            BaselomError::ValidationError(format!("Invalid game state JSON: {}", err))
+        };
+        let mut value: serde_json::Value = serde_json::from_str(json).map_err(invalid)?;
+        let state = value.as_object_mut().ok_or_else(|| {
+            BaselomError::ValidationError("A game state must be a JSON object".to_string())
+        })?;
+        let version = match state.get("schema_version") {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .filter(|&version| version >= 1)
+                .ok_or_else(|| {
+                    BaselomError::ValidationError(format!("Invalid schema version {}", version))
+                })?,
+        };
+        if version > CURRENT_SCHEMA_VERSION {
+            return Err(BaselomError::ValidationError(format!(
+                "Schema version {} is newer than the supported version {}",
+                version, CURRENT_SCHEMA_VERSION
+            )));
+        }
+        for migrate in &MIGRATIONS[(version - 1) as usize..] {
+            migrate(state);
+        }
+        state.insert("schema_version".to_string(), CURRENT_SCHEMA_VERSION.into());
+        serde_json::from_value(value).map_err(invalid)
+    }
+
+    /// Load a state, rejecting any field this library does not know.
+    ///
+    /// Missing optional fields still take their defaults. Returns
+    /// `BaselomError::ValidationError` naming the first unknown field.
+    pub fn from_json_strict(json: &str) -> Result<GameState, BaselomError> {
+        from_json_strict(json)
+    }
+
+    /// Load a state, ignoring unknown fields (such as those written by a
+    /// newer library) and defaulting missing optional ones.
+    pub fn from_json_lenient(json: &str) -> Result<GameState, BaselomError> {
+        from_json_lenient(json)
+    }
+
+    /// Serialize with camelCase keys (`currentBatterId`) for JavaScript
+    /// clients; plain serde serialization stays snake_case.
+    pub fn to_json_camel(&self) -> Result<String, BaselomError> {
+        to_json_camel(self)
+    }
+
+    /// Load a state written by [`GameState::to_json_camel`].
+    ///
+    /// Returns `BaselomError::ValidationError` for malformed JSON.
+    pub fn from_json_camel(json: &str) -> Result<GameState, BaselomError> {
+        from_json_camel(json)
+    }
+
+    /// Serialize as MessagePack, a compact binary form for storing or
+    /// streaming many snapshots.
+    ///
+    /// Fields are written by name, so the bytes load with the same
+    /// defaults for missing fields as the JSON form.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BaselomError> {
+        rmp_serde::to_vec_named(self).map_err(|err| BaselomError::StateError(err.to_string()))
+    }
+
+    /// Load a state written by [`GameState::to_bytes`].
+    ///
+    /// Returns `BaselomError::ValidationError` for truncated or malformed
+    /// input.
+    pub fn from_bytes(bytes: &[u8]) -> Result<GameState, BaselomError> {
+        rmp_serde::from_slice(bytes).map_err(|err| {
+            BaselomError::ValidationError(format!("Invalid binary game state: {}", err))
+        })
+    }
+}
+
+/// Deserialize `json`, ignoring unknown fields.
+fn from_json_lenient<T: DeserializeOwned>(json: &str) -> Result<T, BaselomError> {
+    serde_json::from_str(json).map_err(invalid_json)
+}
+
+/// Deserialize `json`, failing if it has a field that does not survive a
+/// round trip through `T`.
+fn from_json_strict<T: Serialize + DeserializeOwned>(json: &str) -> Result<T, BaselomError> {
+    let input: serde_json::Value = serde_json::from_str(json).map_err(invalid_json)?;
+    let parsed = T::deserialize(&input).map_err(invalid_json)?;
+    let known = serde_json::to_value(&parsed).map_err(invalid_json)?;
+    match unknown_field(&input, &known) {
+        Some(path) => Err(BaselomError::ValidationError(format!(
+            "Unknown field '{}'",
+            path
+        ))),
+        None => Ok(parsed),
+    }
+}
+
+/// Path of the first key in `input` that `known` lacks, descending into
+/// objects and arrays.
+fn unknown_field(input: &serde_json::Value, known: &serde_json::Value) -> Option<String> {
+    use serde_json::Value;
+    match (input, known) {
+        (Value::Object(input), Value::Object(known)) => {
+            input.iter().find_map(|(key, value)| match known.get(key) {
+                None => Some(key.clone()),
+                Some(known) => unknown_field(value, known).map(|path| format!("{}.{}", key, path)),
+            })
+        }
+        (Value::Array(input), Value::Array(known)) => {
+            input
+                .iter()
+                .zip(known)
+                .enumerate()
+                .find_map(|(index, (value, known))| {
+                    unknown_field(value, known).map(|path| format!("{}.{}", index, path))
+                })
+        }
+        _ => None,
+    }
+}
+
+fn invalid_json(err: serde_json::Error) -> BaselomError {
+    BaselomError::ValidationError(format!("Invalid JSON: {}", err))
+}
+
+/// Fields holding maps keyed by player ID (or by position, for
+/// `positions`), whose keys are data and keep their spelling when keys are
+/// converted between snake_case and camelCase.
+pub(crate) const ID_KEYED_FIELDS: [&str; 7] = [
+    "batters_faced",
+    "pitch_counts",
+    "runs_allowed",
+    "earned_runs_allowed",
+    "batting",
+    "pitching",
+    "positions",
+];
+
+/// Serialize `value` with camelCase object keys.
+///
+/// Only keys change: enum values such as `"in_progress"` keep their
+/// snake_case spelling.
+pub(crate) fn to_json_camel<T: Serialize>(value: &T) -> Result<String, BaselomError> {
+    let mut value =
+        serde_json::to_value(value).map_err(|err| BaselomError::StateError(err.to_string()))?;
+    convert_keys(&mut value, snake_to_camel);
+    Ok(value.to_string())
+}
+
+/// Deserialize `json` written with camelCase object keys. snake_case keys
+/// are accepted too.
+pub(crate) fn from_json_camel<T: DeserializeOwned>(json: &str) -> Result<T, BaselomError> {
+    let mut value: serde_json::Value = serde_json::from_str(json).map_err(invalid_json)?;
+    convert_keys(&mut value, camel_to_snake);
+    serde_json::from_value(value).map_err(invalid_json)
+}
+
+/// Rename every object key in `value` with `convert`, leaving the keys of
+/// [`ID_KEYED_FIELDS`] alone but renaming inside their values.
+fn convert_keys(value: &mut serde_json::Value, convert: fn(&str) -> String) {
+    use serde_json::Value;
+    match value {
+        Value::Object(map) => {
+            *map = std::mem::take(map)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    let renamed = convert(&key);
+                    let keyed_by_id = ID_KEYED_FIELDS.contains(&key.as_str())
+                        || ID_KEYED_FIELDS.contains(&renamed.as_str());
+                    match &mut value {
+                        Value::Object(by_id) if keyed_by_id => {
+                            for entry in by_id.values_mut() {
+                                convert_keys(entry, convert);
+                            }
+                        }
+                        _ => convert_keys(&mut value, convert),
+                    }
+                    (renamed, value)
+                })
+                .collect();
+        }
+        Value::Array(items) => {
+            for item in items {
+                convert_keys(item, convert);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn snake_to_camel(key: &str) -> String {
+    let mut parts = key.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+pub(crate) fn camel_to_snake(key: &str) -> String {
+    let mut snake = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Fluent builder for a [`GameState`], created with [`GameState::builder`].
+#[derive(Debug, Clone)]
+pub struct GameStateBuilder {
+    state: GameState,
+}
+
+impl Default for GameStateBuilder {
+    fn default() -> Self {
+        GameStateBuilder {
+            state: GameState {
+                status: GameStatus::InProgress,
+                line_score: Vec::new(),
+                ..GameState::new()
+            },
+        }
+    }
+}
+
+impl GameStateBuilder {
+    /// Set the inning number.
+    pub fn inning(mut self, inning: u8) -> Self {
+        self.state.inning = inning;
+        self
+    }
+
+    /// Put the away team at bat.
+    pub fn top(mut self) -> Self {
+        self.state.half = InningHalf::Top;
+        self
+    }
+
+    /// Put the home team at bat.
+    pub fn bottom(mut self) -> Self {
+        self.state.half = InningHalf::Bottom;
+        self
+    }
+
+    /// Set the number of outs.
+    pub fn outs(mut self, outs: u8) -> Self {
+        self.state.outs = outs;
+        self
+    }
+
+    /// Set the balls and strikes in the current count.
+    pub fn count(mut self, balls: u8, strikes: u8) -> Self {
+        self.state.count = Count::new(balls, strikes);
+        self
+    }
+
+    /// Place a runner on a base.
+    pub fn runner_on(mut self, base: Base, runner_id: impl Into<String>) -> Self {
+        self.state.bases.set(base, Some(runner_id.into()));
+        self
+    }
+
+    /// Set the home and away score.
+    pub fn score(mut self, home: u32, away: u32) -> Self {
+        self.state.score = Score { home, away };
+        self
+    }
+
+    /// Set the home and away team IDs.
+    pub fn teams(mut self, home_id: impl Into<String>, away_id: impl Into<String>) -> Self {
+        self.state.home_team_id = Some(home_id.into());
+        self.state.away_team_id = Some(away_id.into());
+        self
+    }
+
+    /// Set the current batter.
+    pub fn batter(mut self, batter_id: impl Into<String>) -> Self {
+        self.state.current_batter_id = Some(batter_id.into());
+        self
+    }
+
+    /// Set the current pitcher.
+    pub fn pitcher(mut self, pitcher_id: impl Into<String>) -> Self {
+        self.state.current_pitcher_id = Some(pitcher_id.into());
+        self
+    }
+
+    /// Set the game status.
+    pub fn status(mut self, status: GameStatus) -> Self {
+        self.state.status = status;
+        self
+    }
+
+    /// Set both batting orders. Unless a batter is set, the batting team's
+    /// next batter comes up.
+    pub fn lineups(mut self, home: Lineup, away: Lineup) -> Self {
+        self.state.lineups = PerTeam { home, away };
+        self
+    }
+
+    /// Set who plays each position for both teams.
+    pub fn defensive_lineups(mut self, home: DefensiveLineup, away: DefensiveLineup) -> Self {
+        self.state.defensive_lineups = PerTeam { home, away };
+        self
+    }
+
+    /// Validate and return the state.
+    ///
+    /// Returns `BaselomError::InvalidState` if the state is inconsistent.
+    pub fn build(mut self) -> Result<GameState, BaselomError> {
+        if self.state.current_batter_id.is_none() {
+            self.state.current_batter_id = self.state.due_up().map(str::to_string);
+        }
+        crate::validators::validate_state(&self.state)?;
+        Ok(self.state)
+    }
+}
+
+/// The abstract situation of a [`GameState`], ignoring who is on base or at
+/// bat. Created with [`GameState::situation_key`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SituationKey {
+    pub inning: u8,
+    pub half: InningHalf,
+    pub outs: u8,
+    /// Occupied bases as bits: 1 for first, 2 for second, 4 for third
+    pub bases_occupied: u8,
+    pub balls: u8,
+    pub strikes: u8,
+}
+
+/// Balls and strikes on the current batter.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Count {
+    /// Balls in the count (0-3 under default rules)
+    pub balls: u8,
+    /// Strikes in the count (0-2 under default rules)
+    pub strikes: u8,
+}
+
+impl Count {
+    /// A count of `balls` and `strikes`.
+    pub fn new(balls: u8, strikes: u8) -> Self {
+        Count { balls, strikes }
+    }
+
+    /// Whether one more ball walks the batter and one more strike retires
+    /// them (3-2 under default rules).
+    pub fn is_full(&self, rules: &GameRules) -> bool {
+        self.balls + 1 == rules.balls_for_walk && self.strikes + 1 == rules.strikes_for_out
+    }
+
+    /// Back to 0-0 for a new batter.
+    pub fn reset(&mut self) {
+        *self = Count::default();
+    }
+
+    /// Whether the count is below both the walk and strikeout thresholds.
+    pub fn is_valid(&self, rules: &GameRules) -> bool {
+        self.balls < rules.balls_for_walk && self.strikes < rules.strikes_for_out
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.balls, self.strikes)
+    }
+}
+
+/// Which half of an inning is being played.
+///
+/// Serializes as `"top"` or `"bottom"`; the boolean form used by older
+/// states (`true` for the top half) is accepted on input.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum InningHalf {
+    /// Away team batting
+    #[default]
+    Top,
+    /// Home team batting
+    Bottom,
+}
+
+impl InningHalf {
+    /// The team at bat in this half.
+    pub fn batting_team(self) -> Team {
+        match self {
+            InningHalf::Top => Team::Away,
+            InningHalf::Bottom => Team::Home,
+        }
+    }
+
+    /// The team in the field in this half.
+    pub fn fielding_team(self) -> Team {
+        self.batting_team().opponent()
+    }
+
+    /// The other half of the inning.
+    pub fn opposite(self) -> InningHalf {
+        match self {
+            InningHalf::Top => InningHalf::Bottom,
+            InningHalf::Bottom => InningHalf::Top,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InningHalf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum Named {
+            Top,
+            Bottom,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Top(bool),
+            Named(Named),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Top(true) | Repr::Named(Named::Top) => Ok(InningHalf::Top),
+            Repr::Top(false) | Repr::Named(Named::Bottom) => Ok(InningHalf::Bottom),
+        }
+    }
+}
+
+/// One of the two teams in a game.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Team {
+    Home,
+    Away,
+}
+
+impl Team {
+    /// The other team.
+    pub fn opponent(self) -> Team {
+        match self {
+            Team::Home => Team::Away,
+            Team::Away => Team::Home,
+        }
+    }
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Team::Home => "home",
+            Team::Away => "away",
+        })
+    }
+}
+
+/// A value tracked separately for the home and away teams.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PerTeam<T> {
+    pub home: T,
+    pub away: T,
+}
+
+impl<T> PerTeam<T> {
+    /// The value for one team.
+    pub fn get(&self, team: Team) -> &T {
+        match team {
+            Team::Home => &self.home,
+            Team::Away => &self.away,
+        }
+    }
+
+    /// Mutable access to the value for one team.
+    pub fn get_mut(&mut self, team: Team) -> &mut T {
+        match team {
+            Team::Home => &mut self.home,
+            Team::Away => &mut self.away,
+        }
+    }
+}
+
+/// A team's batting order.
+///
+/// The lineup does not reset between innings: `index` keeps pointing at the
+/// batter at the plate or due up next, wrapping from the last slot to the
+/// first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "Lineup"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>"))]
+pub struct Lineup<P = String> {
+    /// Player IDs in batting order
+    pub players: Vec<P>,
+    /// Index into `players` of the batter at the plate or due up
+    #[serde(default)]
+    pub index: usize,
+    /// The team's pitcher, who is also in `players` unless the team uses
+    /// the designated hitter
+    #[serde(default)]
+    pub pitcher: Option<P>,
+}
+
+impl<P> Default for Lineup<P> {
+    fn default() -> Self {
+        Lineup {
+            players: Vec::new(),
+            index: 0,
+            pitcher: None,
+        }
+    }
+}
+
+impl Lineup {
+    /// A lineup with the first player due up.
+    pub fn new<I, S>(players: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Lineup {
+            players: players.into_iter().map(Into::into).collect(),
+            index: 0,
+            pitcher: None,
+        }
+    }
+
+    /// Set the team's pitcher.
+    pub fn with_pitcher(mut self, pitcher_id: impl Into<String>) -> Self {
+        self.pitcher = Some(pitcher_id.into());
+        self
+    }
+}
+
+impl<P: PlayerKey> Lineup<P> {
+    /// The batter at the plate or due up, if the lineup is not empty.
+    pub fn current(&self) -> Option<&P::Key> {
+        self.players.get(self.index).map(Borrow::borrow)
+    }
+
+    /// Move to the next batter, wrapping around to the top of the order.
+    pub fn advance(&mut self) {
+        if !self.players.is_empty() {
+            self.index = (self.index + 1) % self.players.len();
+        }
+    }
+
+    /// Index of the slot `player_id` bats in.
+    pub fn slot_of(&self, player_id: &P::Key) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.borrow() == player_id)
+    }
+
+    /// Number of slots.
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Whether the lineup has no players (the team's batters are not
+    /// tracked).
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+/// A defensive position, serialized as its scorecard abbreviation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Position {
+    #[serde(rename = "P")]
+    Pitcher,
+    #[serde(rename = "C")]
+    Catcher,
+    #[serde(rename = "1B")]
+    FirstBase,
+    #[serde(rename = "2B")]
+    SecondBase,
+    #[serde(rename = "3B")]
+    ThirdBase,
+    #[serde(rename = "SS")]
+    Shortstop,
+    #[serde(rename = "LF")]
+    LeftField,
+    #[serde(rename = "CF")]
+    CenterField,
+    #[serde(rename = "RF")]
+    RightField,
+    /// Designated hitter, who bats but does not field
+    #[serde(rename = "DH")]
+    DesignatedHitter,
+}
+
+impl Position {
+    /// The nine fielding positions, in scorekeeping order (1-9).
+    pub const FIELDERS: [Position; 9] = [
+        Position::Pitcher,
+        Position::Catcher,
+        Position::FirstBase,
+        Position::SecondBase,
+        Position::ThirdBase,
+        Position::Shortstop,
+        Position::LeftField,
+        Position::CenterField,
+        Position::RightField,
+    ];
+
+    /// Scorecard abbreviation, as used in serialization.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Position::Pitcher => "P",
+            Position::Catcher => "C",
+            Position::FirstBase => "1B",
+            Position::SecondBase => "2B",
+            Position::ThirdBase => "3B",
+            Position::Shortstop => "SS",
+            Position::LeftField => "LF",
+            Position::CenterField => "CF",
+            Position::RightField => "RF",
+            Position::DesignatedHitter => "DH",
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Who plays each position for one team.
+///
+/// An empty defensive lineup means the team's positions are not tracked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "DefensiveLineup"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>"))]
+pub struct DefensiveLineup<P = String> {
+    /// Player ID at each filled position
+    #[serde(default)]
+    pub positions: BTreeMap<Position, P>,
+}
+
+impl<P> Default for DefensiveLineup<P> {
+    fn default() -> Self {
+        DefensiveLineup {
+            positions: BTreeMap::new(),
+        }
+    }
+}
+
+impl DefensiveLineup {
+    /// A defensive lineup from `(position, player)` pairs.
+    pub fn new<I, S>(positions: I) -> Self
+    where
+        I: IntoIterator<Item = (Position, S)>,
+        S: Into<String>,
+    {
+        DefensiveLineup {
+            positions: positions
+                .into_iter()
+                .map(|(position, player)| (position, player.into()))
+                .collect(),
+        }
+    }
+}
+
+impl<P: PlayerKey> DefensiveLineup<P> {
+    /// The player at `position`, if it is filled.
+    pub fn player_at(&self, position: Position) -> Option<&P::Key> {
+        self.positions.get(&position).map(Borrow::borrow)
+    }
+
+    /// The position `player_id` plays, if any.
+    pub fn position_of(&self, player_id: &P::Key) -> Option<Position> {
+        self.positions
+            .iter()
+            .find(|(_, player)| (*player).borrow() == player_id)
+            .map(|(&position, _)| position)
+    }
+
+    /// Put `new_player_id` at the position `replaced` plays, if any.
+    pub(crate) fn replace(&mut self, replaced: &P::Key, new_player_id: &P::Key) {
+        if let Some(position) = self.position_of(replaced) {
+            self.positions.insert(position, new_player_id.to_owned());
+        }
+    }
+
+    /// Whether no positions are filled (the team's defense is not tracked).
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+/// Lifecycle status of a game.
+///
+/// Deserializing a state without a `status` field yields `InProgress`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GameStatus {
+    /// Game has been set up but the first pitch has not been thrown
+    NotStarted,
+    /// Game is being played
+    #[default]
+    InProgress,
+    /// Game is over; no further transitions are accepted
+    Final,
+    /// Play has been halted and may be resumed later
+    Suspended,
+}
+
+/// Score tracking for both teams.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Score {
+    pub home: u32,
+    pub away: u32,
+}
+
+impl Score {
+    /// Runs scored by `team`.
+    pub fn get(&self, team: Team) -> u32 {
+        match team {
+            Team::Home => self.home,
+            Team::Away => self.away,
+        }
+    }
+
+    /// Mutable access to the runs scored by `team`.
+    pub fn get_mut(&mut self, team: Team) -> &mut u32 {
+        match team {
+            Team::Home => &mut self.home,
+            Team::Away => &mut self.away,
+        }
+    }
+
+    /// Home runs minus away runs: positive while the home team leads.
+    pub fn differential(&self) -> i64 {
+        i64::from(self.home) - i64::from(self.away)
+    }
+
+    /// The team ahead, or `None` if the score is tied.
+    pub fn leader(&self) -> Option<Team> {
+        match self.differential().signum() {
+            1 => Some(Team::Home),
+            -1 => Some(Team::Away),
+            _ => None,
+        }
+    }
+}
+
+/// The result of applying a pitch or event, with what happened on the way.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "TransitionOutcome"))]
+#[serde(bound(deserialize = "P: PlayerKey + Deserialize<'de>"))]
+pub struct TransitionOutcome<P = String> {
+    /// State after the transition
+    pub state: GameState<P>,
+    /// Runs scored during the transition
+    pub runs_scored: u32,
+    /// IDs of the players who scored, in the order they crossed the plate
+    pub scorers: Vec<P>,
+    /// Pitcher charged with each run, in the order the runs scored
+    pub charged_pitchers: Vec<P>,
+    /// Whether each run in `charged_pitchers` was earned
+    pub earned: Vec<bool>,
+    /// Runs batted in by the batter
+    #[serde(default)]
+    pub rbis: u8,
+    /// Outs recorded during the transition
+    pub outs_recorded: u8,
+    /// Whether the half inning ended (including on the game's final out)
+    pub half_inning_ended: bool,
+    /// Whether the half inning ended because the batting team reached
+    /// `GameRules::inning_run_limit`
+    #[serde(default)]
+    pub half_inning_ended_by_run_limit: bool,
+    /// Whether the game is over after the transition
+    pub game_ended: bool,
+}
+
+/// What happened during a transition applied in place, as returned by
+/// [`crate::engine::step`]; a [`TransitionOutcome`] without the state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "StepOutcome"))]
+pub struct StepOutcome<P = String> {
+    /// Runs scored during the transition
+    pub runs_scored: u32,
+    /// IDs of the players who scored, in the order they crossed the plate
+    pub scorers: Vec<P>,
+    /// Pitcher charged with each run, in the order the runs scored
+    pub charged_pitchers: Vec<P>,
+    /// Whether each run in `charged_pitchers` was earned
+    pub earned: Vec<bool>,
+    /// Runs batted in by the batter
+    #[serde(default)]
+    pub rbis: u8,
+    /// Outs recorded during the transition
+    pub outs_recorded: u8,
+    /// Whether the half inning ended (including on the game's final out)
+    pub half_inning_ended: bool,
+    /// Whether the half inning ended because the batting team reached
+    /// `GameRules::inning_run_limit`
+    #[serde(default)]
+    pub half_inning_ended_by_run_limit: bool,
+    /// Whether the game is over after the transition
+    pub game_ended: bool,
+}
+
+impl<P> Default for StepOutcome<P> {
+    fn default() -> Self {
+        StepOutcome {
+            runs_scored: 0,
+            scorers: Vec::new(),
+            charged_pitchers: Vec::new(),
+            earned: Vec::new(),
+            rbis: 0,
+            outs_recorded: 0,
+            half_inning_ended: false,
+            half_inning_ended_by_run_limit: false,
+            game_ended: false,
+        }
+    }
+}
+
+impl<P> StepOutcome<P> {
+    /// Attach the state the transition led to.
+    pub fn with_state(self, state: GameState<P>) -> TransitionOutcome<P> {
+        TransitionOutcome {
+            state,
+            runs_scored: self.runs_scored,
+            scorers: self.scorers,
+            charged_pitchers: self.charged_pitchers,
+            earned: self.earned,
+            rbis: self.rbis,
+            outs_recorded: self.outs_recorded,
+            half_inning_ended: self.half_inning_ended,
+            half_inning_ended_by_run_limit: self.half_inning_ended_by_run_limit,
+            game_ended: self.game_ended,
+        }
+    }
+}
+
+/// Hits and errors credited to one team, for the R/H/E scoreboard.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct TeamTotals {
+    /// Hits by the team's batters
+    pub hits: u32,
+    /// Errors committed by the team's fielders
+    pub errors: u32,
+    /// Runners still on base when each of the team's half innings ended,
+    /// or when the game ended during one
+    pub left_on_base: u32,
+}
+
+/// A play after which a different team led, or the game became tied.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "LeadChange"))]
+pub struct LeadChange<P = String> {
+    /// Inning of the play
+    pub inning: u8,
+    /// Half inning of the play
+    pub half: InningHalf,
+    /// Team ahead after the play, or `None` if the game became tied
+    pub leader: Option<Team>,
+    /// Score after the play
+    pub score: Score,
+    /// Each team's pitcher at the time: the one on the mound for the
+    /// fielding team, and the lineup's pitcher for the batting team
+    pub pitchers: PerTeam<Option<P>>,
+}
+
+/// Runs scored by each team in one inning.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InningScore {
+    /// Runs by the away team
+    pub away: u32,
+    /// Runs by the home team, or `None` until the bottom half starts (and
+    /// for a bottom half that is never played)
+    pub home: Option<u32>,
+}
+
+/// Configurable game rules.
+///
+/// Fields missing from serialized rules take their default values, or the
+/// values of the preset named by `preset` (see [`crate::presets`]).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, remote = "Self")]
+pub struct GameRules {
+    /// Name of the preset these rules started from, if any
+    pub preset: Option<String>,
+    /// Whether designated hitter is used (teams may still forfeit it during
+    /// the game)
+    pub designated_hitter: bool,
+    /// Maximum number of innings (None for unlimited)
+    pub max_innings: Option<u8>,
+    /// Extra innings tiebreaker rule
+    pub extra_innings_tiebreaker: Option<ExtraInningsTiebreaker>,
+    /// Whether an intentional walk may be awarded without throwing pitches
+    pub allow_auto_intentional_walk: bool,
+    /// Whether a balk with the bases empty counts as a ball (otherwise it is
+    /// rejected)
+    pub balk_with_empty_bases_is_ball: bool,
+    /// Whether the batter may run on an uncaught third strike
+    pub dropped_third_strike: bool,
+    /// Balls that award the batter first base
+    pub balls_for_walk: u8,
+    /// Strikes that retire the batter
+    pub strikes_for_out: u8,
+    /// Count each batter starts with, such as 1-1 in slow-pitch softball
+    pub starting_count: Count,
+    /// Early end to lopsided games (None to always play to completion)
+    pub mercy_rule: Option<MercyRule>,
+    /// Runs after which a half inning ends, even with fewer than three outs
+    /// (None for no limit)
+    pub inning_run_limit: Option<u32>,
+    /// Whether `inning_run_limit` is lifted from the last regulation inning
+    /// on
+    pub last_inning_uncapped: bool,
+    /// Inning after which a tied game ends as a tie (None to play until
+    /// there is a winner)
+    pub allow_tie_after_innings: Option<u8>,
+    /// Fewest players a lineup may have
+    pub min_lineup_size: u8,
+    /// Whether a substituted player may return to the game
+    pub allow_reentry: bool,
+    /// Whether a pitcher must face three batters, or finish the half
+    /// inning, before being replaced
+    pub three_batter_minimum: bool,
+    /// Pitches after which a pitcher may not throw again (None for no
+    /// limit)
+    pub pitch_limit: Option<u16>,
+    /// Whether a pitcher who reaches `pitch_limit` during a plate
+    /// appearance may finish it
+    pub pitch_limit_finish_batter: bool,
+    /// Mound visits each team may make per game (None for no limit)
+    pub mound_visit_limit: Option<u8>,
+    /// Whether a visit past `mound_visit_limit` is allowed but forces a
+    /// pitching change, instead of being rejected
+    pub mound_visit_over_limit_removes_pitcher: bool,
+    /// Whether a starting pitcher must complete five innings (four in a
+    /// five-inning game) to be credited with the win
+    pub starter_win_requires_five_innings: bool,
+    /// Which runners may be replaced by a courtesy runner
+    pub courtesy_runner: CourtesyRunnerRule,
+}
+
+impl Serialize for GameRules {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GameRules::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameRules {
+    /// Deserialize rules, starting from the named preset when a `preset` key
+    /// is given.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        use serde_json::Value;
+
+        let mut value = Value::deserialize(deserializer)?;
+        if let Some(name) = value.get("preset").and_then(Value::as_str) {
+            let preset = GameRules::preset(name).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "unknown rules preset \"{}\", expected one of {}",
+                    name,
+                    crate::presets::PRESET_NAMES.join(", ")
+                ))
+            })?;
+            let mut expanded = serde_json::to_value(preset).map_err(D::Error::custom)?;
+            if let (Value::Object(expanded), Value::Object(given)) = (&mut expanded, value) {
+                expanded.extend(given);
+            }
+            value = expanded;
+        }
+        GameRules::deserialize(value).map_err(D::Error::custom)
+    }
+}
+
+/// Ends a game early once one team leads by a set margin.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MercyRule {
+    /// Lead (in runs) that ends the game
+    pub run_differential: u32,
+    /// First inning in which the rule applies
+    pub after_inning: u8,
+}
+
+/// Whose runner may be replaced by a courtesy runner, who runs for them
+/// without substituting them out of the game.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CourtesyRunnerRule {
+    /// No courtesy runners, as in MLB
+    #[default]
+    None,
+    /// Only for the catcher
+    CatcherOnly,
+    /// For the catcher or the pitcher
+    CatcherAndPitcher,
+}
+
+impl CourtesyRunnerRule {
+    /// Whether the player at `position` may get a courtesy runner.
+    pub fn allows(self, position: Position) -> bool {
+        match self {
+            CourtesyRunnerRule::None => false,
+            CourtesyRunnerRule::CatcherOnly => position == Position::Catcher,
+            CourtesyRunnerRule::CatcherAndPitcher => {
+                matches!(position, Position::Catcher | Position::Pitcher)
+            }
+        }
+    }
+}
+
+/// Automatic runner placement at the start of each extra half inning.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ExtraInningsTiebreaker {
+    /// The batting team's previous batter starts the half inning on second
+    RunnerOnSecond,
+}
+
+/// Outcome of a single pitch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PitchResult {
+    /// Pitch outside the strike zone, not swung at
+    Ball,
+    /// Pitch in the strike zone, not swung at
+    StrikeCalled,
+    /// Swing and miss
+    StrikeSwinging,
+    /// Foul ball (a strike unless the batter already has two)
+    Foul,
+    /// Foul tip caught by the catcher (always a strike)
+    FoulTip,
+    /// Batter hit by the pitch and awarded first base
+    HitByPitch,
+    /// Swinging strike the catcher fails to catch. On strike three the
+    /// batter reaches first safely when the dropped third strike rule
+    /// applies, and is out otherwise (report a batter thrown out at first
+    /// as `StrikeSwinging`)
+    StrikeSwingingDropped,
+    /// Bunt attempt fouled off (a strikeout with two strikes)
+    FoulBunt,
+}
+
+impl PitchResult {
+    /// Every pitch result, in declaration order.
+    pub const ALL: [PitchResult; 8] = [
+        PitchResult::Ball,
+        PitchResult::StrikeCalled,
+        PitchResult::StrikeSwinging,
+        PitchResult::Foul,
+        PitchResult::FoulTip,
+        PitchResult::HitByPitch,
+        PitchResult::StrikeSwingingDropped,
+        PitchResult::FoulBunt,
+    ];
+
+    /// Canonical string form, matching the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PitchResult::Ball => "ball",
+            PitchResult::StrikeCalled => "strike_called",
+            PitchResult::StrikeSwinging => "strike_swinging",
+            PitchResult::Foul => "foul",
+            PitchResult::FoulTip => "foul_tip",
+            PitchResult::HitByPitch => "hit_by_pitch",
+            PitchResult::StrikeSwingingDropped => "strike_swinging_dropped",
+            PitchResult::FoulBunt => "foul_bunt",
+        }
+    }
+
+    /// Pitch for a scorekeeping shorthand letter: `B` ball, `C` called
+    /// strike, `S` swinging strike, `F` foul, `T` foul tip, `H` hit by pitch.
+    pub fn from_code(code: char) -> Option<Self> {
+        match code {
+            'B' => Some(PitchResult::Ball),
+            'C' => Some(PitchResult::StrikeCalled),
+            'S' => Some(PitchResult::StrikeSwinging),
+            'F' => Some(PitchResult::Foul),
+            'T' => Some(PitchResult::FoulTip),
+            'H' => Some(PitchResult::HitByPitch),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PitchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PitchResult {
+    type Err = BaselomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PitchResult::ALL
+            .into_iter()
+            .find(|pitch| pitch.as_str() == s)
+            .ok_or_else(|| {
+                let allowed: Vec<&str> = PitchResult::ALL.iter().map(|p| p.as_str()).collect();
+                BaselomError::InvalidPitchResult(format!(
+                    "Invalid pitch result '{}', expected one of: {}",
+                    s,
+                    allowed.join(", ")
+                ))
+            })
+    }
+}
+
+/// A base a runner can occupy, ordered from first to third.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Base {
+    First,
+    Second,
+    Third,
+}
+
+impl Base {
+    /// Every base, in the order a runner reaches them.
+    pub const ALL: [Base; 3] = [Base::First, Base::Second, Base::Third];
+
+    /// The base a runner advancing one base reaches, or `None` from third
+    /// (the runner scores).
+    pub fn next(self) -> Option<Base> {
+        match self {
+            Base::First => Some(Base::Second),
+            Base::Second => Some(Base::Third),
+            Base::Third => None,
+        }
+    }
+
+    /// The base `count` bases ahead of this one, or `None` if the runner
+    /// would reach home.
+    pub fn advanced_by(self, count: u8) -> Option<Base> {
+        Base::from_number(self.number().saturating_add(count))
+    }
+
+    /// Base number (1 for first through 3 for third).
+    pub fn number(self) -> u8 {
+        match self {
+            Base::First => 1,
+            Base::Second => 2,
+            Base::Third => 3,
+        }
+    }
+
+    /// Position in per-base arrays, first to third.
+    pub(crate) fn index(self) -> usize {
+        usize::from(self.number() - 1)
+    }
+
+    /// The base with a given number (1-3).
+    pub fn from_number(number: u8) -> Option<Base> {
+        match number {
+            1 => Some(Base::First),
+            2 => Some(Base::Second),
+            3 => Some(Base::Third),
+            _ => None,
+        }
+    }
+
+    /// Name of the base as used in serialization.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Base::First => "first",
+            Base::Second => "second",
+            Base::Third => "third",
+        }
+    }
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<u8> for Base {
+    type Error = BaselomError;
+
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        Base::from_number(number).ok_or_else(|| {
+            BaselomError::ValidationError(format!("Invalid base number {}, expected 1-3", number))
+        })
+    }
+}
+
+/// Runners on first, second and third.
+///
+/// Serializes as a three-element array (`[first, second, third]`), the same
+/// encoding as the tuple it replaces.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "Bases"))]
+#[serde(transparent)]
+pub struct Bases<P = String> {
+    runners: [Option<P>; 3],
+}
+
+impl<P> Default for Bases<P> {
+    fn default() -> Self {
+        Bases {
+            runners: [None, None, None],
+        }
+    }
+}
+
+impl Bases {
+    /// Empty bases.
+    pub fn new() -> Self {
+        Bases::default()
+    }
+}
+
+impl<P: PlayerKey> Bases<P> {
+    /// ID of the runner on a base, if any.
+    pub fn runner_at(&self, base: Base) -> Option<&P::Key> {
+        self.runners[Self::index(base)].as_ref().map(Borrow::borrow)
+    }
+
+    /// Put a runner on a base, or clear it with `None`.
+    pub fn set(&mut self, base: Base, runner: Option<P>) {
+        self.runners[Self::index(base)] = runner;
+    }
+
+    /// Remove and return the runner on a base.
+    pub fn take(&mut self, base: Base) -> Option<P> {
+        self.runners[Self::index(base)].take()
+    }
+
+    /// Whether every base is occupied.
+    pub fn is_loaded(&self) -> bool {
+        self.runners.iter().all(Option::is_some)
+    }
+
+    /// Whether no base is occupied.
+    pub fn is_empty(&self) -> bool {
+        self.runners.iter().all(Option::is_none)
+    }
+
+    /// Occupied bases as bits: 1 for first, 2 for second, 4 for third.
+    pub fn occupied_mask(&self) -> u8 {
+        self.iter()
+            .fold(0, |mask, (base, _)| mask | 1 << (base.number() - 1))
+    }
+
+    /// Number of runners on base.
+    pub fn count_runners(&self) -> usize {
+        self.runners
+            .iter()
+            .filter(|runner| runner.is_some())
+            .count()
+    }
+
+    /// Whether a force play is possible at a base when the batter becomes a
+    /// runner: first always, later bases only if every base behind them is
+    /// occupied.
+    pub fn force_at(&self, base: Base) -> bool {
+        self.runners[..Self::index(base)]
+            .iter()
+            .all(Option::is_some)
+    }
+
+    /// Occupied bases and their runners, from first to third.
+    pub fn iter(&self) -> impl Iterator<Item = (Base, &P::Key)> {
+        Base::ALL
+            .into_iter()
+            .zip(&self.runners)
+            .filter_map(|(base, runner)| runner.as_ref().map(|runner| (base, runner.borrow())))
+    }
+
+    /// The runners as an array indexed from first base.
+    pub fn into_array(self) -> [Option<P>; 3] {
+        self.runners
+    }
+
+    fn index(base: Base) -> usize {
+        base.index()
+    }
+}
+
+impl<P> From<[Option<P>; 3]> for Bases<P> {
+    fn from(runners: [Option<P>; 3]) -> Self {
+        Bases { runners }
+    }
+}
+
+impl<P> From<(Option<P>, Option<P>, Option<P>)> for Bases<P> {
+    fn from((first, second, third): (Option<P>, Option<P>, Option<P>)) -> Self {
+        Bases {
+            runners: [first, second, third],
+        }
+    }
+}
+
+/// One of the 24 base-out states: which bases are occupied and how many
+/// outs there are (0-2), without runner identities.
+///
+/// Packs into a `u8` (`outs * 8 + bases_occupied`), its serialized form and
+/// its index in [`BaseOutState::ALL`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "u8", into = "u8")]
+pub struct BaseOutState {
+    bases_occupied: u8,
+    outs: u8,
+}
+
+impl BaseOutState {
+    /// Every base-out state, in packed order.
+    pub const ALL: [BaseOutState; 24] = {
+        let mut all = [BaseOutState {
+            bases_occupied: 0,
+            outs: 0,
+        }; 24];
+        let mut index = 0;
+        while index < all.len() {
+            all[index] = BaseOutState {
+                bases_occupied: (index % 8) as u8,
+                outs: (index / 8) as u8,
+            };
+            index += 1;
+        }
+        all
+    };
+
+    /// The state with occupied bases as bits (1 for first, 2 for second, 4
+    /// for third) and `outs` outs, or `None` if either is out of range.
+    pub fn new(bases_occupied: u8, outs: u8) -> Option<Self> {
+        (bases_occupied < 8 && outs < 3).then_some(BaseOutState {
+            bases_occupied,
+            outs,
+        })
+    }
+
+    /// Occupied bases as bits: 1 for first, 2 for second, 4 for third.
+    pub fn bases_occupied(self) -> u8 {
+        self.bases_occupied
+    }
+
+    pub fn outs(self) -> u8 {
+        self.outs
+    }
+
+    /// Whether `base` has a runner.
+    pub fn is_occupied(self, base: Base) -> bool {
+        self.bases_occupied & base_bit(base) != 0
+    }
+
+    /// Whether a runner on `base` would be forced by the batter: first
+    /// always, later bases only if every base behind them is occupied.
+    pub fn is_force_at(self, base: Base) -> bool {
+        let behind = base_bit(base) - 1;
+        self.bases_occupied & behind == behind
+    }
+
+    /// Number of runners on base.
+    pub fn runners(self) -> u8 {
+        self.bases_occupied.count_ones() as u8
+    }
+
+    /// The packed form, `outs * 8 + bases_occupied` (0-23).
+    pub fn to_u8(self) -> u8 {
+        self.outs * 8 + self.bases_occupied
+    }
+
+    /// The state for a packed value, or `None` past 23.
+    pub fn from_u8(packed: u8) -> Option<Self> {
+        BaseOutState::ALL.get(usize::from(packed)).copied()
+    }
+
+    /// An in-progress state at the top of the 1st with this situation,
+    /// using `runner_1`, `runner_2` and `runner_3` as runner IDs.
+    pub fn to_game_state(self) -> GameState {
+        let mut bases = Bases::new();
+        for base in Base::ALL.into_iter().filter(|&base| self.is_occupied(base)) {
+            bases.set(base, Some(format!("runner_{}", base.number())));
+        }
+        GameState {
+            outs: self.outs,
+            bases,
+            status: GameStatus::InProgress,
+            line_score: Vec::new(),
+            ..GameState::new()
+        }
+    }
+}
+
+fn base_bit(base: Base) -> u8 {
+    1 << (base.number() - 1)
+}
+
+impl From<BaseOutState> for u8 {
+    fn from(state: BaseOutState) -> u8 {
+        state.to_u8()
+    }
+}
+
+impl TryFrom<u8> for BaseOutState {
+    type Error = BaselomError;
+
+    fn try_from(packed: u8) -> Result<Self, Self::Error> {
+        BaseOutState::from_u8(packed).ok_or_else(|| {
+            BaselomError::ValidationError(format!(
+                "Invalid base-out state {}, expected 0-23",
+                packed
+            ))
+        })
+    }
+}
+
+/// The base-out state of a game, dropping runner identities.
+impl TryFrom<&GameState> for BaseOutState {
+    type Error = BaselomError;
+
+    fn try_from(state: &GameState) -> Result<Self, Self::Error> {
+        BaseOutState::new(state.bases.occupied_mask(), state.outs).ok_or_else(|| {
+            BaselomError::ValidationError(format!("Invalid outs {}, expected 0-2", state.outs))
+        })
+    }
+}
+
+/// A plate-appearance result for [`crate::engine::base_out_transition`],
+/// with runners moving as the engine moves them by default.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BaseOutEvent {
+    /// Batter to first; only forced runners advance
+    Walk,
+    /// Batter to first; runners advance one base
+    Single,
+    /// Batter to second; runners advance two bases
+    Double,
+    /// Batter to third; all runners score
+    Triple,
+    /// Batter and all runners score
+    HomeRun,
+    /// Batter out; runners hold
+    Out,
+}
+
+impl BaseOutEvent {
+    /// Every event, in declaration order.
+    pub const ALL: [BaseOutEvent; 6] = [
+        BaseOutEvent::Walk,
+        BaseOutEvent::Single,
+        BaseOutEvent::Double,
+        BaseOutEvent::Triple,
+        BaseOutEvent::HomeRun,
+        BaseOutEvent::Out,
+    ];
+
+    /// The base-out event an engine event amounts to, if it moves the
+    /// batter and runners exactly as this event does. Events with explicit
+    /// advancement, or that can move runners another way (errors, double
+    /// plays, sacrifices and the like), have none.
+    pub fn from_event(event: &GameEvent) -> Option<BaseOutEvent> {
+        match event {
+            GameEvent::IntentionalWalk | GameEvent::CatchersInterference => {
+                Some(BaseOutEvent::Walk)
+            }
+            GameEvent::Single { advancement } if advancement.is_empty() => {
+                Some(BaseOutEvent::Single)
+            }
+            GameEvent::Double { advancement } if advancement.is_empty() => {
+                Some(BaseOutEvent::Double)
+            }
+            GameEvent::GroundRuleDouble => Some(BaseOutEvent::Double),
+            GameEvent::Triple { advancement } if advancement.is_empty() => {
+                Some(BaseOutEvent::Triple)
+            }
+            GameEvent::HomeRun => Some(BaseOutEvent::HomeRun),
+            GameEvent::GroundOut { advancement }
+            | GameEvent::FlyOut { advancement }
+            | GameEvent::LineOut { advancement }
+            | GameEvent::InfieldFly { advancement }
+                if advancement.is_empty() =>
+            {
+                Some(BaseOutEvent::Out)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result of a [`BaseOutEvent`] in a [`BaseOutState`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BaseOutTransition {
+    /// State after the event, or `None` if it made the third out
+    pub next: Option<BaseOutState>,
+    /// Runs scored on the event
+    pub runs_scored: u8,
+}
+
+/// A play that ends or interrupts a plate appearance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GameEvent {
+    /// Batter reaches first; runners advance one base unless `advancement`
+    /// says otherwise
+    Single {
+        #[serde(default)]
+        advancement: Vec<RunnerAdvance>,
+    },
+    /// Batter reaches second; runners advance two bases unless
+    /// `advancement` says otherwise
+    Double {
+        #[serde(default)]
+        advancement: Vec<RunnerAdvance>,
+    },
+    /// Batter reaches third; all runners score unless `advancement` says
+    /// otherwise
+    Triple {
+        #[serde(default)]
+        advancement: Vec<RunnerAdvance>,
+    },
+    /// Batter and all runners score (over the fence or inside the park)
+    HomeRun,
+    /// Batter awarded second and runners two bases when a fair ball bounces
+    /// out of play or lodges in the field; counts as a hit
+    GroundRuleDouble,
+    /// Batter out on a ground ball; runners hold unless `advancement` says
+    /// otherwise
+    GroundOut {
+        #[serde(default)]
+        advancement: Vec<RunnerAdvance>,
+    },
+    /// Batter out on a fly ball; runners hold unless `advancement` says
+    /// otherwise
+    FlyOut {
+        #[serde(default)]
+        advancement: Vec<RunnerAdvance>,
+    },
+    /// Batter out on a line drive; runners hold unless `advancement` says
+    /// otherwise
+    LineOut {
+        #[serde(default)]
+        advancement: Vec<RunnerAdvance>,
+    },
+    /// Batter out under the infield fly rule (fewer than two outs, runners
+    /// on first and second); runners hold unless they advance at their own
+    /// risk per `advancement`
+    InfieldFly {
+        #[serde(default)]
+        advancement: Vec<RunnerAdvance>,
+    },
+    /// Batter awarded first base without pitches, regardless of the count
+    IntentionalWalk,
+    /// Runners advance one base on a pitch the catcher could not handle
+    WildPitch,
+    /// Runners advance one base on a pitch the catcher should have handled
+    PassedBall,
+    /// Runners advance one base on an illegal pitcher motion
+    Balk,
+    /// The runner at `base` is picked off for an out
+    Pickoff { base: Base },
+    /// A pickoff throw goes astray and the runner at `base` advances one base
+    PickoffError { base: Base },
+    /// Two or three outs on one play (a triple play when three are recorded).
+    ///
+    /// The runners on `runners_out` are retired, plus the batter when
+    /// `batter_out` is set. Surviving runners advance only if forced.
+    DoublePlay {
+        runners_out: Vec<Base>,
+        batter_out: bool,
+    },
+    /// Batter out on a fly ball while the runner from third tags up and
+    /// scores; runners on `tagging_up` also advance one base
+    SacFly {
+        #[serde(default)]
+        tagging_up: Vec<Base>,
+    },
+    /// Batter bunts and is out at first while the runners on `advancing`
+    /// move up one base (a squeeze when the runner from third scores)
+    SacBunt {
+        #[serde(default)]
+        advancing: Vec<Base>,
+    },
+    /// Batter reaches first while the runner who started on `runner_out_at`
+    /// is put out; runners forced by the batter advance
+    FieldersChoice { runner_out_at: Base },
+    /// Batter awarded `batter_to` and every runner `runners_advance` bases
+    /// (obstruction, overthrows out of play); not a hit
+    AwardBases {
+        batter_to: Base,
+        runners_advance: u8,
+    },
+    /// Batter reaches `batter_to` on a fielding error and every runner
+    /// advances `runners_advance` bases; charges an error, not a hit
+    ReachedOnError {
+        batter_to: Base,
+        runners_advance: u8,
+    },
+    /// Batter awarded first after the catcher interferes with the swing;
+    /// runners advance only if forced and the catcher is charged an error
+    CatchersInterference,
+    /// The runner at `runner` is obstructed and awarded `awarded`
+    Obstruction { runner: Base, awarded: Advance },
+}
+
+/// Where a runner ends up on a batted ball.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Advance {
+    /// Safe at a base
+    Base(Base),
+    /// Scores
+    Home,
+    /// Put out
+    Out,
+}
+
+/// A scorer's call for one runner on a batted ball, overriding the event's
+/// default advancement.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RunnerAdvance {
+    /// Base the runner started on
+    pub from: Base,
+    /// Where the runner ended up
+    pub to: Advance,
+    /// Whether a runner put out was forced, which decides if runs scored
+    /// on a third-out play count
+    #[serde(default)]
+    pub out_was_force: bool,
+    /// The scorer's ruling that a runner scoring here was not batted in,
+    /// such as one who would have been out but for a misplay
+    #[serde(default)]
+    pub force_no_rbi: bool,
+}
+
+/// One step of a scripted or recorded game.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(rename = "Play"))]
+#[serde(rename_all = "snake_case")]
+pub enum Play<P = String> {
+    /// A new batter steps in (the engine clears the batter after each plate
+    /// appearance)
+    Batter(P),
+    /// A pitch
+    Pitch(PitchResult),
+    /// A plate-appearance or baserunning event
+    Event(GameEvent),
+    /// A mound visit by the fielding team
+    MoundVisit(Team),
+    /// A new pitcher for the fielding team (see
+    /// [`crate::engine::substitute_pitcher`])
+    PitchingChange {
+        pitcher: P,
+        /// Whether the pitcher replaced is injured, which waives the
+        /// three-batter minimum
+        #[serde(default)]
+        injured: bool,
+    },
+    /// A new pitcher and fielder for the fielding team, swapping batting
+    /// slots (see [`crate::engine::double_switch`])
+    DoubleSwitch {
+        pitcher: P,
+        /// Batting slot the new pitcher takes over
+        lineup_slot: usize,
+        fielder: P,
+    },
+}
+
+impl GameEvent {
+    /// Whether the event completes the batter's plate appearance.
+    ///
+    /// Events that do not (wild pitches, passed balls, balks, pickoffs,
+    /// obstruction) leave the count and the batter unchanged.
+    pub fn ends_plate_appearance(&self) -> bool {
+        !matches!(
+            self,
+            GameEvent::WildPitch
+                | GameEvent::PassedBall
+                | GameEvent::Balk
+                | GameEvent::Pickoff { .. }
+                | GameEvent::PickoffError { .. }
+                | GameEvent::Obstruction { .. }
+        )
+    }
+
+    /// Whether runs scoring on the event are batted in: on every event that
+    /// ends the plate appearance except errors and double plays, including
+    /// a batted ball whose `advancement` makes it a force double play.
+    pub fn bats_in_runs(&self) -> bool {
+        self.ends_plate_appearance()
+            && !self.is_force_double_play()
+            && !matches!(
+                self,
+                GameEvent::ReachedOnError { .. } | GameEvent::DoublePlay { .. }
+            )
+    }
+
+    /// Whether a batted ball records two or more outs, one of them a runner
+    /// forced out (`RunnerAdvance::out_was_force`).
+    fn is_force_double_play(&self) -> bool {
+        let (advancement, batter_out) = match self {
+            GameEvent::Single { advancement }
+            | GameEvent::Double { advancement }
+            | GameEvent::Triple { advancement } => (advancement, false),
+            GameEvent::GroundOut { advancement }
+            | GameEvent::FlyOut { advancement }
+            | GameEvent::LineOut { advancement }
+            | GameEvent::InfieldFly { advancement } => (advancement, true),
+            _ => return false,
+        };
+        let runners_out = advancement
+            .iter()
+            .filter(|advance| advance.to == Advance::Out)
+            .count();
+        runners_out + usize::from(batter_out) >= 2
+            && advancement.iter().any(|advance| advance.out_was_force)
+    }
+
+    /// Name of the event, matching its serde tag (`"single"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameEvent::Single { .. } => "single",
+            GameEvent::Double { .. } => "double",
+            GameEvent::Triple { .. } => "triple",
+            GameEvent::HomeRun => "home_run",
+            GameEvent::GroundRuleDouble => "ground_rule_double",
+            GameEvent::GroundOut { .. } => "ground_out",
+            GameEvent::FlyOut { .. } => "fly_out",
+            GameEvent::LineOut { .. } => "line_out",
+            GameEvent::InfieldFly { .. } => "infield_fly",
+            GameEvent::IntentionalWalk => "intentional_walk",
+            GameEvent::WildPitch => "wild_pitch",
+            GameEvent::PassedBall => "passed_ball",
+            GameEvent::Balk => "balk",
+            GameEvent::Pickoff { .. } => "pickoff",
+            GameEvent::PickoffError { .. } => "pickoff_error",
+            GameEvent::DoublePlay { .. } => "double_play",
+            GameEvent::SacFly { .. } => "sac_fly",
+            GameEvent::SacBunt { .. } => "sac_bunt",
+            GameEvent::FieldersChoice { .. } => "fielders_choice",
+            GameEvent::AwardBases { .. } => "award_bases",
+            GameEvent::ReachedOnError { .. } => "reached_on_error",
+            GameEvent::CatchersInterference => "catchers_interference",
+            GameEvent::Obstruction { .. } => "obstruction",
+        }
+    }
 }
 
-/// Score tracking for both teams.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
-pub struct Score {
-    pub home: u32,
-    pub away: u32,
+/// A kind of [`GameEvent`], as listed by [`crate::engine::legal_events`].
+///
+/// Events that name a runner carry the runner's base; the rest of an
+/// event's details take their simplest form in [`GameEventKind::event`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GameEventKind {
+    Single,
+    Double,
+    Triple,
+    HomeRun,
+    GroundRuleDouble,
+    GroundOut,
+    FlyOut,
+    LineOut,
+    InfieldFly,
+    IntentionalWalk,
+    WildPitch,
+    PassedBall,
+    Balk,
+    /// Picking off the runner on the base
+    Pickoff(Base),
+    /// An errant pickoff throw to the base
+    PickoffError(Base),
+    /// A double play retiring the runner on the base and the batter
+    DoublePlay(Base),
+    SacFly,
+    /// A sacrifice bunt advancing the runner on the base
+    SacBunt(Base),
+    /// A fielder's choice putting out the runner on the base
+    FieldersChoice(Base),
+    /// Batter awarded first, runners one base
+    AwardBases,
+    /// Batter reaching first on an error, runners one base
+    ReachedOnError,
+    CatchersInterference,
+    /// Obstruction of the runner on the base, awarded the next base
+    Obstruction(Base),
 }
 
-/// Configurable game rules.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct GameRules {
-    /// Whether designated hitter is used
-    pub designated_hitter: bool,
-    /// Maximum number of innings (None for unlimited)
-    pub max_innings: Option<u8>,
-    /// Extra innings tiebreaker rule
-    pub extra_innings_tiebreaker: Option<String>,
+impl GameEventKind {
+    /// Every event kind, in declaration order with bases from first.
+    pub const ALL: [GameEventKind; 35] = [
+        GameEventKind::Single,
+        GameEventKind::Double,
+        GameEventKind::Triple,
+        GameEventKind::HomeRun,
+        GameEventKind::GroundRuleDouble,
+        GameEventKind::GroundOut,
+        GameEventKind::FlyOut,
+        GameEventKind::LineOut,
+        GameEventKind::InfieldFly,
+        GameEventKind::IntentionalWalk,
+        GameEventKind::WildPitch,
+        GameEventKind::PassedBall,
+        GameEventKind::Balk,
+        GameEventKind::Pickoff(Base::First),
+        GameEventKind::Pickoff(Base::Second),
+        GameEventKind::Pickoff(Base::Third),
+        GameEventKind::PickoffError(Base::First),
+        GameEventKind::PickoffError(Base::Second),
+        GameEventKind::PickoffError(Base::Third),
+        GameEventKind::DoublePlay(Base::First),
+        GameEventKind::DoublePlay(Base::Second),
+        GameEventKind::DoublePlay(Base::Third),
+        GameEventKind::SacFly,
+        GameEventKind::SacBunt(Base::First),
+        GameEventKind::SacBunt(Base::Second),
+        GameEventKind::SacBunt(Base::Third),
+        GameEventKind::FieldersChoice(Base::First),
+        GameEventKind::FieldersChoice(Base::Second),
+        GameEventKind::FieldersChoice(Base::Third),
+        GameEventKind::AwardBases,
+        GameEventKind::ReachedOnError,
+        GameEventKind::CatchersInterference,
+        GameEventKind::Obstruction(Base::First),
+        GameEventKind::Obstruction(Base::Second),
+        GameEventKind::Obstruction(Base::Third),
+    ];
+
+    /// The event of this kind, with runners moving as the engine moves them
+    /// by default.
+    pub fn event(self) -> GameEvent {
+        match self {
+            GameEventKind::Single => GameEvent::Single {
+                advancement: Vec::new(),
+            },
+            GameEventKind::Double => GameEvent::Double {
+                advancement: Vec::new(),
+            },
+            GameEventKind::Triple => GameEvent::Triple {
+                advancement: Vec::new(),
+            },
+            GameEventKind::HomeRun => GameEvent::HomeRun,
+            GameEventKind::GroundRuleDouble => GameEvent::GroundRuleDouble,
+            GameEventKind::GroundOut => GameEvent::GroundOut {
+                advancement: Vec::new(),
+            },
+            GameEventKind::FlyOut => GameEvent::FlyOut {
+                advancement: Vec::new(),
+            },
+            GameEventKind::LineOut => GameEvent::LineOut {
+                advancement: Vec::new(),
+            },
+            GameEventKind::InfieldFly => GameEvent::InfieldFly {
+                advancement: Vec::new(),
+            },
+            GameEventKind::IntentionalWalk => GameEvent::IntentionalWalk,
+            GameEventKind::WildPitch => GameEvent::WildPitch,
+            GameEventKind::PassedBall => GameEvent::PassedBall,
+            GameEventKind::Balk => GameEvent::Balk,
+            GameEventKind::Pickoff(base) => GameEvent::Pickoff { base },
+            GameEventKind::PickoffError(base) => GameEvent::PickoffError { base },
+            GameEventKind::DoublePlay(base) => GameEvent::DoublePlay {
+                runners_out: vec![base],
+                batter_out: true,
+            },
+            GameEventKind::SacFly => GameEvent::SacFly {
+                tagging_up: Vec::new(),
+            },
+            GameEventKind::SacBunt(base) => GameEvent::SacBunt {
+                advancing: vec![base],
+            },
+            GameEventKind::FieldersChoice(base) => GameEvent::FieldersChoice {
+                runner_out_at: base,
+            },
+            GameEventKind::AwardBases => GameEvent::AwardBases {
+                batter_to: Base::First,
+                runners_advance: 1,
+            },
+            GameEventKind::ReachedOnError => GameEvent::ReachedOnError {
+                batter_to: Base::First,
+                runners_advance: 1,
+            },
+            GameEventKind::CatchersInterference => GameEvent::CatchersInterference,
+            GameEventKind::Obstruction(base) => GameEvent::Obstruction {
+                runner: base,
+                awarded: base.next().map_or(Advance::Home, Advance::Base),
+            },
+        }
+    }
+}
+
+impl GameRules {
+    /// Load rules, rejecting any field this library does not know.
+    ///
+    /// Missing fields still take their defaults. Returns
+    /// `BaselomError::ValidationError` naming the first unknown field.
+    pub fn from_json_strict(json: &str) -> Result<GameRules, BaselomError> {
+        from_json_strict(json)
+    }
+
+    /// Load rules, ignoring unknown fields and defaulting missing ones.
+    pub fn from_json_lenient(json: &str) -> Result<GameRules, BaselomError> {
+        from_json_lenient(json)
+    }
+
+    /// Serialize with camelCase keys (`designatedHitter`) for JavaScript
+    /// clients.
+    pub fn to_json_camel(&self) -> Result<String, BaselomError> {
+        to_json_camel(self)
+    }
+
+    /// Load rules written by [`GameRules::to_json_camel`].
+    pub fn from_json_camel(json: &str) -> Result<GameRules, BaselomError> {
+        from_json_camel(json)
+    }
 }
 
 impl Default for GameRules {
     fn default() -> Self {
         Self {
+            preset: None,
             designated_hitter: false,
             max_innings: Some(9),
             extra_innings_tiebreaker: None,
+            allow_auto_intentional_walk: true,
+            balk_with_empty_bases_is_ball: false,
+            dropped_third_strike: true,
+            balls_for_walk: 4,
+            strikes_for_out: 3,
+            starting_count: Count::default(),
+            mercy_rule: None,
+            inning_run_limit: None,
+            last_inning_uncapped: false,
+            allow_tie_after_innings: None,
+            min_lineup_size: 9,
+            allow_reentry: false,
+            three_batter_minimum: false,
+            pitch_limit: None,
+            pitch_limit_finish_batter: true,
+            mound_visit_limit: None,
+            mound_visit_over_limit_removes_pitcher: false,
+            starter_win_requires_five_innings: true,
+            courtesy_runner: CourtesyRunnerRule::None,
         }
     }
 }
@@ -73,20 +2518,46 @@ mod tests {
         assert!(!rules.designated_hitter);
         assert_eq!(rules.max_innings, Some(9));
         assert!(rules.extra_innings_tiebreaker.is_none());
+        assert!(rules.allow_auto_intentional_walk);
+        assert_eq!(rules.balls_for_walk, 4);
+        assert_eq!(rules.strikes_for_out, 3);
     }
 
     #[test]
     fn test_game_rules_custom() {
         let rules = GameRules {
+            preset: None,
             designated_hitter: true,
             max_innings: Some(7),
-            extra_innings_tiebreaker: Some("runner_on_second".to_string()),
+            extra_innings_tiebreaker: Some(ExtraInningsTiebreaker::RunnerOnSecond),
+            allow_auto_intentional_walk: false,
+            balk_with_empty_bases_is_ball: true,
+            dropped_third_strike: false,
+            balls_for_walk: 3,
+            strikes_for_out: 2,
+            starting_count: Count::new(1, 1),
+            mercy_rule: Some(MercyRule {
+                run_differential: 10,
+                after_inning: 5,
+            }),
+            inning_run_limit: Some(5),
+            last_inning_uncapped: true,
+            allow_tie_after_innings: Some(12),
+            min_lineup_size: 10,
+            allow_reentry: true,
+            three_batter_minimum: true,
+            pitch_limit: Some(85),
+            pitch_limit_finish_batter: false,
+            mound_visit_limit: Some(5),
+            mound_visit_over_limit_removes_pitcher: true,
+            starter_win_requires_five_innings: false,
+            courtesy_runner: CourtesyRunnerRule::CatcherAndPitcher,
         };
         assert!(rules.designated_hitter);
         assert_eq!(rules.max_innings, Some(7));
         assert_eq!(
             rules.extra_innings_tiebreaker,
-            Some("runner_on_second".to_string())
+            Some(ExtraInningsTiebreaker::RunnerOnSecond)
         );
     }
 
@@ -94,18 +2565,260 @@ mod tests {
     fn test_game_state_creation() {
         let state = GameState {
             inning: 1,
-            top: true,
+            half: InningHalf::Top,
             outs: 0,
-            bases: (None, None, None),
+            count: Count::default(),
+            bases: Bases::new(),
             score: Score::default(),
+            home_team_id: None,
+            away_team_id: None,
             current_batter_id: None,
             current_pitcher_id: None,
+            status: GameStatus::InProgress,
+            last_batters: PerTeam::default(),
+            line_score: Vec::new(),
+            team_totals: PerTeam::default(),
+            lineups: PerTeam::default(),
+            defensive_lineups: PerTeam::default(),
+            removed_players: PerTeam::default(),
+            batters_faced: BTreeMap::new(),
+            half_inning_batters: 0,
+            half_inning_runs: 0,
+            dh_forfeited: PerTeam::default(),
+            pitch_counts: BTreeMap::new(),
+            plate_appearance_pitches: 0,
+            mound_visits: PerTeam::default(),
+            pitcher_removal_required: None,
+            responsible_pitchers: Bases::new(),
+            runs_allowed: BTreeMap::new(),
+            earned_runs_allowed: BTreeMap::new(),
+            unearned_runners: [false; 3],
+            outs_lost_to_errors: 0,
+            lead_changes: Vec::new(),
+            schema_version: CURRENT_SCHEMA_VERSION,
         };
         assert_eq!(state.inning, 1);
-        assert!(state.top);
+        assert_eq!(state.half, InningHalf::Top);
         assert_eq!(state.outs, 0);
     }
 
+    #[test]
+    fn test_new_game_state() {
+        let state = GameState::new();
+        assert_eq!(state.inning, 1);
+        assert_eq!(state.half, InningHalf::Top);
+        assert_eq!(
+            (state.outs, state.count.balls, state.count.strikes),
+            (0, 0, 0)
+        );
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+        assert_eq!(state.score, Score::default());
+        assert_eq!(state.status, GameStatus::NotStarted);
+        assert_eq!(GameState::default(), state);
+        assert!(crate::validators::validate_state(&state).is_ok());
+    }
+
+    #[test]
+    fn test_game_state_with_rules() {
+        let rules = GameRules {
+            max_innings: Some(1),
+            extra_innings_tiebreaker: Some(ExtraInningsTiebreaker::RunnerOnSecond),
+            ..GameRules::default()
+        };
+        let state = GameState::with_rules(&rules);
+        assert_eq!(state.bases, Bases::from((None, None, None)));
+        assert_eq!(state, GameState::new());
+        assert!(crate::validators::validate_state_with_rules(&state, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_base_serialization() {
+        for base in Base::ALL {
+            let json = serde_json::to_string(&base).unwrap();
+            assert_eq!(json, format!("\"{}\"", base));
+            let deserialized: Base = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized, base);
+        }
+        assert!(serde_json::from_str::<Base>("\"home\"").is_err());
+    }
+
+    #[test]
+    fn test_base_ordering_and_advancement() {
+        assert!(Base::First < Base::Second && Base::Second < Base::Third);
+        assert_eq!(Base::First.next(), Some(Base::Second));
+        assert_eq!(Base::Second.next(), Some(Base::Third));
+        assert_eq!(Base::Third.next(), None);
+        assert_eq!(Base::First.advanced_by(2), Some(Base::Third));
+        assert_eq!(Base::Second.advanced_by(2), None);
+        for base in Base::ALL {
+            assert_eq!(Base::try_from(base.number()).unwrap(), base);
+        }
+        assert!(matches!(
+            Base::try_from(4),
+            Err(BaselomError::ValidationError(_))
+        ));
+        assert_eq!(Base::Second.to_string(), "second");
+    }
+
+    #[test]
+    fn test_bases_helpers() {
+        let mut bases = Bases::new();
+        assert!(bases.is_empty());
+        assert!(bases.force_at(Base::First));
+        assert!(!bases.force_at(Base::Second));
+
+        bases.set(Base::First, Some("r1".to_string()));
+        bases.set(Base::Third, Some("r3".to_string()));
+        assert_eq!(bases.runner_at(Base::First), Some("r1"));
+        assert_eq!(bases.runner_at(Base::Second), None);
+        assert_eq!(bases.count_runners(), 2);
+        assert!(!bases.is_empty());
+        assert!(!bases.is_loaded());
+        assert!(bases.force_at(Base::Second));
+        assert!(!bases.force_at(Base::Third));
+        assert_eq!(
+            bases.iter().collect::<Vec<_>>(),
+            vec![(Base::First, "r1"), (Base::Third, "r3")]
+        );
+
+        bases.set(Base::Second, Some("r2".to_string()));
+        assert!(bases.is_loaded());
+        assert!(bases.force_at(Base::Third));
+        assert_eq!(bases.take(Base::Second), Some("r2".to_string()));
+        assert_eq!(bases.runner_at(Base::Second), None);
+    }
+
+    #[test]
+    fn test_bases_serialize_as_tuple() {
+        let bases = Bases::from((Some("r1".to_string()), None, Some("r3".to_string())));
+        let json = serde_json::to_string(&bases).unwrap();
+        assert_eq!(json, r#"["r1",null,"r3"]"#);
+
+        let tuple: (Option<String>, Option<String>, Option<String>) =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(Bases::from(tuple), bases);
+        let deserialized: Bases = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, bases);
+    }
+
+    #[test]
+    fn test_builder() {
+        let state = GameState::builder()
+            .inning(7)
+            .bottom()
+            .outs(2)
+            .runner_on(Base::Second, "r2")
+            .count(3, 2)
+            .score(4, 3)
+            .teams("bears", "owls")
+            .batter("b")
+            .pitcher("p")
+            .build()
+            .unwrap();
+        assert_eq!(state.inning, 7);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.outs, 2);
+        assert_eq!(
+            state.bases,
+            Bases::from((None, Some("r2".to_string()), None))
+        );
+        assert_eq!((state.count.balls, state.count.strikes), (3, 2));
+        assert_eq!(state.score, Score { home: 4, away: 3 });
+        assert_eq!(state.team_id(Team::Home), Some("bears"));
+        assert_eq!(state.team_id(Team::Away), Some("owls"));
+        assert_eq!(state.current_batter_id, Some("b".to_string()));
+        assert_eq!(state.current_pitcher_id, Some("p".to_string()));
+        assert_eq!(state.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_state() {
+        let result = GameState::builder().outs(4).build();
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_winner() {
+        let mut state = GameState {
+            inning: 9,
+            half: InningHalf::Bottom,
+            outs: 0,
+            count: Count::default(),
+            bases: Bases::new(),
+            score: Score { home: 2, away: 3 },
+            home_team_id: None,
+            away_team_id: None,
+            current_batter_id: None,
+            current_pitcher_id: None,
+            status: GameStatus::InProgress,
+            last_batters: PerTeam::default(),
+            line_score: Vec::new(),
+            team_totals: PerTeam::default(),
+            lineups: PerTeam::default(),
+            defensive_lineups: PerTeam::default(),
+            removed_players: PerTeam::default(),
+            batters_faced: BTreeMap::new(),
+            half_inning_batters: 0,
+            half_inning_runs: 0,
+            dh_forfeited: PerTeam::default(),
+            pitch_counts: BTreeMap::new(),
+            plate_appearance_pitches: 0,
+            mound_visits: PerTeam::default(),
+            pitcher_removal_required: None,
+            responsible_pitchers: Bases::new(),
+            runs_allowed: BTreeMap::new(),
+            earned_runs_allowed: BTreeMap::new(),
+            unearned_runners: [false; 3],
+            outs_lost_to_errors: 0,
+            lead_changes: Vec::new(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+        };
+        assert_eq!(state.winner(), None);
+
+        state.status = GameStatus::Final;
+        assert_eq!(state.winner(), Some(Team::Away));
+        state.score.home = 4;
+        assert_eq!(state.winner(), Some(Team::Home));
+        state.score.away = 4;
+        assert_eq!(state.winner(), None);
+    }
+
+    #[test]
+    fn test_count_helpers() {
+        let rules = GameRules::default();
+        let mut count = Count::new(3, 2);
+        assert_eq!(count.to_string(), "3-2");
+        assert!(count.is_full(&rules));
+        assert!(count.is_valid(&rules));
+        assert!(!Count::new(3, 1).is_full(&rules));
+        assert!(!Count::new(4, 0).is_valid(&rules));
+        assert!(!Count::new(0, 3).is_valid(&rules));
+
+        let short_count = GameRules {
+            balls_for_walk: 3,
+            strikes_for_out: 2,
+            ..GameRules::default()
+        };
+        assert!(Count::new(2, 1).is_full(&short_count));
+        assert!(!count.is_valid(&short_count));
+
+        count.reset();
+        assert_eq!(count, Count::default());
+        assert_eq!(count.to_string(), "0-0");
+    }
+
+    #[test]
+    fn test_count_serializes_flat() {
+        let state = GameState::builder().count(2, 1).build().unwrap();
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["balls"], 2);
+        assert_eq!(value["strikes"], 1);
+        assert!(value.get("count").is_none());
+
+        let deserialized: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.count, Count::new(2, 1));
+    }
+
     #[test]
     fn test_score_serialization() {
         let score = Score { home: 5, away: 3 };
@@ -114,6 +2827,305 @@ mod tests {
         assert_eq!(score, deserialized);
     }
 
+    #[test]
+    fn test_pitch_result_serde_round_trip() {
+        for pitch in PitchResult::ALL {
+            let json = serde_json::to_string(&pitch).unwrap();
+            assert_eq!(json, format!("\"{}\"", pitch));
+            let deserialized: PitchResult = serde_json::from_str(&json).unwrap();
+            assert_eq!(pitch, deserialized);
+        }
+    }
+
+    #[test]
+    fn test_pitch_result_from_str_round_trip() {
+        for pitch in PitchResult::ALL {
+            assert_eq!(pitch.to_string().parse::<PitchResult>().unwrap(), pitch);
+        }
+    }
+
+    #[test]
+    fn test_lineup_advance_wraps() {
+        let mut lineup = Lineup::new(["a", "b", "c"]);
+        assert_eq!(lineup.current(), Some("a"));
+        lineup.advance();
+        lineup.advance();
+        assert_eq!(lineup.current(), Some("c"));
+        lineup.advance();
+        assert_eq!(lineup.current(), Some("a"));
+        assert_eq!(lineup.slot_of("b"), Some(1));
+
+        let mut empty: Lineup = Lineup::default();
+        empty.advance();
+        assert_eq!(empty.current(), None);
+    }
+
+    #[test]
+    fn test_lineup_serialization() {
+        let json = r#"{"players":["a","b"],"index":1,"pitcher":"p"}"#;
+        let lineup: Lineup = serde_json::from_str(json).unwrap();
+        assert_eq!(lineup.current(), Some("b"));
+        assert_eq!(serde_json::to_string(&lineup).unwrap(), json);
+
+        let lineup: Lineup = serde_json::from_str(r#"{"players":["a","b"]}"#).unwrap();
+        assert_eq!(lineup.index, 0);
+        assert_eq!(lineup.pitcher, None);
+
+        let state: GameState = serde_json::from_str(
+            r#"{"inning":1,"half":"top","outs":0,"balls":0,"strikes":0,
+                "bases":[null,null,null],"score":{"home":0,"away":0},
+                "current_batter_id":null,"current_pitcher_id":null}"#,
+        )
+        .unwrap();
+        assert!(state.lineups.home.is_empty());
+    }
+
+    #[test]
+    fn test_builder_sends_up_lineup_batter() {
+        let home = Lineup::new((1..=9).map(|slot| format!("h{}", slot)));
+        let away = Lineup::new((1..=9).map(|slot| format!("a{}", slot)));
+        let state = GameState::builder()
+            .bottom()
+            .lineups(home, away)
+            .build()
+            .unwrap();
+        assert_eq!(state.current_batter_id.as_deref(), Some("h1"));
+    }
+
+    #[test]
+    fn test_pitch_result_from_code() {
+        assert_eq!(PitchResult::from_code('B'), Some(PitchResult::Ball));
+        assert_eq!(PitchResult::from_code('T'), Some(PitchResult::FoulTip));
+        assert_eq!(PitchResult::from_code('H'), Some(PitchResult::HitByPitch));
+        assert_eq!(PitchResult::from_code('b'), None);
+        assert_eq!(PitchResult::from_code('X'), None);
+    }
+
+    #[test]
+    fn test_pitch_result_from_str_invalid() {
+        let err = "strike_calld".parse::<PitchResult>().unwrap_err();
+        assert!(matches!(err, BaselomError::InvalidPitchResult(_)));
+        assert!(err.to_string().contains("strike_called"));
+        assert!(err.to_string().contains("foul_tip"));
+    }
+
+    #[test]
+    fn test_tiebreaker_serialization() {
+        let json = r#"{
+            "designated_hitter": false,
+            "max_innings": 9,
+            "extra_innings_tiebreaker": "runner_on_second"
+        }"#;
+        let rules: GameRules = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            rules.extra_innings_tiebreaker,
+            Some(ExtraInningsTiebreaker::RunnerOnSecond)
+        );
+
+        let invalid = json.replace("runner_on_second", "runner_on_third");
+        assert!(serde_json::from_str::<GameRules>(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_game_state_status_defaults_when_missing() {
+        let json = r#"{
+            "inning": 3,
+            "top": false,
+            "outs": 1,
+            "balls": 0,
+            "strikes": 0,
+            "bases": [null, null, null],
+            "score": {"home": 0, "away": 0},
+            "current_batter_id": null,
+            "current_pitcher_id": null
+        }"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_inning_half_serialization() {
+        assert_eq!(
+            serde_json::to_string(&InningHalf::Bottom).unwrap(),
+            "\"bottom\""
+        );
+        for (json, half) in [
+            ("\"top\"", InningHalf::Top),
+            ("\"bottom\"", InningHalf::Bottom),
+            ("true", InningHalf::Top),
+            ("false", InningHalf::Bottom),
+        ] {
+            assert_eq!(serde_json::from_str::<InningHalf>(json).unwrap(), half);
+        }
+        assert!(serde_json::from_str::<InningHalf>("\"middle\"").is_err());
+    }
+
+    #[test]
+    fn test_inning_half_teams() {
+        assert_eq!(InningHalf::Top.batting_team(), Team::Away);
+        assert_eq!(InningHalf::Top.fielding_team(), Team::Home);
+        assert_eq!(InningHalf::Bottom.batting_team(), Team::Home);
+        assert_eq!(InningHalf::Bottom.fielding_team(), Team::Away);
+        assert_eq!(InningHalf::Top.opposite(), InningHalf::Bottom);
+
+        let mut last_batters = PerTeam::default();
+        *last_batters.get_mut(Team::Home) = Some("h1".to_string());
+        assert_eq!(last_batters.get(Team::Home), &Some("h1".to_string()));
+        assert_eq!(last_batters.get(Team::Away), &None);
+    }
+
+    #[test]
+    fn test_team_ids_default_when_missing() {
+        let json = r#"{
+            "inning": 2,
+            "half": "bottom",
+            "outs": 0,
+            "balls": 0,
+            "strikes": 0,
+            "bases": [null, null, null],
+            "score": {"home": 1, "away": 4},
+            "current_batter_id": null,
+            "current_pitcher_id": null
+        }"#;
+        let mut state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.home_team_id, None);
+        assert_eq!(state.away_team_id, None);
+        assert_eq!(state.batting_team(), (Team::Home, None));
+        assert_eq!(state.fielding_team(), (Team::Away, None));
+        assert_eq!(state.score.get(state.batting_team().0), 1);
+
+        state.home_team_id = Some("bears".to_string());
+        state.away_team_id = Some("owls".to_string());
+        assert_eq!(state.batting_team(), (Team::Home, Some("bears")));
+        assert_eq!(state.fielding_team(), (Team::Away, Some("owls")));
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["home_team_id"], "bears");
+        let round_trip: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip, state);
+    }
+
+    #[test]
+    fn test_defensive_lineup_serialization() {
+        let defense = DefensiveLineup::new([
+            (Position::Shortstop, "ss"),
+            (Position::Pitcher, "p"),
+            (Position::DesignatedHitter, "dh"),
+        ]);
+        let json = serde_json::to_string(&defense).unwrap();
+        assert_eq!(json, r#"{"positions":{"P":"p","SS":"ss","DH":"dh"}}"#);
+        assert_eq!(
+            serde_json::from_str::<DefensiveLineup>(&json).unwrap(),
+            defense
+        );
+        assert_eq!(defense.player_at(Position::Shortstop), Some("ss"));
+        assert_eq!(defense.position_of("dh"), Some(Position::DesignatedHitter));
+        assert_eq!(defense.position_of("c"), None);
+        assert_eq!(Position::ThirdBase.to_string(), "3B");
+    }
+
+    #[test]
+    fn test_team_totals_default_when_missing() {
+        let json = r#"{
+            "inning": 1,
+            "half": "top",
+            "outs": 0,
+            "balls": 0,
+            "strikes": 0,
+            "bases": [null, null, null],
+            "score": {"home": 0, "away": 0},
+            "current_batter_id": null,
+            "current_pitcher_id": null
+        }"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.team_totals, PerTeam::default());
+
+        let partial: TeamTotals = serde_json::from_str(r#"{"hits": 3}"#).unwrap();
+        assert_eq!(
+            partial,
+            TeamTotals {
+                hits: 3,
+                ..TeamTotals::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_game_state_migrates_boolean_top() {
+        let json = r#"{
+            "inning": 3,
+            "top": false,
+            "outs": 1,
+            "balls": 0,
+            "strikes": 0,
+            "bases": [null, null, null],
+            "score": {"home": 0, "away": 0},
+            "current_batter_id": null,
+            "current_pitcher_id": null
+        }"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.half, InningHalf::Bottom);
+
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["half"], "bottom");
+        assert!(value.get("top").is_none());
+        let round_trip: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip, state);
+    }
+
+    #[test]
+    fn test_game_event_serialization() {
+        let json = serde_json::to_string(&GameEvent::HomeRun).unwrap();
+        assert_eq!(json, "\"home_run\"");
+        let deserialized: GameEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, GameEvent::HomeRun);
+
+        let event = GameEvent::Pickoff { base: Base::Second };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(json, r#"{"pickoff":{"base":"second"}}"#);
+        let deserialized: GameEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, event);
+    }
+
+    #[test]
+    fn test_game_event_name_matches_serde_tag() {
+        for kind in GameEventKind::ALL {
+            let event = kind.event();
+            let tag = match serde_json::to_value(&event).unwrap() {
+                serde_json::Value::String(tag) => tag,
+                serde_json::Value::Object(map) => map.keys().next().unwrap().clone(),
+                other => panic!("unexpected {:?}", other),
+            };
+            assert_eq!(event.as_str(), tag);
+        }
+    }
+
+    #[test]
+    fn test_game_status_serialization() {
+        for (status, expected) in [
+            (GameStatus::NotStarted, "\"not_started\""),
+            (GameStatus::InProgress, "\"in_progress\""),
+            (GameStatus::Final, "\"final\""),
+            (GameStatus::Suspended, "\"suspended\""),
+        ] {
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(json, expected);
+            let deserialized: GameStatus = serde_json::from_str(&json).unwrap();
+            assert_eq!(status, deserialized);
+        }
+    }
+
+    #[test]
+    fn test_game_rules_missing_fields_default() {
+        let json =
+            r#"{"designated_hitter": true, "max_innings": 7, "extra_innings_tiebreaker": null}"#;
+        let rules: GameRules = serde_json::from_str(json).unwrap();
+        assert!(rules.designated_hitter);
+        assert_eq!(rules.max_innings, Some(7));
+        assert!(rules.allow_auto_intentional_walk);
+        assert_eq!(rules.balls_for_walk, 4);
+        assert_eq!(rules.strikes_for_out, 3);
+    }
+
     #[test]
     fn test_game_rules_serialization() {
         let rules = GameRules::default();
@@ -121,4 +3133,341 @@ mod tests {
         let deserialized: GameRules = serde_json::from_str(&json).unwrap();
         assert_eq!(rules, deserialized);
     }
+
+    #[test]
+    fn test_new_state_has_current_schema_version() {
+        let state = GameState::new();
+        assert_eq!(state.schema_version, CURRENT_SCHEMA_VERSION);
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["schema_version"], CURRENT_SCHEMA_VERSION);
+
+        let mut unversioned = value;
+        unversioned
+            .as_object_mut()
+            .unwrap()
+            .remove("schema_version");
+        let state: GameState = serde_json::from_value(unversioned).unwrap();
+        assert_eq!(state.schema_version, 1);
+    }
+
+    #[test]
+    fn test_migrate_v1_fixture() {
+        let json = include_str!("../tests/fixtures/game_state_v1.json");
+        let state = GameState::from_json_migrating(json).unwrap();
+        assert_eq!(state.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(state.inning, 4);
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(state.count, Count::default());
+        assert_eq!(state.bases.runner_at(Base::First), Some("h4"));
+        assert_eq!(state.bases.runner_at(Base::Third), Some("h2"));
+        assert_eq!(state.score, Score { home: 2, away: 3 });
+        assert_eq!(state.status, GameStatus::InProgress);
+        assert!(state.lineups.home.players.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_v1_fixture_with_later_fields() {
+        let json = include_str!("../tests/fixtures/game_state_v1_with_status.json");
+        let state = GameState::from_json_migrating(json).unwrap();
+        assert_eq!(state.half, InningHalf::Top);
+        assert_eq!(state.count, Count::new(3, 1));
+        assert_eq!(state.pitches_thrown("h_rp"), 14);
+        assert_eq!(*state.mound_visits.get(Team::Home), 0);
+    }
+
+    #[test]
+    fn test_current_version_loads_unchanged() {
+        let state = GameState::builder()
+            .inning(6)
+            .bottom()
+            .runner_on(Base::Second, "r2")
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(GameState::from_json_migrating(&json).unwrap(), state);
+    }
+
+    #[test]
+    fn test_newer_schema_version_rejected() {
+        let mut value = serde_json::to_value(GameState::new()).unwrap();
+        value["schema_version"] = (CURRENT_SCHEMA_VERSION + 1).into();
+        let result = GameState::from_json_migrating(&value.to_string());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "Validation error: Schema version {} is newer than the supported version {}",
+                CURRENT_SCHEMA_VERSION + 1,
+                CURRENT_SCHEMA_VERSION
+            )
+        );
+
+        value["schema_version"] = 0.into();
+        let result = GameState::from_json_migrating(&value.to_string());
+        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+        assert!(matches!(
+            GameState::from_json_migrating("[1, 2]"),
+            Err(BaselomError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_count_and_score_default_when_missing() {
+        let json = r#"{"inning": 2, "half": "bottom", "outs": 0, "bases": [null, null, null]}"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.count, Count::default());
+        assert_eq!(state.score, Score::default());
+        assert_eq!(state.status, GameStatus::InProgress);
+
+        let score: Score = serde_json::from_str(r#"{"home": 4}"#).unwrap();
+        assert_eq!(score, Score { home: 4, away: 0 });
+    }
+
+    #[test]
+    fn test_lenient_ignores_unknown_fields() {
+        let state = GameState::builder().count(2, 1).build().unwrap();
+        let mut value = serde_json::to_value(&state).unwrap();
+        value["weather"] = "rain".into();
+        value["score"]["hits"] = 7.into();
+        let loaded = GameState::from_json_lenient(&value.to_string()).unwrap();
+        assert_eq!(loaded, state);
+
+        let rules = GameRules::from_json_lenient(r#"{"pitch_limit": 90, "pitch_clock": 15}"#);
+        assert_eq!(rules.unwrap().pitch_limit, Some(90));
+    }
+
+    #[test]
+    fn test_strict_rejects_unknown_fields() {
+        let state = GameState::builder().count(2, 1).build().unwrap();
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(GameState::from_json_strict(&json).unwrap(), state);
+
+        let mut value = serde_json::to_value(&state).unwrap();
+        value["score"]["hits"] = 7.into();
+        let result = GameState::from_json_strict(&value.to_string());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Unknown field 'score.hits'"
+        );
+
+        let result = GameRules::from_json_strict(r#"{"pitch_clock": 15}"#);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Unknown field 'pitch_clock'"
+        );
+    }
+
+    #[test]
+    fn test_strict_allows_missing_fields() {
+        let json = r#"{"inning": 5, "half": "top", "outs": 1, "bases": [null, "r2", null]}"#;
+        let state = GameState::from_json_strict(json).unwrap();
+        assert_eq!(state.bases.runner_at(Base::Second), Some("r2"));
+        assert_eq!(state.schema_version, 1);
+
+        let rules = GameRules::from_json_strict(r#"{"designated_hitter": true}"#).unwrap();
+        assert_eq!(
+            rules,
+            GameRules {
+                designated_hitter: true,
+                ..GameRules::default()
+            }
+        );
+        assert!(matches!(
+            GameRules::from_json_strict("{"),
+            Err(BaselomError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_json_key_case() {
+        let mut state = GameState::builder()
+            .batter("b_1")
+            .pitcher("p_1")
+            .count(1, 0)
+            .build()
+            .unwrap();
+        state.pitch_counts.insert("p_1".to_string(), 12);
+        state.defensive_lineups.home =
+            DefensiveLineup::new([(Position::Pitcher, "p_1"), (Position::FirstBase, "f_1")]);
+
+        let snake: serde_json::Value = serde_json::to_value(&state).unwrap();
+        assert_eq!(snake["current_batter_id"], "b_1");
+        assert_eq!(snake["pitch_counts"]["p_1"], 12);
+        assert!(snake.get("currentBatterId").is_none());
+
+        let json = state.to_json_camel().unwrap();
+        let camel: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(camel["currentBatterId"], "b_1");
+        assert_eq!(camel["currentPitcherId"], "p_1");
+        assert_eq!(camel["schemaVersion"], CURRENT_SCHEMA_VERSION);
+        assert_eq!(camel["status"], "in_progress");
+        assert_eq!(camel["pitchCounts"]["p_1"], 12);
+        assert!(camel.get("current_batter_id").is_none());
+        assert_eq!(camel["teamTotals"]["home"]["hits"], 0);
+        assert_eq!(camel["defensiveLineups"]["home"]["positions"]["1B"], "f_1");
+
+        assert_eq!(GameState::from_json_camel(&json).unwrap(), state);
+        assert_eq!(
+            GameState::from_json_camel(&snake.to_string()).unwrap(),
+            state
+        );
+    }
+
+    #[test]
+    fn test_rules_json_key_case() {
+        let rules = GameRules {
+            mercy_rule: Some(MercyRule {
+                run_differential: 10,
+                after_inning: 5,
+            }),
+            ..GameRules::default()
+        };
+        let json = rules.to_json_camel().unwrap();
+        let camel: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(camel["designatedHitter"], false);
+        assert_eq!(camel["ballsForWalk"], 4);
+        assert_eq!(camel["mercyRule"]["runDifferential"], 10);
+        assert!(camel.get("designated_hitter").is_none());
+        assert_eq!(GameRules::from_json_camel(&json).unwrap(), rules);
+
+        let snake = serde_json::to_value(&rules).unwrap();
+        assert_eq!(snake["mercy_rule"]["run_differential"], 10);
+        assert!(matches!(
+            GameRules::from_json_camel("{"),
+            Err(BaselomError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let mut state = GameState::builder()
+            .inning(8)
+            .bottom()
+            .outs(1)
+            .count(2, 2)
+            .runner_on(Base::First, "h7")
+            .runner_on(Base::Second, "h6")
+            .runner_on(Base::Third, "h5")
+            .score(3, 4)
+            .batter("h8")
+            .pitcher("a_rp")
+            .build()
+            .unwrap();
+        state.pitch_counts.insert("a_rp".to_string(), 21);
+
+        let bytes = state.to_bytes().unwrap();
+        assert_eq!(GameState::from_bytes(&bytes).unwrap(), state);
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(bytes.len() < json.len());
+    }
+
+    #[test]
+    fn test_bytes_rejects_truncated_input() {
+        let bytes = GameState::new().to_bytes().unwrap();
+        for len in [0, 1, bytes.len() / 2, bytes.len() - 1] {
+            assert!(matches!(
+                GameState::from_bytes(&bytes[..len]),
+                Err(BaselomError::ValidationError(_))
+            ));
+        }
+        assert!(matches!(
+            GameState::from_bytes(br#"{"inning": 1}"#),
+            Err(BaselomError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_state_hash_and_situation_key() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash(state: &GameState) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            state.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let situation = |runner: &str| {
+            GameState::builder()
+                .inning(3)
+                .outs(1)
+                .count(2, 1)
+                .runner_on(Base::First, runner)
+                .runner_on(Base::Third, "r3")
+                .build()
+                .unwrap()
+        };
+        let state = situation("r1");
+        assert_eq!(hash(&state), hash(&state.clone()));
+
+        let other_runner = situation("x1");
+        assert_ne!(state, other_runner);
+        assert_ne!(hash(&state), hash(&other_runner));
+        assert_eq!(state.situation_key(), other_runner.situation_key());
+        assert_eq!(
+            state.situation_key(),
+            SituationKey {
+                inning: 3,
+                half: InningHalf::Top,
+                outs: 1,
+                bases_occupied: 0b101,
+                balls: 2,
+                strikes: 1,
+            }
+        );
+
+        let mut cache = std::collections::HashSet::new();
+        cache.insert(state.clone());
+        assert!(cache.contains(&situation("r1")));
+        assert_eq!(Bases::new().occupied_mask(), 0);
+    }
+
+    #[test]
+    fn test_base_out_state_packing() {
+        for (index, state) in BaseOutState::ALL.into_iter().enumerate() {
+            assert_eq!(usize::from(state.to_u8()), index);
+            assert_eq!(BaseOutState::from_u8(state.to_u8()), Some(state));
+            let game = state.to_game_state();
+            assert_eq!(BaseOutState::try_from(&game).unwrap(), state);
+            assert!(crate::validators::validate_state(&game).is_ok());
+        }
+        assert_eq!(BaseOutState::from_u8(24), None);
+        assert_eq!(BaseOutState::new(8, 0), None);
+        assert_eq!(BaseOutState::new(0, 3), None);
+        assert!(matches!(
+            BaseOutState::try_from(24),
+            Err(BaselomError::ValidationError(_))
+        ));
+
+        let state = BaseOutState::new(0b101, 2).unwrap();
+        assert_eq!(serde_json::to_string(&state).unwrap(), "21");
+        assert_eq!(serde_json::from_str::<BaseOutState>("21").unwrap(), state);
+        assert!(serde_json::from_str::<BaseOutState>("24").is_err());
+    }
+
+    #[test]
+    fn test_base_out_state_helpers() {
+        let state = BaseOutState::new(0b101, 1).unwrap();
+        assert!(state.is_occupied(Base::First) && state.is_occupied(Base::Third));
+        assert!(!state.is_occupied(Base::Second));
+        assert_eq!(state.runners(), 2);
+        assert_eq!(state.outs(), 1);
+        for bases in BaseOutState::ALL {
+            let game = bases.to_game_state();
+            for base in Base::ALL {
+                assert_eq!(bases.is_force_at(base), game.bases.force_at(base));
+            }
+        }
+
+        let game = GameState::builder()
+            .outs(2)
+            .runner_on(Base::Second, "x")
+            .build()
+            .unwrap();
+        let state = BaseOutState::try_from(&game).unwrap();
+        assert_eq!((state.bases_occupied(), state.outs()), (0b010, 2));
+        assert_eq!(
+            state.to_game_state().bases.runner_at(Base::Second),
+            Some("runner_2")
+        );
+    }
 }
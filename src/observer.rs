@@ -0,0 +1,216 @@
+//! Callbacks for what happens during a transition.
+//!
+//! [`crate::engine::apply_pitch_observed`] and
+//! [`crate::engine::apply_event_observed`] report each pitch, run, out,
+//! half-inning end and game end to a [`TransitionObserver`], so embedders
+//! can react to them without diffing states. The plain engine functions
+//! never call an observer and are unaffected.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{GameState, InningHalf, PitchResult, StepOutcome, Team};
+use crate::player::PlayerKey;
+
+/// Receives the moments of a transition, in the order pitch, runs, outs,
+/// half-inning end and game end. Every method does nothing by default.
+pub trait TransitionObserver<P = String> {
+    /// A pitch was thrown.
+    fn on_pitch(&mut self, _pitch_result: PitchResult) {}
+
+    /// `scorer` crossed the plate for `team`.
+    fn on_run_scored(&mut self, _scorer: &P, _team: Team) {}
+
+    /// An out was recorded.
+    fn on_out(&mut self) {}
+
+    /// The `half` of `inning` ended.
+    fn on_half_inning_end(&mut self, _inning: u8, _half: InningHalf) {}
+
+    /// The game ended, won by `winner` or tied.
+    fn on_game_end(&mut self, _winner: Option<Team>) {}
+}
+
+/// A moment reported to a [`TransitionObserver`], as collected by the
+/// observer implementation for `Vec<GameAnnouncement>`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum GameAnnouncement<P = String> {
+    Pitch { pitch_result: PitchResult },
+    RunScored { scorer: P, team: Team },
+    Out,
+    HalfInningEnd { inning: u8, half: InningHalf },
+    GameEnd { winner: Option<Team> },
+}
+
+impl<P: Clone> TransitionObserver<P> for Vec<GameAnnouncement<P>> {
+    fn on_pitch(&mut self, pitch_result: PitchResult) {
+        self.push(GameAnnouncement::Pitch { pitch_result });
+    }
+
+    fn on_run_scored(&mut self, scorer: &P, team: Team) {
+        self.push(GameAnnouncement::RunScored {
+            scorer: scorer.clone(),
+            team,
+        });
+    }
+
+    fn on_out(&mut self) {
+        self.push(GameAnnouncement::Out);
+    }
+
+    fn on_half_inning_end(&mut self, inning: u8, half: InningHalf) {
+        self.push(GameAnnouncement::HalfInningEnd { inning, half });
+    }
+
+    fn on_game_end(&mut self, winner: Option<Team>) {
+        self.push(GameAnnouncement::GameEnd { winner });
+    }
+}
+
+/// Report a completed transition that started in the `half` of `inning`
+/// and led to `state`.
+pub(crate) fn notify<P: PlayerKey>(
+    observer: &mut (impl TransitionObserver<P> + ?Sized),
+    inning: u8,
+    half: InningHalf,
+    pitch_result: Option<PitchResult>,
+    outcome: &StepOutcome<P>,
+    state: &GameState<P>,
+) {
+    if let Some(pitch_result) = pitch_result {
+        observer.on_pitch(pitch_result);
+    }
+    for scorer in &outcome.scorers {
+        observer.on_run_scored(scorer, half.batting_team());
+    }
+    for _ in 0..outcome.outs_recorded {
+        observer.on_out();
+    }
+    if outcome.half_inning_ended {
+        observer.on_half_inning_end(inning, half);
+    }
+    if outcome.game_ended {
+        observer.on_game_end(state.winner());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{apply_event, apply_event_observed, apply_pitch, apply_pitch_observed};
+    use crate::models::{Base, GameEvent, GameRules, GameStatus};
+
+    fn walk_off_spot() -> GameState {
+        GameState::builder()
+            .inning(9)
+            .bottom()
+            .outs(2)
+            .count(3, 1)
+            .score(3, 3)
+            .runner_on(Base::First, "h1")
+            .runner_on(Base::Second, "h2")
+            .runner_on(Base::Third, "h3")
+            .batter("h4")
+            .pitcher("a9")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_walk_off_walk() {
+        let rules = GameRules::default();
+        let state = walk_off_spot();
+        let mut announcements = Vec::new();
+        let next =
+            apply_pitch_observed(&state, PitchResult::Ball, &rules, &mut announcements).unwrap();
+        assert_eq!(
+            next,
+            apply_pitch(&state, PitchResult::Ball, &rules).unwrap()
+        );
+        assert_eq!(next.status, GameStatus::Final);
+        assert_eq!(
+            announcements,
+            vec![
+                GameAnnouncement::Pitch {
+                    pitch_result: PitchResult::Ball
+                },
+                GameAnnouncement::RunScored {
+                    scorer: "h3".to_string(),
+                    team: Team::Home
+                },
+                GameAnnouncement::GameEnd {
+                    winner: Some(Team::Home)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inning_ending_out() {
+        let rules = GameRules::default();
+        let state = walk_off_spot();
+        let event = GameEvent::FlyOut {
+            advancement: Vec::new(),
+        };
+        let mut announcements = Vec::new();
+        let next = apply_event_observed(&state, &event, &rules, &mut announcements).unwrap();
+        assert_eq!(next, apply_event(&state, &event, &rules).unwrap());
+        assert_eq!(
+            announcements,
+            vec![
+                GameAnnouncement::Out,
+                GameAnnouncement::HalfInningEnd {
+                    inning: 9,
+                    half: InningHalf::Bottom
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejected_play_is_not_reported() {
+        let rules = GameRules::default();
+        let mut state = walk_off_spot();
+        state.current_batter_id = None;
+        let mut announcements = Vec::new();
+        assert!(
+            apply_pitch_observed(&state, PitchResult::Ball, &rules, &mut announcements).is_err()
+        );
+        assert!(announcements.is_empty());
+    }
+
+    #[test]
+    fn test_dyn_observer_with_defaults() {
+        #[derive(Default)]
+        struct Runs(u32);
+
+        impl TransitionObserver for Runs {
+            fn on_run_scored(&mut self, _scorer: &String, _team: Team) {
+                self.0 += 1;
+            }
+        }
+
+        let rules = GameRules::default();
+        let mut runs = Runs::default();
+        let observer: &mut dyn TransitionObserver = &mut runs;
+        apply_event_observed(&walk_off_spot(), &GameEvent::HomeRun, &rules, observer).unwrap();
+        assert_eq!(runs.0, 4);
+    }
+
+    #[test]
+    fn test_announcement_serialization() {
+        let announcement: GameAnnouncement = GameAnnouncement::HalfInningEnd {
+            inning: 3,
+            half: InningHalf::Top,
+        };
+        let json = serde_json::to_value(&announcement).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "half_inning_end", "inning": 3, "half": "top"})
+        );
+        assert_eq!(
+            serde_json::from_value::<GameAnnouncement>(json).unwrap(),
+            announcement
+        );
+    }
+}
@@ -0,0 +1,1251 @@
+//! Retrosheet play notation and situation shorthand.
+//!
+//! [`parse_play`] reads the play field of a Retrosheet event file (such as
+//! `"S8.2-H;1-3"` or `"64(1)3/GDP"`) into an engine [`Play`], and
+//! [`parse_record`] reads a whole `play` record, pitches included.
+//!
+//! Strikeouts, walks and hit batsmen are pitches in this engine, so they
+//! parse to [`Play::Pitch`]. Stolen bases and caught stealing parse to the
+//! events that move the runner the same way ([`GameEvent::Obstruction`]
+//! awarding the base, and [`GameEvent::Pickoff`]), as the engine has no
+//! stolen-base event.
+//!
+//! Anything the engine cannot represent is rejected, naming the part of
+//! the play that could not be used, rather than dropped. Descriptive
+//! modifiers (hit locations, ball types, fielding credits, `#`, `!` and
+//! `?` marks) carry no state and are accepted.
+//!
+//! [`GameState::from_situation`] builds a state from a description such as
+//! `"bottom 9, 2 out, runners on 1st and 3rd, 3-2 count, home down 1"`.
+
+use crate::errors::BaselomError;
+use crate::models::{
+    Advance, Base, Count, GameEvent, GameState, GameStateBuilder, InningHalf, PitchResult, Play,
+    RunnerAdvance,
+};
+
+/// A `play` record from a Retrosheet event file:
+/// `play,inning,team,batter,count,pitches,event`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayRecord {
+    pub inning: u8,
+    /// `Top` for the visiting team (`0`), `Bottom` for the home team (`1`)
+    pub half: InningHalf,
+    /// Retrosheet player ID of the batter
+    pub batter: String,
+    /// Count when the play happened, or `None` if unknown (`??`)
+    pub count: Option<Count>,
+    /// Pitches thrown since the previous record for this batter
+    pub pitches: Vec<PitchResult>,
+    /// The play itself
+    pub play: Play,
+}
+
+impl PlayRecord {
+    /// The engine plays for the record: the batter stepping in, the new
+    /// pitches, then the play. A strikeout, walk or hit batsman is left out
+    /// when pitches were recorded, since the last pitch already was it.
+    pub fn plays(&self) -> Vec<Play> {
+        let mut plays = vec![Play::Batter(self.batter.clone())];
+        plays.extend(self.pitches.iter().copied().map(Play::Pitch));
+        if !matches!(self.play, Play::Pitch(_)) || self.pitches.is_empty() {
+            plays.push(self.play.clone());
+        }
+        plays
+    }
+}
+
+/// Parse a `play` record line.
+///
+/// Returns `BaselomError::ValidationError` if the line is not a `play`
+/// record or any field cannot be parsed.
+pub fn parse_record(line: &str) -> Result<PlayRecord, BaselomError> {
+    let fields: Vec<&str> = line.trim().split(',').collect();
+    let [kind, inning, team, batter, count, pitches, play] = fields[..] else {
+        return Err(invalid_record(line, "expected 7 fields"));
+    };
+    if kind != "play" {
+        return Err(invalid_record(line, "not a play record"));
+    }
+    let inning = inning
+        .parse()
+        .map_err(|_| invalid_record(line, "invalid inning"))?;
+    let half = match team {
+        "0" => InningHalf::Top,
+        "1" => InningHalf::Bottom,
+        _ => return Err(invalid_record(line, "team must be 0 or 1")),
+    };
+    let count = match count.as_bytes() {
+        [b'?', b'?'] => None,
+        [balls @ b'0'..=b'3', strikes @ b'0'..=b'2'] => {
+            Some(Count::new(balls - b'0', strikes - b'0'))
+        }
+        _ => return Err(invalid_record(line, "invalid count")),
+    };
+    Ok(PlayRecord {
+        inning,
+        half,
+        batter: batter.to_string(),
+        count,
+        pitches: parse_pitches(pitches)?,
+        play: parse_play(play)?,
+    })
+}
+
+fn invalid_record(line: &str, reason: &str) -> BaselomError {
+    BaselomError::ValidationError(format!(
+        "Invalid Retrosheet record \"{}\": {}",
+        line, reason
+    ))
+}
+
+/// Parse a Retrosheet pitch sequence, keeping the pitches after the last
+/// `.` (earlier ones belong to previous records for the same batter).
+///
+/// Balls in play (`X`, `Y`) are left out, as the play field describes
+/// them; so are markers that are not pitches (`+`, `*`, `>`, pickoff
+/// throws `1`-`3` and `N`).
+///
+/// Returns `BaselomError::ValidationError` naming an unknown or
+/// unknown-result (`U`) pitch code.
+pub fn parse_pitches(sequence: &str) -> Result<Vec<PitchResult>, BaselomError> {
+    let new_pitches = sequence.rsplit('.').next().unwrap_or_default();
+    let mut pitches = Vec::new();
+    for code in new_pitches.chars() {
+        let pitch = match code {
+            'B' | 'I' | 'P' | 'V' => PitchResult::Ball,
+            'C' | 'K' => PitchResult::StrikeCalled,
+            'S' | 'M' | 'Q' => PitchResult::StrikeSwinging,
+            'F' | 'R' => PitchResult::Foul,
+            'T' | 'O' => PitchResult::FoulTip,
+            'L' => PitchResult::FoulBunt,
+            'H' => PitchResult::HitByPitch,
+            'X' | 'Y' | '+' | '*' | '>' | '1' | '2' | '3' | 'N' => continue,
+            _ => {
+                return Err(BaselomError::ValidationError(format!(
+                    "Unsupported Retrosheet pitch code '{}' in \"{}\"",
+                    code, sequence
+                )))
+            }
+        };
+        pitches.push(pitch);
+    }
+    Ok(pitches)
+}
+
+/// Parse a Retrosheet play field.
+///
+/// Runners the play does not move get the engine's default advancement
+/// for the event.
+///
+/// Returns `BaselomError::ValidationError` naming the unsupported part of
+/// the play.
+pub fn parse_play(play: &str) -> Result<Play, BaselomError> {
+    let cleaned: String = play.chars().filter(|c| !"#!?".contains(*c)).collect();
+    let (description, advances) = match cleaned.split_once('.') {
+        Some((description, advances)) => (description, Some(advances)),
+        None => (cleaned.as_str(), None),
+    };
+    let mut parts = description.split('/');
+    let basic = parts.next().unwrap_or_default();
+    let modifiers = Modifiers::parse(play, parts)?;
+    let advances = match advances {
+        Some(advances) => parse_advances(play, advances)?,
+        None => Vec::new(),
+    };
+    parse_basic(play, basic, &modifiers, &advances)
+}
+
+/// The modifiers that affect how a play is mapped.
+#[derive(Debug, Default)]
+struct Modifiers {
+    fly: bool,
+    line: bool,
+    ground: bool,
+    infield_fly: bool,
+    sac_fly: bool,
+    sac_hit: bool,
+    /// An error modifier (`E2` in `C/E2`)
+    error: bool,
+}
+
+impl Modifiers {
+    fn parse<'a>(play: &str, parts: impl Iterator<Item = &'a str>) -> Result<Self, BaselomError> {
+        let mut modifiers = Modifiers::default();
+        for part in parts {
+            let part = part.trim_end_matches(['+', '-']);
+            match part {
+                "FL" | "FO" | "DP" | "GDP" | "LDP" | "FDP" | "BGDP" | "BPDP" | "TP" | "GTP"
+                | "LTP" | "NDP" | "AP" | "R" | "BR" | "TH" | "TH1" | "TH2" | "TH3" | "THH" => {}
+                "IF" => modifiers.infield_fly = true,
+                "SF" => modifiers.sac_fly = true,
+                "SH" => modifiers.sac_hit = true,
+                _ if is_error_modifier(part) => modifiers.error = true,
+                _ if is_location(part) => {}
+                _ => {
+                    // A ball type, optionally followed by its location (`G6`).
+                    let ball_type = ["BG", "BP", "BL", "G", "F", "L", "P"]
+                        .into_iter()
+                        .find(|prefix| {
+                            part.strip_prefix(prefix)
+                                .is_some_and(|rest| rest.is_empty() || is_location(rest))
+                        })
+                        .ok_or_else(|| unsupported(play, part))?;
+                    match ball_type {
+                        "G" | "BG" => modifiers.ground = true,
+                        "L" | "BL" => modifiers.line = true,
+                        _ => modifiers.fly = true,
+                    }
+                }
+            }
+        }
+        Ok(modifiers)
+    }
+}
+
+/// Whether `part` is an error modifier such as `E2`.
+fn is_error_modifier(part: &str) -> bool {
+    matches!(part.as_bytes(), [b'E', b'1'..=b'9'])
+}
+
+/// Whether `part` is a hit location such as `8`, `78XD` or `9LS`.
+fn is_location(part: &str) -> bool {
+    part.starts_with(|c: char| c.is_ascii_digit())
+        && part
+            .chars()
+            .all(|c| c.is_ascii_digit() || "LMSDXF".contains(c))
+}
+
+/// A runner movement from the advance section, such as `1-3` or `2XH`.
+#[derive(Debug, Clone, Copy)]
+struct Movement<'a> {
+    /// Base the runner started on, or `None` for the batter
+    from: Option<Base>,
+    to: Advance,
+    /// Bases gained by a runner who was not put out
+    bases: u8,
+    text: &'a str,
+}
+
+impl Movement<'_> {
+    fn runner_advance(&self) -> Option<RunnerAdvance> {
+        self.from.map(|from| RunnerAdvance {
+            from,
+            to: self.to,
+            out_was_force: false,
+            force_no_rbi: false,
+        })
+    }
+
+    /// Whether a runner moved up exactly one base.
+    fn is_one_base(&self) -> bool {
+        self.from.is_some() && self.bases == 1 && self.to != Advance::Out
+    }
+}
+
+fn parse_advances<'a>(play: &str, advances: &'a str) -> Result<Vec<Movement<'a>>, BaselomError> {
+    advances
+        .split(';')
+        .map(|text| {
+            let bytes = text.as_bytes();
+            let (from, out, to) = match bytes {
+                [from, kind @ (b'-' | b'X'), to, ..] => (*from, *kind == b'X', *to),
+                _ => return Err(unsupported(play, text)),
+            };
+            let start = match from {
+                b'B' => 0,
+                b'1'..=b'3' => from - b'0',
+                _ => return Err(unsupported(play, text)),
+            };
+            let end = match to {
+                b'1'..=b'3' => to - b'0',
+                b'H' => 4,
+                _ => return Err(unsupported(play, text)),
+            };
+            // Parenthesized notes credit fielders or mark unearned runs,
+            // except errors, which the engine cannot charge to a runner.
+            let notes = &text[3..];
+            if notes.contains('E') || end <= start {
+                return Err(unsupported(play, text));
+            }
+            let to = if out {
+                Advance::Out
+            } else {
+                Base::from_number(end).map_or(Advance::Home, Advance::Base)
+            };
+            Ok(Movement {
+                from: Base::from_number(start),
+                to,
+                bases: if out { 0 } else { end - start },
+                text,
+            })
+        })
+        .collect()
+}
+
+fn unsupported(play: &str, part: &str) -> BaselomError {
+    BaselomError::ValidationError(format!(
+        "Unsupported Retrosheet play \"{}\": cannot use \"{}\"",
+        play, part
+    ))
+}
+
+/// Whether `rest` is empty or only fielder digits.
+fn only_fielders(rest: &str) -> bool {
+    rest.chars().all(|c| c.is_ascii_digit())
+}
+
+fn parse_basic(
+    play: &str,
+    basic: &str,
+    modifiers: &Modifiers,
+    advances: &[Movement<'_>],
+) -> Result<Play, BaselomError> {
+    let unsupported_basic = || Err(unsupported(play, basic));
+    if let Some(index) = basic.find('+') {
+        return Err(unsupported(play, &basic[index..]));
+    }
+    if modifiers.error && basic != "C" {
+        return Err(unsupported(play, "error modifier"));
+    }
+    let event = match basic {
+        "HP" => return pitch(play, PitchResult::HitByPitch, advances, true),
+        "W" => return pitch(play, PitchResult::Ball, advances, true),
+        "IW" | "I" => walk_like(play, GameEvent::IntentionalWalk, advances)?,
+        "C" => walk_like(play, GameEvent::CatchersInterference, advances)?,
+        "WP" => runners_one_base(play, GameEvent::WildPitch, advances)?,
+        "PB" => runners_one_base(play, GameEvent::PassedBall, advances)?,
+        "BK" => runners_one_base(play, GameEvent::Balk, advances)?,
+        "DGR" => hit(play, Base::Second, advances, |_| {
+            GameEvent::GroundRuleDouble
+        })?,
+        "NP" => return unsupported_basic(),
+        _ if basic.starts_with("HR") && only_fielders(&basic[2..])
+            || basic.starts_with('H') && only_fielders(&basic[1..]) =>
+        {
+            home_run(play, advances)?
+        }
+        _ if basic.starts_with('K') && only_fielders(&basic[1..]) => {
+            return pitch(play, PitchResult::StrikeSwinging, advances, false)
+        }
+        _ if basic.starts_with("SB") => stolen_base(play, &basic[2..], advances)?,
+        _ if basic.starts_with("CS") => caught(play, basic, &basic[2..], true, advances)?,
+        _ if basic.starts_with("PO") => caught(play, basic, &basic[2..], false, advances)?,
+        _ if basic.starts_with("FC") && only_fielders(&basic[2..]) => {
+            fielders_choice(play, None, advances)?
+        }
+        _ if basic.starts_with('S') && only_fielders(&basic[1..]) => {
+            hit(play, Base::First, advances, |advancement| {
+                GameEvent::Single { advancement }
+            })?
+        }
+        _ if basic.starts_with('D') && only_fielders(&basic[1..]) => {
+            hit(play, Base::Second, advances, |advancement| {
+                GameEvent::Double { advancement }
+            })?
+        }
+        _ if basic.starts_with('T') && only_fielders(&basic[1..]) => {
+            hit(play, Base::Third, advances, |advancement| {
+                GameEvent::Triple { advancement }
+            })?
+        }
+        _ if is_error_modifier(basic) => reached_on_error(play, advances)?,
+        _ if basic.starts_with(|c: char| c.is_ascii_digit()) => {
+            out(play, basic, modifiers, advances)?
+        }
+        _ => return unsupported_basic(),
+    };
+    Ok(Play::Event(event))
+}
+
+/// A strikeout, walk or hit batsman, which the engine records as the
+/// deciding pitch. Walks and hit batsmen accept the forced advances.
+fn pitch(
+    play: &str,
+    pitch_result: PitchResult,
+    advances: &[Movement<'_>],
+    batter_to_first: bool,
+) -> Result<Play, BaselomError> {
+    for movement in advances {
+        let batter_default =
+            batter_to_first && movement.from.is_none() && movement.to == Advance::Base(Base::First);
+        if !(batter_default || (batter_to_first && is_forced(movement, advances, &[]))) {
+            return Err(unsupported(play, movement.text));
+        }
+    }
+    Ok(Play::Pitch(pitch_result))
+}
+
+/// Whether a runner moved up one base because the batter reaching first
+/// forced them: the runner on first always is, and any other runner only
+/// when the runner behind them moved or was put out (in the advances or on
+/// `out` bases).
+fn is_forced(movement: &Movement<'_>, advances: &[Movement<'_>], out: &[Base]) -> bool {
+    if !movement.is_one_base() {
+        return false;
+    }
+    match movement_behind(movement) {
+        None => true,
+        Some(behind) => {
+            out.contains(&behind) || advances.iter().any(|other| other.from == Some(behind))
+        }
+    }
+}
+
+/// The base behind the one a runner started on, if any.
+fn movement_behind(movement: &Movement<'_>) -> Option<Base> {
+    movement
+        .from
+        .and_then(|from| Base::from_number(from.number() - 1))
+}
+
+/// An event putting the batter on first and forcing runners along.
+fn walk_like(
+    play: &str,
+    event: GameEvent,
+    advances: &[Movement<'_>],
+) -> Result<GameEvent, BaselomError> {
+    pitch(play, PitchResult::Ball, advances, true).map(|_| event)
+}
+
+/// An event that moves every runner up one base.
+fn runners_one_base(
+    play: &str,
+    event: GameEvent,
+    advances: &[Movement<'_>],
+) -> Result<GameEvent, BaselomError> {
+    match advances.iter().find(|movement| !movement.is_one_base()) {
+        Some(movement) => Err(unsupported(play, movement.text)),
+        None => Ok(event),
+    }
+}
+
+/// A hit putting the batter on `batter_to`, with runner advances as
+/// explicit advancement.
+fn hit(
+    play: &str,
+    batter_to: Base,
+    advances: &[Movement<'_>],
+    event: impl FnOnce(Vec<RunnerAdvance>) -> GameEvent,
+) -> Result<GameEvent, BaselomError> {
+    let mut advancement = Vec::new();
+    for movement in advances {
+        match movement.runner_advance() {
+            Some(advance) => advancement.push(advance),
+            None if movement.to == Advance::Base(batter_to) => {}
+            None => return Err(unsupported(play, movement.text)),
+        }
+    }
+    Ok(event(advancement))
+}
+
+/// A home run, which scores every runner.
+fn home_run(play: &str, advances: &[Movement<'_>]) -> Result<GameEvent, BaselomError> {
+    match advances
+        .iter()
+        .find(|movement| movement.to != Advance::Home)
+    {
+        Some(movement) => Err(unsupported(play, movement.text)),
+        None => Ok(GameEvent::HomeRun),
+    }
+}
+
+/// A stolen base such as `SB2`, awarding the runner the base.
+fn stolen_base(
+    play: &str,
+    target: &str,
+    advances: &[Movement<'_>],
+) -> Result<GameEvent, BaselomError> {
+    let (runner, awarded) = match target {
+        "2" => (Base::First, Advance::Base(Base::Second)),
+        "3" => (Base::Second, Advance::Base(Base::Third)),
+        "H" => (Base::Third, Advance::Home),
+        _ => return Err(unsupported(play, target)),
+    };
+    let event = GameEvent::Obstruction { runner, awarded };
+    only_runner(play, runner, advances).map(|_| event)
+}
+
+/// Caught stealing (`CS2(24)`) or a pickoff (`PO1(13)`), putting the
+/// runner out, or a pickoff error (`PO1(E3)`) advancing the runner.
+fn caught(
+    play: &str,
+    basic: &str,
+    rest: &str,
+    stealing: bool,
+    advances: &[Movement<'_>],
+) -> Result<GameEvent, BaselomError> {
+    let (target, fielders) = rest.split_at(rest.len().min(1));
+    if !stealing && matches!(fielders.as_bytes(), [b'(', b'E', b'1'..=b'9', b')']) {
+        let base = match target {
+            "1" => Base::First,
+            "2" => Base::Second,
+            "3" => Base::Third,
+            _ => return Err(unsupported(play, basic)),
+        };
+        if let Some(movement) = advances.iter().find(|movement| !movement.is_one_base()) {
+            return Err(unsupported(play, movement.text));
+        }
+        return only_runner(play, base, advances).map(|_| GameEvent::PickoffError { base });
+    }
+    let credits_ok = fielders.is_empty()
+        || (fielders.starts_with('(')
+            && fielders.ends_with(')')
+            && only_fielders(&fielders[1..fielders.len() - 1]));
+    if !credits_ok {
+        return Err(unsupported(play, fielders));
+    }
+    let base = match (stealing, target) {
+        (true, "2") | (false, "1") => Base::First,
+        (true, "3") | (false, "2") => Base::Second,
+        (true, "H") | (false, "3") => Base::Third,
+        _ => return Err(unsupported(play, basic)),
+    };
+    only_runner(play, base, advances).map(|_| GameEvent::Pickoff { base })
+}
+
+/// Reject advances for anyone but the runner on `base`.
+fn only_runner(play: &str, base: Base, advances: &[Movement<'_>]) -> Result<(), BaselomError> {
+    match advances.iter().find(|movement| movement.from != Some(base)) {
+        Some(movement) => Err(unsupported(play, movement.text)),
+        None => Ok(()),
+    }
+}
+
+/// A fielder's choice, with the runner put out given by a force-out in
+/// the description or by the advances.
+fn fielders_choice(
+    play: &str,
+    forced: Option<Base>,
+    advances: &[Movement<'_>],
+) -> Result<GameEvent, BaselomError> {
+    let mut runner_out_at = forced;
+    for movement in advances {
+        match (movement.from, movement.to) {
+            (None, Advance::Base(Base::First)) => {}
+            (Some(from), Advance::Out) if runner_out_at.is_none_or(|out| out == from) => {
+                runner_out_at = Some(from);
+            }
+            _ if is_forced(movement, advances, forced.as_slice()) => {}
+            _ => return Err(unsupported(play, movement.text)),
+        }
+    }
+    match runner_out_at {
+        Some(runner_out_at) => Ok(GameEvent::FieldersChoice { runner_out_at }),
+        None => Err(unsupported(play, "FC without a runner put out")),
+    }
+}
+
+/// The batter reaching on an error (`E6`). Runners all advance as far as
+/// the farthest-moving one, or score.
+fn reached_on_error(play: &str, advances: &[Movement<'_>]) -> Result<GameEvent, BaselomError> {
+    let mut batter_to = Base::First;
+    let runners_advance = advances
+        .iter()
+        .filter(|movement| movement.from.is_some())
+        .map(|movement| movement.bases)
+        .max()
+        .unwrap_or(0);
+    for movement in advances {
+        match (movement.from, movement.to) {
+            (None, Advance::Base(base)) => batter_to = base,
+            (Some(_), Advance::Base(_)) if movement.bases == runners_advance => {}
+            (Some(_), Advance::Home) if movement.bases <= runners_advance => {}
+            _ => return Err(unsupported(play, movement.text)),
+        }
+    }
+    Ok(GameEvent::ReachedOnError {
+        batter_to,
+        runners_advance,
+    })
+}
+
+/// An out described by fielders, such as `8`, `63`, `54(1)` or
+/// `64(1)3`.
+fn out(
+    play: &str,
+    basic: &str,
+    modifiers: &Modifiers,
+    advances: &[Movement<'_>],
+) -> Result<GameEvent, BaselomError> {
+    let mut runners_out = Vec::new();
+    let mut batter_out = false;
+    let mut fielders = 0;
+    let mut trailing_fielders = false;
+    let mut rest = basic;
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() {
+            fielders += 1;
+            trailing_fielders = true;
+            rest = &rest[1..];
+        } else if let Some(group) = rest.strip_prefix('(') {
+            let Some((runner, after)) = group.split_once(')') else {
+                return Err(unsupported(play, rest));
+            };
+            match runner {
+                "B" => batter_out = true,
+                _ => match runner.parse().ok().and_then(Base::from_number) {
+                    Some(base) => runners_out.push(base),
+                    None => return Err(unsupported(play, rest)),
+                },
+            }
+            trailing_fielders = false;
+            rest = after;
+        } else {
+            return Err(unsupported(play, rest));
+        }
+    }
+    // Fielders after the last runner put out retired the batter, as does a
+    // plain out with no runners marked.
+    batter_out |= trailing_fielders && (!runners_out.is_empty() || !batter_out);
+
+    for movement in advances {
+        if movement.to == Advance::Out
+            && movement
+                .from
+                .is_some_and(|from| !runners_out.contains(&from))
+        {
+            runners_out.extend(movement.from);
+        }
+    }
+    let outs = runners_out.len() + usize::from(batter_out);
+    if outs >= 2 {
+        // Besides the outs, only the batter reaching first when not retired
+        // and forced runners fit the engine's double play.
+        let extra = advances.iter().find(|movement| {
+            movement.to != Advance::Out
+                && !(movement.from.is_none()
+                    && !batter_out
+                    && movement.to == Advance::Base(Base::First))
+                && !is_forced(movement, advances, &runners_out)
+        });
+        if let Some(movement) = extra {
+            return Err(unsupported(play, movement.text));
+        }
+        return Ok(GameEvent::DoublePlay {
+            runners_out,
+            batter_out,
+        });
+    }
+    if !batter_out {
+        return fielders_choice(play, runners_out.first().copied(), advances);
+    }
+
+    if let Some(movement) = advances.iter().find(|movement| movement.from.is_none()) {
+        return Err(unsupported(play, movement.text));
+    }
+    if modifiers.sac_fly {
+        return sacrifice(play, advances, true);
+    }
+    if modifiers.sac_hit {
+        return sacrifice(play, advances, false);
+    }
+    let advancement = advances
+        .iter()
+        .filter_map(Movement::runner_advance)
+        .collect();
+    Ok(if modifiers.infield_fly {
+        GameEvent::InfieldFly { advancement }
+    } else if modifiers.line {
+        GameEvent::LineOut { advancement }
+    } else if modifiers.fly || (!modifiers.ground && fielders == 1) {
+        GameEvent::FlyOut { advancement }
+    } else {
+        GameEvent::GroundOut { advancement }
+    })
+}
+
+/// A sacrifice fly or bunt, where only one-base advances are possible.
+fn sacrifice(play: &str, advances: &[Movement<'_>], fly: bool) -> Result<GameEvent, BaselomError> {
+    let mut moving = Vec::new();
+    for movement in advances {
+        match movement.from {
+            Some(from) if movement.is_one_base() => moving.push(from),
+            _ => return Err(unsupported(play, movement.text)),
+        }
+    }
+    Ok(if fly {
+        moving.retain(|&base| base != Base::Third);
+        GameEvent::SacFly { tagging_up: moving }
+    } else {
+        GameEvent::SacBunt { advancing: moving }
+    })
+}
+
+impl GameState {
+    /// Build a state from a short description such as
+    /// `"bottom 9, 2 out, runners on 1st and 3rd, 3-2 count, home down 1"`.
+    ///
+    /// The description is a comma-separated list of clauses, in any order
+    /// and case:
+    ///
+    /// | Clause | Examples |
+    /// |--------|----------|
+    /// | Inning and half | `top 3`, `bottom 9th`, `top of the 1st` |
+    /// | Outs | `1 out`, `2 outs`, `no outs` |
+    /// | Bases | `bases empty`, `bases loaded`, `runner on 2nd`, `runners on 1st=smith and third` |
+    /// | Count | `3-2 count`, `count 1-1`, `0-2` |
+    /// | Score | `tied`, `home down 1`, `away up 3`, `score 4-2` (away first) |
+    ///
+    /// Runners without an ID are `r1`, `r2` and `r3` by base. A team up or
+    /// down leads a scoreless opponent. Anything not described is as in
+    /// [`GameState::builder`]: top of the first, no outs, bases empty, 0-0
+    /// and tied.
+    ///
+    /// Returns `BaselomError::ValidationError` naming the first unexpected
+    /// word, and the builder's error if the state is inconsistent.
+    pub fn from_situation(text: &str) -> Result<GameState, BaselomError> {
+        let mut builder = GameState::builder();
+        if !text.trim().is_empty() {
+            for clause in text.split(',') {
+                builder = situation_clause(text, clause, builder)?;
+            }
+        }
+        builder.build()
+    }
+}
+
+fn situation_clause(
+    text: &str,
+    clause: &str,
+    builder: GameStateBuilder,
+) -> Result<GameStateBuilder, BaselomError> {
+    let words: Vec<&str> = clause.split_whitespace().collect();
+    let lower: Vec<String> = words.iter().map(|word| word.to_ascii_lowercase()).collect();
+    let lower: Vec<&str> = lower.iter().map(String::as_str).collect();
+    let unexpected = |index: usize| match words.get(index) {
+        Some(word) => BaselomError::ValidationError(format!(
+            "Unexpected \"{}\" in situation \"{}\"",
+            word, text
+        )),
+        None => BaselomError::ValidationError(format!(
+            "Unexpected end of \"{}\" in situation \"{}\"",
+            clause.trim(),
+            text
+        )),
+    };
+    let number = |index: usize| -> Result<u32, BaselomError> {
+        lower
+            .get(index)
+            .and_then(|word| word.parse().ok())
+            .ok_or_else(|| unexpected(index))
+    };
+    let pair = |index: usize| -> Result<(u32, u32), BaselomError> {
+        lower
+            .get(index)
+            .and_then(|word| word.split_once('-'))
+            .and_then(|(first, second)| Some((first.parse().ok()?, second.parse().ok()?)))
+            .ok_or_else(|| unexpected(index))
+    };
+    let small = |value: u32, index: usize| u8::try_from(value).map_err(|_| unexpected(index));
+    let end = |index: usize| {
+        if words.len() > index {
+            Err(unexpected(index))
+        } else {
+            Ok(())
+        }
+    };
+
+    Ok(match lower[..] {
+        [] => return Err(unexpected(0)),
+        ["top" | "bottom", ..] => {
+            let at = if lower[1..].starts_with(&["of", "the"]) {
+                3
+            } else {
+                1
+            };
+            let inning = lower
+                .get(at)
+                .map(|word| {
+                    ["st", "nd", "rd", "th"]
+                        .into_iter()
+                        .find_map(|suffix| word.strip_suffix(suffix))
+                        .unwrap_or(word)
+                })
+                .and_then(|digits| digits.parse().ok())
+                .filter(|&inning: &u8| inning > 0)
+                .ok_or_else(|| unexpected(at))?;
+            end(at + 1)?;
+            let builder = builder.inning(inning);
+            if lower[0] == "top" {
+                builder.top()
+            } else {
+                builder.bottom()
+            }
+        }
+        ["no", "out" | "outs"] => builder.outs(0),
+        [_, "out" | "outs", ..] => {
+            let outs = small(number(0)?, 0)?;
+            end(2)?;
+            builder.outs(outs)
+        }
+        ["bases", ..] => {
+            end(2)?;
+            match lower.get(1) {
+                Some(&"empty") => builder,
+                Some(&"loaded") => builder
+                    .runner_on(Base::First, "r1")
+                    .runner_on(Base::Second, "r2")
+                    .runner_on(Base::Third, "r3"),
+                _ => return Err(unexpected(1)),
+            }
+        }
+        ["runner" | "runners", ..] => {
+            if lower.get(1) != Some(&"on") {
+                return Err(unexpected(1));
+            }
+            let mut builder = builder;
+            let mut index = 2;
+            loop {
+                let word = words.get(index).ok_or_else(|| unexpected(index))?;
+                let (base, id) = match word.split_once('=') {
+                    Some((base, id)) if !id.is_empty() => (base, Some(id)),
+                    Some(_) => return Err(unexpected(index)),
+                    None => (*word, None),
+                };
+                let base = match base.to_ascii_lowercase().as_str() {
+                    "1st" | "first" => Base::First,
+                    "2nd" | "second" => Base::Second,
+                    "3rd" | "third" => Base::Third,
+                    _ => return Err(unexpected(index)),
+                };
+                let id = id.map_or_else(|| format!("r{}", base.number()), str::to_string);
+                builder = builder.runner_on(base, id);
+                match lower.get(index + 1) {
+                    None => break builder,
+                    Some(&"and") => index += 2,
+                    Some(_) => return Err(unexpected(index + 1)),
+                }
+            }
+        }
+        ["tied"] => builder.score(0, 0),
+        ["count", _] | [_, "count"] | [_] => {
+            let at = usize::from(lower[0] == "count");
+            let (balls, strikes) = pair(at)?;
+            builder.count(small(balls, at)?, small(strikes, at)?)
+        }
+        ["score", ..] => {
+            let (away, home) = pair(1)?;
+            end(2)?;
+            builder.score(home, away)
+        }
+        ["home" | "away", ..] => {
+            let margin = number(2)?;
+            end(3)?;
+            let leading = match (lower[0], lower.get(1)) {
+                ("home", Some(&"up")) | ("away", Some(&"down")) => true,
+                ("home", Some(&"down")) | ("away", Some(&"up")) => false,
+                _ => return Err(unexpected(1)),
+            };
+            if leading {
+                builder.score(margin, 0)
+            } else {
+                builder.score(0, margin)
+            }
+        }
+        _ => return Err(unexpected(0)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{apply_play, start};
+    use crate::models::{GameRules, GameState, InningScore};
+
+    fn event(play: &str) -> GameEvent {
+        match parse_play(play).unwrap() {
+            Play::Event(event) => event,
+            other => panic!("{} parsed to {:?}", play, other),
+        }
+    }
+
+    fn advance(from: Base, to: Advance) -> RunnerAdvance {
+        RunnerAdvance {
+            from,
+            to,
+            out_was_force: false,
+            force_no_rbi: false,
+        }
+    }
+
+    fn unsupported_part(play: &str) -> String {
+        match parse_play(play) {
+            Err(BaselomError::ValidationError(message)) => message
+                .rsplit_once("cannot use ")
+                .map(|(_, part)| part.trim_matches('"').to_string())
+                .unwrap(),
+            other => panic!("{} parsed to {:?}", play, other),
+        }
+    }
+
+    #[test]
+    fn test_hits() {
+        assert_eq!(
+            event("S8"),
+            GameEvent::Single {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(
+            event("S9/L9S.2-H;1-3"),
+            GameEvent::Single {
+                advancement: vec![
+                    advance(Base::Second, Advance::Home),
+                    advance(Base::First, Advance::Base(Base::Third)),
+                ]
+            }
+        );
+        assert_eq!(
+            event("D7/G5.B-2"),
+            GameEvent::Double {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(
+            event("T9/F9LD"),
+            GameEvent::Triple {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(event("DGR/L9LS.1-3"), GameEvent::GroundRuleDouble);
+        assert_eq!(event("HR/F78XD.2-H;1-H"), GameEvent::HomeRun);
+        assert_eq!(event("H"), GameEvent::HomeRun);
+        assert_eq!(event("HR9"), GameEvent::HomeRun);
+    }
+
+    #[test]
+    fn test_pitch_results() {
+        assert_eq!(
+            parse_play("K").unwrap(),
+            Play::Pitch(PitchResult::StrikeSwinging)
+        );
+        assert_eq!(
+            parse_play("K23").unwrap(),
+            Play::Pitch(PitchResult::StrikeSwinging)
+        );
+        assert_eq!(parse_play("W.1-2").unwrap(), Play::Pitch(PitchResult::Ball));
+        assert_eq!(
+            parse_play("HP.2-3;1-2").unwrap(),
+            Play::Pitch(PitchResult::HitByPitch)
+        );
+        assert_eq!(event("IW"), GameEvent::IntentionalWalk);
+        assert_eq!(event("I.1-2"), GameEvent::IntentionalWalk);
+        assert_eq!(event("C/E2.1-2"), GameEvent::CatchersInterference);
+    }
+
+    #[test]
+    fn test_runner_events() {
+        assert_eq!(event("WP.2-3"), GameEvent::WildPitch);
+        assert_eq!(event("PB.3-H;1-2"), GameEvent::PassedBall);
+        assert_eq!(event("BK.1-2"), GameEvent::Balk);
+        assert_eq!(
+            event("SB2"),
+            GameEvent::Obstruction {
+                runner: Base::First,
+                awarded: Advance::Base(Base::Second)
+            }
+        );
+        assert_eq!(
+            event("SBH"),
+            GameEvent::Obstruction {
+                runner: Base::Third,
+                awarded: Advance::Home
+            }
+        );
+        assert_eq!(event("CS2(24)"), GameEvent::Pickoff { base: Base::First });
+        assert_eq!(event("CSH(12)"), GameEvent::Pickoff { base: Base::Third });
+        assert_eq!(event("PO1(13)"), GameEvent::Pickoff { base: Base::First });
+        assert_eq!(
+            event("PO2(E4).2-3"),
+            GameEvent::PickoffError { base: Base::Second }
+        );
+    }
+
+    #[test]
+    fn test_errors_and_fielders_choice() {
+        assert_eq!(
+            event("E6"),
+            GameEvent::ReachedOnError {
+                batter_to: Base::First,
+                runners_advance: 0
+            }
+        );
+        assert_eq!(
+            event("E5/TH.B-2;1-3"),
+            GameEvent::ReachedOnError {
+                batter_to: Base::Second,
+                runners_advance: 2
+            }
+        );
+        assert_eq!(
+            event("E6.3-H;1-3"),
+            GameEvent::ReachedOnError {
+                batter_to: Base::First,
+                runners_advance: 2
+            }
+        );
+        assert_eq!(
+            event("FC5.2X3(25)"),
+            GameEvent::FieldersChoice {
+                runner_out_at: Base::Second
+            }
+        );
+        assert_eq!(
+            event("54(1)/FO/G5.2-3"),
+            GameEvent::FieldersChoice {
+                runner_out_at: Base::First
+            }
+        );
+    }
+
+    #[test]
+    fn test_outs() {
+        assert_eq!(
+            event("63/G6"),
+            GameEvent::GroundOut {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(
+            event("43.2-3"),
+            GameEvent::GroundOut {
+                advancement: vec![advance(Base::Second, Advance::Base(Base::Third))]
+            }
+        );
+        assert_eq!(
+            event("8/F8"),
+            GameEvent::FlyOut {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(
+            event("7"),
+            GameEvent::FlyOut {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(
+            event("6/L6"),
+            GameEvent::LineOut {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(
+            event("4/IF"),
+            GameEvent::InfieldFly {
+                advancement: Vec::new()
+            }
+        );
+        assert_eq!(
+            event("9/SF.3-H;2-3"),
+            GameEvent::SacFly {
+                tagging_up: vec![Base::Second]
+            }
+        );
+        assert_eq!(
+            event("13/SH.1-2"),
+            GameEvent::SacBunt {
+                advancing: vec![Base::First]
+            }
+        );
+        assert_eq!(
+            event("64(1)3/GDP"),
+            GameEvent::DoublePlay {
+                runners_out: vec![Base::First],
+                batter_out: true
+            }
+        );
+        assert_eq!(
+            event("8(B)84(2)/LDP/L8"),
+            GameEvent::DoublePlay {
+                runners_out: vec![Base::Second],
+                batter_out: true
+            }
+        );
+        assert_eq!(
+            event("54(2)4(1)/GDP.B-1"),
+            GameEvent::DoublePlay {
+                runners_out: vec![Base::Second, Base::First],
+                batter_out: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_unsupported_parts_are_named() {
+        assert_eq!(unsupported_part("K+WP.1-2"), "+WP");
+        assert_eq!(unsupported_part("NP"), "NP");
+        assert_eq!(unsupported_part("S8.1-3(E8)"), "1-3(E8)");
+        assert_eq!(unsupported_part("W.2-3"), "2-3");
+        assert_eq!(unsupported_part("WP.1-3"), "1-3");
+        assert_eq!(unsupported_part("SB2.2-3"), "2-3");
+        assert_eq!(unsupported_part("S8/XYZ"), "XYZ");
+        assert_eq!(unsupported_part("63.B-2"), "B-2");
+        assert_eq!(unsupported_part("FC6"), "FC without a runner put out");
+        assert_eq!(unsupported_part("OA.1-2"), "OA");
+        assert_eq!(unsupported_part("E6.3-H;1-2;2-H"), "1-2");
+        assert_eq!(unsupported_part("64(1)3/GDP.3-H"), "3-H");
+    }
+
+    #[test]
+    fn test_pitch_sequences() {
+        use PitchResult::*;
+
+        assert_eq!(
+            parse_pitches("CBFX").unwrap(),
+            vec![StrikeCalled, Ball, Foul]
+        );
+        assert_eq!(
+            parse_pitches("B1>S.CFT*BH").unwrap(),
+            vec![StrikeCalled, Foul, FoulTip, Ball, HitByPitch]
+        );
+        assert_eq!(parse_pitches("").unwrap(), Vec::new());
+        assert!(parse_pitches("BU").is_err());
+    }
+
+    #[test]
+    fn test_record() {
+        let record = parse_record("play,7,1,smitj001,32,BCFBB,W.1-2").unwrap();
+        assert_eq!(record.inning, 7);
+        assert_eq!(record.half, InningHalf::Bottom);
+        assert_eq!(record.count, Some(Count::new(3, 2)));
+        assert_eq!(record.pitches.len(), 5);
+        assert_eq!(record.play, Play::Pitch(PitchResult::Ball));
+        // The walk is the last pitch, not a separate play.
+        assert_eq!(record.plays().len(), 6);
+
+        let record = parse_record("play,7,1,smitj001,??,,W").unwrap();
+        assert_eq!(record.count, None);
+        assert_eq!(
+            record.plays(),
+            vec![
+                Play::Batter("smitj001".to_string()),
+                Play::Pitch(PitchResult::Ball)
+            ]
+        );
+
+        assert!(parse_record("sub,smitj001,\"John Smith\",1,5,7").is_err());
+        assert!(parse_record("play,7,2,smitj001,00,,W").is_err());
+        assert!(parse_record("play,7,1,smitj001,40,,W").is_err());
+    }
+
+    #[test]
+    fn test_replay_half_inning_fixture() {
+        let mut state = start(&GameState::new()).unwrap();
+        state.current_pitcher_id = Some("homep001".to_string());
+        let rules = GameRules::default();
+        let fixture = include_str!("../tests/fixtures/retrosheet_half_inning.txt");
+        for line in fixture.lines().filter(|line| line.starts_with("play,")) {
+            let record = parse_record(line).unwrap();
+            assert_eq!((record.inning, record.half), (state.inning, state.half));
+            let plays = record.plays();
+            for (index, play) in plays.iter().enumerate() {
+                // The recorded count is the one the last pitch was thrown in.
+                if index + 1 == plays.len() {
+                    assert_eq!(Some(state.count), record.count, "{}", line);
+                }
+                state = apply_play(&state, play, &rules)
+                    .unwrap_or_else(|err| panic!("{}: {:?}", line, err));
+            }
+        }
+
+        assert_eq!(
+            (state.inning, state.half, state.outs),
+            (1, InningHalf::Bottom, 0)
+        );
+        assert_eq!((state.score.away, state.score.home), (2, 0));
+        assert_eq!(
+            state.line_score,
+            vec![InningScore {
+                away: 2,
+                home: Some(0)
+            }]
+        );
+        assert_eq!(state.team_totals.away.hits, 2);
+        assert_eq!(state.team_totals.home.errors, 1);
+    }
+
+    #[test]
+    fn test_situations() {
+        let builder = GameState::builder;
+        let cases = [
+            ("", builder()),
+            (
+                "bottom 9, 2 out, runners on 1st and 3rd, 3-2 count, home down 1",
+                builder()
+                    .inning(9)
+                    .bottom()
+                    .outs(2)
+                    .runner_on(Base::First, "r1")
+                    .runner_on(Base::Third, "r3")
+                    .count(3, 2)
+                    .score(0, 1),
+            ),
+            ("top 3", builder().inning(3).top()),
+            ("Top of the 1st, no outs", builder()),
+            ("bottom 12th, 1 out", builder().inning(12).bottom().outs(1)),
+            (
+                "bases loaded, 2 outs",
+                builder()
+                    .runner_on(Base::First, "r1")
+                    .runner_on(Base::Second, "r2")
+                    .runner_on(Base::Third, "r3")
+                    .outs(2),
+            ),
+            ("bases empty, tied", builder()),
+            (
+                "runner on second=Ortiz",
+                builder().runner_on(Base::Second, "Ortiz"),
+            ),
+            (
+                "runners on 1st=smith and 2nd, count 1-1",
+                builder()
+                    .runner_on(Base::First, "smith")
+                    .runner_on(Base::Second, "r2")
+                    .count(1, 1),
+            ),
+            ("0-2", builder().count(0, 2)),
+            ("away up 3, top 5", builder().inning(5).score(0, 3)),
+            ("home up 2", builder().score(2, 0)),
+            (
+                "score 4-2, bottom 7",
+                builder().inning(7).bottom().score(2, 4),
+            ),
+            ("AWAY DOWN 1", builder().score(1, 0)),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(
+                GameState::from_situation(text).unwrap(),
+                expected.build().unwrap(),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_situation_errors() {
+        let message = |text: &str| GameState::from_situation(text).unwrap_err().to_string();
+        assert_eq!(
+            message("bottom 9, 2 out, runners on 1st and 4th"),
+            "Validation error: Unexpected \"4th\" in situation \"bottom 9, 2 out, runners on 1st and 4th\""
+        );
+        assert!(message("top nine").contains("Unexpected \"nine\""));
+        assert!(message("top 0").contains("Unexpected \"0\""));
+        assert!(message("2 outs please").contains("Unexpected \"please\""));
+        assert!(message("runners at 1st").contains("Unexpected \"at\""));
+        assert!(message("runners on 1st or 2nd").contains("Unexpected \"or\""));
+        assert!(message("runner on 1st=").contains("Unexpected \"1st=\""));
+        assert!(message("home behind 2").contains("Unexpected \"behind\""));
+        assert!(message("count three").contains("Unexpected \"three\""));
+        assert!(message("top 9,").contains("Unexpected end"));
+        assert!(message("runners on").contains("Unexpected end of \"runners on\""));
+        assert!(message("sunny day").contains("Unexpected \"sunny\""));
+        // Well-formed but impossible states fail validation.
+        assert!(matches!(
+            GameState::from_situation("4 outs"),
+            Err(BaselomError::InvalidState(_))
+        ));
+    }
+}
@@ -0,0 +1,288 @@
+//! Player identifiers.
+//!
+//! The engine is generic over how players are identified. [`GameState`]
+//! uses the caller's string IDs, which is what gets serialized. For
+//! simulation loops, a [`PlayerRegistry`] maps those strings to small
+//! [`PlayerId`] numbers, and the engine runs on the resulting
+//! [`CompactGameState`] without cloning strings on every transition.
+
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::BaselomError;
+use crate::models::GameState;
+
+/// A type that identifies players in a [`GameState`].
+///
+/// `Key` is the borrowed form used for lookups, such as `str` for
+/// `String`.
+pub trait PlayerKey: Clone + Ord + Hash + fmt::Debug + fmt::Display + Borrow<Self::Key> {
+    /// Borrowed form of the ID.
+    type Key: ?Sized + Ord + Hash + fmt::Debug + fmt::Display + ToOwned<Owned = Self>;
+}
+
+impl PlayerKey for String {
+    type Key = str;
+}
+
+impl PlayerKey for PlayerId {
+    type Key = PlayerId;
+}
+
+/// A player interned in a [`PlayerRegistry`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct PlayerId(pub u16);
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// A game state with interned player IDs, for simulation workloads.
+pub type CompactGameState = GameState<PlayerId>;
+
+/// Two-way mapping between string player IDs and [`PlayerId`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerRegistry {
+    names: Vec<String>,
+    ids: BTreeMap<String, PlayerId>,
+}
+
+impl PlayerRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        PlayerRegistry::default()
+    }
+
+    /// The ID for `name`, registering it if it is new.
+    ///
+    /// Returns `BaselomError::ValidationError` once every `u16` is taken.
+    pub fn intern(&mut self, name: &str) -> Result<PlayerId, BaselomError> {
+        if let Some(&id) = self.ids.get(name) {
+            return Ok(id);
+        }
+        let id = u16::try_from(self.names.len()).map(PlayerId).map_err(|_| {
+            BaselomError::ValidationError(format!(
+                "Cannot register more than {} players",
+                u32::from(u16::MAX) + 1
+            ))
+        })?;
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        Ok(id)
+    }
+
+    /// The ID registered for `name`, if any.
+    pub fn id(&self, name: &str) -> Option<PlayerId> {
+        self.ids.get(name).copied()
+    }
+
+    /// The name registered as `id`, if any.
+    pub fn name(&self, id: PlayerId) -> Option<&str> {
+        self.names.get(usize::from(id.0)).map(String::as_str)
+    }
+
+    /// Number of registered players.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether no players are registered.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Intern every player in `state`.
+    ///
+    /// Errors as [`PlayerRegistry::intern`].
+    pub fn compact(&mut self, state: &GameState) -> Result<CompactGameState, BaselomError> {
+        state.map_players(|name| self.intern(name))
+    }
+
+    /// The string-based state for a compact one, for serialization.
+    ///
+    /// Returns `BaselomError::ValidationError` for an ID this registry did
+    /// not hand out.
+    pub fn expand(&self, state: &CompactGameState) -> Result<GameState, BaselomError> {
+        state.map_players(|&id| {
+            self.name(id)
+                .map(str::to_string)
+                .ok_or_else(|| BaselomError::ValidationError(format!("Unknown player ID {}", id)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{
+        apply_event_detailed, apply_pitch, resume, step, step_pitch, substitute_batter,
+        substitute_runner, suspend,
+    };
+    use crate::models::{
+        Advance, Base, GameEvent, GameRules, InningHalf, Lineup, PitchResult, RunnerAdvance,
+    };
+
+    fn tracked_state() -> GameState {
+        let mut state = GameState::builder()
+            .runner_on(Base::First, "a2")
+            .runner_on(Base::Third, "a1")
+            .batter("a3")
+            .pitcher("h9")
+            .lineups(
+                Lineup::new((1..=9).map(|n| format!("h{}", n))).with_pitcher("h9"),
+                Lineup::new((1..=9).map(|n| format!("a{}", n))).with_pitcher("a9"),
+            )
+            .build()
+            .unwrap();
+        state.lineups.away.index = 2;
+        state.pitch_counts.insert("h9".to_string(), 12);
+        state.batters_faced.insert("h9".to_string(), 2);
+        state.last_batters.away = Some("a2".to_string());
+        state.removed_players.home.push("h0".to_string());
+        state
+    }
+
+    #[test]
+    fn test_intern() {
+        let mut registry = PlayerRegistry::new();
+        assert!(registry.is_empty());
+        let a = registry.intern("a").unwrap();
+        let b = registry.intern("b").unwrap();
+        assert_eq!(registry.intern("a").unwrap(), a);
+        assert_ne!(a, b);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.id("b"), Some(b));
+        assert_eq!(registry.id("c"), None);
+        assert_eq!(registry.name(a), Some("a"));
+        assert_eq!(registry.name(PlayerId(7)), None);
+    }
+
+    #[test]
+    fn test_registry_full() {
+        let mut registry = PlayerRegistry::new();
+        for n in 0..=u32::from(u16::MAX) {
+            registry.intern(&n.to_string()).unwrap();
+        }
+        let result = registry.intern("one too many");
+        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+        assert!(registry.intern("0").is_ok());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut registry = PlayerRegistry::new();
+        let state = tracked_state();
+        let compact = registry.compact(&state).unwrap();
+        assert_eq!(
+            compact.bases.runner_at(Base::First),
+            registry.id("a2").as_ref()
+        );
+        assert_eq!(compact.bases.runner_at(Base::Second), None);
+        assert_eq!(compact.pitches_thrown(&registry.id("h9").unwrap()), 12);
+        assert_eq!(registry.expand(&compact).unwrap(), state);
+    }
+
+    #[test]
+    fn test_round_trip_without_players() {
+        let mut registry = PlayerRegistry::new();
+        let state = GameState::new();
+        let compact = registry.compact(&state).unwrap();
+        assert!(registry.is_empty());
+        assert_eq!(compact.current_batter_id, None);
+        assert_eq!(compact.current_pitcher_id, None);
+        assert!(compact.bases.is_empty());
+        assert_eq!(registry.expand(&compact).unwrap(), state);
+    }
+
+    #[test]
+    fn test_expand_unknown_id() {
+        let mut registry = PlayerRegistry::new();
+        let mut compact = registry.compact(&tracked_state()).unwrap();
+        compact.current_batter_id = Some(PlayerId(40));
+        assert_eq!(
+            registry.expand(&compact).unwrap_err().to_string(),
+            "Validation error: Unknown player ID #40"
+        );
+    }
+
+    #[test]
+    fn test_engine_runs_on_compact_state() {
+        let rules = GameRules::default();
+        let mut registry = PlayerRegistry::new();
+        let mut state = tracked_state();
+        let mut compact = registry.compact(&state).unwrap();
+
+        let pitches = [
+            PitchResult::Ball,
+            PitchResult::Foul,
+            PitchResult::StrikeSwinging,
+            PitchResult::StrikeSwinging,
+        ];
+        for pitch in pitches {
+            state = apply_pitch(&state, pitch, &rules).unwrap();
+            step_pitch(&mut compact, pitch, &rules).unwrap();
+        }
+        let events = [
+            GameEvent::Single {
+                advancement: vec![RunnerAdvance {
+                    from: Base::Third,
+                    to: Advance::Home,
+                    out_was_force: false,
+                    force_no_rbi: false,
+                }],
+            },
+            GameEvent::HomeRun,
+            GameEvent::GroundOut {
+                advancement: vec![],
+            },
+            GameEvent::FlyOut {
+                advancement: vec![],
+            },
+        ];
+        for event in &events {
+            state.current_batter_id = state.due_up().map(str::to_string);
+            compact.current_batter_id = compact.due_up().copied();
+            let expected = apply_event_detailed(&state, event, &rules).unwrap();
+            let outcome = step(&mut compact, event, &rules).unwrap();
+            let scorers: Vec<_> = outcome
+                .scorers
+                .iter()
+                .map(|&id| registry.name(id).unwrap())
+                .collect();
+            assert_eq!(scorers, expected.scorers);
+            state = expected.state;
+        }
+
+        assert_eq!(state.half, InningHalf::Bottom);
+        assert_eq!(registry.expand(&compact).unwrap(), state);
+    }
+
+    #[test]
+    fn test_substitutions_on_compact_state() {
+        let rules = GameRules::default();
+        let mut registry = PlayerRegistry::new();
+        let state = tracked_state();
+        let compact = registry.compact(&state).unwrap();
+        let pinch_hitter = registry.intern("ph").unwrap();
+        let pinch_runner = registry.intern("pr").unwrap();
+
+        let state = resume(&suspend(&state).unwrap()).unwrap();
+        let state = substitute_batter(&state, 2, "ph", &rules).unwrap();
+        let state = substitute_runner(&state, Base::First, "pr", &rules).unwrap();
+        let compact = resume(&suspend(&compact).unwrap()).unwrap();
+        let compact = substitute_batter(&compact, 2, &pinch_hitter, &rules).unwrap();
+        let compact = substitute_runner(&compact, Base::First, &pinch_runner, &rules).unwrap();
+
+        assert_eq!(compact.current_batter_id, Some(pinch_hitter));
+        assert_eq!(compact.bases.runner_at(Base::First), Some(&pinch_runner));
+        assert_eq!(registry.expand(&compact).unwrap(), state);
+    }
+}
@@ -0,0 +1,191 @@
+//! Named rule sets for common leagues.
+//!
+//! Each preset is a [`GameRules`] constructor, also reachable by name with
+//! [`GameRules::preset`]. Serialized rules with a `preset` key start from
+//! that preset, and any other keys override its values.
+
+use crate::models::{Count, CourtesyRunnerRule, ExtraInningsTiebreaker, GameRules, MercyRule};
+
+/// Names accepted by [`GameRules::preset`] and the `preset` key.
+pub const PRESET_NAMES: [&str; 4] = ["mlb", "little_league", "slowpitch_softball", "npb"];
+
+impl GameRules {
+    /// The rules preset called `name` (one of [`PRESET_NAMES`]).
+    pub fn preset(name: &str) -> Option<GameRules> {
+        match name {
+            "mlb" => Some(GameRules::mlb()),
+            "little_league" => Some(GameRules::little_league()),
+            "slowpitch_softball" => Some(GameRules::slowpitch_softball()),
+            "npb" => Some(GameRules::npb()),
+            _ => None,
+        }
+    }
+
+    /// Major League Baseball: universal designated hitter, a runner on
+    /// second in extra innings, the three-batter minimum and five mound
+    /// visits per game.
+    pub fn mlb() -> GameRules {
+        GameRules {
+            preset: Some("mlb".to_string()),
+            designated_hitter: true,
+            extra_innings_tiebreaker: Some(ExtraInningsTiebreaker::RunnerOnSecond),
+            three_batter_minimum: true,
+            mound_visit_limit: Some(5),
+            ..GameRules::default()
+        }
+    }
+
+    /// Little League Major Division: six innings, a ten-run rule after four,
+    /// an 85-pitch limit, no dropped third strike, re-entry, and a ball
+    /// instead of a balk with the bases empty.
+    pub fn little_league() -> GameRules {
+        GameRules {
+            preset: Some("little_league".to_string()),
+            max_innings: Some(6),
+            dropped_third_strike: false,
+            balk_with_empty_bases_is_ball: true,
+            mercy_rule: Some(MercyRule {
+                run_differential: 10,
+                after_inning: 4,
+            }),
+            allow_reentry: true,
+            pitch_limit: Some(85),
+            starter_win_requires_five_innings: false,
+            ..GameRules::default()
+        }
+    }
+
+    /// Recreational slow-pitch softball: seven innings, every batter
+    /// starting at 1-1, five runs a half inning until an open last inning,
+    /// ten-player lineups, re-entry, courtesy runners for the catcher, no
+    /// dropped third strike and a ten-run rule after five.
+    pub fn slowpitch_softball() -> GameRules {
+        GameRules {
+            preset: Some("slowpitch_softball".to_string()),
+            max_innings: Some(7),
+            dropped_third_strike: false,
+            starting_count: Count::new(1, 1),
+            mercy_rule: Some(MercyRule {
+                run_differential: 10,
+                after_inning: 5,
+            }),
+            inning_run_limit: Some(5),
+            last_inning_uncapped: true,
+            min_lineup_size: 10,
+            allow_reentry: true,
+            starter_win_requires_five_innings: false,
+            courtesy_runner: CourtesyRunnerRule::CatcherOnly,
+            ..GameRules::default()
+        }
+    }
+
+    /// Nippon Professional Baseball regular season, Central League: no
+    /// designated hitter (set `designated_hitter` for Pacific League games)
+    /// and games ending tied after twelve innings.
+    pub fn npb() -> GameRules {
+        GameRules {
+            preset: Some("npb".to_string()),
+            allow_tie_after_innings: Some(12),
+            ..GameRules::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{apply_event, apply_pitch, start};
+    use crate::models::{GameEvent, GameState, GameStatus, PitchResult};
+
+    #[test]
+    fn test_presets_by_name() {
+        for name in PRESET_NAMES {
+            let rules = GameRules::preset(name).unwrap();
+            assert_eq!(rules.preset.as_deref(), Some(name));
+        }
+        assert_eq!(GameRules::preset("cricket"), None);
+    }
+
+    #[test]
+    fn test_preset_key_fields() {
+        let mlb = GameRules::mlb();
+        assert!(mlb.designated_hitter);
+        assert_eq!(mlb.max_innings, Some(9));
+        assert_eq!(
+            mlb.extra_innings_tiebreaker,
+            Some(ExtraInningsTiebreaker::RunnerOnSecond)
+        );
+        assert!(mlb.three_batter_minimum);
+        assert_eq!(mlb.courtesy_runner, CourtesyRunnerRule::None);
+
+        let little_league = GameRules::little_league();
+        assert_eq!(little_league.max_innings, Some(6));
+        assert_eq!(little_league.pitch_limit, Some(85));
+        assert!(!little_league.dropped_third_strike);
+
+        let softball = GameRules::slowpitch_softball();
+        assert_eq!(softball.max_innings, Some(7));
+        assert_eq!(softball.starting_count, Count::new(1, 1));
+        assert!(softball.allow_reentry);
+        assert_eq!(softball.min_lineup_size, 10);
+        assert_eq!(softball.inning_run_limit, Some(5));
+        assert!(softball.last_inning_uncapped);
+
+        let npb = GameRules::npb();
+        assert!(!npb.designated_hitter);
+        assert_eq!(npb.allow_tie_after_innings, Some(12));
+        assert_eq!(npb.extra_innings_tiebreaker, None);
+    }
+
+    #[test]
+    fn test_preset_key_expands_on_deserialization() {
+        let rules: GameRules = serde_json::from_str(r#"{"preset": "slowpitch_softball"}"#).unwrap();
+        assert_eq!(rules, GameRules::slowpitch_softball());
+
+        let rules: GameRules =
+            serde_json::from_str(r#"{"preset": "mlb", "designated_hitter": false}"#).unwrap();
+        assert_eq!(
+            rules,
+            GameRules {
+                designated_hitter: false,
+                ..GameRules::mlb()
+            }
+        );
+
+        let json = serde_json::to_string(&GameRules::npb()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<GameRules>(&json).unwrap(),
+            GameRules::npb()
+        );
+        let camel = GameRules::little_league().to_json_camel().unwrap();
+        assert_eq!(
+            GameRules::from_json_camel(&camel).unwrap(),
+            GameRules::little_league()
+        );
+
+        let result = serde_json::from_str::<GameRules>(r#"{"preset": "cricket"}"#);
+        assert!(result.unwrap_err().to_string().contains("cricket"));
+    }
+
+    #[test]
+    fn test_softball_game_ends_after_seven_innings() {
+        let rules = GameRules::slowpitch_softball();
+        let mut state = start(&GameState {
+            current_batter_id: Some("batter".to_string()),
+            current_pitcher_id: Some("pitcher".to_string()),
+            ..GameState::with_rules(&rules)
+        })
+        .unwrap();
+        assert_eq!(state.count, Count::new(1, 1));
+        state = apply_event(&state, &GameEvent::HomeRun, &rules).unwrap();
+        while state.status == GameStatus::InProgress {
+            state.current_batter_id = Some("batter".to_string());
+            state = apply_pitch(&state, PitchResult::StrikeSwinging, &rules).unwrap();
+            assert_ne!(state.count.strikes, 0);
+            state = apply_pitch(&state, PitchResult::StrikeSwinging, &rules).unwrap();
+        }
+        assert_eq!(state.inning, 7);
+        assert_eq!(state.line_score.len(), 7);
+        assert_eq!(state.score.away, 1);
+    }
+}
@@ -0,0 +1,93 @@
+//! JSON Schema for the serialized public types (feature `schema`).
+//!
+//! The schemas describe the default snake_case serde form, for validating
+//! payloads and generating client types such as TypeScript definitions.
+
+use schemars::{schema_for, JsonSchema};
+
+use crate::errors::BaselomError;
+use crate::models::{GameEvent, GameRules, GameState};
+
+/// Schema of a serialized [`GameState`].
+pub fn game_state_schema() -> serde_json::Value {
+    to_value::<GameState>()
+}
+
+/// Schema of serialized [`GameRules`].
+pub fn game_rules_schema() -> serde_json::Value {
+    to_value::<GameRules>()
+}
+
+/// Schema of a serialized [`GameEvent`].
+pub fn game_event_schema() -> serde_json::Value {
+    to_value::<GameEvent>()
+}
+
+/// Schema of a serialized [`BaselomError`] (`{"code": ..., "message": ...}`).
+pub fn error_schema() -> serde_json::Value {
+    to_value::<BaselomError>()
+}
+
+fn to_value<T: JsonSchema>() -> serde_json::Value {
+    schema_for!(T).to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every schema, keyed by name, as stored in the snapshot.
+    ///
+    /// Descriptions come from doc comments and are left out, so only
+    /// changes to the shape fail the snapshot test.
+    fn schemas() -> serde_json::Value {
+        let mut schemas = serde_json::json!({
+            "game_state": game_state_schema(),
+            "game_rules": game_rules_schema(),
+            "game_event": game_event_schema(),
+            "error": error_schema(),
+        });
+        strip_descriptions(&mut schemas);
+        schemas
+    }
+
+    fn strip_descriptions(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.remove("description");
+                map.values_mut().for_each(strip_descriptions);
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(strip_descriptions),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_schema_matches_snapshot() {
+        let snapshot: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/schema.json")).unwrap();
+        let current = schemas();
+        assert!(
+            current == snapshot,
+            "The serialized shape of the public types changed. If intended, \
+             replace tests/fixtures/schema.json with:\n{}",
+            serde_json::to_string_pretty(&current).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_game_state_schema_fields() {
+        let schema = game_state_schema();
+        let properties = &schema["properties"];
+        for field in [
+            "inning", "half", "outs", "balls", "strikes", "bases", "score",
+        ] {
+            assert!(properties.get(field).is_some(), "missing {}", field);
+        }
+        assert!(properties.get("count").is_none());
+        assert_eq!(
+            error_schema()["required"],
+            serde_json::json!(["code", "message"])
+        );
+    }
+}
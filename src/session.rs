@@ -0,0 +1,301 @@
+//! Game session with undo and redo.
+//!
+//! A [`GameSession`] holds the current state and rules for an interactive
+//! client, keeping snapshots of earlier states so mistaken plays can be taken
+//! back. It performs no I/O; every transition goes through the engine.
+
+use std::collections::VecDeque;
+
+use crate::engine::apply_play_detailed;
+use crate::errors::BaselomError;
+use crate::models::{GameEvent, GameRules, GameState, PitchResult, Play, Team, TransitionOutcome};
+
+/// Number of undoable plays kept by [`GameSession::new`].
+pub const DEFAULT_HISTORY_LIMIT: usize = 256;
+
+/// Current game state plus bounded undo/redo history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSession {
+    state: GameState,
+    rules: GameRules,
+    history: VecDeque<GameState>,
+    redo: Vec<GameState>,
+    history_limit: usize,
+}
+
+impl GameSession {
+    /// Start a session at `state`, keeping up to [`DEFAULT_HISTORY_LIMIT`]
+    /// undoable plays.
+    pub fn new(state: GameState, rules: GameRules) -> Self {
+        Self::with_history_limit(state, rules, DEFAULT_HISTORY_LIMIT)
+    }
+
+    /// Start a session keeping up to `history_limit` undoable plays; the
+    /// oldest snapshot is dropped once the limit is reached, and a limit of
+    /// zero disables undo.
+    pub fn with_history_limit(state: GameState, rules: GameRules, history_limit: usize) -> Self {
+        GameSession {
+            state,
+            rules,
+            history: VecDeque::new(),
+            redo: Vec::new(),
+            history_limit,
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Rules the session applies.
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    /// Number of plays that can be undone.
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Number of undone plays that can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Apply `play` and make it undoable, discarding anything undone.
+    ///
+    /// Returns the engine's error, leaving the session unchanged, if the
+    /// play is rejected.
+    pub fn apply(&mut self, play: &Play) -> Result<TransitionOutcome, BaselomError> {
+        let outcome = apply_play_detailed(&self.state, play, &self.rules)?;
+        let previous = std::mem::replace(&mut self.state, outcome.state.clone());
+        self.push_history(previous);
+        self.redo.clear();
+        Ok(outcome)
+    }
+
+    /// Send `batter` to the plate.
+    pub fn batter(&mut self, batter: &str) -> Result<TransitionOutcome, BaselomError> {
+        self.apply(&Play::Batter(batter.to_string()))
+    }
+
+    /// Apply a pitch.
+    pub fn pitch(&mut self, pitch_result: PitchResult) -> Result<TransitionOutcome, BaselomError> {
+        self.apply(&Play::Pitch(pitch_result))
+    }
+
+    /// Apply an event.
+    pub fn event(&mut self, event: GameEvent) -> Result<TransitionOutcome, BaselomError> {
+        self.apply(&Play::Event(event))
+    }
+
+    /// Record a mound visit by `team`.
+    pub fn mound_visit(&mut self, team: Team) -> Result<TransitionOutcome, BaselomError> {
+        self.apply(&Play::MoundVisit(team))
+    }
+
+    /// Bring in `pitcher` for the fielding team.
+    pub fn pitching_change(
+        &mut self,
+        pitcher: &str,
+        injured: bool,
+    ) -> Result<TransitionOutcome, BaselomError> {
+        self.apply(&Play::PitchingChange {
+            pitcher: pitcher.to_string(),
+            injured,
+        })
+    }
+
+    /// Make a double switch for the fielding team.
+    pub fn double_switch(
+        &mut self,
+        pitcher: &str,
+        lineup_slot: usize,
+        fielder: &str,
+    ) -> Result<TransitionOutcome, BaselomError> {
+        self.apply(&Play::DoubleSwitch {
+            pitcher: pitcher.to_string(),
+            lineup_slot,
+            fielder: fielder.to_string(),
+        })
+    }
+
+    /// Restore the state before the last play.
+    ///
+    /// Returns `BaselomError::StateError` if there is nothing to undo.
+    pub fn undo(&mut self) -> Result<(), BaselomError> {
+        let previous = self
+            .history
+            .pop_back()
+            .ok_or_else(|| BaselomError::StateError("Nothing to undo".to_string()))?;
+        self.redo.push(std::mem::replace(&mut self.state, previous));
+        Ok(())
+    }
+
+    /// Reapply the last undone play.
+    ///
+    /// Returns `BaselomError::StateError` if there is nothing to redo.
+    pub fn redo(&mut self) -> Result<(), BaselomError> {
+        let next = self
+            .redo
+            .pop()
+            .ok_or_else(|| BaselomError::StateError("Nothing to redo".to_string()))?;
+        let previous = std::mem::replace(&mut self.state, next);
+        self.push_history(previous);
+        Ok(())
+    }
+
+    fn push_history(&mut self, state: GameState) {
+        if self.history_limit == 0 {
+            return;
+        }
+        if self.history.len() == self.history_limit {
+            self.history.pop_front();
+        }
+        self.history.push_back(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{GameStatus, InningHalf};
+
+    fn session() -> GameSession {
+        let state = GameState {
+            current_batter_id: Some("batter".to_string()),
+            current_pitcher_id: Some("pitcher".to_string()),
+            status: GameStatus::InProgress,
+            ..GameState::new()
+        };
+        GameSession::new(state, GameRules::default())
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut session = session();
+        let start = session.state().clone();
+        session.pitch(PitchResult::Ball).unwrap();
+        let after_ball = session.state().clone();
+        session.pitch(PitchResult::StrikeCalled).unwrap();
+        assert_eq!(session.history_len(), 2);
+
+        session.undo().unwrap();
+        assert_eq!(*session.state(), after_ball);
+        session.undo().unwrap();
+        assert_eq!(*session.state(), start);
+        assert_eq!(session.history_len(), 0);
+        assert_eq!(session.redo_len(), 2);
+
+        session.redo().unwrap();
+        assert_eq!(*session.state(), after_ball);
+        assert_eq!(session.history_len(), 1);
+    }
+
+    #[test]
+    fn test_undo_across_half_inning_rollover() {
+        let mut session = session();
+        session
+            .event(GameEvent::Single {
+                advancement: vec![],
+            })
+            .unwrap();
+        session.batter("b2").unwrap();
+        session
+            .event(GameEvent::GroundOut {
+                advancement: vec![],
+            })
+            .unwrap();
+        session.batter("b3").unwrap();
+        session
+            .event(GameEvent::FlyOut {
+                advancement: vec![],
+            })
+            .unwrap();
+        session.batter("b4").unwrap();
+        session.pitch(PitchResult::Ball).unwrap();
+        session.pitch(PitchResult::StrikeSwinging).unwrap();
+        let before = session.state().clone();
+        assert_eq!(before.outs, 2);
+        assert!(!before.bases.is_empty());
+
+        session
+            .event(GameEvent::LineOut {
+                advancement: vec![],
+            })
+            .unwrap();
+        assert_eq!(session.state().half, InningHalf::Bottom);
+        assert!(session.state().bases.is_empty());
+
+        session.undo().unwrap();
+        assert_eq!(*session.state(), before);
+        assert_eq!(session.state().count.balls, 1);
+        assert_eq!(session.state().count.strikes, 1);
+    }
+
+    #[test]
+    fn test_new_play_discards_redo() {
+        let mut session = session();
+        session.pitch(PitchResult::Ball).unwrap();
+        session.undo().unwrap();
+        session.pitch(PitchResult::Foul).unwrap();
+        assert_eq!(session.redo_len(), 0);
+        assert!(matches!(session.redo(), Err(BaselomError::StateError(_))));
+        assert_eq!(session.state().count.strikes, 1);
+    }
+
+    #[test]
+    fn test_nothing_to_undo() {
+        let mut session = session();
+        let result = session.undo();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "State error: Nothing to undo"
+        );
+    }
+
+    #[test]
+    fn test_rejected_play_is_not_recorded() {
+        let mut session = session();
+        session
+            .event(GameEvent::Single {
+                advancement: vec![],
+            })
+            .unwrap();
+        assert!(session
+            .event(GameEvent::Single {
+                advancement: vec![]
+            })
+            .is_err());
+        assert_eq!(session.history_len(), 1);
+    }
+
+    #[test]
+    fn test_history_limit() {
+        let state = session().state().clone();
+        let mut session = GameSession::with_history_limit(state, GameRules::default(), 2);
+        for _ in 0..3 {
+            session.pitch(PitchResult::Foul).unwrap();
+        }
+        assert_eq!(session.history_len(), 2);
+        session.undo().unwrap();
+        session.undo().unwrap();
+        assert!(session.undo().is_err());
+        assert_eq!(session.state().count.strikes, 1);
+
+        let mut no_history =
+            GameSession::with_history_limit(session.state().clone(), GameRules::default(), 0);
+        no_history.pitch(PitchResult::Ball).unwrap();
+        assert_eq!(no_history.history_len(), 0);
+    }
+
+    #[test]
+    fn test_mound_visit_is_undoable() {
+        let mut session = session();
+        session.mound_visit(Team::Home).unwrap();
+        assert_eq!(session.state().mound_visits.home, 1);
+        session.undo().unwrap();
+        assert_eq!(session.state().mound_visits.home, 0);
+    }
+}
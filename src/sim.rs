@@ -0,0 +1,834 @@
+//! Seeded game simulation.
+//!
+//! A [`Simulator`] plays a game to the end by asking an [`OutcomeModel`]
+//! for each plate appearance's event and applying it with the engine, so
+//! simulated games follow the same rules as recorded ones. The same seed
+//! always plays the same game. [`win_probability`] plays many games from
+//! one state to estimate each team's chance of winning, and
+//! [`run_expectancy`] works out a model's expected runs in each base-out
+//! state without playing any. With the `parallel` feature,
+//! [`simulate_many`] spreads many games across threads.
+
+use std::collections::BTreeMap;
+use std::ops::Index;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{base_out_transition, is_game_over, start, step};
+use crate::errors::BaselomError;
+use crate::log::GameLog;
+use crate::models::{
+    BaseOutEvent, BaseOutState, GameEvent, GameRules, GameState, GameStatus, Score,
+};
+
+/// Plate appearances a simulated game may last before it is abandoned.
+pub const MAX_PLATE_APPEARANCES: usize = 10_000;
+
+/// Plate appearances sampled per base-out state when estimating a model's
+/// [`OutcomeModel::base_out_probabilities`].
+pub const ESTIMATE_SAMPLES: usize = 10_000;
+
+/// Chooses the result of a plate appearance.
+///
+/// Models shared across threads by `simulate_many` must also be `Sync`.
+pub trait OutcomeModel {
+    /// The event ending the plate appearance at `state`, which must be one
+    /// the engine accepts there.
+    fn sample_plate_appearance(&self, state: &GameState, rng: &mut impl Rng) -> GameEvent;
+
+    /// Probability of each [`BaseOutEvent`], in [`BaseOutEvent::ALL`]
+    /// order, for a plate appearance in `state`.
+    ///
+    /// By default this is estimated from [`ESTIMATE_SAMPLES`] plate
+    /// appearances sampled at [`BaseOutState::to_game_state`] with a fixed
+    /// seed, leaving out events with no [`BaseOutEvent::from_event`].
+    /// Models that know their probabilities should return them instead.
+    fn base_out_probabilities(&self, state: BaseOutState) -> [f64; 6] {
+        let game = state.to_game_state();
+        let mut rng = StdRng::seed_from_u64(u64::from(state.to_u8()));
+        let mut counts = [0usize; 6];
+        for _ in 0..ESTIMATE_SAMPLES {
+            let event = self.sample_plate_appearance(&game, &mut rng);
+            if let Some(event) = BaseOutEvent::from_event(&event) {
+                counts[event as usize] += 1;
+            }
+        }
+        let total = counts.iter().sum::<usize>().max(1) as f64;
+        counts.map(|count| count as f64 / total)
+    }
+}
+
+impl<M: OutcomeModel> OutcomeModel for &M {
+    fn sample_plate_appearance(&self, state: &GameState, rng: &mut impl Rng) -> GameEvent {
+        (**self).sample_plate_appearance(state, rng)
+    }
+
+    fn base_out_probabilities(&self, state: BaseOutState) -> [f64; 6] {
+        (**self).base_out_probabilities(state)
+    }
+}
+
+/// Fixed per-plate-appearance probabilities close to a recent major league
+/// average, whatever the situation.
+///
+/// Strikeouts count as outs in play, and walks and hit batters are recorded
+/// as intentional walks, so the rules must allow
+/// `GameRules::allow_auto_intentional_walk` (the default).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeagueAverage;
+
+impl LeagueAverage {
+    /// Each event with its probability.
+    pub const OUTCOMES: [(f64, GameEvent); 8] = [
+        (0.097, GameEvent::IntentionalWalk),
+        (
+            0.140,
+            GameEvent::Single {
+                advancement: Vec::new(),
+            },
+        ),
+        (
+            0.044,
+            GameEvent::Double {
+                advancement: Vec::new(),
+            },
+        ),
+        (
+            0.004,
+            GameEvent::Triple {
+                advancement: Vec::new(),
+            },
+        ),
+        (0.031, GameEvent::HomeRun),
+        (
+            0.330,
+            GameEvent::GroundOut {
+                advancement: Vec::new(),
+            },
+        ),
+        (
+            0.270,
+            GameEvent::FlyOut {
+                advancement: Vec::new(),
+            },
+        ),
+        (
+            0.084,
+            GameEvent::LineOut {
+                advancement: Vec::new(),
+            },
+        ),
+    ];
+}
+
+impl OutcomeModel for LeagueAverage {
+    fn sample_plate_appearance(&self, _state: &GameState, rng: &mut impl Rng) -> GameEvent {
+        let mut roll: f64 = rng.random();
+        for (probability, event) in &Self::OUTCOMES {
+            if roll < *probability {
+                return event.clone();
+            }
+            roll -= probability;
+        }
+        // Rounding can leave a sliver past the last outcome.
+        Self::OUTCOMES[Self::OUTCOMES.len() - 1].1.clone()
+    }
+
+    fn base_out_probabilities(&self, _state: BaseOutState) -> [f64; 6] {
+        let mut probabilities = [0.0; 6];
+        for (probability, event) in &Self::OUTCOMES {
+            if let Some(event) = BaseOutEvent::from_event(event) {
+                probabilities[event as usize] += probability;
+            }
+        }
+        probabilities
+    }
+}
+
+/// Plays games from one starting state with an outcome model.
+#[derive(Debug, Clone)]
+pub struct Simulator<M> {
+    rules: GameRules,
+    initial: GameState,
+    seed: u64,
+    model: M,
+}
+
+impl<M: OutcomeModel> Simulator<M> {
+    /// A simulator playing from `initial` (started first if it has not
+    /// been) under `rules`, drawing from an RNG seeded with `seed`.
+    pub fn new(rules: GameRules, initial: GameState, seed: u64, model: M) -> Self {
+        Simulator {
+            rules,
+            initial,
+            seed,
+            model,
+        }
+    }
+
+    /// Play the game to the end, returning the log of its plate appearances;
+    /// the log's state is the final state.
+    ///
+    /// Returns the engine's error if the initial state cannot be played or
+    /// the model chooses an event the engine rejects (including
+    /// `BaselomError::NoCurrentBatter` when the batting team has no
+    /// lineup), and `BaselomError::StateError` if the game lasts more than
+    /// [`MAX_PLATE_APPEARANCES`].
+    pub fn run(&self) -> Result<GameLog, BaselomError> {
+        let mut log = GameLog::new(started(&self.initial)?);
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        for _ in 0..MAX_PLATE_APPEARANCES {
+            if is_game_over(log.state(), &self.rules) {
+                return Ok(log);
+            }
+            let event = self.model.sample_plate_appearance(log.state(), &mut rng);
+            log.event(event, &self.rules)?;
+        }
+        Err(unfinished())
+    }
+}
+
+/// Share of simulated games won by each team or left tied.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct WinProb {
+    pub home: f64,
+    pub away: f64,
+    pub tie: f64,
+}
+
+/// Estimate each team's chance of winning from `state` by playing `n`
+/// games to the end with `model`.
+///
+/// Games end exactly when the engine ends them, so walk-offs, the mercy
+/// rule, `GameRules::max_innings` and `GameRules::allow_tie_after_innings`
+/// are all taken into account. A state
+/// that is already final counts as its result `n` times.
+///
+/// Returns `BaselomError::ValidationError` if `n` is zero, and otherwise
+/// errors as [`Simulator::run`].
+pub fn win_probability(
+    state: &GameState,
+    rules: &GameRules,
+    model: &impl OutcomeModel,
+    n: usize,
+    seed: u64,
+) -> Result<WinProb, BaselomError> {
+    if n == 0 {
+        return Err(BaselomError::ValidationError(
+            "Win probability needs at least one simulated game".to_string(),
+        ));
+    }
+    let initial = started(state)?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (mut home, mut away) = (0usize, 0usize);
+    for _ in 0..n {
+        let mut game = initial.clone();
+        play_out(&mut game, rules, model, &mut rng)?;
+        match game.score.home.cmp(&game.score.away) {
+            std::cmp::Ordering::Greater => home += 1,
+            std::cmp::Ordering::Less => away += 1,
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+    let total = n as f64;
+    Ok(WinProb {
+        home: home as f64 / total,
+        away: away as f64 / total,
+        tie: (n - home - away) as f64 / total,
+    })
+}
+
+/// Expected runs from each base-out state to the end of the half inning.
+///
+/// Serializes as the 24 values in [`BaseOutState::ALL`] order.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct RunExpectancyMatrix {
+    runs: [f64; 24],
+}
+
+impl RunExpectancyMatrix {
+    /// Expected runs from `state` to the end of the half inning.
+    pub fn get(&self, state: BaseOutState) -> f64 {
+        self.runs[usize::from(state.to_u8())]
+    }
+
+    /// Each base-out state with its expected runs, in
+    /// [`BaseOutState::ALL`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (BaseOutState, f64)> + '_ {
+        BaseOutState::ALL.into_iter().zip(self.runs.iter().copied())
+    }
+}
+
+impl Index<BaseOutState> for RunExpectancyMatrix {
+    type Output = f64;
+
+    fn index(&self, state: BaseOutState) -> &f64 {
+        &self.runs[usize::from(state.to_u8())]
+    }
+}
+
+/// The run expectancy matrix for `model`, from its
+/// [`OutcomeModel::base_out_probabilities`] and
+/// [`crate::engine::base_out_transition`].
+///
+/// Solved exactly rather than simulated: outs never go down, so the states
+/// with two outs are solved first, then one, then none.
+///
+/// Returns `BaselomError::ValidationError` if a state's probabilities are
+/// not a distribution, or if the model can keep a half inning going
+/// forever without recording outs.
+pub fn run_expectancy(model: &impl OutcomeModel) -> Result<RunExpectancyMatrix, BaselomError> {
+    const BASE_STATES: usize = 8;
+    let mut runs = [0.0; 24];
+    for outs in (0..3).rev() {
+        // One row per base state: x[b] - sum p * x[next] = sum p * (runs + known).
+        let mut system = [[0.0; BASE_STATES + 1]; BASE_STATES];
+        for (bases, row) in system.iter_mut().enumerate() {
+            let state = BaseOutState::ALL[outs * BASE_STATES + bases];
+            let probabilities = model.base_out_probabilities(state);
+            check_distribution(state, &probabilities)?;
+            row[bases] += 1.0;
+            for (event, probability) in BaseOutEvent::ALL.into_iter().zip(probabilities) {
+                let transition = base_out_transition(state, event);
+                let mut constant = f64::from(transition.runs_scored);
+                match transition.next {
+                    Some(next) if usize::from(next.outs()) == outs => {
+                        row[usize::from(next.bases_occupied())] -= probability;
+                    }
+                    Some(next) => constant += runs[usize::from(next.to_u8())],
+                    None => {}
+                }
+                row[BASE_STATES] += probability * constant;
+            }
+        }
+        let solution = solve(system).ok_or_else(|| {
+            BaselomError::ValidationError(format!(
+                "Outcome model can bat forever with {} out(s)",
+                outs
+            ))
+        })?;
+        runs[outs * BASE_STATES..(outs + 1) * BASE_STATES].copy_from_slice(&solution);
+    }
+    Ok(RunExpectancyMatrix { runs })
+}
+
+/// Check that `probabilities` for `state` are non-negative and sum to 1.
+fn check_distribution(state: BaseOutState, probabilities: &[f64; 6]) -> Result<(), BaselomError> {
+    let total: f64 = probabilities.iter().sum();
+    if probabilities.iter().all(|p| *p >= 0.0) && (total - 1.0).abs() < 1e-6 {
+        return Ok(());
+    }
+    Err(BaselomError::ValidationError(format!(
+        "Outcome probabilities for base-out state {} are not a distribution: {:?}",
+        state.to_u8(),
+        probabilities
+    )))
+}
+
+/// Solve an augmented `N`x`N` linear system by Gaussian elimination, or `None`
+/// if it is singular.
+fn solve<const N: usize, const M: usize>(mut system: [[f64; M]; N]) -> Option<[f64; N]> {
+    for column in 0..N {
+        let pivot = (column..N)
+            .max_by(|&a, &b| system[a][column].abs().total_cmp(&system[b][column].abs()))?;
+        if system[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        system.swap(column, pivot);
+        let pivot_row = system[column];
+        for (index, row) in system.iter_mut().enumerate() {
+            if index != column {
+                let factor = row[column] / pivot_row[column];
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(column) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+    let mut solution = [0.0; N];
+    for (row, value) in solution.iter_mut().enumerate() {
+        *value = system[row][M - 1] / system[row][row];
+    }
+    Some(solution)
+}
+
+/// Results of many simulated games from one state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SimSummary {
+    /// Number of games played
+    pub games: usize,
+    pub home_wins: usize,
+    pub away_wins: usize,
+    pub ties: usize,
+    /// Mean of home runs minus away runs at the end of each game
+    pub mean_run_differential: f64,
+    /// Population standard deviation of the run differential
+    pub run_differential_stddev: f64,
+    /// How many games ended with each final score, in order of away then
+    /// home runs
+    pub scores: Vec<ScoreFrequency>,
+}
+
+/// A final score and the number of simulated games ending with it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScoreFrequency {
+    pub score: Score,
+    pub games: usize,
+}
+
+impl SimSummary {
+    /// Summarize the final scores of a batch of games, such as those
+    /// played one at a time with [`Simulator`].
+    pub fn from_scores(scores: &[Score]) -> Self {
+        let mut summary = SimSummary {
+            games: scores.len(),
+            ..SimSummary::default()
+        };
+        let mut histogram = BTreeMap::new();
+        for score in scores {
+            match score.home.cmp(&score.away) {
+                std::cmp::Ordering::Greater => summary.home_wins += 1,
+                std::cmp::Ordering::Less => summary.away_wins += 1,
+                std::cmp::Ordering::Equal => summary.ties += 1,
+            }
+            *histogram.entry((score.away, score.home)).or_insert(0) += 1;
+        }
+        if !scores.is_empty() {
+            let differentials: Vec<f64> = scores
+                .iter()
+                .map(|score| f64::from(score.home) - f64::from(score.away))
+                .collect();
+            let games = scores.len() as f64;
+            let mean = differentials.iter().sum::<f64>() / games;
+            let variance = differentials
+                .iter()
+                .map(|differential| (differential - mean).powi(2))
+                .sum::<f64>()
+                / games;
+            summary.mean_run_differential = mean;
+            summary.run_differential_stddev = variance.sqrt();
+        }
+        summary.scores = histogram
+            .into_iter()
+            .map(|((away, home), games)| ScoreFrequency {
+                score: Score { home, away },
+                games,
+            })
+            .collect();
+        summary
+    }
+}
+
+/// Play `n` games from `state` across rayon's thread pool and summarize
+/// them.
+///
+/// Game `i` draws from an RNG seeded with `seed + i`, so the summary
+/// depends only on the arguments, not on how many threads run it. The
+/// model is shared between threads, hence the `Sync` bound.
+///
+/// Errors as [`Simulator::run`], with the error from the lowest-numbered
+/// failing game.
+#[cfg(feature = "parallel")]
+pub fn simulate_many(
+    state: &GameState,
+    rules: &GameRules,
+    model: &(impl OutcomeModel + Sync),
+    n: usize,
+    seed: u64,
+) -> Result<SimSummary, BaselomError> {
+    let initial = started(state)?;
+    let scores = (0..n)
+        .into_par_iter()
+        .map(|index| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(index as u64));
+            let mut game = initial.clone();
+            play_out(&mut game, rules, model, &mut rng)?;
+            Ok(game.score)
+        })
+        .collect::<Result<Vec<Score>, BaselomError>>()?;
+    Ok(SimSummary::from_scores(&scores))
+}
+
+/// `state`, started first if it has not been.
+fn started(state: &GameState) -> Result<GameState, BaselomError> {
+    match state.status {
+        GameStatus::NotStarted => start(state),
+        _ => Ok(state.clone()),
+    }
+}
+
+/// Play `state` to the end in place without recording the plays.
+fn play_out(
+    state: &mut GameState,
+    rules: &GameRules,
+    model: &impl OutcomeModel,
+    rng: &mut StdRng,
+) -> Result<(), BaselomError> {
+    for _ in 0..MAX_PLATE_APPEARANCES {
+        if is_game_over(state, rules) {
+            return Ok(());
+        }
+        let event = model.sample_plate_appearance(state, rng);
+        step(state, &event, rules)?;
+    }
+    Err(unfinished())
+}
+
+/// The error for a game that outlasts [`MAX_PLATE_APPEARANCES`].
+fn unfinished() -> BaselomError {
+    BaselomError::StateError(format!(
+        "Simulated game did not finish within {} plate appearances",
+        MAX_PLATE_APPEARANCES
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{InningHalf, Lineup, MercyRule, Play, Score};
+
+    fn new_game() -> GameState {
+        GameState {
+            lineups: crate::models::PerTeam {
+                home: Lineup::new((1..=9).map(|n| format!("h{}", n))).with_pitcher("h9"),
+                away: Lineup::new((1..=9).map(|n| format!("a{}", n))).with_pitcher("a9"),
+            },
+            current_batter_id: Some("a1".to_string()),
+            current_pitcher_id: Some("h9".to_string()),
+            ..GameState::new()
+        }
+    }
+
+    fn simulate(seed: u64) -> GameLog {
+        Simulator::new(GameRules::default(), new_game(), seed, LeagueAverage)
+            .run()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_outcome_probabilities_sum_to_one() {
+        let total: f64 = LeagueAverage::OUTCOMES.iter().map(|(p, _)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_same_seed_same_game() {
+        let first = simulate(7);
+        let second = simulate(7);
+        assert_eq!(first.state().score, second.state().score);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_game_is_played_to_the_end() {
+        let rules = GameRules::default();
+        for seed in 0..20 {
+            let log = simulate(seed);
+            let state = log.state();
+            assert_eq!(state.status, GameStatus::Final);
+            assert!(state.inning >= 9);
+            assert!(state.winner().is_some());
+            assert!(log
+                .entries()
+                .iter()
+                .all(|entry| matches!(entry.play, Play::Event(_))));
+            let started = start(&new_game()).unwrap();
+            assert_eq!(&log.replay(&started, &rules).unwrap(), state);
+        }
+    }
+
+    #[test]
+    fn test_seeds_play_different_games() {
+        let logs: Vec<_> = (0..5).map(simulate).collect();
+        assert!(logs.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_model_errors_are_returned() {
+        let rules = GameRules {
+            allow_auto_intentional_walk: false,
+            ..GameRules::default()
+        };
+        let results: Vec<_> = (0..20)
+            .map(|seed| Simulator::new(rules.clone(), new_game(), seed, LeagueAverage).run())
+            .collect();
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Err(BaselomError::RuleViolation(_)))));
+    }
+
+    fn late_game(inning: u8, half: InningHalf, score: Score) -> GameState {
+        let mut state = start(&new_game()).unwrap();
+        state.inning = inning;
+        state.half = half;
+        state.score = score;
+        state.line_score.clear();
+        if half == InningHalf::Bottom {
+            state.current_batter_id = Some("h1".to_string());
+            state.current_pitcher_id = Some("a9".to_string());
+        }
+        state
+    }
+
+    #[test]
+    fn test_win_probability_big_lead() {
+        let state = late_game(9, InningHalf::Top, Score { home: 10, away: 0 });
+        let odds = win_probability(&state, &GameRules::default(), &LeagueAverage, 500, 1).unwrap();
+        assert!(odds.home > 0.99, "{:?}", odds);
+        assert!((odds.home + odds.away + odds.tie - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_win_probability_is_deterministic() {
+        let state = late_game(7, InningHalf::Bottom, Score { home: 2, away: 3 });
+        let rules = GameRules::default();
+        let first = win_probability(&state, &rules, &LeagueAverage, 200, 9).unwrap();
+        assert_eq!(
+            first,
+            win_probability(&state, &rules, &LeagueAverage, 200, 9).unwrap()
+        );
+        assert!(first.home > 0.0 && first.away > 0.0);
+        assert_eq!(first.tie, 0.0);
+    }
+
+    #[test]
+    fn test_win_probability_respects_end_conditions() {
+        // A walk-off lead in the bottom of the ninth has already won.
+        let state = late_game(9, InningHalf::Bottom, Score { home: 4, away: 3 });
+        let odds = win_probability(&state, &GameRules::default(), &LeagueAverage, 50, 0).unwrap();
+        assert_eq!(odds.home, 1.0);
+
+        // So has a mercy rule margin.
+        let rules = GameRules {
+            mercy_rule: Some(MercyRule {
+                run_differential: 10,
+                after_inning: 5,
+            }),
+            ..GameRules::default()
+        };
+        let state = late_game(5, InningHalf::Bottom, Score { home: 12, away: 0 });
+        let odds = win_probability(&state, &rules, &LeagueAverage, 50, 0).unwrap();
+        assert_eq!(odds.home, 1.0);
+
+        // A game allowed to end tied after nine innings sometimes does.
+        let rules = GameRules {
+            allow_tie_after_innings: Some(9),
+            ..GameRules::default()
+        };
+        let state = late_game(9, InningHalf::Top, Score { home: 0, away: 0 });
+        let odds = win_probability(&state, &rules, &LeagueAverage, 500, 0).unwrap();
+        assert!(odds.tie > 0.0, "{:?}", odds);
+    }
+
+    #[test]
+    fn test_win_probability_needs_games() {
+        let result = win_probability(&new_game(), &GameRules::default(), &LeagueAverage, 0, 0);
+        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+    }
+
+    /// Home runs with probability `home_run`, outs otherwise.
+    struct HomeRunOrOut {
+        home_run: f64,
+    }
+
+    impl OutcomeModel for HomeRunOrOut {
+        fn sample_plate_appearance(&self, _state: &GameState, rng: &mut impl Rng) -> GameEvent {
+            if rng.random::<f64>() < self.home_run {
+                GameEvent::HomeRun
+            } else {
+                GameEvent::FlyOut {
+                    advancement: Vec::new(),
+                }
+            }
+        }
+
+        fn base_out_probabilities(&self, _state: BaseOutState) -> [f64; 6] {
+            [0.0, 0.0, 0.0, 0.0, self.home_run, 1.0 - self.home_run]
+        }
+    }
+
+    /// Singles and outs at even odds, leaving the probabilities to be
+    /// estimated.
+    struct CoinFlip;
+
+    impl OutcomeModel for CoinFlip {
+        fn sample_plate_appearance(&self, _state: &GameState, rng: &mut impl Rng) -> GameEvent {
+            if rng.random() {
+                GameEvent::Single {
+                    advancement: Vec::new(),
+                }
+            } else {
+                GameEvent::GroundOut {
+                    advancement: Vec::new(),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_expectancy_closed_form() {
+        let home_run = 0.2;
+        let out = 1.0 - home_run;
+        let matrix = run_expectancy(&HomeRunOrOut { home_run }).unwrap();
+        for (state, runs) in matrix.iter() {
+            // Each remaining out allows home_run / out homers on average, and
+            // the runners on base score if any homer comes.
+            let outs_left = 3 - i32::from(state.outs());
+            let expected = f64::from(outs_left) * home_run / out
+                + f64::from(state.runners()) * (1.0 - out.powi(outs_left));
+            assert!((runs - expected).abs() < 1e-9, "{:?}: {}", state, runs);
+        }
+    }
+
+    #[test]
+    fn test_run_expectancy_ordering() {
+        let loaded = BaseOutState::new(0b111, 0).unwrap();
+        let empty_two_outs = BaseOutState::new(0, 2).unwrap();
+        let matrices = [
+            run_expectancy(&LeagueAverage).unwrap(),
+            run_expectancy(&HomeRunOrOut { home_run: 0.05 }).unwrap(),
+            run_expectancy(&CoinFlip).unwrap(),
+        ];
+        for matrix in matrices {
+            assert!(matrix[loaded] > matrix[empty_two_outs]);
+            for state in BaseOutState::ALL {
+                assert!(matrix.get(state) >= 0.0);
+                if state.outs() < 2 {
+                    let more_outs =
+                        BaseOutState::new(state.bases_occupied(), state.outs() + 1).unwrap();
+                    assert!(matrix[state] > matrix[more_outs]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_expectancy_league_average() {
+        let matrix = run_expectancy(&LeagueAverage).unwrap();
+        let empty = BaseOutState::new(0, 0).unwrap();
+        assert!(
+            matrix[empty] > 0.3 && matrix[empty] < 0.7,
+            "{}",
+            matrix[empty]
+        );
+        let third = BaseOutState::new(0b100, 0).unwrap();
+        assert!(matrix[third] > matrix[empty] + 0.5);
+    }
+
+    #[test]
+    fn test_estimated_probabilities() {
+        let state = BaseOutState::new(0b011, 1).unwrap();
+        let estimated = CoinFlip.base_out_probabilities(state);
+        assert!((estimated[BaseOutEvent::Single as usize] - 0.5).abs() < 0.02);
+        assert!((estimated.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(estimated, CoinFlip.base_out_probabilities(state));
+    }
+
+    #[test]
+    fn test_run_expectancy_degenerate_models() {
+        let never_out = HomeRunOrOut { home_run: 1.0 };
+        assert!(matches!(
+            run_expectancy(&never_out),
+            Err(BaselomError::ValidationError(_))
+        ));
+        let too_likely = HomeRunOrOut { home_run: 1.5 };
+        assert!(matches!(
+            run_expectancy(&too_likely),
+            Err(BaselomError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_run_expectancy_serialization() {
+        let matrix = run_expectancy(&LeagueAverage).unwrap();
+        let json = serde_json::to_value(matrix).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 24);
+        let restored: RunExpectancyMatrix = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, matrix);
+    }
+
+    #[test]
+    fn test_sim_summary() {
+        let score = |home, away| Score { home, away };
+        let summary =
+            SimSummary::from_scores(&[score(3, 1), score(1, 3), score(3, 1), score(2, 2)]);
+        assert_eq!(
+            (
+                summary.games,
+                summary.home_wins,
+                summary.away_wins,
+                summary.ties
+            ),
+            (4, 2, 1, 1)
+        );
+        assert_eq!(summary.mean_run_differential, 0.5);
+        assert!((summary.run_differential_stddev - 2.75f64.sqrt()).abs() < 1e-12);
+        assert_eq!(
+            summary.scores,
+            vec![
+                ScoreFrequency {
+                    score: score(3, 1),
+                    games: 2
+                },
+                ScoreFrequency {
+                    score: score(2, 2),
+                    games: 1
+                },
+                ScoreFrequency {
+                    score: score(1, 3),
+                    games: 1
+                },
+            ]
+        );
+        assert_eq!(SimSummary::from_scores(&[]), SimSummary::default());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_simulate_many_ignores_thread_count() {
+        let rules = GameRules::default();
+        let run = |threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| simulate_many(&new_game(), &rules, &LeagueAverage, 300, 11).unwrap())
+        };
+        let single = run(1);
+        assert_eq!(single, run(4));
+        assert_eq!(single.games, 300);
+        assert_eq!(single.home_wins + single.away_wins + single.ties, 300);
+        assert_eq!(
+            single.scores.iter().map(|entry| entry.games).sum::<usize>(),
+            300
+        );
+        assert!(single.run_differential_stddev > 0.0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_simulate_many_matches_simulator() {
+        let rules = GameRules::default();
+        let summary = simulate_many(&new_game(), &rules, &LeagueAverage, 3, 20).unwrap();
+        let scores: Vec<_> = (20..23)
+            .map(|seed| simulate(seed).state().score.clone())
+            .collect();
+        assert_eq!(summary, SimSummary::from_scores(&scores));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_simulate_many_errors() {
+        let rules = GameRules {
+            allow_auto_intentional_walk: false,
+            ..GameRules::default()
+        };
+        let result = simulate_many(&new_game(), &rules, &LeagueAverage, 50, 0);
+        assert!(matches!(result, Err(BaselomError::RuleViolation(_))));
+    }
+}
@@ -0,0 +1,809 @@
+//! Box score lines accumulated from a game's plays.
+//!
+//! [`BoxScore`] folds logged plays, or transitions as they are applied,
+//! into a batting line for each batter and a pitching line for each
+//! pitcher, so applications do not each redo the stat accounting.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::log::{GameLog, LogEntry};
+use crate::models::{
+    Count, GameEvent, GameRules, GameState, GameStatus, PitchResult, Play, Team, TransitionOutcome,
+};
+
+/// One batter's line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct BattingLine {
+    pub plate_appearances: u32,
+    /// Plate appearances less walks, hit by pitches, sacrifices and bases
+    /// awarded for interference or obstruction
+    pub at_bats: u32,
+    pub hits: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub home_runs: u32,
+    /// Walks, intentional ones included
+    pub walks: u32,
+    pub hit_by_pitch: u32,
+    pub strikeouts: u32,
+    pub runs_batted_in: u32,
+    /// Runs scored
+    pub runs: u32,
+}
+
+/// One pitcher's line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct PitchingLine {
+    pub batters_faced: u32,
+    /// Outs recorded while pitching: innings pitched in thirds
+    pub outs: u32,
+    pub hits: u32,
+    /// Runs charged, inherited runners who scored included
+    pub runs: u32,
+    pub earned_runs: u32,
+    /// Walks, intentional ones included
+    pub walks: u32,
+    pub strikeouts: u32,
+    /// Pitches recorded as pitch plays (batted-ball events add none)
+    pub pitches: u32,
+}
+
+impl PitchingLine {
+    /// Innings pitched in the usual notation, such as `6.2` for six and
+    /// two thirds.
+    pub fn innings_pitched(&self) -> String {
+        format!("{}.{}", self.outs / 3, self.outs % 3)
+    }
+}
+
+/// Batting and pitching lines by player ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct BoxScore {
+    pub batting: BTreeMap<String, BattingLine>,
+    pub pitching: BTreeMap<String, PitchingLine>,
+}
+
+/// How a plate appearance ended, as far as the box score is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlateAppearanceEnd {
+    /// A hit for this many bases
+    Hit(u8),
+    Walk,
+    HitByPitch,
+    Strikeout,
+    Sacrifice,
+    /// First base or more awarded for interference or obstruction
+    Awarded,
+    /// Any other at bat: an out, a fielder's choice or reaching on an error
+    Out,
+}
+
+impl PlateAppearanceEnd {
+    /// How `entry`'s play ended the plate appearance, or `None` if it did
+    /// not end one.
+    fn of(entry: &LogEntry, rules: &GameRules) -> Option<Self> {
+        match &entry.play {
+            Play::Pitch(pitch) => Self::of_pitch(*pitch, &entry.before.count, rules),
+            Play::Event(event) => Self::of_event(event),
+            Play::Batter(_)
+            | Play::MoundVisit(_)
+            | Play::PitchingChange { .. }
+            | Play::DoubleSwitch { .. } => None,
+        }
+    }
+
+    fn of_pitch(pitch: PitchResult, count: &Count, rules: &GameRules) -> Option<Self> {
+        match pitch {
+            PitchResult::Ball if count.balls + 1 >= rules.balls_for_walk => Some(Self::Walk),
+            PitchResult::HitByPitch => Some(Self::HitByPitch),
+            PitchResult::StrikeCalled
+            | PitchResult::StrikeSwinging
+            | PitchResult::FoulTip
+            | PitchResult::FoulBunt
+            | PitchResult::StrikeSwingingDropped
+                if count.strikes + 1 >= rules.strikes_for_out =>
+            {
+                Some(Self::Strikeout)
+            }
+            _ => None,
+        }
+    }
+
+    fn of_event(event: &GameEvent) -> Option<Self> {
+        let end = match event {
+            GameEvent::Single { .. } => Self::Hit(1),
+            GameEvent::Double { .. } | GameEvent::GroundRuleDouble => Self::Hit(2),
+            GameEvent::Triple { .. } => Self::Hit(3),
+            GameEvent::HomeRun => Self::Hit(4),
+            GameEvent::IntentionalWalk => Self::Walk,
+            GameEvent::SacFly { .. } | GameEvent::SacBunt { .. } => Self::Sacrifice,
+            GameEvent::AwardBases { .. } | GameEvent::CatchersInterference => Self::Awarded,
+            GameEvent::ReachedOnError { .. }
+            | GameEvent::DoublePlay { .. }
+            | GameEvent::GroundOut { .. }
+            | GameEvent::FlyOut { .. }
+            | GameEvent::LineOut { .. }
+            | GameEvent::InfieldFly { .. }
+            | GameEvent::FieldersChoice { .. } => Self::Out,
+            GameEvent::WildPitch
+            | GameEvent::PassedBall
+            | GameEvent::Balk
+            | GameEvent::Pickoff { .. }
+            | GameEvent::PickoffError { .. }
+            | GameEvent::Obstruction { .. } => return None,
+        };
+        Some(end)
+    }
+
+    fn is_at_bat(self) -> bool {
+        !matches!(
+            self,
+            Self::Walk | Self::HitByPitch | Self::Sacrifice | Self::Awarded
+        )
+    }
+}
+
+impl BoxScore {
+    /// An empty box score.
+    pub fn new() -> Self {
+        BoxScore::default()
+    }
+
+    /// The box score of every play in `log`, played under `rules`.
+    pub fn from_log(log: &GameLog, rules: &GameRules) -> Self {
+        let mut box_score = BoxScore::new();
+        for entry in log.entries() {
+            box_score.record(entry, rules);
+        }
+        box_score
+    }
+
+    /// Add a transition as it is applied: `play` took `before` to
+    /// `outcome.state`.
+    pub fn record_transition(
+        &mut self,
+        play: &Play,
+        before: &GameState,
+        outcome: &TransitionOutcome,
+        rules: &GameRules,
+    ) {
+        self.record(&LogEntry::new(play.clone(), before, outcome), rules);
+    }
+
+    /// Add one logged play.
+    ///
+    /// The batter and pitcher are the ones the play was applied with, and
+    /// runs go to the pitchers the engine charged them to.
+    pub fn record(&mut self, entry: &LogEntry, rules: &GameRules) {
+        let end = PlateAppearanceEnd::of(entry, rules);
+
+        if let Some(pitcher) = &entry.before.pitcher {
+            let line = self.pitching.entry(pitcher.clone()).or_default();
+            if matches!(entry.play, Play::Pitch(_)) {
+                line.pitches += 1;
+            }
+            line.outs += u32::from(entry.outs_recorded);
+            if let Some(end) = end {
+                line.batters_faced += 1;
+                match end {
+                    PlateAppearanceEnd::Hit(_) => line.hits += 1,
+                    PlateAppearanceEnd::Walk => line.walks += 1,
+                    PlateAppearanceEnd::Strikeout => line.strikeouts += 1,
+                    _ => {}
+                }
+            }
+        }
+        for (pitcher, &earned) in entry.charged_pitchers.iter().zip(&entry.earned) {
+            let line = self.pitching.entry(pitcher.clone()).or_default();
+            line.runs += 1;
+            if earned {
+                line.earned_runs += 1;
+            }
+        }
+
+        for scorer in &entry.scorers {
+            self.batting.entry(scorer.clone()).or_default().runs += 1;
+        }
+        let (Some(end), Some(batter)) = (end, &entry.before.batter) else {
+            return;
+        };
+        let line = self.batting.entry(batter.clone()).or_default();
+        line.plate_appearances += 1;
+        if end.is_at_bat() {
+            line.at_bats += 1;
+        }
+        line.runs_batted_in += u32::from(entry.rbis);
+        match end {
+            PlateAppearanceEnd::Hit(bases) => {
+                line.hits += 1;
+                match bases {
+                    2 => line.doubles += 1,
+                    3 => line.triples += 1,
+                    4 => line.home_runs += 1,
+                    _ => {}
+                }
+            }
+            PlateAppearanceEnd::Walk => line.walks += 1,
+            PlateAppearanceEnd::HitByPitch => line.hit_by_pitch += 1,
+            PlateAppearanceEnd::Strikeout => line.strikeouts += 1,
+            _ => {}
+        }
+    }
+}
+
+/// The pitchers credited with a game's win, loss and save.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Decisions {
+    pub winner: Option<String>,
+    pub loser: Option<String>,
+    pub save: Option<String>,
+}
+
+/// A pitcher's time in the game, from entering to being replaced.
+struct Appearance<'a> {
+    pitcher: &'a str,
+    team: Team,
+    /// Index of the first play the pitcher was in for
+    entered: usize,
+    /// Outs recorded while in
+    outs: u32,
+}
+
+/// Outs in five innings, which a starter needs to win.
+const STARTER_OUTS_FOR_WIN: u32 = 15;
+
+/// Outs in four innings, which a starter needs to win a game their team
+/// fields for only five.
+const SHORT_GAME_STARTER_OUTS_FOR_WIN: u32 = 12;
+
+/// Outs in six innings: fewer makes a short game for the starter rule.
+const SHORT_GAME_OUTS: u32 = 18;
+
+/// The winning, losing and saving pitchers of a finished game, from the
+/// pitcher each play was made against.
+///
+/// The winner is the winning team's pitcher of record when it last took the
+/// lead, and the loser the pitcher charged with the run that gave it. With
+/// `GameRules::starter_win_requires_five_innings`, a starter short of five
+/// innings (four if the team fielded fewer than six) cannot win, and the
+/// win goes to the team's reliever with the most outs.
+///
+/// The save goes to the winning team's last pitcher, unless they won, if
+/// they relieved with the lead already taken for good, recorded an out, and
+/// either entered leading by at most three and pitched an inning, entered
+/// with the tying run on base, at bat or on deck, or pitched three innings.
+///
+/// A game that is not final, or ended tied, has no decisions.
+pub fn decisions(log: &GameLog, rules: &GameRules) -> Decisions {
+    let final_state = log.state();
+    let (winners, losers) = match final_state.score.home.cmp(&final_state.score.away) {
+        Ordering::Greater => (Team::Home, Team::Away),
+        Ordering::Less => (Team::Away, Team::Home),
+        Ordering::Equal => return Decisions::default(),
+    };
+    if final_state.status != GameStatus::Final {
+        return Decisions::default();
+    }
+
+    let mut appearances: Vec<Appearance> = Vec::new();
+    // The play on which the winners last went ahead, with the pitcher
+    // charged with the go-ahead run.
+    let mut go_ahead: Option<(usize, Option<&str>)> = None;
+    for (index, entry) in log.entries().iter().enumerate() {
+        let fielding = entry.before.half.fielding_team();
+        if let Some(pitcher) = entry.before.pitcher.as_deref() {
+            let current = appearances
+                .iter()
+                .rposition(|appearance| appearance.team == fielding)
+                .filter(|&current| appearances[current].pitcher == pitcher);
+            let current = current.unwrap_or_else(|| {
+                appearances.push(Appearance {
+                    pitcher,
+                    team: fielding,
+                    entered: index,
+                    outs: 0,
+                });
+                appearances.len() - 1
+            });
+            appearances[current].outs += u32::from(entry.outs_recorded);
+        }
+
+        let winners_before = entry.before.score.get(winners);
+        let losers_score = entry.before.score.get(losers);
+        if fielding == losers
+            && winners_before <= losers_score
+            && winners_before + entry.runs_scored > losers_score
+        {
+            let run = (losers_score - winners_before) as usize;
+            let charged = entry
+                .charged_pitchers
+                .get(run)
+                .or(entry.before.pitcher.as_ref());
+            go_ahead = Some((index, charged.map(String::as_str)));
+        }
+    }
+    let Some((go_ahead_play, loser)) = go_ahead else {
+        return Decisions::default();
+    };
+
+    let team_appearances: Vec<&Appearance> = appearances
+        .iter()
+        .filter(|appearance| appearance.team == winners)
+        .collect();
+    let Some((starter, relievers)) = team_appearances.split_first() else {
+        return Decisions {
+            loser: loser.map(str::to_string),
+            ..Decisions::default()
+        };
+    };
+    // Before the winners first take the field their pitcher of record is
+    // the starter.
+    let of_record = team_appearances
+        .iter()
+        .rev()
+        .find(|appearance| appearance.entered <= go_ahead_play)
+        .unwrap_or(starter);
+    let team_outs: u32 = team_appearances
+        .iter()
+        .map(|appearance| appearance.outs)
+        .sum();
+    let starter_outs_needed = if team_outs < SHORT_GAME_OUTS {
+        SHORT_GAME_STARTER_OUTS_FOR_WIN
+    } else {
+        STARTER_OUTS_FOR_WIN
+    };
+    let short_start = rules.starter_win_requires_five_innings
+        && of_record.entered == starter.entered
+        && starter.outs < starter_outs_needed;
+    let winner = match relievers.iter().rev().max_by_key(|reliever| reliever.outs) {
+        Some(reliever) if short_start => reliever,
+        _ => of_record,
+    };
+
+    let finisher = team_appearances.last().unwrap_or(starter);
+    let entered = &log.entries()[finisher.entered].before;
+    let lead = entered
+        .score
+        .get(winners)
+        .saturating_sub(entered.score.get(losers));
+    let tying_run_due = lead as usize <= entered.bases.count_runners() + 2;
+    let saved = finisher.entered != starter.entered
+        && finisher.pitcher != winner.pitcher
+        && finisher.entered > go_ahead_play
+        && finisher.outs > 0
+        && lead > 0
+        && ((lead <= 3 && finisher.outs >= 3) || tying_run_due || finisher.outs >= 9);
+
+    Decisions {
+        winner: Some(winner.pitcher.to_string()),
+        loser: loser.map(str::to_string),
+        save: saved.then(|| finisher.pitcher.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::substitute_pitcher;
+    use crate::models::{from_json_camel, to_json_camel, Advance, Base, Lineup, RunnerAdvance};
+
+    fn send_up(log: &mut GameLog, batter: &str, pitches: &[PitchResult], rules: &GameRules) {
+        log.batter(batter, rules).unwrap();
+        for &pitch in pitches {
+            log.pitch(pitch, rules).unwrap();
+        }
+    }
+
+    fn scores_from(from: Base) -> Vec<RunnerAdvance> {
+        vec![RunnerAdvance {
+            from,
+            to: Advance::Home,
+            out_was_force: false,
+            force_no_rbi: false,
+        }]
+    }
+
+    /// A scripted top of the first: the starter gives up a single, a homer,
+    /// a walk and an error around a strikeout, and the reliever lets both
+    /// runners score, unearned as they moved up on the error, before the
+    /// reliever's own run scores unearned after the would-be third out.
+    fn scripted_half_inning(rules: &GameRules) -> (GameLog, GameLog) {
+        use PitchResult::*;
+
+        let start = GameState::builder().pitcher("h_sp").build().unwrap();
+        let mut starter = GameLog::new(start);
+        send_up(&mut starter, "a1", &[Ball, StrikeCalled], rules);
+        starter
+            .event(
+                GameEvent::Single {
+                    advancement: vec![],
+                },
+                rules,
+            )
+            .unwrap();
+        send_up(&mut starter, "a2", &[Foul], rules);
+        starter.event(GameEvent::HomeRun, rules).unwrap();
+        send_up(
+            &mut starter,
+            "a3",
+            &[StrikeSwinging, Foul, StrikeSwinging],
+            rules,
+        );
+        send_up(
+            &mut starter,
+            "a4",
+            &[Ball, Ball, StrikeCalled, Ball, Ball],
+            rules,
+        );
+        send_up(&mut starter, "a5", &[], rules);
+        starter
+            .event(
+                GameEvent::ReachedOnError {
+                    batter_to: Base::First,
+                    runners_advance: 1,
+                },
+                rules,
+            )
+            .unwrap();
+
+        let relieved = substitute_pitcher(starter.state(), "h_rp", false, rules).unwrap();
+        let mut reliever = GameLog::new(relieved);
+        send_up(&mut reliever, "a6", &[Ball], rules);
+        reliever
+            .event(
+                GameEvent::Double {
+                    advancement: scores_from(Base::First),
+                },
+                rules,
+            )
+            .unwrap();
+        send_up(&mut reliever, "a7", &[], rules);
+        reliever
+            .event(
+                GameEvent::SacBunt {
+                    advancing: vec![Base::Second],
+                },
+                rules,
+            )
+            .unwrap();
+        send_up(&mut reliever, "a8", &[], rules);
+        reliever
+            .event(
+                GameEvent::Single {
+                    advancement: scores_from(Base::Third),
+                },
+                rules,
+            )
+            .unwrap();
+        send_up(
+            &mut reliever,
+            "a9",
+            &[StrikeCalled, StrikeCalled, FoulTip],
+            rules,
+        );
+        assert!(reliever.entries().last().unwrap().half_inning_ended);
+        (starter, reliever)
+    }
+
+    fn scripted_box_score(rules: &GameRules) -> BoxScore {
+        let (starter, reliever) = scripted_half_inning(rules);
+        let mut box_score = BoxScore::from_log(&starter, rules);
+        for entry in reliever.entries() {
+            box_score.record(entry, rules);
+        }
+        box_score
+    }
+
+    #[test]
+    fn test_scripted_box_score() {
+        let rules = GameRules::default();
+        let box_score = scripted_box_score(&rules);
+
+        let single = BattingLine {
+            plate_appearances: 1,
+            at_bats: 1,
+            hits: 1,
+            ..BattingLine::default()
+        };
+        let strikeout = BattingLine {
+            plate_appearances: 1,
+            at_bats: 1,
+            strikeouts: 1,
+            ..BattingLine::default()
+        };
+        let batting = BTreeMap::from([
+            (
+                "a1",
+                BattingLine {
+                    runs: 1,
+                    ..single.clone()
+                },
+            ),
+            (
+                "a2",
+                BattingLine {
+                    home_runs: 1,
+                    runs_batted_in: 2,
+                    runs: 1,
+                    ..single.clone()
+                },
+            ),
+            ("a3", strikeout.clone()),
+            (
+                "a4",
+                BattingLine {
+                    plate_appearances: 1,
+                    walks: 1,
+                    runs: 1,
+                    ..BattingLine::default()
+                },
+            ),
+            (
+                "a5",
+                BattingLine {
+                    plate_appearances: 1,
+                    at_bats: 1,
+                    runs: 1,
+                    ..BattingLine::default()
+                },
+            ),
+            (
+                "a6",
+                BattingLine {
+                    doubles: 1,
+                    runs_batted_in: 2,
+                    runs: 1,
+                    ..single.clone()
+                },
+            ),
+            (
+                "a7",
+                BattingLine {
+                    plate_appearances: 1,
+                    ..BattingLine::default()
+                },
+            ),
+            (
+                "a8",
+                BattingLine {
+                    runs_batted_in: 1,
+                    ..single
+                },
+            ),
+            ("a9", strikeout),
+        ]);
+        let pitching = BTreeMap::from([
+            (
+                "h_rp",
+                PitchingLine {
+                    batters_faced: 4,
+                    outs: 2,
+                    hits: 2,
+                    runs: 1,
+                    earned_runs: 0,
+                    walks: 0,
+                    strikeouts: 1,
+                    pitches: 4,
+                },
+            ),
+            (
+                "h_sp",
+                PitchingLine {
+                    batters_faced: 5,
+                    outs: 1,
+                    hits: 2,
+                    runs: 4,
+                    earned_runs: 2,
+                    walks: 1,
+                    strikeouts: 1,
+                    pitches: 11,
+                },
+            ),
+        ]);
+        let expected = BoxScore {
+            batting: batting
+                .into_iter()
+                .map(|(id, line)| (id.to_string(), line))
+                .collect(),
+            pitching: pitching
+                .into_iter()
+                .map(|(id, line)| (id.to_string(), line))
+                .collect(),
+        };
+        assert_eq!(box_score, expected);
+        assert_eq!(box_score.pitching["h_sp"].innings_pitched(), "0.1");
+        assert_eq!(box_score.pitching["h_rp"].innings_pitched(), "0.2");
+    }
+
+    #[test]
+    fn test_record_transition_matches_log() {
+        let rules = GameRules::default();
+        let (starter, _) = scripted_half_inning(&rules);
+        let mut state = GameState::builder().pitcher("h_sp").build().unwrap();
+        let mut box_score = BoxScore::new();
+        for entry in starter.entries() {
+            let outcome = crate::engine::apply_play_detailed(&state, &entry.play, &rules).unwrap();
+            box_score.record_transition(&entry.play, &state, &outcome, &rules);
+            state = outcome.state;
+        }
+        assert_eq!(box_score, BoxScore::from_log(&starter, &rules));
+    }
+
+    #[test]
+    fn test_camel_json_keeps_player_ids() {
+        let rules = GameRules::default();
+        let box_score = scripted_box_score(&rules);
+        let json = to_json_camel(&box_score).unwrap();
+        assert!(json.contains("\"h_sp\""));
+        assert!(json.contains("\"battersFaced\""));
+        assert_eq!(from_json_camel::<BoxScore>(&json).unwrap(), box_score);
+    }
+    /// A game between lineups whose ninth hitters (`h9`, `a9`) start on the
+    /// mound, scripted play by play.
+    struct Game {
+        log: GameLog,
+        rules: GameRules,
+    }
+
+    impl Game {
+        fn new(rules: GameRules) -> Self {
+            let lineup = |team: &str| {
+                Lineup::new((1..=9).map(|n| format!("{}{}", team, n)))
+                    .with_pitcher(format!("{}9", team))
+            };
+            let state = GameState::builder()
+                .lineups(lineup("h"), lineup("a"))
+                .pitcher("h9")
+                .build()
+                .unwrap();
+            Game {
+                log: GameLog::new(state),
+                rules,
+            }
+        }
+
+        fn strikeouts(&mut self, count: usize) -> &mut Self {
+            for _ in 0..count * 3 {
+                self.log
+                    .pitch(PitchResult::StrikeSwinging, &self.rules)
+                    .unwrap();
+            }
+            self
+        }
+
+        /// Three up, three down for `halves` half innings.
+        fn quiet(&mut self, halves: usize) -> &mut Self {
+            self.strikeouts(3 * halves)
+        }
+
+        fn event(&mut self, event: GameEvent, count: usize) -> &mut Self {
+            for _ in 0..count {
+                self.log.event(event.clone(), &self.rules).unwrap();
+            }
+            self
+        }
+
+        fn homers(&mut self, count: usize) -> &mut Self {
+            self.event(GameEvent::HomeRun, count)
+        }
+
+        fn singles(&mut self, count: usize) -> &mut Self {
+            self.event(
+                GameEvent::Single {
+                    advancement: vec![],
+                },
+                count,
+            )
+        }
+
+        fn change(&mut self, pitcher: &str) -> &mut Self {
+            self.log
+                .pitching_change(pitcher, false, &self.rules)
+                .unwrap();
+            self
+        }
+
+        fn decisions(&self) -> Decisions {
+            assert_eq!(self.log.state().status, GameStatus::Final);
+            decisions(&self.log, &self.rules)
+        }
+    }
+
+    fn credited(winner: &str, loser: &str, save: Option<&str>) -> Decisions {
+        Decisions {
+            winner: Some(winner.to_string()),
+            loser: Some(loser.to_string()),
+            save: save.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_starter_wins_and_closer_saves_one_run_game() {
+        let mut game = Game::new(GameRules::default());
+        game.quiet(1).homers(1).strikeouts(3).quiet(14);
+        game.change("h_cl").strikeouts(3);
+        assert_eq!(game.decisions(), credited("h9", "a9", Some("h_cl")));
+    }
+
+    #[test]
+    fn test_reliever_who_blows_the_lead_can_win_without_a_save() {
+        let mut game = Game::new(GameRules::default());
+        game.quiet(1).homers(1).strikeouts(3).quiet(12);
+        // The setup man gives up the tying run in the eighth...
+        game.change("h_su").homers(1).strikeouts(3);
+        // ...and is the pitcher of record when the home team goes back ahead.
+        game.homers(1).strikeouts(3);
+        game.change("h_cl").strikeouts(3);
+        assert_eq!(game.decisions(), credited("h_su", "a9", Some("h_cl")));
+
+        // Had the setup man finished, the blown lead would rule out a save.
+        let mut game = Game::new(GameRules::default());
+        game.quiet(1).homers(1).strikeouts(3).quiet(12);
+        game.change("h_su").homers(1).strikeouts(3);
+        game.homers(1).strikeouts(3);
+        game.strikeouts(3);
+        assert_eq!(game.decisions(), credited("h_su", "a9", None));
+    }
+
+    #[test]
+    fn test_walk_off_win_goes_to_the_pitcher_of_record() {
+        let mut game = Game::new(GameRules::default());
+        game.quiet(17);
+        game.change("a_rp").homers(1);
+        assert_eq!(game.decisions(), credited("h9", "a_rp", None));
+    }
+
+    #[test]
+    fn test_short_start_gives_the_win_to_the_busiest_reliever() {
+        let script = |rules: GameRules| {
+            let mut game = Game::new(rules);
+            game.homers(5).strikeouts(3);
+            // The away starter gets through four innings, and the first
+            // reliever three.
+            game.quiet(8);
+            game.change("a_rp1").quiet(6);
+            game.change("a_rp2").quiet(3);
+            game.decisions()
+        };
+        assert_eq!(script(GameRules::default()), credited("a_rp1", "h9", None));
+
+        let rules = GameRules {
+            starter_win_requires_five_innings: false,
+            ..GameRules::default()
+        };
+        assert_eq!(script(rules), credited("a9", "h9", None));
+    }
+
+    #[test]
+    fn test_save_with_the_tying_run_on_deck() {
+        let mut game = Game::new(GameRules::default());
+        game.homers(4).strikeouts(3).quiet(16);
+        // Two on in the ninth bring the tying run to the on-deck circle.
+        game.singles(2).change("a_cl").strikeouts(3);
+        assert_eq!(game.decisions(), credited("a9", "h9", Some("a_cl")));
+
+        // A four-run lead with the bases empty is not a save situation.
+        let mut game = Game::new(GameRules::default());
+        game.homers(4).strikeouts(3).quiet(16);
+        game.change("a_cl").strikeouts(3);
+        assert_eq!(game.decisions(), credited("a9", "h9", None));
+    }
+
+    #[test]
+    fn test_unfinished_game_has_no_decisions() {
+        let mut game = Game::new(GameRules::default());
+        game.homers(1).strikeouts(3);
+        assert_eq!(decisions(&game.log, &game.rules), Decisions::default());
+    }
+}
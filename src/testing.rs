@@ -0,0 +1,172 @@
+//! Random values for property tests and fuzzing.
+//!
+//! [`Arbitrary`] draws engine inputs from any [`Rng`], so downstream
+//! property tests and fuzzers need not hand-roll them. Generated states
+//! always pass [`crate::validators::validate_state`], and generated rules
+//! accept every generated state.
+
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::models::{
+    Base, ExtraInningsTiebreaker, GameEvent, GameEventKind, GameRules, GameState, MercyRule,
+    PitchResult,
+};
+
+/// A type that can be drawn at random.
+pub trait Arbitrary: Sized {
+    fn arbitrary(rng: &mut impl Rng) -> Self;
+}
+
+impl Arbitrary for PitchResult {
+    fn arbitrary(rng: &mut impl Rng) -> Self {
+        *PitchResult::ALL.choose(rng).unwrap()
+    }
+}
+
+impl Arbitrary for GameEvent {
+    /// An event of any kind, in its simplest form. It may not be legal in a
+    /// given state.
+    fn arbitrary(rng: &mut impl Rng) -> Self {
+        GameEventKind::ALL.choose(rng).unwrap().event()
+    }
+}
+
+impl Arbitrary for GameRules {
+    /// Rules within regulation ranges: walks on 4 or 5 balls, strikeouts on
+    /// 3 or 4 strikes and games of at most nine innings.
+    fn arbitrary(rng: &mut impl Rng) -> Self {
+        GameRules {
+            designated_hitter: rng.random(),
+            max_innings: *[Some(7), Some(9), None].choose(rng).unwrap(),
+            extra_innings_tiebreaker: rng
+                .random_bool(0.5)
+                .then_some(ExtraInningsTiebreaker::RunnerOnSecond),
+            allow_auto_intentional_walk: rng.random(),
+            balk_with_empty_bases_is_ball: rng.random(),
+            dropped_third_strike: rng.random(),
+            balls_for_walk: rng.random_range(4..=5),
+            strikes_for_out: rng.random_range(3..=4),
+            mercy_rule: rng.random_bool(0.25).then(|| MercyRule {
+                run_differential: rng.random_range(7..=15),
+                after_inning: rng.random_range(5..=7),
+            }),
+            pitch_limit: rng.random_bool(0.25).then(|| rng.random_range(50..=120)),
+            ..GameRules::default()
+        }
+    }
+}
+
+impl Arbitrary for GameState {
+    /// An in-progress state in the first seven innings, with a batter and a
+    /// pitcher and runners whose IDs are all distinct.
+    fn arbitrary(rng: &mut impl Rng) -> Self {
+        let mut builder = GameState::builder()
+            .inning(rng.random_range(1..=7))
+            .outs(rng.random_range(0..=2))
+            .count(rng.random_range(0..=3), rng.random_range(0..=2))
+            .score(rng.random_range(0..=10), rng.random_range(0..=10))
+            .batter("batter")
+            .pitcher("pitcher");
+        if rng.random() {
+            builder = builder.bottom();
+        }
+        for base in Base::ALL {
+            if rng.random() {
+                builder = builder.runner_on(base, format!("runner{}", base.number()));
+            }
+        }
+        builder.build().expect("generated states are always valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::engine::{apply_pitch, apply_play};
+    use crate::models::{GameStatus, Play};
+    use crate::validators::{validate_state, validate_state_with_rules};
+
+    const CASES: u64 = 300;
+    const PLAYS_PER_CASE: usize = 60;
+
+    /// Run `check` on a fresh random state and rules for each case, with
+    /// the case's seed in any failure message.
+    fn for_each_case(mut check: impl FnMut(u64, &mut StdRng, GameState, GameRules)) {
+        for seed in 0..CASES {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let rules = GameRules::arbitrary(&mut rng);
+            let state = GameState::arbitrary(&mut rng);
+            check(seed, &mut rng, state, rules);
+        }
+    }
+
+    /// Send a new batter up when the last plate appearance ended.
+    fn next_batter(state: &GameState, rules: &GameRules, number: usize) -> GameState {
+        if state.current_batter_id.is_some() || state.status == GameStatus::Final {
+            return state.clone();
+        }
+        apply_play(state, &Play::Batter(format!("batter{}", number)), rules).unwrap()
+    }
+
+    #[test]
+    fn test_generated_values_are_valid() {
+        for_each_case(|seed, _, state, rules| {
+            validate_state(&state).unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+            validate_state_with_rules(&state, &rules)
+                .unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+        });
+    }
+
+    #[test]
+    fn test_pitches_never_produce_invalid_states() {
+        let mut applied = 0;
+        for_each_case(|seed, rng, mut state, rules| {
+            for number in 0..PLAYS_PER_CASE {
+                state = next_batter(&state, &rules, number);
+                if state.status == GameStatus::Final {
+                    break;
+                }
+                let pitch = PitchResult::arbitrary(rng);
+                if let Ok(next) = apply_pitch(&state, pitch, &rules) {
+                    validate_state_with_rules(&next, &rules).unwrap_or_else(|err| {
+                        panic!("seed {} after {:?} from {:?}: {}", seed, pitch, state, err)
+                    });
+                    state = next;
+                    applied += 1;
+                }
+            }
+        });
+        // Most pitches are accepted, so the property is not vacuous.
+        assert!(applied > CASES as usize * PLAYS_PER_CASE / 2);
+    }
+
+    #[test]
+    fn test_scores_never_decrease() {
+        for_each_case(|seed, rng, mut state, rules| {
+            for number in 0..PLAYS_PER_CASE {
+                state = next_batter(&state, &rules, number);
+                if state.status == GameStatus::Final {
+                    break;
+                }
+                let play = if rng.random_bool(0.6) {
+                    Play::Pitch(PitchResult::arbitrary(rng))
+                } else {
+                    Play::Event(GameEvent::arbitrary(rng))
+                };
+                if let Ok(next) = apply_play(&state, &play, &rules) {
+                    assert!(
+                        next.score.home >= state.score.home && next.score.away >= state.score.away,
+                        "seed {} after {:?}",
+                        seed,
+                        play
+                    );
+                    state = next;
+                }
+            }
+        });
+    }
+}
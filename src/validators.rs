@@ -1,41 +1,508 @@
 //! State validation rules.
 
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
 use crate::errors::BaselomError;
-use crate::models::GameState;
+use crate::models::{
+    ExtraInningsTiebreaker, GameRules, GameState, GameStatus, Position, Team, MAX_INNING,
+};
+use crate::player::PlayerKey;
+
+/// Kind of problem found in a game state.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationCode {
+    TooManyOuts,
+    TooManyBalls,
+    TooManyStrikes,
+    InvalidInning,
+    DuplicateRunner,
+    BatterOnBase,
+    LineScoreMismatch,
+    FinalPastTieLimit,
+    FinalExtraInningTie,
+    MissingTiebreakerRunner,
+    LineupTooShort,
+    DuplicateLineupPlayer,
+    InvalidLineupIndex,
+    PitcherBatsWithDesignatedHitter,
+    PitcherNotInLineup,
+    TooManyMoundVisits,
+    MissingPosition,
+    PlayerAtTwoPositions,
+    DesignatedHitterNotUsed,
+    PitcherPositionMismatch,
+}
+
+/// One problem found in a game state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ValidationIssue {
+    /// Machine-readable kind of problem
+    pub code: ValidationCode,
+    /// Serialized name of the offending field (e.g. `"outs"`, `"bases"`)
+    pub field: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(code: ValidationCode, field: &str, message: impl Into<String>) -> Self {
+        ValidationIssue {
+            code,
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ValidationIssue> for BaselomError {
+    fn from(issue: ValidationIssue) -> Self {
+        BaselomError::InvalidState(issue)
+    }
+}
 
 /// Validate that a game state is consistent.
-pub fn validate_state(state: &GameState) -> Result<(), BaselomError> {
+///
+/// Only checks invariants that hold under any rules, with count limits from
+/// the default rules (four balls, three strikes); use
+/// [`validate_state_with_rules`] for games played with other thresholds.
+///
+/// Returns the first issue [`validate_state_full`] finds as a
+/// `BaselomError::InvalidState`.
+pub fn validate_state<P: PlayerKey>(state: &GameState<P>) -> Result<(), BaselomError> {
+    first_issue(validate_state_full(state))
+}
+
+/// Validate that a game state is consistent under the given rules.
+///
+/// Performs every check in [`validate_state`] with count limits taken from
+/// `rules`, plus:
+/// - a final game never runs past `allow_tie_after_innings`, and only ends
+///   tied in extra innings once that limit or [`MAX_INNING`] is reached
+/// - with the `RunnerOnSecond` tiebreaker, an extra half inning that has no
+///   outs or runs yet has its automatic runner on base
+pub fn validate_state_with_rules<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+) -> Result<(), BaselomError> {
+    first_issue(validate_state_full_with_rules(state, rules))
+}
+
+/// Every problem [`validate_state`] checks for, in a fixed order.
+pub fn validate_state_full<P: PlayerKey>(state: &GameState<P>) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    check_core(state, &GameRules::default(), &mut issues);
+    issues
+}
+
+/// Every problem [`validate_state_with_rules`] checks for, in a fixed order.
+pub fn validate_state_full_with_rules<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    check_core(state, rules, &mut issues);
+    check_final_inning(state, rules, &mut issues);
+    check_tiebreaker_runner(state, rules, &mut issues);
+    issues
+}
+
+fn first_issue(issues: Vec<ValidationIssue>) -> Result<(), BaselomError> {
+    match issues.into_iter().next() {
+        Some(issue) => Err(issue.into()),
+        None => Ok(()),
+    }
+}
+
+/// Checks shared by both validators.
+fn check_core<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    issues: &mut Vec<ValidationIssue>,
+) {
     // Validate outs (u8 type guarantees non-negative, so only check upper bound)
     if state.outs > 2 {
-        return Err(BaselomError::ValidationError(
-            "Outs must be between 0 and 2".to_string(),
+        issues.push(ValidationIssue::new(
+            ValidationCode::TooManyOuts,
+            "outs",
+            "Outs must be between 0 and 2",
+        ));
+    }
+
+    // Validate count against the rules' thresholds
+    if state.count.balls >= rules.balls_for_walk {
+        issues.push(ValidationIssue::new(
+            ValidationCode::TooManyBalls,
+            "balls",
+            format!(
+                "Balls must be between 0 and {}",
+                rules.balls_for_walk.saturating_sub(1)
+            ),
+        ));
+    }
+
+    if state.count.strikes >= rules.strikes_for_out {
+        issues.push(ValidationIssue::new(
+            ValidationCode::TooManyStrikes,
+            "strikes",
+            format!(
+                "Strikes must be between 0 and {}",
+                rules.strikes_for_out.saturating_sub(1)
+            ),
         ));
     }
 
     // Validate inning (u8 type guarantees non-negative, so only check for zero)
     if state.inning == 0 {
-        return Err(BaselomError::ValidationError(
-            "Inning must be at least 1".to_string(),
+        issues.push(ValidationIssue::new(
+            ValidationCode::InvalidInning,
+            "inning",
+            "Inning must be at least 1",
+        ));
+    }
+
+    // Validate that no runner is on two bases
+    let runners: Vec<_> = state.bases.iter().collect();
+    for (index, (base, runner)) in runners.iter().enumerate() {
+        if let Some((other, _)) = runners[index + 1..].iter().find(|(_, id)| id == runner) {
+            issues.push(ValidationIssue::new(
+                ValidationCode::DuplicateRunner,
+                "bases",
+                format!("Runner {} is on both {} and {} base", runner, base, other),
+            ));
+        }
+    }
+
+    // Validate that the batter is not also a runner
+    if let Some(batter) = state.current_batter_id.as_ref().map(Borrow::borrow) {
+        if let Some((base, _)) = state.bases.iter().find(|(_, runner)| *runner == batter) {
+            issues.push(ValidationIssue::new(
+                ValidationCode::BatterOnBase,
+                "current_batter_id",
+                format!("Current batter {} is also on {} base", batter, base),
+            ));
+        }
+    }
+
+    // Validate the line score against the totals, when one is kept
+    if !state.line_score.is_empty() {
+        // Summed wider than the score so corrupt entries cannot overflow
+        let away: u64 = state
+            .line_score
+            .iter()
+            .map(|inning| u64::from(inning.away))
+            .sum();
+        let home: u64 = state
+            .line_score
+            .iter()
+            .filter_map(|inning| inning.home.map(u64::from))
+            .sum();
+        if away != u64::from(state.score.away) || home != u64::from(state.score.home) {
+            issues.push(ValidationIssue::new(
+                ValidationCode::LineScoreMismatch,
+                "line_score",
+                format!(
+                    "Line score totals (away {}, home {}) do not match the score (away {}, home {})",
+                    away, home, state.score.away, state.score.home
+                ),
+            ));
+        }
+    }
+
+    check_lineups(state, rules, issues);
+    check_defense(state, rules, issues);
+    check_mound_visits(state, rules, issues);
+}
+
+/// Check each tracked lineup's size and index, that the pitcher bats only
+/// without a designated hitter, and that no player appears twice across
+/// both lineups.
+fn check_lineups<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut seen = HashSet::new();
+    for team in [Team::Away, Team::Home] {
+        let lineup = state.lineups.get(team);
+        if lineup.is_empty() {
+            continue;
+        }
+        if lineup.len() < usize::from(rules.min_lineup_size) {
+            issues.push(ValidationIssue::new(
+                ValidationCode::LineupTooShort,
+                "lineups",
+                format!(
+                    "The {} lineup must have at least {} players",
+                    team, rules.min_lineup_size
+                ),
+            ));
+        }
+        if lineup.index >= lineup.len() {
+            issues.push(ValidationIssue::new(
+                ValidationCode::InvalidLineupIndex,
+                "lineups",
+                format!(
+                    "The {} lineup index {} is past its last slot",
+                    team, lineup.index
+                ),
+            ));
+        }
+        if let Some(pitcher) = &lineup.pitcher {
+            let batting = lineup.slot_of(pitcher.borrow()).is_some();
+            if state.uses_designated_hitter(team, rules) && batting {
+                issues.push(ValidationIssue::new(
+                    ValidationCode::PitcherBatsWithDesignatedHitter,
+                    "lineups",
+                    format!(
+                        "The {} pitcher {} cannot bat while the team uses a designated hitter",
+                        team, pitcher
+                    ),
+                ));
+            } else if !state.uses_designated_hitter(team, rules) && !batting {
+                issues.push(ValidationIssue::new(
+                    ValidationCode::PitcherNotInLineup,
+                    "lineups",
+                    format!(
+                        "The {} pitcher {} must bat without a designated hitter",
+                        team, pitcher
+                    ),
+                ));
+            }
+        }
+        for player in &lineup.players {
+            if !seen.insert(player) {
+                issues.push(ValidationIssue::new(
+                    ValidationCode::DuplicateLineupPlayer,
+                    "lineups",
+                    format!("Player {} appears in the lineups more than once", player),
+                ));
+            }
+        }
+    }
+}
+
+/// Check each tracked defense: every fielding position is filled, a
+/// designated hitter is listed exactly while the team uses one, nobody plays
+/// two positions, and the fielding team's pitcher is the one pitching.
+fn check_defense<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for team in [Team::Away, Team::Home] {
+        let defense = state.defensive_lineups.get(team);
+        if defense.is_empty() {
+            continue;
+        }
+        let uses_dh = state.uses_designated_hitter(team, rules);
+        let required = Position::FIELDERS
+            .into_iter()
+            .chain(uses_dh.then_some(Position::DesignatedHitter));
+        for position in required {
+            if defense.player_at(position).is_none() {
+                issues.push(ValidationIssue::new(
+                    ValidationCode::MissingPosition,
+                    "defensive_lineups",
+                    format!("The {} defense has nobody at {}", team, position),
+                ));
+            }
+        }
+        if !uses_dh && defense.player_at(Position::DesignatedHitter).is_some() {
+            issues.push(ValidationIssue::new(
+                ValidationCode::DesignatedHitterNotUsed,
+                "defensive_lineups",
+                format!(
+                    "The {} defense lists a designated hitter without using one",
+                    team
+                ),
+            ));
+        }
+        let mut seen = HashMap::new();
+        for (position, player) in &defense.positions {
+            if let Some(first) = seen.insert(player, position) {
+                issues.push(ValidationIssue::new(
+                    ValidationCode::PlayerAtTwoPositions,
+                    "defensive_lineups",
+                    format!(
+                        "Player {} plays both {} and {} for the {} team",
+                        player, first, position, team
+                    ),
+                ));
+            }
+        }
+    }
+
+    let fielding = state.half.fielding_team();
+    let pitcher = state
+        .defensive_lineups
+        .get(fielding)
+        .player_at(Position::Pitcher);
+    if let (Some(pitcher), Some(current)) = (pitcher, &state.current_pitcher_id) {
+        if pitcher != current.borrow() {
+            issues.push(ValidationIssue::new(
+                ValidationCode::PitcherPositionMismatch,
+                "defensive_lineups",
+                format!(
+                    "The {} defense has {} pitching, not current pitcher {}",
+                    fielding, pitcher, current
+                ),
+            ));
+        }
+    }
+}
+
+/// Check that no team has made more mound visits than the rules allow,
+/// unless extra visits force a pitching change instead.
+fn check_mound_visits<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let Some(limit) = rules.mound_visit_limit else {
+        return;
+    };
+    if rules.mound_visit_over_limit_removes_pitcher {
+        return;
+    }
+    for team in [Team::Away, Team::Home] {
+        if *state.mound_visits.get(team) > limit {
+            issues.push(ValidationIssue::new(
+                ValidationCode::TooManyMoundVisits,
+                "mound_visits",
+                format!("The {} team may make at most {} mound visits", team, limit),
+            ));
+        }
+    }
+}
+
+/// Check that a final game ended in an inning the rules allow.
+fn check_final_inning<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if state.status != GameStatus::Final {
+        return;
+    }
+
+    if let Some(limit) = rules.allow_tie_after_innings {
+        if state.inning > limit {
+            issues.push(ValidationIssue::new(
+                ValidationCode::FinalPastTieLimit,
+                "inning",
+                format!("Final game cannot go past inning {}", limit),
+            ));
+        }
+    }
+
+    let extra_inning = rules.max_innings.is_some_and(|max| state.inning > max);
+    let tie_allowed = state.inning == MAX_INNING
+        || rules
+            .allow_tie_after_innings
+            .is_some_and(|limit| state.inning >= limit);
+    if extra_inning && state.score.home == state.score.away && !tie_allowed {
+        issues.push(ValidationIssue::new(
+            ValidationCode::FinalExtraInningTie,
+            "score",
+            format!(
+                "Final game cannot end tied in extra inning {}",
+                state.inning
+            ),
         ));
     }
+}
+
+/// Check that an extra half inning starts with its automatic runner.
+///
+/// Only a half inning with no outs and no runs (per the line score) is
+/// checked: until then the runner can only have advanced, not left the
+/// bases. States without a line score, or whose batting team has no
+/// previous batter to place, are not checked.
+fn check_tiebreaker_runner<P: PlayerKey>(
+    state: &GameState<P>,
+    rules: &GameRules,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let Some(ExtraInningsTiebreaker::RunnerOnSecond) = rules.extra_innings_tiebreaker else {
+        return;
+    };
+    let extra_inning = rules.max_innings.is_some_and(|max| state.inning > max);
+    let batting_team = state.half.batting_team();
+    if !extra_inning
+        || state.status != GameStatus::InProgress
+        || state.outs > 0
+        || state.last_batters.get(batting_team).is_none()
+    {
+        return;
+    }
 
-    Ok(())
+    let Some(inning) = state.line_score.get(usize::from(state.inning) - 1) else {
+        return;
+    };
+    let runs = match batting_team {
+        Team::Away => inning.away,
+        Team::Home => inning.home.unwrap_or(0),
+    };
+    if runs == 0 && state.bases.is_empty() {
+        issues.push(ValidationIssue::new(
+            ValidationCode::MissingTiebreakerRunner,
+            "bases",
+            format!(
+                "Extra inning {} must start with a runner on second base",
+                state.inning
+            ),
+        ));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Score;
+    use crate::errors::ErrorCode;
+    use crate::models::{
+        Base, Bases, Count, DefensiveLineup, GameStatus, InningHalf, InningScore, Lineup, PerTeam,
+        Score, CURRENT_SCHEMA_VERSION,
+    };
+    use std::collections::BTreeMap;
 
     fn create_test_state(inning: u8, outs: u8) -> GameState {
         GameState {
             inning,
-            top: true,
+            half: InningHalf::Top,
             outs,
-            bases: (None, None, None),
+            count: Count::default(),
+            bases: Bases::new(),
             score: Score::default(),
+            home_team_id: None,
+            away_team_id: None,
             current_batter_id: None,
             current_pitcher_id: None,
+            status: GameStatus::InProgress,
+            last_batters: PerTeam::default(),
+            line_score: Vec::new(),
+            team_totals: PerTeam::default(),
+            lineups: PerTeam::default(),
+            defensive_lineups: PerTeam::default(),
+            removed_players: PerTeam::default(),
+            batters_faced: BTreeMap::new(),
+            half_inning_batters: 0,
+            half_inning_runs: 0,
+            dh_forfeited: PerTeam::default(),
+            pitch_counts: BTreeMap::new(),
+            plate_appearance_pitches: 0,
+            mound_visits: PerTeam::default(),
+            pitcher_removal_required: None,
+            responsible_pitchers: Bases::new(),
+            runs_allowed: BTreeMap::new(),
+            earned_runs_allowed: BTreeMap::new(),
+            unearned_runners: [false; 3],
+            outs_lost_to_errors: 0,
+            lead_changes: Vec::new(),
+            schema_version: CURRENT_SCHEMA_VERSION,
         }
     }
 
@@ -56,7 +523,7 @@ mod tests {
         let state = create_test_state(1, 3);
         let result = validate_state(&state);
         assert!(result.is_err());
-        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
     }
 
     #[test]
@@ -64,7 +531,433 @@ mod tests {
         let state = create_test_state(0, 0);
         let result = validate_state(&state);
         assert!(result.is_err());
-        assert!(matches!(result, Err(BaselomError::ValidationError(_))));
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_invalid_balls_too_many() {
+        let mut state = create_test_state(1, 0);
+        state.count.balls = 4;
+        assert!(matches!(
+            validate_state(&state),
+            Err(BaselomError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_strikes_too_many() {
+        let mut state = create_test_state(1, 0);
+        state.count.strikes = 3;
+        assert!(matches!(
+            validate_state(&state),
+            Err(BaselomError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn test_distinct_runners_valid() {
+        let mut state = create_test_state(1, 0);
+        state.current_batter_id = Some("b1".to_string());
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r3".to_string()),
+        ));
+        assert!(validate_state(&state).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_runner_rejected() {
+        let mut state = create_test_state(1, 0);
+        state.bases = Bases::from((
+            Some("r1".to_string()),
+            Some("r2".to_string()),
+            Some("r1".to_string()),
+        ));
+        let result = validate_state(&state);
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Runner r1 is on both first and third base"
+        );
+    }
+
+    #[test]
+    fn test_batter_on_base_rejected() {
+        let mut state = create_test_state(1, 0);
+        state.current_batter_id = Some("b1".to_string());
+        state.bases.set(Base::First, Some("r1".to_string()));
+        assert!(validate_state(&state).is_ok());
+
+        state.bases.set(Base::Second, Some("b1".to_string()));
+        let result = validate_state(&state);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Current batter b1 is also on second base"
+        );
+    }
+
+    #[test]
+    fn test_rules_free_validation_ignores_rule_invariants() {
+        let mut state = create_test_state(15, 0);
+        state.status = GameStatus::Final;
+        let rules = GameRules {
+            allow_tie_after_innings: Some(12),
+            ..GameRules::default()
+        };
+        assert!(validate_state(&state).is_ok());
+        assert!(validate_state_with_rules(&state, &rules).is_err());
+    }
+
+    #[test]
+    fn test_final_game_past_tie_limit_rejected() {
+        let rules = GameRules {
+            allow_tie_after_innings: Some(12),
+            ..GameRules::default()
+        };
+        let mut state = create_test_state(12, 0);
+        state.status = GameStatus::Final;
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+
+        state.inning = 13;
+        state.score = Score { home: 1, away: 0 };
+        let result = validate_state_with_rules(&state, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Final game cannot go past inning 12"
+        );
+    }
+
+    #[test]
+    fn test_final_extra_inning_tie_rejected() {
+        let mut state = create_test_state(10, 0);
+        state.status = GameStatus::Final;
+        let result = validate_state_with_rules(&state, &GameRules::default());
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+
+        state.status = GameStatus::InProgress;
+        assert!(validate_state_with_rules(&state, &GameRules::default()).is_ok());
+        state.status = GameStatus::Final;
+        state.score = Score { home: 0, away: 1 };
+        assert!(validate_state_with_rules(&state, &GameRules::default()).is_ok());
+    }
+
+    #[test]
+    fn test_final_tie_allowed_in_last_inning() {
+        let mut state = create_test_state(MAX_INNING, 0);
+        state.status = GameStatus::Final;
+        assert!(validate_state_with_rules(&state, &GameRules::default()).is_ok());
+
+        state.inning = MAX_INNING - 1;
+        assert!(validate_state_with_rules(&state, &GameRules::default()).is_err());
+    }
+
+    #[test]
+    fn test_overflowing_line_score_is_a_mismatch() {
+        let mut state = create_test_state(2, 0);
+        state.score.away = u32::MAX;
+        state.line_score = vec![
+            InningScore {
+                away: u32::MAX,
+                home: Some(0),
+            },
+            InningScore {
+                away: 1,
+                home: None,
+            },
+        ];
+        let issues = validate_state_full(&state);
+        assert_eq!(issues[0].code, ValidationCode::LineScoreMismatch);
+
+        state.line_score[1].away = 0;
+        assert!(validate_state(&state).is_ok());
+    }
+
+    #[test]
+    fn test_missing_tiebreaker_runner_rejected() {
+        let rules = GameRules {
+            extra_innings_tiebreaker: Some(ExtraInningsTiebreaker::RunnerOnSecond),
+            ..GameRules::default()
+        };
+        let mut state = create_test_state(10, 0);
+        state.last_batters.away = Some("a9".to_string());
+        state.line_score = vec![
+            InningScore {
+                away: 0,
+                home: Some(0),
+            };
+            10
+        ];
+        state.line_score[9].home = None;
+        let result = validate_state_with_rules(&state, &rules);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Extra inning 10 must start with a runner on second base"
+        );
+
+        state.bases.set(Base::Third, Some("a9".to_string()));
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+
+        state.bases = Bases::new();
+        state.outs = 1;
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+        state.outs = 0;
+        assert!(validate_state(&state).is_ok());
+    }
+
+    #[test]
+    fn test_full_validation_reports_every_issue_in_order() {
+        let mut state = create_test_state(0, 3);
+        state.count.balls = 4;
+        let issues = validate_state_full(&state);
+        let codes: Vec<_> = issues.iter().map(|issue| issue.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                ValidationCode::TooManyOuts,
+                ValidationCode::TooManyBalls,
+                ValidationCode::InvalidInning,
+            ]
+        );
+        assert_eq!(issues[1].field, "balls");
+        assert_eq!(issues[1].message, "Balls must be between 0 and 3");
+
+        let err = validate_state(&state).unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::TooManyOuts);
+        assert_eq!(
+            err.to_string(),
+            "Validation error: Outs must be between 0 and 2"
+        );
+        assert!(validate_state_full(&create_test_state(1, 0)).is_empty());
+    }
+
+    fn lineup(team: &str, size: usize) -> Lineup {
+        Lineup::new((1..=size).map(|slot| format!("{}{}", team, slot)))
+    }
+
+    #[test]
+    fn test_lineups_validated() {
+        let mut state = create_test_state(1, 0);
+        state.lineups = PerTeam {
+            home: lineup("h", 9),
+            away: lineup("a", 9),
+        };
+        assert!(validate_state(&state).is_ok());
+
+        state.lineups.home.players[8] = "a1".to_string();
+        state.lineups.away = lineup("a", 8);
+        state.lineups.away.index = 8;
+        let codes: Vec<_> = validate_state_full(&state)
+            .into_iter()
+            .map(|issue| issue.code)
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                ValidationCode::LineupTooShort,
+                ValidationCode::InvalidLineupIndex,
+                ValidationCode::DuplicateLineupPlayer,
+            ]
+        );
+        assert_eq!(
+            validate_state(&state).unwrap_err().to_string(),
+            "Validation error: The away lineup must have at least 9 players"
+        );
+    }
+
+    #[test]
+    fn test_pitcher_placement_follows_designated_hitter() {
+        let dh = GameRules {
+            designated_hitter: true,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state(1, 0);
+        state.lineups.home = lineup("h", 9).with_pitcher("hp");
+        assert!(validate_state_with_rules(&state, &dh).is_ok());
+        assert_eq!(
+            validate_state(&state).unwrap_err().to_string(),
+            "Validation error: The home pitcher hp must bat without a designated hitter"
+        );
+
+        state.lineups.home = lineup("h", 9).with_pitcher("h9");
+        assert!(validate_state(&state).is_ok());
+        let issues = validate_state_full_with_rules(&state, &dh);
+        assert_eq!(
+            issues[0].code,
+            ValidationCode::PitcherBatsWithDesignatedHitter
+        );
+
+        state.dh_forfeited.home = true;
+        assert!(validate_state_with_rules(&state, &dh).is_ok());
+    }
+
+    #[test]
+    fn test_lineup_size_follows_rules() {
+        let rules = GameRules {
+            min_lineup_size: 8,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state(1, 0);
+        state.lineups.away = lineup("a", 8);
+        assert!(validate_state(&state).is_err());
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+    }
+
+    /// `team`'s nine fielders, numbered by position: pitcher `{team}1`
+    /// through right fielder `{team}9`.
+    fn defense(team: &str) -> DefensiveLineup {
+        DefensiveLineup::new(
+            Position::FIELDERS
+                .into_iter()
+                .zip(1..)
+                .map(|(position, number)| (position, format!("{}{}", team, number))),
+        )
+    }
+
+    fn codes<P: PlayerKey>(state: &GameState<P>, rules: &GameRules) -> Vec<ValidationCode> {
+        validate_state_full_with_rules(state, rules)
+            .into_iter()
+            .map(|issue| issue.code)
+            .collect()
+    }
+
+    #[test]
+    fn test_defense_missing_catcher_rejected() {
+        let mut state = create_test_state(1, 0);
+        state.defensive_lineups.home = defense("h");
+        assert!(validate_state(&state).is_ok());
+
+        state
+            .defensive_lineups
+            .home
+            .positions
+            .remove(&Position::Catcher);
+        assert_eq!(
+            codes(&state, &GameRules::default()),
+            vec![ValidationCode::MissingPosition]
+        );
+        assert_eq!(
+            validate_state(&state).unwrap_err().to_string(),
+            "Validation error: The home defense has nobody at C"
+        );
+    }
+
+    #[test]
+    fn test_defense_player_at_two_positions_rejected() {
+        let mut state = create_test_state(1, 0);
+        state.defensive_lineups.away = defense("a");
+        state
+            .defensive_lineups
+            .away
+            .positions
+            .insert(Position::RightField, "a2".to_string());
+        assert_eq!(
+            codes(&state, &GameRules::default()),
+            vec![ValidationCode::PlayerAtTwoPositions]
+        );
+        assert_eq!(
+            validate_state(&state).unwrap_err().to_string(),
+            "Validation error: Player a2 plays both C and RF for the away team"
+        );
+    }
+
+    #[test]
+    fn test_defense_designated_hitter_follows_rules() {
+        let dh = GameRules {
+            designated_hitter: true,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state(1, 0);
+        state.defensive_lineups.home = defense("h");
+        assert_eq!(codes(&state, &dh), vec![ValidationCode::MissingPosition]);
+
+        state
+            .defensive_lineups
+            .home
+            .positions
+            .insert(Position::DesignatedHitter, "h10".to_string());
+        assert!(validate_state_with_rules(&state, &dh).is_ok());
+        assert_eq!(
+            codes(&state, &GameRules::default()),
+            vec![ValidationCode::DesignatedHitterNotUsed]
+        );
+    }
+
+    #[test]
+    fn test_defense_pitcher_must_be_pitching() {
+        let mut state = create_test_state(1, 0);
+        state.defensive_lineups = PerTeam {
+            home: defense("h"),
+            away: defense("a"),
+        };
+        state.current_pitcher_id = Some("h1".to_string());
+        assert!(validate_state(&state).is_ok());
+
+        state.current_pitcher_id = Some("a1".to_string());
+        assert_eq!(
+            validate_state(&state).unwrap_err().to_string(),
+            "Validation error: The home defense has h1 pitching, not current pitcher a1"
+        );
+        state.half = InningHalf::Bottom;
+        assert!(validate_state(&state).is_ok());
+    }
+
+    #[test]
+    fn test_validation_issue_serialization() {
+        let issue = ValidationIssue::new(
+            ValidationCode::TooManyOuts,
+            "outs",
+            "Outs must be between 0 and 2",
+        );
+        let json = serde_json::to_string(&issue).unwrap();
+        assert_eq!(
+            json,
+            r#"{"code":"too_many_outs","field":"outs","message":"Outs must be between 0 and 2"}"#
+        );
+        let deserialized: ValidationIssue = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, issue);
+    }
+
+    #[test]
+    fn test_line_score_must_match_score() {
+        let mut state = create_test_state(2, 0);
+        state.score = Score { home: 1, away: 2 };
+        state.line_score = vec![
+            InningScore {
+                away: 2,
+                home: Some(1),
+            },
+            InningScore {
+                away: 0,
+                home: None,
+            },
+        ];
+        assert!(validate_state(&state).is_ok());
+
+        state.score.away = 3;
+        let result = validate_state(&state);
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_count_limits_follow_rules() {
+        let rules = GameRules {
+            balls_for_walk: 5,
+            strikes_for_out: 2,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state(1, 0);
+        state.count.balls = 4;
+        assert!(validate_state(&state).is_err());
+        assert!(validate_state_with_rules(&state, &rules).is_ok());
+
+        state.count.strikes = 2;
+        let result = validate_state_with_rules(&state, &rules);
+        assert!(matches!(result, Err(BaselomError::InvalidState(_))));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Validation error: Strikes must be between 0 and 1"
+        );
     }
 
     #[test]
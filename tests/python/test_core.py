@@ -0,0 +1,243 @@
+"""Tests for the compiled Rust engine bindings."""
+
+import copy
+import pickle
+from typing import Any
+
+import pytest
+
+import baselom_core
+
+core = pytest.importorskip("baselom_core._core")
+
+
+def in_progress_state(**fields: Any) -> Any:  # noqa: ANN401
+    """Build an in-progress state with a batter and pitcher, overriding `fields`."""
+    data = core.GameState().to_dict()
+    data.update(status="in_progress", current_batter_id="a1", current_pitcher_id="p1")
+    data.update(fields)
+    return core.GameState.from_dict(data)
+
+
+class TestApplyPitch:
+    """Tests for driving the engine from Python."""
+
+    def test_strikeout(self) -> None:
+        """Test that three strikes retire the batter."""
+        rules = core.GameRules()
+        state = in_progress_state()
+        for pitch in ("strike_called", "strike_swinging", "foul"):
+            state = core.apply_pitch(state, pitch, rules)
+        assert state.strikes == 2
+        state = core.apply_pitch(state, "strike_swinging", rules)
+        assert state.outs == 1
+        assert state.balls == 0
+        assert state.strikes == 0
+        assert state.current_batter_id is None
+
+    def test_bases_loaded_walk(self) -> None:
+        """Test that a walk with the bases loaded forces in a run."""
+        state = in_progress_state(bases=["r1", "r2", "r3"])
+        for _ in range(4):
+            state = core.apply_pitch(state, "ball")
+        assert state.score == core.Score(away=1)
+        assert state.bases == ("a1", "r1", "r2")
+
+    def test_rules_keyword_arguments(self) -> None:
+        """Test that rules take keyword overrides and round-trip through dicts."""
+        rules = core.GameRules(balls_for_walk=3)
+        assert rules.balls_for_walk == 3
+        assert core.GameRules.from_dict(rules.to_dict()) == rules
+        state = in_progress_state()
+        for _ in range(3):
+            state = core.apply_pitch(state, "ball", rules)
+        assert state.bases == ("a1", None, None)
+
+
+class TestApplyEventsBatch:
+    """Tests for applying several plays in one call."""
+
+    def test_batch_matches_single_calls(self) -> None:
+        """Test that a batch reaches the same state as pitch-by-pitch calls."""
+        state = in_progress_state()
+        pitches = ["ball", "strike_called", "foul", "strike_swinging"]
+        expected = state
+        for pitch in pitches:
+            expected = core.apply_pitch(expected, pitch)
+        final, outcomes = core.apply_events_batch(state, pitches)
+        assert final == expected
+        assert len(outcomes) == 4
+        assert outcomes[-1].outs_recorded == 1
+
+    def test_batch_accepts_play_dicts(self) -> None:
+        """Test that dicts and event names can be mixed with pitches."""
+        state = in_progress_state()
+        final, outcomes = core.apply_events_batch(
+            state, ["home_run", {"batter": "a2"}, "ball"]
+        )
+        assert outcomes[0].runs_scored == 1
+        assert outcomes[0].scorers == ["a1"]
+        assert final.current_batter_id == "a2"
+        assert final.balls == 1
+
+    def test_batch_error_includes_index(self) -> None:
+        """Test that a rejected play reports its position in the batch."""
+        state = in_progress_state()
+        with pytest.raises(core.StateException) as excinfo:
+            core.apply_events_batch(state, ["ball", "home_run", "single"])
+        assert excinfo.value.index == 2
+        assert excinfo.value.code == "NO_CURRENT_BATTER"
+        assert "Play 2" in str(excinfo.value)
+
+    def test_batch_unknown_name(self) -> None:
+        """Test that an unknown play name is a validation error."""
+        with pytest.raises(core.ValidationException) as excinfo:
+            core.apply_events_batch(in_progress_state(), ["ball", "knuckleball"])
+        assert excinfo.value.index == 1
+
+
+class TestWinProbability:
+    """Tests for the Monte Carlo win probability helper."""
+
+    @staticmethod
+    def late_lead() -> Any:  # noqa: ANN401
+        """Build the top of the ninth with the home team up ten runs."""
+        lineups = {
+            team: {
+                "players": [f"{team[0]}{n}" for n in range(1, 10)],
+                "pitcher": f"{team[0]}9",
+            }
+            for team in ("home", "away")
+        }
+        return in_progress_state(
+            inning=9,
+            score={"home": 10, "away": 0},
+            line_score=[],
+            lineups=lineups,
+            current_pitcher_id="h9",
+        )
+
+    def test_big_lead(self) -> None:
+        """Test that a ten-run lead in the ninth is all but certain to hold."""
+        odds = core.win_probability(self.late_lead(), n=200, seed=3)
+        assert odds["home"] > 0.99
+        assert odds["home"] + odds["away"] + odds["tie"] == pytest.approx(1.0)
+        assert core.win_probability(self.late_lead(), n=200, seed=3) == odds
+
+    def test_no_simulations(self) -> None:
+        """Test that zero simulations are rejected."""
+        with pytest.raises(core.ValidationException):
+            core.win_probability(self.late_lead(), n=0)
+
+
+class TestGameState:
+    """Tests for the GameState wrapper."""
+
+    def test_dict_round_trip(self) -> None:
+        """Test that to_dict and from_dict preserve the state."""
+        state = in_progress_state(outs=2)
+        restored = core.GameState.from_dict(state.to_dict())
+        assert restored == state
+        assert restored.outs == 2
+        assert restored.half == "top"
+        assert restored.status == "in_progress"
+
+    def test_bytes_round_trip(self) -> None:
+        """Test that to_bytes and from_bytes preserve the state."""
+        state = in_progress_state(bases=["r1", "r2", "r3"], outs=1)
+        data = state.to_bytes()
+        assert isinstance(data, bytes)
+        assert core.GameState.from_bytes(data) == state
+        with pytest.raises(core.ValidationException):
+            core.GameState.from_bytes(data[:-1])
+
+    def test_repr(self) -> None:
+        """Test the state's repr."""
+        assert repr(core.GameState()) == (
+            "GameState(inning=1, half='top', outs=0, count=0-0, score=0-0, "
+            "status='not_started')"
+        )
+
+
+class TestCopying:
+    """Tests for pickling and copying the wrappers."""
+
+    def test_pickle_mid_game_state(self) -> None:
+        """Test that a mid-game state survives a pickle round trip."""
+        state = in_progress_state(bases=["r1", None, "r3"], outs=1)
+        state = core.apply_pitch(state, "ball")
+        restored = pickle.loads(pickle.dumps(state))  # noqa: S301
+        assert restored == state
+        assert restored.balls == 1
+        assert restored.bases == ("r1", None, "r3")
+
+    def test_pickle_rules(self) -> None:
+        """Test that rules survive a pickle round trip."""
+        rules = core.GameRules(designated_hitter=True, pitch_limit=100)
+        restored = pickle.loads(pickle.dumps(rules))  # noqa: S301
+        assert restored == rules
+        assert restored.pitch_limit == 100
+
+    def test_copy_and_deepcopy(self) -> None:
+        """Test that copies compare equal to the original."""
+        state = in_progress_state(outs=2)
+        assert copy.copy(state) == state
+        assert copy.deepcopy(state) == state
+        rules = core.GameRules(balls_for_walk=3)
+        assert copy.deepcopy(rules) == rules
+
+
+class TestErrors:
+    """Tests for the exception hierarchy."""
+
+    def test_exception_classes(self) -> None:
+        """Test that engine errors raise BaselomException subclasses."""
+        with pytest.raises(core.ValidationException):
+            core.apply_pitch(in_progress_state(), "knuckleball")
+        with pytest.raises(core.StateException):
+            core.apply_pitch(core.GameState(), "ball")
+        assert issubclass(core.RuleViolationException, core.BaselomException)
+        assert issubclass(core.BaselomException, ValueError)
+
+    def test_earlier_names_are_aliases(self) -> None:
+        """Test that the earlier ``*Error`` names refer to the same classes."""
+        assert core.BaselomError is core.BaselomException
+        assert core.ValidationError is core.ValidationException
+        assert core.StateError is core.StateException
+        assert core.RuleViolation is core.RuleViolationException
+
+    def test_invalid_pitch_result_code(self) -> None:
+        """Test the class and code raised for an unknown pitch result."""
+        with pytest.raises(core.ValidationException) as excinfo:
+            core.apply_pitch(in_progress_state(), "knuckleball")
+        assert excinfo.value.code == "INVALID_PITCH_RESULT"
+
+    def test_final_game_code(self) -> None:
+        """Test the class and code raised for a transition on a final game."""
+        state = in_progress_state(status="final")
+        with pytest.raises(core.StateException) as excinfo:
+            core.apply_pitch(state, "ball")
+        assert excinfo.value.code == "GAME_ALREADY_FINAL"
+
+
+class TestPackageExports:
+    """Tests for the engine as imported from the package itself."""
+
+    def test_package_reexports_core(self) -> None:
+        """Test that the package exposes the compiled classes and functions."""
+        assert baselom_core.GameState is core.GameState
+        assert baselom_core.GameRules is core.GameRules
+        assert baselom_core.Score is core.Score
+        assert baselom_core.apply_pitch is core.apply_pitch
+        assert baselom_core.BaselomError is core.BaselomError
+        assert baselom_core.ValidationError is core.ValidationError
+        assert baselom_core.StateError is core.StateError
+        assert baselom_core.RuleViolationError is core.RuleViolation
+
+    def test_apply_pitch_from_package(self) -> None:
+        """Test driving the engine and catching its errors via the package."""
+        rules = baselom_core.GameRules()
+        state = baselom_core.apply_pitch(in_progress_state(), "ball", rules)
+        assert state.balls == 1
+        with pytest.raises(baselom_core.ValidationError):
+            baselom_core.apply_pitch(state, "bogus", rules)
@@ -0,0 +1,121 @@
+//! Tests of the WASM exports, run under `wasm-bindgen-test`:
+//!
+//! ```text
+//! cargo test --target wasm32-unknown-unknown --features wasm --test wasm
+//! ```
+//!
+//! with `wasm-bindgen-test-runner` as the target's runner.
+
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use baselom_core::{apply_pitch_js, apply_pitch_wasm, new_game, validate_state_js};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// A property of a thrown error object, as a string.
+fn error_property(error: &JsValue, name: &str) -> Option<String> {
+    js_sys::Reflect::get(error, &JsValue::from_str(name))
+        .ok()?
+        .as_string()
+}
+
+/// A property of a JS object.
+fn property(object: &JsValue, name: &str) -> JsValue {
+    js_sys::Reflect::get(object, &JsValue::from_str(name)).unwrap()
+}
+
+/// Set a property of a JS object.
+fn set_property(object: &JsValue, name: &str, value: JsValue) {
+    js_sys::Reflect::set(object, &JsValue::from_str(name), &value).unwrap();
+}
+
+/// A new game with a batter and pitcher set, as a plain JS object.
+fn game_object() -> JsValue {
+    js_sys::JSON::parse(&game_with_batter()).unwrap()
+}
+
+/// A new game with a batter and pitcher set, as JSON.
+fn game_with_batter() -> String {
+    let mut state: serde_json::Value = serde_json::from_str(&new_game("{}").unwrap()).unwrap();
+    state["currentBatterId"] = "a1".into();
+    state["currentPitcherId"] = "h1".into();
+    state.to_string()
+}
+
+#[wasm_bindgen_test]
+fn test_strikeout_round_trip() {
+    let mut state = game_with_batter();
+    for _ in 0..3 {
+        state = apply_pitch_wasm(&state, "strike_swinging", "{}").unwrap();
+    }
+    let state: serde_json::Value = serde_json::from_str(&state).unwrap();
+    assert_eq!(state["outs"], 1);
+}
+
+#[wasm_bindgen_test]
+fn test_errors_are_code_and_message_objects() {
+    let error = apply_pitch_wasm(&game_with_batter(), "bogus", "{}").unwrap_err();
+    assert_eq!(
+        error_property(&error, "code").as_deref(),
+        Some("INVALID_PITCH_RESULT")
+    );
+    assert!(error_property(&error, "message").is_some_and(|message| message.contains("bogus")));
+
+    let error = apply_pitch_wasm("{", "ball", "{}").unwrap_err();
+    assert_eq!(
+        error_property(&error, "code").as_deref(),
+        Some("VALIDATION_ERROR")
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_apply_pitch_js_round_trip() {
+    let mut state = game_object();
+    for _ in 0..3 {
+        state = apply_pitch_js(state, "strike_swinging", JsValue::UNDEFINED).unwrap();
+    }
+    assert_eq!(property(&state, "outs").as_f64(), Some(1.0));
+    assert!(property(&state, "halfInningRuns").as_f64().is_some());
+    assert!(property(&state, "half_inning_runs").is_undefined());
+    // Player IDs keep their spelling as keys
+    let pitch_counts = property(&state, "pitchCounts");
+    assert_eq!(property(&pitch_counts, "h1").as_f64(), Some(3.0));
+}
+
+#[wasm_bindgen_test]
+fn test_apply_pitch_js_errors() {
+    let error = apply_pitch_js(game_object(), "bogus", JsValue::NULL).unwrap_err();
+    assert_eq!(
+        error_property(&error, "code").as_deref(),
+        Some("INVALID_PITCH_RESULT")
+    );
+
+    // Numbers are not coerced into integer fields
+    let state = game_object();
+    set_property(&state, "outs", JsValue::from_f64(1.5));
+    let error = apply_pitch_js(state, "ball", JsValue::NULL).unwrap_err();
+    assert_eq!(
+        error_property(&error, "code").as_deref(),
+        Some("VALIDATION_ERROR")
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_validate_state_js() {
+    assert!(validate_state_js(game_object()).is_ok());
+
+    let state = game_object();
+    set_property(&state, "outs", JsValue::from_f64(3.0));
+    let error = validate_state_js(state).unwrap_err();
+    assert_eq!(
+        error_property(&error, "code").as_deref(),
+        Some("TOO_MANY_OUTS")
+    );
+    assert!(error_property(&error, "message").is_some());
+
+    let error = validate_state_js(JsValue::from_str("not a state")).unwrap_err();
+    assert_eq!(
+        error_property(&error, "code").as_deref(),
+        Some("VALIDATION_ERROR")
+    );
+}